@@ -1,19 +1,31 @@
+use std::collections::BTreeMap;
 use std::io::{BufWriter, Write};
 use std::{fs, io};
+#[cfg(feature = "compress")]
+use std::thread;
 
-use crate::rolling::Rotation;
+use crate::rolling::Builder;
 use chrono::prelude::*;
 use std::fmt::Debug;
 use std::fs::{File, OpenOptions};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// The number of writes between checks that the current log file still exists.
+const FILE_EXISTENCE_CHECK_INTERVAL: usize = 100;
+
+/// The maximum delay between attempts to recreate the log file after a failure.
+const MAX_REOPEN_BACKOFF_SECS: i64 = 30;
 
 #[derive(Debug)]
 pub(crate) struct InnerAppender {
-    log_directory: String,
-    log_filename_prefix: String,
+    log_directory: PathBuf,
     writer: BufWriter<File>,
+    current_filename: String,
     next_date: DateTime<Utc>,
-    rotation: Rotation,
+    writes_since_existence_check: usize,
+    next_reopen_retry: Option<DateTime<Utc>>,
+    reopen_backoff: chrono::Duration,
+    config: Builder,
 }
 
 impl io::Write for InnerAppender {
@@ -30,23 +42,32 @@ impl io::Write for InnerAppender {
 impl InnerAppender {
     pub(crate) fn new(
         log_directory: &Path,
-        log_filename_prefix: &Path,
-        rotation: Rotation,
         now: DateTime<Utc>,
+        config: Builder,
     ) -> io::Result<Self> {
-        let log_directory = log_directory.to_str().unwrap();
-        let log_filename_prefix = log_filename_prefix.to_str().unwrap();
+        let (filename, next_date) = filename_and_next_date(&config, &now);
 
-        let filename = rotation.join_date(log_filename_prefix, &now);
-        let next_date = rotation.next_date(&now);
-
-        Ok(InnerAppender {
-            log_directory: log_directory.to_string(),
-            log_filename_prefix: log_filename_prefix.to_string(),
+        let appender = InnerAppender {
+            log_directory: log_directory.to_path_buf(),
             writer: create_writer(log_directory, &filename)?,
+            current_filename: filename,
             next_date,
-            rotation,
-        })
+            writes_since_existence_check: 0,
+            next_reopen_retry: None,
+            reopen_backoff: chrono::Duration::zero(),
+            config,
+        };
+        appender.update_current_symlink();
+        Ok(appender)
+    }
+
+    /// Discards the current writer and opens the current log file again,
+    /// recreating the log directory if it no longer exists.
+    pub(crate) fn reopen(&mut self) -> io::Result<()> {
+        self.writer = create_writer(&self.log_directory, &self.current_filename)?;
+        self.next_reopen_retry = None;
+        self.reopen_backoff = chrono::Duration::zero();
+        Ok(())
     }
 
     fn write_timestamped(&mut self, buf: &[u8], date: DateTime<Utc>) -> io::Result<usize> {
@@ -54,17 +75,35 @@ impl InnerAppender {
         // and proceed with the write.
         let buf_len = buf.len();
         self.refresh_writer(date);
+        self.check_file_exists(date);
         self.writer.write_all(buf).map(|_| buf_len)
     }
 
     fn refresh_writer(&mut self, now: DateTime<Utc>) {
         if self.should_rollover(now) {
-            let filename = self.rotation.join_date(&self.log_filename_prefix, &now);
+            let (filename, next_date) = filename_and_next_date(&self.config, &now);
 
-            self.next_date = self.rotation.next_date(&now);
+            self.next_date = next_date;
 
             match create_writer(&self.log_directory, &filename) {
-                Ok(writer) => self.writer = writer,
+                Ok(writer) => {
+                    // Flush any buffered writes to the old file before it is
+                    // rotated away from (and, if compression is enabled, read
+                    // by the compression thread).
+                    let _ = self.writer.flush();
+                    #[cfg(feature = "compress")]
+                    let old_path = self.log_directory.join(&self.current_filename);
+                    self.writer = writer;
+                    self.current_filename = filename;
+                    #[cfg(feature = "compress")]
+                    if self.config.compress_rotated {
+                        thread::spawn(move || compress_log_file(&old_path));
+                    }
+                    self.update_current_symlink();
+                    if let Some(max_files) = self.config.max_files {
+                        self.prune_old_logs(max_files);
+                    }
+                }
                 Err(err) => eprintln!("Couldn't create writer for logs: {}", err),
             }
         }
@@ -73,10 +112,252 @@ impl InnerAppender {
     fn should_rollover(&self, date: DateTime<Utc>) -> bool {
         date >= self.next_date
     }
+
+    /// Periodically checks that the file the writer was created with still
+    /// exists, recreating it (and the log directory) if it was deleted out
+    /// from under the appender.
+    fn check_file_exists(&mut self, now: DateTime<Utc>) {
+        self.writes_since_existence_check += 1;
+        if self.writes_since_existence_check < FILE_EXISTENCE_CHECK_INTERVAL {
+            return;
+        }
+        self.writes_since_existence_check = 0;
+
+        let path = self.log_directory.join(&self.current_filename);
+        if path.exists() {
+            return;
+        }
+
+        // Retry with backoff so that a persistent failure (e.g. a permissions
+        // problem) doesn't report an error on every write.
+        if let Some(retry) = self.next_reopen_retry {
+            if now < retry {
+                return;
+            }
+        }
+
+        if let Err(err) = self.reopen() {
+            self.reopen_backoff = chrono::Duration::seconds(
+                (self.reopen_backoff.num_seconds() * 2)
+                    .max(1)
+                    .min(MAX_REOPEN_BACKOFF_SECS),
+            );
+            self.next_reopen_retry = Some(now + self.reopen_backoff);
+            eprintln!("Couldn't recreate log file: {}", err);
+        }
+    }
+
+    fn prune_old_logs(&self, max_files: usize) {
+        let read_dir = match fs::read_dir(&self.log_directory) {
+            Ok(read_dir) => read_dir,
+            Err(err) => {
+                eprintln!("Error reading the log directory/files: {}", err);
+                return;
+            }
+        };
+
+        let link_name = self.current_link_name();
+        let prefix = self.config.filename_prefix.as_deref();
+        let suffix = self.config.filename_suffix.as_deref();
+        // Compression appends `.gz` after the configured suffix, and while a
+        // rotated file is being compressed both it and its `.gz` counterpart
+        // exist. Group entries by the uncompressed name, so that a compressed
+        // log still matches the suffix and such a pair counts as one retained
+        // log rather than two.
+        let mut files = BTreeMap::<String, Vec<fs::DirEntry>>::new();
+        for entry in read_dir.flatten() {
+            let is_file = entry
+                .metadata()
+                .map(|metadata| metadata.is_file())
+                .unwrap_or(false);
+            if !is_file {
+                continue;
+            }
+
+            let filename = entry.file_name();
+            let filename = match filename.to_str() {
+                Some(filename) => filename,
+                None => continue,
+            };
+            let base = filename.strip_suffix(".gz").unwrap_or(filename);
+            if let Some(prefix) = prefix {
+                if !base.starts_with(prefix) {
+                    continue;
+                }
+            }
+            if let Some(suffix) = suffix {
+                if !base.ends_with(suffix) {
+                    continue;
+                }
+            }
+            // The stable link to the current file shares the prefix, but
+            // is not a rotated log.
+            if Some(filename) == link_name.as_deref() {
+                continue;
+            }
+
+            files.entry(base.to_owned()).or_default().push(entry);
+        }
+
+        if files.len() <= max_files {
+            return;
+        }
+
+        // The date is encoded at the end of the file name, so the map's
+        // ordering by name sorts the rotated logs from oldest to newest.
+        let excess = files.len() - max_files;
+        for file in files.into_values().take(excess).flatten() {
+            if let Err(err) = fs::remove_file(file.path()) {
+                eprintln!(
+                    "Failed to remove old log file {}: {}",
+                    file.path().display(),
+                    err
+                );
+            }
+        }
+    }
+
+    /// Returns the name of the stable link to the current log file, if one
+    /// should be maintained.
+    fn current_link_name(&self) -> Option<String> {
+        if !self.config.create_current_symlink {
+            return None;
+        }
+        let prefix = self.config.filename_prefix.as_deref();
+        let suffix = self.config.filename_suffix.as_deref();
+        let name = match (prefix, suffix) {
+            (Some(prefix), Some(suffix)) => format!("{}.{}", prefix, suffix),
+            (Some(prefix), None) => prefix.to_string(),
+            (None, Some(suffix)) => suffix.to_string(),
+            // With neither a prefix nor a suffix, there is no stable name to
+            // link from.
+            (None, None) => return None,
+        };
+        // With `Rotation::NEVER`, the current file already has the stable
+        // name, and a link would point at itself.
+        if name == self.current_filename {
+            return None;
+        }
+        Some(name)
+    }
+
+    /// Points the stable link at the file currently being written, so that
+    /// `tail -F` keeps following the log across rotations.
+    fn update_current_symlink(&self) {
+        let link_name = match self.current_link_name() {
+            Some(name) => name,
+            None => return,
+        };
+        let link = self.log_directory.join(&link_name);
+        if let Err(err) = replace_link(&link, &self.current_filename) {
+            eprintln!(
+                "Couldn't link {} to current log file {}: {}",
+                link.display(),
+                self.current_filename,
+                err
+            );
+        }
+    }
+}
+
+/// Replaces `link` with a symbolic link to `target`, a file name in the same
+/// directory.
+#[cfg(unix)]
+fn replace_link(link: &Path, target: &str) -> io::Result<()> {
+    // Create the new link under a temporary name and rename it over the old
+    // one, so that the stable name never dangles. The temporary name starts
+    // with a `.` so that it is never mistaken for a rotated log.
+    let link_name = link
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("current");
+    let tmp = link.with_file_name(format!(".{}.tmp", link_name));
+    let _ = fs::remove_file(&tmp);
+    std::os::unix::fs::symlink(target, &tmp)?;
+    fs::rename(&tmp, link)
+}
+
+/// Replaces `link` with a hard link to `target`, a file name in the same
+/// directory.
+///
+/// Creating symbolic links on Windows requires elevated privileges, so a hard
+/// link to the current file is created instead.
+#[cfg(windows)]
+fn replace_link(link: &Path, target: &str) -> io::Result<()> {
+    let target = match link.parent() {
+        Some(dir) => dir.join(target),
+        None => PathBuf::from(target),
+    };
+    let _ = fs::remove_file(link);
+    fs::hard_link(target, link)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn replace_link(_link: &Path, _target: &str) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Other,
+        "links are not supported on this platform",
+    ))
+}
+
+/// Gzip-compresses the rotated log file at `path` to `path.gz`, removing the
+/// original once compression succeeds.
+///
+/// On failure, the partially written `.gz` file is removed and the original
+/// is left in place.
+#[cfg(feature = "compress")]
+fn compress_log_file(path: &Path) {
+    let mut gz_name = path.as_os_str().to_os_string();
+    gz_name.push(".gz");
+    let gz_path = PathBuf::from(gz_name);
+
+    let compress = || -> io::Result<()> {
+        let mut input = File::open(path)?;
+        let output = BufWriter::new(File::create(&gz_path)?);
+        let mut encoder = flate2::write::GzEncoder::new(output, flate2::Compression::default());
+        io::copy(&mut input, &mut encoder)?;
+        encoder.finish()?.flush()
+    };
+    if let Err(err) = compress() {
+        let _ = fs::remove_file(&gz_path);
+        eprintln!(
+            "Failed to compress rotated log file {}: {}",
+            path.display(),
+            err
+        );
+        return;
+    }
+
+    if let Err(err) = fs::remove_file(path) {
+        eprintln!(
+            "Failed to remove rotated log file {} after compressing it: {}",
+            path.display(),
+            err
+        );
+    }
+}
+
+/// Returns the file name for the log file written at `now`, and the instant of
+/// the next rollover, honoring the configured timezone, date format, and
+/// suffix.
+fn filename_and_next_date(config: &Builder, now: &DateTime<Utc>) -> (String, DateTime<Utc>) {
+    let prefix = config.filename_prefix.as_deref();
+    let format = config.date_format.as_deref();
+    let suffix = config.filename_suffix.as_deref();
+    if config.use_local_time {
+        let now = now.with_timezone(&Local);
+        let filename = config.rotation.join_date(prefix, &now, format, suffix);
+        let next_date = config.rotation.next_date(&now).with_timezone(&Utc);
+        (filename, next_date)
+    } else {
+        let filename = config.rotation.join_date(prefix, now, format, suffix);
+        let next_date = config.rotation.next_date(now);
+        (filename, next_date)
+    }
 }
 
-fn create_writer(directory: &str, filename: &str) -> io::Result<BufWriter<File>> {
-    let file_path = Path::new(directory).join(filename);
+fn create_writer(directory: &Path, filename: &str) -> io::Result<BufWriter<File>> {
+    let file_path = directory.join(filename);
     Ok(BufWriter::new(open_file_create_parent_dirs(&file_path)?))
 }
 
@@ -94,3 +375,281 @@ fn open_file_create_parent_dirs(path: &Path) -> io::Result<File> {
 
     new_file
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rolling::Rotation;
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_prune_old_logs() {
+        let directory = TempDir::new("prune").expect("Failed to create tempdir");
+        let now = Utc.ymd(2020, 2, 1).and_hms(10, 1, 0);
+
+        let config = Builder::new()
+            .rotation(Rotation::MINUTELY)
+            .filename_prefix("prune.log")
+            .max_files(2);
+        let mut appender =
+            InnerAppender::new(directory.path(), now, config).expect("Failed to create appender");
+
+        // Force a rollover once per mock minute.
+        for minute in 0..5 {
+            let now = now + chrono::Duration::minutes(minute);
+            appender
+                .write_timestamped(b"Hello\n", now)
+                .expect("Failed to write to appender");
+            appender.flush().expect("Failed to flush!");
+        }
+
+        let files = fs::read_dir(directory.path())
+            .expect("Failed to read directory")
+            .map(|entry| entry.expect("Expected dir entry").file_name())
+            .collect::<Vec<_>>();
+        assert_eq!(files.len(), 2);
+        assert!(files.contains(&"prune.log.2020-02-01-10-04".into()));
+        assert!(files.contains(&"prune.log.2020-02-01-10-05".into()));
+
+        directory
+            .close()
+            .expect("Failed to explicitly close TempDir. TempDir should delete once out of scope.")
+    }
+
+    #[test]
+    fn test_recreates_deleted_log_file() {
+        let directory = TempDir::new("deleted").expect("Failed to create tempdir");
+        let now = Utc.ymd(2020, 2, 1).and_hms(10, 1, 0);
+
+        let config = Builder::new()
+            .rotation(Rotation::NEVER)
+            .filename_prefix("deleted.log");
+        let mut appender =
+            InnerAppender::new(directory.path(), now, config).expect("Failed to create appender");
+
+        appender
+            .write_timestamped(b"Hello\n", now)
+            .expect("Failed to write to appender");
+        appender.flush().expect("Failed to flush!");
+
+        let path = directory.path().join("deleted.log");
+        fs::remove_file(&path).expect("Failed to delete log file");
+
+        // The existence check only runs every `FILE_EXISTENCE_CHECK_INTERVAL`
+        // writes, so enough writes must happen for the file to be recreated.
+        for _ in 0..=FILE_EXISTENCE_CHECK_INTERVAL {
+            appender
+                .write_timestamped(b"World\n", now)
+                .expect("Failed to write to appender");
+        }
+        appender.flush().expect("Failed to flush!");
+
+        assert!(path.exists());
+        let contents = fs::read_to_string(&path).expect("Failed to read file");
+        assert!(contents.contains("World"));
+
+        directory
+            .close()
+            .expect("Failed to explicitly close TempDir. TempDir should delete once out of scope.")
+    }
+
+    #[test]
+    fn test_reopen() {
+        let directory = TempDir::new("reopen").expect("Failed to create tempdir");
+        let now = Utc.ymd(2020, 2, 1).and_hms(10, 1, 0);
+
+        let config = Builder::new()
+            .rotation(Rotation::NEVER)
+            .filename_prefix("reopen.log");
+        let mut appender =
+            InnerAppender::new(directory.path(), now, config).expect("Failed to create appender");
+
+        let path = directory.path().join("reopen.log");
+        fs::remove_file(&path).expect("Failed to delete log file");
+
+        appender.reopen().expect("Failed to reopen");
+        appender
+            .write_timestamped(b"Hello\n", now)
+            .expect("Failed to write to appender");
+        appender.flush().expect("Failed to flush!");
+
+        assert!(path.exists());
+
+        directory
+            .close()
+            .expect("Failed to explicitly close TempDir. TempDir should delete once out of scope.")
+    }
+
+    #[test]
+    #[cfg(all(unix, feature = "compress"))]
+    fn test_compress_rotated_log() {
+        use std::io::Read;
+
+        let directory = TempDir::new("compress").expect("Failed to create tempdir");
+        let now = Utc.ymd(2020, 2, 1).and_hms(10, 1, 0);
+
+        let config = Builder::new()
+            .rotation(Rotation::MINUTELY)
+            .filename_prefix("compress.log")
+            .compress_rotated(true);
+        let mut appender =
+            InnerAppender::new(directory.path(), now, config).expect("Failed to create appender");
+
+        appender
+            .write_timestamped(b"Hello\n", now)
+            .expect("Failed to write to appender");
+        // Force a rollover; the first file should be compressed in the
+        // background.
+        appender
+            .write_timestamped(b"World\n", now + chrono::Duration::minutes(1))
+            .expect("Failed to write to appender");
+        appender.flush().expect("Failed to flush!");
+
+        let rotated = directory.path().join("compress.log.2020-02-01-10-01");
+        let gz_path = directory.path().join("compress.log.2020-02-01-10-01.gz");
+
+        // Compression happens off the write path, so wait for it to finish.
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        while (rotated.exists() || !gz_path.exists()) && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(gz_path.exists(), "compressed file should exist");
+        assert!(
+            !rotated.exists(),
+            "uncompressed file should be removed after compression"
+        );
+
+        let mut contents = String::new();
+        flate2::read::GzDecoder::new(File::open(&gz_path).expect("Failed to open gz file"))
+            .read_to_string(&mut contents)
+            .expect("Failed to decompress file");
+        assert_eq!(contents, "Hello\n");
+
+        directory
+            .close()
+            .expect("Failed to explicitly close TempDir. TempDir should delete once out of scope.")
+    }
+
+    #[test]
+    #[cfg(all(unix, feature = "compress"))]
+    fn test_prune_compressed_logs_with_suffix() {
+        let directory = TempDir::new("prune-compress").expect("Failed to create tempdir");
+        let now = Utc.ymd(2020, 2, 1).and_hms(10, 1, 0);
+
+        let config = Builder::new()
+            .rotation(Rotation::MINUTELY)
+            .filename_prefix("prune")
+            .filename_suffix("log")
+            .compress_rotated(true)
+            .max_files(2);
+        let mut appender =
+            InnerAppender::new(directory.path(), now, config).expect("Failed to create appender");
+
+        // Compression happens off the write path, so wait for each rotated
+        // file to finish compressing before forcing the next rollover; the
+        // retention limit must then be applied to the `.gz` files.
+        let wait_for = |path: &Path| {
+            let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+            while !path.exists() && std::time::Instant::now() < deadline {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+            assert!(path.exists(), "{} should exist", path.display());
+        };
+
+        appender
+            .write_timestamped(b"Hello\n", now)
+            .expect("Failed to write to appender");
+        for minute in 1..5 {
+            appender
+                .write_timestamped(b"Hello\n", now + chrono::Duration::minutes(minute))
+                .expect("Failed to write to appender");
+            appender.flush().expect("Failed to flush!");
+            let rotated = format!("prune.2020-02-01-10-{:02}.log.gz", minute);
+            wait_for(&directory.path().join(rotated));
+        }
+
+        let files = fs::read_dir(directory.path())
+            .expect("Failed to read directory")
+            .map(|entry| entry.expect("Expected dir entry").file_name())
+            .collect::<Vec<_>>();
+        assert_eq!(files.len(), 2, "directory contains {:?}", files);
+        assert!(files.contains(&"prune.2020-02-01-10-04.log.gz".into()));
+        assert!(files.contains(&"prune.2020-02-01-10-05.log".into()));
+
+        directory
+            .close()
+            .expect("Failed to explicitly close TempDir. TempDir should delete once out of scope.")
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_current_symlink_follows_rotation() {
+        let directory = TempDir::new("symlink").expect("Failed to create tempdir");
+        let now = Utc.ymd(2020, 2, 1).and_hms(10, 1, 0);
+
+        let config = Builder::new()
+            .rotation(Rotation::MINUTELY)
+            .filename_prefix("current.log")
+            .create_current_symlink(true);
+        let mut appender =
+            InnerAppender::new(directory.path(), now, config).expect("Failed to create appender");
+
+        let link = directory.path().join("current.log");
+        appender
+            .write_timestamped(b"Hello\n", now)
+            .expect("Failed to write to appender");
+        appender.flush().expect("Failed to flush!");
+
+        assert_eq!(
+            fs::read_link(&link).expect("Failed to read link"),
+            Path::new("current.log.2020-02-01-10-01")
+        );
+        assert_eq!(
+            fs::read_to_string(&link).expect("Failed to read through link"),
+            "Hello\n"
+        );
+
+        // After a rollover, the link must point at the new file.
+        appender
+            .write_timestamped(b"World\n", now + chrono::Duration::minutes(1))
+            .expect("Failed to write to appender");
+        appender.flush().expect("Failed to flush!");
+
+        assert_eq!(
+            fs::read_link(&link).expect("Failed to read link"),
+            Path::new("current.log.2020-02-01-10-02")
+        );
+        assert_eq!(
+            fs::read_to_string(&link).expect("Failed to read through link"),
+            "World\n"
+        );
+
+        directory
+            .close()
+            .expect("Failed to explicitly close TempDir. TempDir should delete once out of scope.")
+    }
+
+    #[test]
+    fn test_filename_suffix_after_date() {
+        let directory = TempDir::new("suffix").expect("Failed to create tempdir");
+        let now = Utc.ymd(2020, 2, 1).and_hms(10, 1, 0);
+
+        let config = Builder::new()
+            .rotation(Rotation::DAILY)
+            .filename_prefix("app")
+            .filename_suffix("log");
+        let mut appender =
+            InnerAppender::new(directory.path(), now, config).expect("Failed to create appender");
+
+        appender
+            .write_timestamped(b"Hello\n", now)
+            .expect("Failed to write to appender");
+        appender.flush().expect("Failed to flush!");
+
+        assert!(directory.path().join("app.2020-02-01.log").exists());
+
+        directory
+            .close()
+            .expect("Failed to explicitly close TempDir. TempDir should delete once out of scope.")
+    }
+}
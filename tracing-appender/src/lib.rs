@@ -183,5 +183,7 @@ pub fn non_blocking<T: Write + Send + Sync + 'static>(writer: T) -> (NonBlocking
 #[derive(Debug)]
 pub(crate) enum Msg {
     Line(Vec<u8>),
+    /// Flush the underlying writer, then acknowledge on the provided channel.
+    Flush(crossbeam_channel::Sender<()>),
     Shutdown,
 }
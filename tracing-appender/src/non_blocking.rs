@@ -48,14 +48,15 @@
 //! ```
 use crate::worker::Worker;
 use crate::Msg;
-use crossbeam_channel::{bounded, SendTimeoutError, Sender};
+use crossbeam_channel::{bounded, RecvTimeoutError, SendTimeoutError, Sender};
+use std::fmt;
 use std::io;
 use std::io::Write;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tracing_subscriber::fmt::MakeWriter;
 
 /// The default maximum number of buffered log lines.
@@ -104,6 +105,7 @@ pub const DEFAULT_BUFFERED_LINES_LIMIT: usize = 128_000;
 #[derive(Debug)]
 pub struct WorkerGuard {
     guard: Option<JoinHandle<()>>,
+    error_counter: Arc<AtomicU64>,
     sender: Sender<Msg>,
     shutdown: Sender<()>,
 }
@@ -145,19 +147,26 @@ impl NonBlocking {
         writer: T,
         buffered_lines_limit: usize,
         is_lossy: bool,
+        on_error: Box<dyn Fn(io::Error) + Send + Sync>,
     ) -> (NonBlocking, WorkerGuard) {
         let (sender, receiver) = bounded(buffered_lines_limit);
 
         let (shutdown_sender, shutdown_receiver) = bounded(0);
 
-        let worker = Worker::new(receiver, writer, shutdown_receiver);
-        let worker_guard =
-            WorkerGuard::new(worker.worker_thread(), sender.clone(), shutdown_sender);
+        let error_counter = Arc::new(AtomicU64::new(0));
+
+        let worker = Worker::new(receiver, writer, shutdown_receiver, on_error);
+        let worker_guard = WorkerGuard::new(
+            worker.worker_thread(),
+            error_counter.clone(),
+            sender.clone(),
+            shutdown_sender,
+        );
 
         (
             Self {
                 channel: sender,
-                error_counter: Arc::new(AtomicU64::new(0)),
+                error_counter,
                 is_lossy,
             },
             worker_guard,
@@ -174,10 +183,10 @@ impl NonBlocking {
 /// A builder for [`NonBlocking`][non-blocking].
 ///
 /// [non-blocking]: NonBlocking
-#[derive(Debug)]
 pub struct NonBlockingBuilder {
     buffered_lines_limit: usize,
     is_lossy: bool,
+    on_error: Box<dyn Fn(io::Error) + Send + Sync>,
 }
 
 impl NonBlockingBuilder {
@@ -198,9 +207,43 @@ impl NonBlockingBuilder {
         self
     }
 
+    /// Sets a callback that is invoked from the worker thread when writing or
+    /// flushing to the underlying writer fails.
+    ///
+    /// By default, errors are reported with `eprintln!`, at most once per
+    /// second to avoid flooding stderr when the writer fails persistently
+    /// (such as when a disk is full).
+    pub fn on_error<F>(mut self, on_error: F) -> NonBlockingBuilder
+    where
+        F: Fn(io::Error) + Send + Sync + 'static,
+    {
+        self.on_error = Box::new(on_error);
+        self
+    }
+
     /// Completes the builder, returning the configured `NonBlocking`.
     pub fn finish<T: Write + Send + Sync + 'static>(self, writer: T) -> (NonBlocking, WorkerGuard) {
-        NonBlocking::create(writer, self.buffered_lines_limit, self.is_lossy)
+        NonBlocking::create(
+            writer,
+            self.buffered_lines_limit,
+            self.is_lossy,
+            self.on_error,
+        )
+    }
+
+    /// Returns the default error callback: an `eprintln!` rate-limited to one
+    /// message per second.
+    fn default_error_callback() -> Box<dyn Fn(io::Error) + Send + Sync> {
+        const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+        let last_report = Mutex::new(None::<Instant>);
+        Box::new(move |error| {
+            let mut last_report = last_report.lock().unwrap();
+            let now = Instant::now();
+            if last_report.map_or(true, |at| now.duration_since(at) >= REPORT_INTERVAL) {
+                eprintln!("tracing-appender: failed to write to log: {}", error);
+                *last_report = Some(now);
+            }
+        })
     }
 }
 
@@ -209,10 +252,20 @@ impl Default for NonBlockingBuilder {
         NonBlockingBuilder {
             buffered_lines_limit: DEFAULT_BUFFERED_LINES_LIMIT,
             is_lossy: true,
+            on_error: Self::default_error_callback(),
         }
     }
 }
 
+impl fmt::Debug for NonBlockingBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NonBlockingBuilder")
+            .field("buffered_lines_limit", &self.buffered_lines_limit)
+            .field("is_lossy", &self.is_lossy)
+            .finish()
+    }
+}
+
 impl std::io::Write for NonBlocking {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         let buf_size = buf.len();
@@ -248,15 +301,92 @@ impl<'a> MakeWriter<'a> for NonBlocking {
 }
 
 impl WorkerGuard {
-    fn new(handle: JoinHandle<()>, sender: Sender<Msg>, shutdown: Sender<()>) -> Self {
+    fn new(
+        handle: JoinHandle<()>,
+        error_counter: Arc<AtomicU64>,
+        sender: Sender<Msg>,
+        shutdown: Sender<()>,
+    ) -> Self {
         WorkerGuard {
             guard: Some(handle),
+            error_counter,
             sender,
             shutdown,
         }
     }
+
+    /// Returns the number of lines that were dropped because the channel was
+    /// at capacity.
+    ///
+    /// This will always return zero if the [`NonBlocking`] this guard belongs
+    /// to is not lossy.
+    pub fn dropped_lines(&self) -> u64 {
+        self.error_counter.load(Ordering::Acquire)
+    }
+
+    /// Flushes all buffered lines to the underlying writer, blocking until the
+    /// worker thread acknowledges the flush or `timeout` elapses.
+    ///
+    /// This can be used to make all logs written so far visible at a
+    /// checkpoint, without shutting the worker down by dropping the guard.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`FlushError`] if the worker thread has shut down, or if it
+    /// did not acknowledge the flush within `timeout`. The latter also occurs
+    /// when flushing the underlying writer fails; the error itself is reported
+    /// to the [error callback](NonBlockingBuilder::on_error).
+    pub fn flush(&self, timeout: Duration) -> Result<(), FlushError> {
+        let start = Instant::now();
+        let (ack_sender, ack_receiver) = bounded(1);
+        match self.sender.send_timeout(Msg::Flush(ack_sender), timeout) {
+            Ok(()) => {}
+            Err(SendTimeoutError::Timeout(_)) => return Err(FlushError(FlushErrorKind::Timeout)),
+            Err(SendTimeoutError::Disconnected(_)) => {
+                return Err(FlushError(FlushErrorKind::Closed))
+            }
+        }
+
+        let remaining = timeout.saturating_sub(start.elapsed());
+        match ack_receiver.recv_timeout(remaining) {
+            Ok(()) => Ok(()),
+            Err(RecvTimeoutError::Timeout) => Err(FlushError(FlushErrorKind::Timeout)),
+            Err(RecvTimeoutError::Disconnected) => Err(FlushError(FlushErrorKind::Closed)),
+        }
+    }
+}
+
+/// An error returned by [`WorkerGuard::flush`].
+#[derive(Debug)]
+pub struct FlushError(FlushErrorKind);
+
+#[derive(Debug)]
+enum FlushErrorKind {
+    /// The worker thread did not acknowledge the flush before the timeout.
+    Timeout,
+    /// The worker thread has shut down.
+    Closed,
+}
+
+impl FlushError {
+    /// Returns `true` if the flush was not acknowledged before the timeout
+    /// elapsed.
+    pub fn is_timeout(&self) -> bool {
+        matches!(self.0, FlushErrorKind::Timeout)
+    }
+}
+
+impl fmt::Display for FlushError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            FlushErrorKind::Timeout => f.pad("flush was not acknowledged before the timeout"),
+            FlushErrorKind::Closed => f.pad("the logging worker thread has shut down"),
+        }
+    }
 }
 
+impl std::error::Error for FlushError {}
+
 impl Drop for WorkerGuard {
     fn drop(&mut self) {
         match self
@@ -428,4 +558,156 @@ mod test {
         assert_eq!(10, hello_count);
         assert_eq!(0, error_count.load(Ordering::Acquire));
     }
+
+    #[test]
+    fn flushed_on_guard_drop() {
+        let (mock_writer, rx) = MockWriter::new(DEFAULT_BUFFERED_LINES_LIMIT);
+
+        let (mut non_blocking, guard) = self::NonBlockingBuilder::default()
+            .lossy(false)
+            .finish(mock_writer);
+
+        for i in 0..10 {
+            non_blocking
+                .write_all(format!("Line {}\n", i).as_bytes())
+                .expect("Failed to write");
+        }
+
+        // All lines written before the guard is dropped must be flushed to the
+        // writer by the time the worker thread has shut down.
+        drop(non_blocking);
+        drop(guard);
+
+        let mut lines = String::new();
+        while let Ok(line) = rx.try_recv() {
+            lines.push_str(&line);
+        }
+
+        for i in 0..10 {
+            assert!(lines.contains(&format!("Line {}", i)));
+        }
+    }
+
+    #[test]
+    fn error_callback_invoked_on_write_failure() {
+        /// A writer that accepts `limit` bytes, then fails every write.
+        struct FailAfter {
+            remaining: usize,
+        }
+
+        impl std::io::Write for FailAfter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                if self.remaining < buf.len() {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::WriteZero,
+                        "disk full",
+                    ));
+                }
+                self.remaining -= buf.len();
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let errors2 = errors.clone();
+        let (mut non_blocking, _guard) = self::NonBlockingBuilder::default()
+            .lossy(false)
+            .on_error(move |e| errors2.lock().unwrap().push(e.to_string()))
+            .finish(FailAfter { remaining: 5 });
+
+        non_blocking.write_all(b"Hello").expect("Failed to write");
+        non_blocking.write_all(b", World").expect("Failed to write");
+
+        // Wait for the worker to hit the failing write and report it.
+        for _ in 0..50 {
+            if !errors.lock().unwrap().is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1, "expected exactly one error: {:?}", *errors);
+        assert!(errors[0].contains("disk full"));
+    }
+
+    #[test]
+    fn flush_makes_buffered_lines_visible() {
+        /// A writer that buffers lines internally, making them visible only
+        /// when flushed.
+        struct BufferedWriter {
+            buf: Vec<u8>,
+            visible: Arc<Mutex<String>>,
+        }
+
+        impl std::io::Write for BufferedWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.buf.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                self.visible
+                    .lock()
+                    .unwrap()
+                    .push_str(&String::from_utf8_lossy(&self.buf));
+                self.buf.clear();
+                Ok(())
+            }
+        }
+
+        let visible = Arc::new(Mutex::new(String::new()));
+        let writer = BufferedWriter {
+            buf: Vec::new(),
+            visible: visible.clone(),
+        };
+
+        let (mut non_blocking, guard) = self::NonBlockingBuilder::default()
+            .lossy(false)
+            .finish(writer);
+
+        for i in 0..10 {
+            non_blocking
+                .write_all(format!("Line {}\n", i).as_bytes())
+                .expect("Failed to write");
+        }
+
+        // Once the flush is acknowledged, every line written before it must
+        // be visible, without dropping the guard.
+        guard
+            .flush(Duration::from_secs(5))
+            .expect("flush should be acknowledged");
+
+        let visible = visible.lock().unwrap();
+        for i in 0..10 {
+            assert!(visible.contains(&format!("Line {}", i)));
+        }
+    }
+
+    #[test]
+    fn dropped_lines_queryable_from_guard() {
+        let (mock_writer, _rx) = MockWriter::new(1);
+
+        let (mut non_blocking, guard) = self::NonBlockingBuilder::default()
+            .lossy(true)
+            .buffered_lines_limit(1)
+            .finish(mock_writer);
+
+        assert_eq!(0, guard.dropped_lines());
+
+        // Fill the channel (and the writer behind it), then keep writing until
+        // a line is dropped rather than blocking the caller.
+        for _ in 0..100 {
+            non_blocking.write_all(b"Hello").expect("Failed to write");
+            if guard.dropped_lines() > 0 {
+                return;
+            }
+        }
+
+        panic!("lossy writer should have dropped at least one line");
+    }
 }
@@ -27,10 +27,10 @@
 //! # }
 //! ```
 use crate::inner::InnerAppender;
-use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
-use std::fmt::Debug;
-use std::io;
-use std::path::Path;
+use chrono::{DateTime, Datelike, LocalResult, NaiveDate, TimeZone, Timelike, Utc};
+use std::fmt::{self, Debug};
+use std::path::{Path, PathBuf};
+use std::{error::Error, fs, io};
 
 /// A file appender with the ability to rotate log files at a fixed schedule.
 ///
@@ -82,16 +82,318 @@ impl RollingFileAppender {
         directory: impl AsRef<Path>,
         file_name_prefix: impl AsRef<Path>,
     ) -> RollingFileAppender {
-        RollingFileAppender {
-            inner: InnerAppender::new(
-                directory.as_ref(),
-                file_name_prefix.as_ref(),
-                rotation,
-                Utc::now(),
-            )
-            .expect("Failed to create appender"),
+        let file_name_prefix = file_name_prefix
+            .as_ref()
+            .to_str()
+            .expect("file name prefix must be valid UTF-8");
+        Builder::new()
+            .rotation(rotation)
+            .filename_prefix(file_name_prefix)
+            .build(directory)
+            .expect("Failed to create appender")
+    }
+
+    /// Returns a new [`Builder`] for configuring a `RollingFileAppender`.
+    ///
+    /// The builder interface can be used to set additional options, such as
+    /// a limit on the number of log files kept on disk. Unlike this type's
+    /// constructors, [`Builder::build`] validates its configuration and
+    /// returns an [`InitError`] rather than panicking when the appender
+    /// cannot be initialized.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # fn docs() -> Result<(), tracing_appender::rolling::InitError> {
+    /// use tracing_appender::rolling::{RollingFileAppender, Rotation};
+    /// let file_appender = RollingFileAppender::builder()
+    ///     .rotation(Rotation::HOURLY)
+    ///     .filename_prefix("prefix.log")
+    ///     .max_files(24)
+    ///     .build("/some/directory")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+
+    /// Discards the current writer and opens the current log file again,
+    /// recreating the log directory if it no longer exists.
+    ///
+    /// The appender also periodically checks on write whether its log file
+    /// still exists, so calling this is only necessary to force a reopen
+    /// immediately — for example, from a `SIGHUP` handler after an external
+    /// log rotation tool has moved the file.
+    pub fn reopen(&mut self) -> io::Result<()> {
+        self.inner.reopen()
+    }
+}
+
+/// A builder for configuring a [`RollingFileAppender`].
+#[derive(Debug)]
+pub struct Builder {
+    pub(crate) rotation: Rotation,
+    pub(crate) max_files: Option<usize>,
+    pub(crate) use_local_time: bool,
+    pub(crate) date_format: Option<String>,
+    pub(crate) filename_prefix: Option<String>,
+    pub(crate) filename_suffix: Option<String>,
+    #[cfg(feature = "compress")]
+    pub(crate) compress_rotated: bool,
+    pub(crate) create_current_symlink: bool,
+    pub(crate) create_directory: bool,
+}
+
+impl Builder {
+    /// Returns a new `Builder` for configuring a [`RollingFileAppender`], with
+    /// the default parameters.
+    ///
+    /// By default, the appender rotates hourly, timestamps file names in UTC,
+    /// creates the log directory if it does not exist, and does not limit the
+    /// number of log files kept on disk.
+    pub fn new() -> Self {
+        Self {
+            rotation: Rotation::HOURLY,
+            max_files: None,
+            use_local_time: false,
+            date_format: None,
+            filename_prefix: None,
+            filename_suffix: None,
+            #[cfg(feature = "compress")]
+            compress_rotated: false,
+            create_current_symlink: false,
+            create_directory: true,
         }
     }
+
+    /// Sets the [rotation strategy][Rotation] used by the appender.
+    ///
+    /// By default, this is [`Rotation::HOURLY`].
+    pub fn rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Keeps at most `n` log files on disk.
+    ///
+    /// After each rollover, files in the log directory whose names begin with
+    /// the appender's file name prefix are enumerated, sorted by the date
+    /// encoded in their names, and the oldest ones beyond the limit are
+    /// deleted. Failures to delete a file are reported to standard error but
+    /// do not interrupt the write path.
+    ///
+    /// By default, no files are removed.
+    pub fn max_files(mut self, n: usize) -> Self {
+        self.max_files = Some(n);
+        self
+    }
+
+    /// Uses the system's local timezone, rather than UTC, for the date
+    /// appended to file names and for determining when to roll over to a new
+    /// file.
+    ///
+    /// When this is set, a daily rotation occurs at _local_ midnight, even on
+    /// 23- and 25-hour days around daylight-savings transitions.
+    ///
+    /// By default, UTC is used.
+    pub fn local_time(mut self, local_time: bool) -> Self {
+        self.use_local_time = local_time;
+        self
+    }
+
+    /// Overrides the `strftime` pattern used to format the date in rolled
+    /// file names.
+    ///
+    /// By default, the pattern is determined by the [rotation
+    /// strategy][Rotation]: `%F-%H-%M` for [`Rotation::MINUTELY`], `%F-%H`
+    /// for [`Rotation::HOURLY`], and `%F` for [`Rotation::DAILY`]. The
+    /// pattern is ignored when the rotation is [`Rotation::NEVER`].
+    pub fn date_format(mut self, format: impl Into<String>) -> Self {
+        self.date_format = Some(format.into());
+        self
+    }
+
+    /// Sets the prefix prepended to file names, _before_ the date.
+    ///
+    /// A prefix of `app` with hourly rotation produces names such as
+    /// `app.2020-02-01-10`. Setting an empty string unsets the prefix, so
+    /// file names begin with the date.
+    ///
+    /// By default, no prefix is prepended; a prefix (or
+    /// [suffix][Self::filename_suffix]) is required when the rotation is
+    /// [`Rotation::NEVER`], as file names would otherwise be empty.
+    pub fn filename_prefix(mut self, prefix: impl Into<String>) -> Self {
+        let prefix = prefix.into();
+        // An empty prefix would produce file names with a leading `.`;
+        // treat it as unsetting the prefix instead.
+        self.filename_prefix = if prefix.is_empty() {
+            None
+        } else {
+            Some(prefix)
+        };
+        self
+    }
+
+    /// Appends `suffix` to file names, _after_ the date.
+    ///
+    /// This allows the file extension to follow the date, producing names
+    /// such as `app.2020-02-01.log` from a prefix of `app` and a suffix of
+    /// `log`.
+    ///
+    /// By default, no suffix is appended.
+    pub fn filename_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.filename_suffix = Some(suffix.into());
+        self
+    }
+
+    /// Gzip-compresses each log file after it is rotated away from.
+    ///
+    /// After a rollover, the just-closed file is compressed on a background
+    /// thread, producing a file with a `.gz` suffix appended to its name; the
+    /// uncompressed file is removed once compression succeeds. Compression
+    /// never blocks the write path, and if it fails, the uncompressed file is
+    /// left in place and the error is reported to standard error.
+    ///
+    /// This method is only available when the `compress` feature flag is
+    /// enabled.
+    ///
+    /// By default, rotated files are not compressed.
+    #[cfg(feature = "compress")]
+    pub fn compress_rotated(mut self, compress: bool) -> Self {
+        self.compress_rotated = compress;
+        self
+    }
+
+    /// Maintains a symbolic link with a stable name pointing at the file
+    /// currently being written.
+    ///
+    /// The link is named after the appender's file name prefix (and
+    /// [suffix][Self::filename_suffix], if one is configured), so a prefix of
+    /// `app` and a suffix of `log` produce a link named `app.log` alongside
+    /// the dated files. The link is updated on every rollover, so `tail -F
+    /// app.log` keeps following the log across rotations.
+    ///
+    /// On Windows, where creating symbolic links requires elevated
+    /// privileges, a hard link to the current file is created instead; if
+    /// that also fails, the link is skipped with a warning. This option has
+    /// no effect with [`Rotation::NEVER`], as the current file already has
+    /// the stable name.
+    ///
+    /// By default, no link is created.
+    pub fn create_current_symlink(mut self, create: bool) -> Self {
+        self.create_current_symlink = create;
+        self
+    }
+
+    /// Creates the log directory if it does not exist.
+    ///
+    /// When this is disabled, [`build`][Self::build] returns an error if the
+    /// directory is missing, rather than creating it.
+    ///
+    /// By default, the directory is created.
+    pub fn create_directory(mut self, create: bool) -> Self {
+        self.create_directory = create;
+        self
+    }
+
+    /// Builds a `RollingFileAppender` that writes files in the provided
+    /// `directory`.
+    ///
+    /// Unlike the [`RollingFileAppender`] constructors, this validates the
+    /// configuration and the directory, returning an [`InitError`] rather
+    /// than panicking if the directory does not exist (and
+    /// [`create_directory`][Self::create_directory] is disabled), is not a
+    /// directory, or the initial log file cannot be created.
+    pub fn build(self, directory: impl AsRef<Path>) -> Result<RollingFileAppender, InitError> {
+        let directory = directory.as_ref();
+        if self.rotation == Rotation::NEVER
+            && self.filename_prefix.is_none()
+            && self.filename_suffix.is_none()
+        {
+            return Err(InitError {
+                kind: InitErrorKind::MissingFilename,
+            });
+        }
+
+        if directory.exists() {
+            if !directory.is_dir() {
+                return Err(InitError {
+                    kind: InitErrorKind::NotADirectory(directory.to_path_buf()),
+                });
+            }
+        } else if self.create_directory {
+            fs::create_dir_all(directory)?;
+        } else {
+            return Err(InitError {
+                kind: InitErrorKind::MissingDirectory(directory.to_path_buf()),
+            });
+        }
+
+        let inner = InnerAppender::new(directory, Utc::now(), self)?;
+        Ok(RollingFileAppender { inner })
+    }
+}
+
+/// An error returned by [`Builder::build`] when a `RollingFileAppender`
+/// cannot be initialized.
+#[derive(Debug)]
+pub struct InitError {
+    kind: InitErrorKind,
+}
+
+#[derive(Debug)]
+enum InitErrorKind {
+    /// The log directory does not exist, and creating it was disabled.
+    MissingDirectory(PathBuf),
+    /// The log directory path exists, but is not a directory.
+    NotADirectory(PathBuf),
+    /// The configuration would produce empty file names.
+    MissingFilename,
+    /// Creating the log directory or the initial log file failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for InitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            InitErrorKind::MissingDirectory(ref path) => {
+                write!(f, "log directory {} does not exist", path.display())
+            }
+            InitErrorKind::NotADirectory(ref path) => {
+                write!(f, "log directory {} is not a directory", path.display())
+            }
+            InitErrorKind::MissingFilename => f.pad(
+                "a filename prefix or suffix is required when using `Rotation::NEVER`, \
+                 as file names would otherwise be empty",
+            ),
+            InitErrorKind::Io(ref err) => {
+                write!(f, "failed to create initial log file: {}", err)
+            }
+        }
+    }
+}
+
+impl Error for InitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self.kind {
+            InitErrorKind::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for InitError {
+    fn from(err: io::Error) -> Self {
+        Self {
+            kind: InitErrorKind::Io(err),
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl io::Write for RollingFileAppender {
@@ -297,45 +599,119 @@ impl Rotation {
     /// Provides a rotation that never rotates.
     pub const NEVER: Self = Self(RotationKind::Never);
 
-    pub(crate) fn next_date(&self, current_date: &DateTime<Utc>) -> DateTime<Utc> {
+    // The next rollover boundary is computed in `current_date`'s timezone, so
+    // that a rollover configured to use local time still occurs at local
+    // midnight on 23- and 25-hour days around daylight-savings transitions.
+    pub(crate) fn next_date<Tz>(&self, current_date: &DateTime<Tz>) -> DateTime<Tz>
+    where
+        Tz: TimeZone,
+    {
         let unrounded_next_date = match *self {
-            Rotation::MINUTELY => *current_date + chrono::Duration::minutes(1),
-            Rotation::HOURLY => *current_date + chrono::Duration::hours(1),
-            Rotation::DAILY => *current_date + chrono::Duration::days(1),
-            Rotation::NEVER => Utc.ymd(9999, 1, 1).and_hms(1, 0, 0),
+            Rotation::MINUTELY => current_date.clone() + chrono::Duration::minutes(1),
+            Rotation::HOURLY => current_date.clone() + chrono::Duration::hours(1),
+            Rotation::DAILY => current_date.clone() + chrono::Duration::days(1),
+            Rotation::NEVER => resolve_wall_clock(&current_date.timezone(), 9999, 1, 1, 1, 0),
         };
         self.round_date(&unrounded_next_date)
     }
 
-    pub(crate) fn round_date(&self, date: &DateTime<Utc>) -> DateTime<Utc> {
+    pub(crate) fn round_date<Tz>(&self, date: &DateTime<Tz>) -> DateTime<Tz>
+    where
+        Tz: TimeZone,
+    {
+        let tz = date.timezone();
+        let (year, month, day) = (date.year(), date.month(), date.day());
         match *self {
-            Rotation::MINUTELY => Utc.ymd(date.year(), date.month(), date.day()).and_hms(
-                date.hour(),
-                date.minute(),
-                0,
-            ),
-            Rotation::HOURLY => {
-                Utc.ymd(date.year(), date.month(), date.day())
-                    .and_hms(date.hour(), 0, 0)
+            Rotation::MINUTELY => {
+                resolve_wall_clock(&tz, year, month, day, date.hour(), date.minute())
             }
-            Rotation::DAILY => Utc
-                .ymd(date.year(), date.month(), date.day())
-                .and_hms(0, 0, 0),
-            Rotation::NEVER => {
-                Utc.ymd(date.year(), date.month(), date.day())
-                    .and_hms(date.hour(), 0, 0)
+            Rotation::HOURLY => resolve_wall_clock(&tz, year, month, day, date.hour(), 0),
+            Rotation::DAILY => resolve_wall_clock(&tz, year, month, day, 0, 0),
+            Rotation::NEVER => resolve_wall_clock(&tz, year, month, day, date.hour(), 0),
+        }
+    }
+
+    pub(crate) fn join_date<Tz>(
+        &self,
+        filename: Option<&str>,
+        date: &DateTime<Tz>,
+        format: Option<&str>,
+        suffix: Option<&str>,
+    ) -> String
+    where
+        Tz: TimeZone,
+        Tz::Offset: fmt::Display,
+    {
+        let date = match (self, format) {
+            (&Rotation::NEVER, _) => None,
+            (_, Some(format)) => Some(date.format(format).to_string()),
+            (&Rotation::MINUTELY, None) => Some(date.format("%F-%H-%M").to_string()),
+            (&Rotation::HOURLY, None) => Some(date.format("%F-%H").to_string()),
+            (&Rotation::DAILY, None) => Some(date.format("%F").to_string()),
+        };
+        match (filename, date, suffix) {
+            (Some(filename), None, None) => filename.to_string(),
+            (Some(filename), None, Some(suffix)) => format!("{}.{}", filename, suffix),
+            (Some(filename), Some(date), None) => format!("{}.{}", filename, date),
+            (Some(filename), Some(date), Some(suffix)) => {
+                format!("{}.{}.{}", filename, date, suffix)
             }
+            (None, Some(date), None) => date,
+            (None, Some(date), Some(suffix)) => format!("{}.{}", date, suffix),
+            (None, None, Some(suffix)) => suffix.to_string(),
+            // `Builder::build` rejects configurations that would produce an
+            // empty file name.
+            (None, None, None) => String::new(),
         }
     }
+}
 
-    pub(crate) fn join_date(&self, filename: &str, date: &DateTime<Utc>) -> String {
-        match *self {
-            Rotation::MINUTELY => format!("{}.{}", filename, date.format("%F-%H-%M")),
-            Rotation::HOURLY => format!("{}.{}", filename, date.format("%F-%H")),
-            Rotation::DAILY => format!("{}.{}", filename, date.format("%F")),
-            Rotation::NEVER => filename.to_string(),
+/// The longest wall-clock gap, in minutes, that [`resolve_wall_clock`] scans
+/// across when a rollover boundary was skipped by an offset transition.
+///
+/// Daylight-savings gaps are an hour (rarely two); the largest gap on record
+/// is the full day Samoa skipped when it crossed the International Date Line
+/// in 2011, so two days covers everything a timezone database can produce.
+const MAX_GAP_SCAN_MINUTES: i64 = 48 * 60;
+
+/// Returns the instant in `tz` whose wall-clock reading is the given date and
+/// time, resolving readings that a daylight-savings transition makes
+/// ambiguous or nonexistent.
+///
+/// A reading that occurs twice (clocks were set back through it, on a
+/// 25-hour day) resolves to the earlier of the two instants, so a rollover
+/// happens the first time the wall clock reaches the boundary. A reading
+/// that never occurs (clocks were set forward over it, on a 23-hour day)
+/// resolves to the first valid reading after the gap: a daily rotation in a
+/// timezone that springs forward at midnight rolls over at 01:00 that day.
+fn resolve_wall_clock<Tz>(
+    tz: &Tz,
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+) -> DateTime<Tz>
+where
+    Tz: TimeZone,
+{
+    let naive = NaiveDate::from_ymd_opt(year, month, day)
+        .and_then(|date| date.and_hms_opt(hour, minute, 0))
+        .expect("rollover boundaries are built from valid date components; this is a bug");
+
+    let mut candidate = naive;
+    for _ in 0..=MAX_GAP_SCAN_MINUTES {
+        match tz.from_local_datetime(&candidate) {
+            LocalResult::Single(datetime) => return datetime,
+            LocalResult::Ambiguous(earliest, _) => return earliest,
+            // The reading was skipped; try the next wall-clock minute.
+            LocalResult::None => candidate += chrono::Duration::minutes(1),
         }
     }
+
+    // A timezone whose gap swallowed the entire scan window; interpret the
+    // boundary as UTC rather than panicking in the write path.
+    tz.from_utc_datetime(&naive)
 }
 
 #[cfg(test)]
@@ -534,7 +910,7 @@ mod test {
     fn test_rotation_path_minutely() {
         let r = Rotation::MINUTELY;
         let mock_now = Utc.ymd(2020, 2, 1).and_hms(10, 3, 1);
-        let path = r.join_date("MyApplication.log", &mock_now);
+        let path = r.join_date(Some("MyApplication.log"), &mock_now, None, None);
         assert_eq!("MyApplication.log.2020-02-01-10-03", path);
     }
 
@@ -542,7 +918,7 @@ mod test {
     fn test_rotation_path_hourly() {
         let r = Rotation::HOURLY;
         let mock_now = Utc.ymd(2020, 2, 1).and_hms(10, 3, 1);
-        let path = r.join_date("MyApplication.log", &mock_now);
+        let path = r.join_date(Some("MyApplication.log"), &mock_now, None, None);
         assert_eq!("MyApplication.log.2020-02-01-10", path);
     }
 
@@ -550,7 +926,7 @@ mod test {
     fn test_rotation_path_daily() {
         let r = Rotation::DAILY;
         let mock_now = Utc.ymd(2020, 2, 1).and_hms(10, 3, 1);
-        let path = r.join_date("MyApplication.log", &mock_now);
+        let path = r.join_date(Some("MyApplication.log"), &mock_now, None, None);
         assert_eq!("MyApplication.log.2020-02-01", path);
     }
 
@@ -581,7 +957,271 @@ mod test {
         let r = Rotation::NEVER;
 
         let mock_now = Utc.ymd(2020, 2, 1).and_hms(0, 0, 0);
-        let joined_date = r.join_date("Hello.log", &mock_now);
+        let joined_date = r.join_date(Some("Hello.log"), &mock_now, None, None);
         assert_eq!(joined_date, "Hello.log");
     }
+
+    #[test]
+    fn test_join_date_custom_format() {
+        let r = Rotation::DAILY;
+
+        let mock_now = Utc.ymd(2020, 2, 1).and_hms(10, 3, 1);
+        let joined_date = r.join_date(Some("app"), &mock_now, Some("%Y%m%d"), None);
+        assert_eq!(joined_date, "app.20200201");
+    }
+
+    #[test]
+    fn test_join_date_suffix() {
+        let r = Rotation::DAILY;
+
+        let mock_now = Utc.ymd(2020, 2, 1).and_hms(10, 3, 1);
+        let joined_date = r.join_date(Some("app"), &mock_now, None, Some("log"));
+        assert_eq!(joined_date, "app.2020-02-01.log");
+
+        let joined_date = Rotation::NEVER.join_date(Some("app"), &mock_now, None, Some("log"));
+        assert_eq!(joined_date, "app.log");
+    }
+
+    #[test]
+    fn builder_missing_directory_is_an_error() {
+        let directory = TempDir::new("missing").expect("Failed to create tempdir");
+        let missing = directory.path().join("does-not-exist");
+
+        let err = RollingFileAppender::builder()
+            .filename_prefix("app.log")
+            .create_directory(false)
+            .build(&missing)
+            .expect_err("building in a missing directory should fail");
+        assert!(
+            err.to_string().contains("does not exist"),
+            "unexpected error: {}",
+            err
+        );
+        assert!(!missing.exists(), "directory should not have been created");
+    }
+
+    #[test]
+    fn builder_creates_missing_directory_by_default() {
+        let directory = TempDir::new("create").expect("Failed to create tempdir");
+        let nested = directory.path().join("logs").join("app");
+
+        let mut appender = RollingFileAppender::builder()
+            .filename_prefix("app.log")
+            .build(&nested)
+            .expect("builder should create the missing directory");
+        write_to_log(&mut appender, "Hello");
+        assert!(find_str_in_log(&nested, "Hello"));
+    }
+
+    #[test]
+    fn builder_file_in_place_of_directory_is_an_error() {
+        let directory = TempDir::new("notadir").expect("Failed to create tempdir");
+        let file = directory.path().join("actually-a-file");
+        fs::write(&file, b"").expect("Failed to create file");
+
+        let err = RollingFileAppender::builder()
+            .filename_prefix("app.log")
+            .build(&file)
+            .expect_err("building in a file should fail");
+        assert!(
+            err.to_string().contains("is not a directory"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn builder_nonexistent_parent_blocked_by_file_is_an_error() {
+        let directory = TempDir::new("blocked").expect("Failed to create tempdir");
+        let file = directory.path().join("actually-a-file");
+        fs::write(&file, b"").expect("Failed to create file");
+
+        // The requested directory's *parent* is a plain file, so it cannot be
+        // created; this must surface as an error rather than a panic.
+        let err = RollingFileAppender::builder()
+            .filename_prefix("app.log")
+            .build(file.join("logs"))
+            .expect_err("building below a file should fail");
+        assert!(
+            err.to_string()
+                .contains("failed to create initial log file"),
+            "unexpected error: {}",
+            err
+        );
+        assert!(
+            std::error::Error::source(&err).is_some(),
+            "io errors should expose a source"
+        );
+    }
+
+    #[test]
+    fn builder_never_rotation_requires_a_filename() {
+        let directory = TempDir::new("unnamed").expect("Failed to create tempdir");
+
+        let err = RollingFileAppender::builder()
+            .rotation(Rotation::NEVER)
+            .build(directory.path())
+            .expect_err("`Rotation::NEVER` with no filename should fail");
+        assert!(
+            err.to_string().contains("prefix or suffix"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn builder_accepts_non_utf8_directories() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let directory = TempDir::new("nonutf8").expect("Failed to create tempdir");
+        // A directory name containing invalid UTF-8; the old constructors
+        // would have panicked on `to_str().unwrap()`.
+        let non_utf8 = directory
+            .path()
+            .join(OsStr::from_bytes(b"bl\xc3\x28rg"))
+            .join("logs");
+
+        let mut appender = RollingFileAppender::builder()
+            .filename_prefix("app.log")
+            .build(&non_utf8)
+            .expect("non-UTF-8 directories should be accepted");
+        write_to_log(&mut appender, "Hello");
+        assert!(find_str_in_log(&non_utf8, "Hello"));
+    }
+
+    #[test]
+    fn test_next_date_daily_fixed_offset() {
+        use chrono::FixedOffset;
+
+        // The rollover boundary must be midnight in the rotation's timezone,
+        // not UTC midnight.
+        let r = Rotation::DAILY;
+        let tz = FixedOffset::east(5 * 3600 + 1800);
+
+        let mock_now = tz.ymd(2020, 8, 1).and_hms(11, 20, 5);
+        let next = r.next_date(&mock_now);
+        assert_eq!(tz.ymd(2020, 8, 2).and_hms(0, 0, 0), next);
+        assert_eq!(Utc.ymd(2020, 8, 1).and_hms(18, 30, 0), next);
+    }
+
+    use chrono::{FixedOffset, NaiveDateTime, Offset};
+
+    /// A mock timezone with daylight-savings transitions at midnight, like
+    /// Cuba's: clocks spring forward from 00:00 to 01:00 on 2020-03-08 (a
+    /// 23-hour day with no local midnight), and fall back from 01:00 to
+    /// 00:00 on 2020-11-01 (a 25-hour day on which the readings between
+    /// 00:00 and 01:00 occur twice).
+    #[derive(Clone, Copy, Debug)]
+    struct MidnightDst;
+
+    #[derive(Clone, Copy, Debug)]
+    struct MidnightDstOffset(FixedOffset);
+
+    impl Offset for MidnightDstOffset {
+        fn fix(&self) -> FixedOffset {
+            self.0
+        }
+    }
+
+    impl MidnightDst {
+        fn std() -> MidnightDstOffset {
+            MidnightDstOffset(FixedOffset::west_opt(5 * 3600).unwrap())
+        }
+
+        fn dst() -> MidnightDstOffset {
+            MidnightDstOffset(FixedOffset::west_opt(4 * 3600).unwrap())
+        }
+
+        fn naive(year: i32, month: u32, day: u32, hour: u32, min: u32) -> NaiveDateTime {
+            NaiveDate::from_ymd_opt(year, month, day)
+                .unwrap()
+                .and_hms_opt(hour, min, 0)
+                .unwrap()
+        }
+    }
+
+    impl TimeZone for MidnightDst {
+        type Offset = MidnightDstOffset;
+
+        fn from_offset(_: &Self::Offset) -> Self {
+            MidnightDst
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<Self::Offset> {
+            self.offset_from_local_datetime(&local.and_hms_opt(12, 0, 0).unwrap())
+        }
+
+        fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<Self::Offset> {
+            let gap_start = Self::naive(2020, 3, 8, 0, 0);
+            let gap_end = Self::naive(2020, 3, 8, 1, 0);
+            let overlap_start = Self::naive(2020, 11, 1, 0, 0);
+            let overlap_end = Self::naive(2020, 11, 1, 1, 0);
+            if (gap_start..gap_end).contains(local) {
+                LocalResult::None
+            } else if (overlap_start..overlap_end).contains(local) {
+                LocalResult::Ambiguous(Self::dst(), Self::std())
+            } else if (gap_end..overlap_start).contains(local) {
+                LocalResult::Single(Self::dst())
+            } else {
+                LocalResult::Single(Self::std())
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> Self::Offset {
+            self.offset_from_utc_datetime(&utc.and_hms_opt(12, 0, 0).unwrap())
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> Self::Offset {
+            // Both transitions occur at 05:00 UTC: 00:00 STD springs forward
+            // to 01:00 DST, and 01:00 DST falls back to 00:00 STD.
+            if (Self::naive(2020, 3, 8, 5, 0)..Self::naive(2020, 11, 1, 5, 0)).contains(utc) {
+                Self::dst()
+            } else {
+                Self::std()
+            }
+        }
+    }
+
+    #[test]
+    fn test_next_date_daily_skipped_midnight() {
+        // On the 23-hour day, there is no local midnight to roll over at;
+        // the rollover must not panic, and lands on the first valid instant
+        // of the new day, 01:00.
+        let r = Rotation::DAILY;
+        let tz = MidnightDst;
+        let current = tz
+            .from_local_datetime(&MidnightDst::naive(2020, 3, 7, 22, 0))
+            .unwrap();
+
+        let next = r.next_date(&current);
+        assert_eq!(
+            tz.from_local_datetime(&MidnightDst::naive(2020, 3, 8, 1, 0))
+                .unwrap(),
+            next,
+        );
+        assert_eq!(Utc.with_ymd_and_hms(2020, 3, 8, 5, 0, 0).unwrap(), next);
+    }
+
+    #[test]
+    fn test_next_date_hourly_ambiguous_hour() {
+        // On the 25-hour day, the 00:00 boundary occurs twice; the rollover
+        // must not panic, and resolves to the earlier (daylight-savings)
+        // instant, so it happens the first time the clock reads 00:00.
+        let r = Rotation::HOURLY;
+        let tz = MidnightDst;
+        let current = tz
+            .from_local_datetime(&MidnightDst::naive(2020, 10, 31, 23, 30))
+            .unwrap();
+
+        let next = r.next_date(&current);
+        assert_eq!(Utc.with_ymd_and_hms(2020, 11, 1, 4, 0, 0).unwrap(), next);
+
+        // Rolling over *out* of the repeated hour (at 01:00 standard time,
+        // which only occurs once) is unambiguous.
+        let in_overlap = next + chrono::Duration::minutes(90);
+        let next = r.next_date(&in_overlap);
+        assert_eq!(Utc.with_ymd_and_hms(2020, 11, 1, 6, 0, 0).unwrap(), next);
+    }
 }
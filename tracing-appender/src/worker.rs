@@ -8,6 +8,7 @@ pub(crate) struct Worker<T: Write + Send + Sync + 'static> {
     writer: T,
     receiver: Receiver<Msg>,
     shutdown: Receiver<()>,
+    on_error: Box<dyn Fn(io::Error) + Send + Sync>,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -19,11 +20,17 @@ pub(crate) enum WorkerState {
 }
 
 impl<T: Write + Send + Sync + 'static> Worker<T> {
-    pub(crate) fn new(receiver: Receiver<Msg>, writer: T, shutdown: Receiver<()>) -> Worker<T> {
+    pub(crate) fn new(
+        receiver: Receiver<Msg>,
+        writer: T,
+        shutdown: Receiver<()>,
+        on_error: Box<dyn Fn(io::Error) + Send + Sync>,
+    ) -> Worker<T> {
         Self {
             writer,
             receiver,
             shutdown,
+            on_error,
         }
     }
 
@@ -33,6 +40,13 @@ impl<T: Write + Send + Sync + 'static> Worker<T> {
                 self.writer.write_all(&msg)?;
                 Ok(WorkerState::Continue)
             }
+            Ok(Msg::Flush(ack)) => {
+                // If the flush fails, the acknowledgment is never sent and the
+                // requesting `WorkerGuard::flush` call will time out.
+                self.writer.flush()?;
+                let _ = ack.send(());
+                Ok(WorkerState::Continue)
+            }
             Ok(Msg::Shutdown) => Ok(WorkerState::Shutdown),
             Err(_) => Ok(WorkerState::Disconnected),
         }
@@ -44,6 +58,11 @@ impl<T: Write + Send + Sync + 'static> Worker<T> {
                 self.writer.write_all(&msg)?;
                 Ok(WorkerState::Continue)
             }
+            Ok(Msg::Flush(ack)) => {
+                self.writer.flush()?;
+                let _ = ack.send(());
+                Ok(WorkerState::Continue)
+            }
             Ok(Msg::Shutdown) => Ok(WorkerState::Shutdown),
             Err(TryRecvError::Empty) => Ok(WorkerState::Empty),
             Err(TryRecvError::Disconnected) => Ok(WorkerState::Disconnected),
@@ -76,13 +95,11 @@ impl<T: Write + Send + Sync + 'static> Worker<T> {
                         let _ = self.shutdown.recv();
                         break;
                     }
-                    Err(_) => {
-                        // TODO: Expose a metric for IO Errors, or print to stderr
-                    }
+                    Err(e) => (self.on_error)(e),
                 }
             }
             if let Err(e) = self.writer.flush() {
-                eprintln!("Failed to flush. Error: {}", e);
+                (self.on_error)(e);
             }
         })
     }
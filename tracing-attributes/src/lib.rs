@@ -113,6 +113,19 @@ use syn::{
 /// - multiple argument names can be passed to `skip`.
 /// - arguments passed to `skip` do _not_ need to implement `fmt::Debug`.
 ///
+/// To skip *all* arguments, use `skip_all` instead of listing every argument
+/// by name.
+///
+/// Arguments whose type is a generic parameter (or `impl Trait`) are only
+/// recorded when that parameter has a `Debug` bound in the function's
+/// signature. Otherwise, the macro cannot know whether the type can be
+/// formatted, so the argument is recorded as [`tracing::field::Empty`] and a
+/// compile error naming the argument is emitted; add a `Debug` bound, list
+/// the argument in `skip(...)` (or use `skip_all`), or provide an explicit
+/// `fields(...)` expression for it.
+///
+/// [`tracing::field::Empty`]: https://docs.rs/tracing/latest/tracing/field/struct.Empty.html
+///
 /// You can also pass additional fields (key-value pairs with arbitrary data)
 /// to the generated span. This is achieved using the `fields` argument on the
 /// `#[instrument]` macro. You can use a string, integer or boolean literal as
@@ -171,6 +184,17 @@ use syn::{
 /// }
 /// ```
 ///
+/// To skip recording all arguments, use `skip_all`:
+///
+/// ```
+/// # use tracing_attributes::instrument;
+/// # struct NonDebug;
+/// #[instrument(skip_all)]
+/// fn my_function<T>(arg: usize, non_debug: NonDebug, generic: T) {
+///     // ...
+/// }
+/// ```
+///
 /// To add an additional context to the span, you can pass key-value pairs to `fields`:
 ///
 /// ```
@@ -192,6 +216,30 @@ use syn::{
 /// }
 /// ```
 ///
+/// Adding the `ret` argument to `#[instrument]` emits an event with the
+/// function's return value when the function returns, recorded via its
+/// `std::fmt::Debug` implementation. The event has the same level and target
+/// as the span:
+///
+/// ```
+/// # use tracing_attributes::instrument;
+/// #[instrument(ret)]
+/// fn my_function() -> i32 {
+///     42
+/// }
+/// ```
+///
+/// When combined with `err`, only the `Ok` variant's value is recorded, and
+/// an error event is still emitted for the `Err` variant:
+///
+/// ```
+/// # use tracing_attributes::instrument;
+/// #[instrument(err, ret)]
+/// fn my_function(arg: usize) -> Result<(), std::io::Error> {
+///     Ok(())
+/// }
+/// ```
+///
 /// `async fn`s may also be instrumented:
 ///
 /// ```
@@ -299,6 +347,7 @@ pub fn instrument(
                     let instrumented_block = gen_block(
                         &async_expr.block,
                         &input.sig.inputs,
+                        &input.sig.generics,
                         true,
                         args,
                         instrumented_function_name.as_str(),
@@ -367,6 +416,7 @@ fn gen_function(
     let body = gen_block(
         block,
         params,
+        &sig.generics,
         asyncness.is_some(),
         args,
         instrumented_function_name,
@@ -388,12 +438,14 @@ fn gen_function(
 fn gen_block(
     block: &Block,
     params: &Punctuated<FnArg, Token![,]>,
+    generics: &syn::Generics,
     async_context: bool,
     mut args: InstrumentArgs,
     instrumented_function_name: &str,
     self_type: Option<&syn::TypePath>,
 ) -> proc_macro2::TokenStream {
     let err = args.err;
+    let ret = args.ret;
 
     // generate the span's name
     let span_name = args
@@ -405,6 +457,10 @@ fn gen_block(
 
     // generate this inside a closure, so we can return early on errors.
     let span = (|| {
+        // Generic type parameters (and `impl Trait` argument types) can only
+        // be recorded with `Debug` when the signature says they implement it.
+        let unbounded_generics = unbounded_generic_params(generics);
+
         // Pull out the arguments-to-be-skipped first, so we can filter results
         // below.
         let param_names: Vec<(Ident, (Ident, RecordType))> = params
@@ -412,7 +468,7 @@ fn gen_block(
             .into_iter()
             .flat_map(|param| match param {
                 FnArg::Typed(PatType { pat, ty, .. }) => {
-                    param_names(*pat, RecordType::parse_from_ty(&*ty))
+                    param_names(*pat, RecordType::parse_from_ty(&*ty, &unbounded_generics))
                 }
                 FnArg::Receiver(_) => Box::new(iter::once((
                     Ident::new("self", param.span()),
@@ -452,10 +508,11 @@ fn gen_block(
         let target = args.target();
 
         // filter out skipped fields
+        let mut record_errors = Vec::new();
         let quoted_fields: Vec<_> = param_names
             .iter()
             .filter(|(param, _)| {
-                if args.skips.contains(param) {
+                if args.skip_all || args.skips.contains(param) {
                     return false;
                 }
 
@@ -473,6 +530,24 @@ fn gen_block(
             .map(|(user_name, (real_name, record_type))| match record_type {
                 RecordType::Value => quote!(#user_name = #real_name),
                 RecordType::Debug => quote!(#user_name = tracing::field::debug(&#real_name)),
+                RecordType::MissingDebugBound(ty) => {
+                    // We can't know whether the type can be formatted, so
+                    // record the field as `Empty` (keeping the span's shape
+                    // intact) and emit an error naming the parameter, rather
+                    // than letting the expansion fail with an inscrutable
+                    // unsatisfied-trait-bound error.
+                    let msg = format!(
+                        "parameter `{}` has type `{}`, which has no `Debug` bound; \
+                         add a `Debug` bound to record it, list it in `skip(...)` \
+                         (or use `skip_all`), or provide an explicit `fields(...)` \
+                         expression for it",
+                        user_name, ty,
+                    );
+                    record_errors.push(quote_spanned! {user_name.span()=>
+                        compile_error!(#msg);
+                    });
+                    quote!(#user_name = tracing::field::Empty)
+                }
             })
             .collect();
 
@@ -496,21 +571,44 @@ fn gen_block(
 
         let custom_fields = &args.fields;
 
-        quote!(tracing::span!(
+        let span = quote!(tracing::span!(
             target: #target,
             #level,
             #span_name,
             #(#quoted_fields,)*
             #custom_fields
 
-        ))
+        ));
+
+        if record_errors.is_empty() {
+            span
+        } else {
+            quote!({
+                #(#record_errors)*
+                #span
+            })
+        }
     })();
 
+    // If `ret` is in args, generate an event that records the function's
+    // return value, at the same level and target as the span.
+    let ret_event = if ret {
+        let level = args.level();
+        let target = args.target();
+        Some(quote!(
+            tracing::event!(target: #target, #level, return = tracing::field::debug(&x))
+        ))
+    } else {
+        None
+    };
+
     // Generate the instrumented function body.
     // If the function is an `async fn`, this will wrap it in an async block,
     // which is `instrument`ed using `tracing-futures`. Otherwise, this will
     // enter the span and then perform the rest of the body.
     // If `err` is in args, instrument any resulting `Err`s.
+    // If `ret` is in args, record the resulting value (or, for a function
+    // returning a `Result` with `err` also in args, any resulting `Ok`s).
     if async_context {
         if err {
             quote_spanned!(block.span()=>
@@ -518,7 +616,10 @@ fn gen_block(
                 tracing::Instrument::instrument(async move {
                     match async move { #block }.await {
                         #[allow(clippy::unit_arg)]
-                        Ok(x) => Ok(x),
+                        Ok(x) => {
+                            #ret_event;
+                            Ok(x)
+                        },
                         Err(e) => {
                             tracing::error!(error = %e);
                             Err(e)
@@ -526,6 +627,15 @@ fn gen_block(
                     }
                 }, __tracing_attr_span).await
             )
+        } else if ret {
+            quote_spanned!(block.span()=>
+                let __tracing_attr_span = #span;
+                tracing::Instrument::instrument(async move {
+                    let x = async move { #block }.await;
+                    #ret_event;
+                    x
+                }, __tracing_attr_span).await
+            )
         } else {
             quote_spanned!(block.span()=>
                 let __tracing_attr_span = #span;
@@ -543,13 +653,25 @@ fn gen_block(
             #[allow(clippy::redundant_closure_call)]
             match (move || #block)() {
                 #[allow(clippy::unit_arg)]
-                Ok(x) => Ok(x),
+                Ok(x) => {
+                    #ret_event;
+                    Ok(x)
+                },
                 Err(e) => {
                     tracing::error!(error = %e);
                     Err(e)
                 }
             }
         )
+    } else if ret {
+        quote_spanned!(block.span()=>
+            let __tracing_attr_span = #span;
+            let __tracing_attr_guard = __tracing_attr_span.enter();
+            #[allow(clippy::redundant_closure_call)]
+            let x = (move || #block)();
+            #ret_event;
+            x
+        )
     } else {
         quote_spanned!(block.span()=>
             let __tracing_attr_span = #span;
@@ -565,8 +687,10 @@ struct InstrumentArgs {
     name: Option<LitStr>,
     target: Option<LitStr>,
     skips: HashSet<Ident>,
+    skip_all: bool,
     fields: Option<Fields>,
     err: bool,
+    ret: bool,
     /// Errors describing any unrecognized parse inputs that we skipped.
     parse_warnings: Vec<syn::Error>,
 }
@@ -683,8 +807,20 @@ impl Parse for InstrumentArgs {
                 if !args.skips.is_empty() {
                     return Err(input.error("expected only a single `skip` argument"));
                 }
+                if args.skip_all {
+                    return Err(input.error("expected either `skip` or `skip_all`, not both"));
+                }
                 let Skips(skips) = input.parse()?;
                 args.skips = skips;
+            } else if lookahead.peek(kw::skip_all) {
+                if args.skip_all {
+                    return Err(input.error("expected only a single `skip_all` argument"));
+                }
+                if !args.skips.is_empty() {
+                    return Err(input.error("expected either `skip` or `skip_all`, not both"));
+                }
+                let _ = input.parse::<kw::skip_all>()?;
+                args.skip_all = true;
             } else if lookahead.peek(kw::fields) {
                 if args.fields.is_some() {
                     return Err(input.error("expected only a single `fields` argument"));
@@ -693,6 +829,9 @@ impl Parse for InstrumentArgs {
             } else if lookahead.peek(kw::err) {
                 let _ = input.parse::<kw::err>()?;
                 args.err = true;
+            } else if lookahead.peek(kw::ret) {
+                let _ = input.parse::<kw::ret>()?;
+                args.ret = true;
             } else if lookahead.peek(Token![,]) {
                 let _ = input.parse::<Token![,]>()?;
             } else {
@@ -874,6 +1013,11 @@ enum RecordType {
     Value,
     /// The field should be recorded using `tracing::field::debug()`.
     Debug,
+    /// The field's type is a generic parameter (or `impl Trait`) with no
+    /// `Debug` bound, so it cannot be recorded; it is emitted as
+    /// `tracing::field::Empty` alongside a compile error naming the
+    /// parameter. The `String` is the type as written in the signature.
+    MissingDebugBound(String),
 }
 
 impl RecordType {
@@ -905,8 +1049,10 @@ impl RecordType {
     ];
 
     /// Parse `RecordType` from [syn::Type] by looking up
-    /// the [RecordType::TYPES_FOR_VALUE] array.
-    fn parse_from_ty(ty: &syn::Type) -> Self {
+    /// the [RecordType::TYPES_FOR_VALUE] array. Types which are a bare
+    /// generic parameter listed in `unbounded_generics`, or `impl Trait`
+    /// without a `Debug` bound, produce [RecordType::MissingDebugBound].
+    fn parse_from_ty(ty: &syn::Type, unbounded_generics: &HashSet<String>) -> Self {
         match ty {
             syn::Type::Path(syn::TypePath { path, .. })
                 if path
@@ -921,14 +1067,72 @@ impl RecordType {
             {
                 RecordType::Value
             }
+            syn::Type::Path(syn::TypePath { qself: None, path })
+                if path
+                    .get_ident()
+                    .map(|ident| unbounded_generics.contains(&ident.to_string()))
+                    .unwrap_or(false) =>
+            {
+                RecordType::MissingDebugBound(path.to_token_stream().to_string())
+            }
+            syn::Type::ImplTrait(impl_trait) if !has_debug_bound(&impl_trait.bounds) => {
+                RecordType::MissingDebugBound(impl_trait.to_token_stream().to_string())
+            }
             syn::Type::Reference(syn::TypeReference { elem, .. }) => {
-                RecordType::parse_from_ty(&*elem)
+                RecordType::parse_from_ty(&*elem, unbounded_generics)
             }
             _ => RecordType::Debug,
         }
     }
 }
 
+/// Returns `true` if any of `bounds` names the `Debug` trait.
+///
+/// This is a best-effort syntactic check: a bound whose final path segment is
+/// `Debug` is assumed to be `std::fmt::Debug`. Bounds that imply `Debug`
+/// through a supertrait cannot be detected; such parameters must either add
+/// an explicit `Debug` bound or be skipped.
+fn has_debug_bound(bounds: &Punctuated<syn::TypeParamBound, Token![+]>) -> bool {
+    bounds.iter().any(|bound| match bound {
+        syn::TypeParamBound::Trait(trait_bound) => trait_bound
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Debug")
+            .unwrap_or(false),
+        _ => false,
+    })
+}
+
+/// Returns the names of the generic type parameters in `generics` that do
+/// not have a `Debug` bound, either inline or in the `where` clause.
+fn unbounded_generic_params(generics: &syn::Generics) -> HashSet<String> {
+    let mut unbounded: HashSet<String> = generics
+        .type_params()
+        .filter(|param| !has_debug_bound(&param.bounds))
+        .map(|param| param.ident.to_string())
+        .collect();
+
+    if let Some(where_clause) = &generics.where_clause {
+        for predicate in &where_clause.predicates {
+            if let syn::WherePredicate::Type(syn::PredicateType {
+                bounded_ty: syn::Type::Path(syn::TypePath { qself: None, path }),
+                bounds,
+                ..
+            }) = predicate
+            {
+                if let Some(ident) = path.get_ident() {
+                    if has_debug_bound(bounds) {
+                        unbounded.remove(&ident.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    unbounded
+}
+
 fn param_names(pat: Pat, record_type: RecordType) -> Box<dyn Iterator<Item = (Ident, RecordType)>> {
     match pat {
         Pat::Ident(PatIdent { ident, .. }) => Box::new(iter::once((ident, record_type))),
@@ -967,10 +1171,12 @@ fn param_names(pat: Pat, record_type: RecordType) -> Box<dyn Iterator<Item = (Id
 mod kw {
     syn::custom_keyword!(fields);
     syn::custom_keyword!(skip);
+    syn::custom_keyword!(skip_all);
     syn::custom_keyword!(level);
     syn::custom_keyword!(target);
     syn::custom_keyword!(name);
     syn::custom_keyword!(err);
+    syn::custom_keyword!(ret);
 }
 
 enum AsyncTraitKind<'a> {
@@ -331,3 +331,45 @@ fn out_of_scope_fields() {
 
     handle.assert_finished();
 }
+
+#[test]
+fn async_fn_with_async_trait_enters_for_events_after_await() {
+    use async_trait::async_trait;
+
+    // events emitted after an `.await` point inside an async-trait method
+    // should still occur inside the method's span.
+    #[async_trait]
+    pub trait Test {
+        async fn call(&self);
+    }
+
+    struct TestImpl;
+
+    #[async_trait]
+    impl Test for TestImpl {
+        #[instrument(skip(self))]
+        async fn call(&self) {
+            let future = PollN::new_ok(2);
+            future.await.unwrap();
+            tracing::trace!(awaited = true);
+        }
+    }
+
+    let span = span::mock().named("call");
+    let (collector, handle) = collector::mock()
+        .new_span(span.clone())
+        .enter(span.clone())
+        .exit(span.clone())
+        .enter(span.clone())
+        .event(event::mock().with_fields(field::mock("awaited").with_value(&true)))
+        .exit(span.clone())
+        .drop_span(span)
+        .done()
+        .run_with_handle();
+
+    with_default(collector, || {
+        block_on_future(async { TestImpl.call().await });
+    });
+
+    handle.assert_finished();
+}
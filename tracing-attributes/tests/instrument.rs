@@ -132,6 +132,33 @@ fn skip() {
     handle.assert_finished();
 }
 
+#[test]
+fn skip_all() {
+    struct UnDebug(pub u32);
+
+    #[instrument(target = "my_target", level = "debug", skip_all)]
+    fn my_fn<T>(_arg1: usize, _arg2: UnDebug, _arg3: T) {}
+
+    let span = span::mock()
+        .named("my_fn")
+        .at_level(Level::DEBUG)
+        .with_target("my_target");
+
+    let (collector, handle) = collector::mock()
+        .new_span(span.clone())
+        .enter(span.clone())
+        .exit(span.clone())
+        .drop_span(span)
+        .done()
+        .run_with_handle();
+
+    with_default(collector, || {
+        my_fn(2, UnDebug(0), UnDebug(1));
+    });
+
+    handle.assert_finished();
+}
+
 #[test]
 fn generics() {
     #[derive(Debug)]
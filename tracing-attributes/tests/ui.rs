@@ -0,0 +1,8 @@
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/generic_function.rs");
+    t.pass("tests/ui/impl_trait_return.rs");
+    t.pass("tests/ui/skip_all_non_debug.rs");
+    t.compile_fail("tests/ui/fail/*.rs");
+}
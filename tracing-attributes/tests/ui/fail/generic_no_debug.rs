@@ -0,0 +1,16 @@
+// A parameter whose type is a generic parameter without a `Debug` bound
+// cannot be recorded; the error names the offending parameter instead of
+// failing with an unsatisfied-trait-bound error inside the expansion.
+use tracing_attributes::instrument;
+
+pub trait Request {
+    type Resp;
+    fn respond(self) -> Self::Resp;
+}
+
+#[instrument]
+fn handle<T: Request>(req: T) -> T::Resp {
+    req.respond()
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+// Generic parameters with `Debug` bounds — inline or in a `where` clause —
+// are recorded with their `Debug` implementations.
+use tracing_attributes::instrument;
+
+#[instrument]
+fn bounded<S, T: std::fmt::Debug>(arg1: S, arg2: T)
+where
+    S: std::fmt::Debug,
+{
+    drop((arg1, arg2));
+}
+
+#[instrument(skip(arg2))]
+fn partially_skipped<S: std::fmt::Debug, T>(arg1: S, arg2: T) {
+    drop((arg1, arg2));
+}
+
+fn main() {
+    bounded("hello", 42);
+    partially_skipped("hello", String::new());
+}
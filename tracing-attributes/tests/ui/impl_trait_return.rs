@@ -0,0 +1,37 @@
+// `impl Trait` return types — including those referencing the function's
+// generic parameters or relying on lifetime elision — expand correctly.
+use std::future::Future;
+use tracing_attributes::instrument;
+
+pub trait Request {
+    type Resp;
+    fn respond(self) -> Self::Resp;
+}
+
+#[instrument(skip_all)]
+fn handle<T: Request>(req: T) -> impl Future<Output = Result<T::Resp, ()>> {
+    async move { Ok(req.respond()) }
+}
+
+#[instrument]
+fn handle_debug<T>(req: T) -> impl Future<Output = Result<T::Resp, ()>>
+where
+    T: Request + std::fmt::Debug,
+{
+    async move { Ok(req.respond()) }
+}
+
+#[instrument]
+fn elided(data: &str) -> impl Iterator<Item = char> + '_ {
+    data.chars()
+}
+
+#[instrument(err)]
+fn elided_err(data: &str) -> Result<impl Iterator<Item = char> + '_, std::fmt::Error> {
+    Ok(data.chars())
+}
+
+fn main() {
+    drop(elided("hello"));
+    drop(elided_err("hello"));
+}
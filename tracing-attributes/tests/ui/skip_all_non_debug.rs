@@ -0,0 +1,14 @@
+// `skip_all` skips every argument, so neither non-`Debug` concrete types nor
+// unbounded generic parameters need to be listed by name.
+use tracing_attributes::instrument;
+
+struct NonDebug;
+
+#[instrument(skip_all)]
+fn my_fn<T>(arg1: usize, arg2: NonDebug, arg3: T) {
+    drop((arg1, arg2, arg3));
+}
+
+fn main() {
+    my_fn(1, NonDebug, NonDebug);
+}
@@ -1,5 +1,24 @@
 //! Callsites represent the source locations from which spans or events
 //! originate.
+//!
+//! # Runtime registration
+//!
+//! Callsites are registered lazily, the first time each span or event is
+//! reached, so new callsites may appear at any point during a program's
+//! execution — including from libraries loaded at runtime with `dlopen`.
+//! [`register`] immediately evaluates the new callsite's [`Interest`] against
+//! every currently active dispatcher, both the global default and any scoped
+//! dispatchers, so callsites that first appear after a collector is installed
+//! are enabled without further action.
+//!
+//! Note that the callsite registry is a `static` inside this crate: a
+//! dynamically loaded library that statically links its own copy of
+//! `tracing-core` has its own, separate registry and default dispatchers.
+//! Spans and events in such a library are only visible to the host's
+//! collector if the host shares its [`Dispatch`] with the library (or symbols
+//! are otherwise unified across the boundary). After reconfiguring a
+//! collector's filters at runtime, [`rebuild_interest_cache`] re-evaluates
+//! the interest of every registered callsite.
 use crate::{
     collect::Interest,
     dispatch::{self, Dispatch},
@@ -62,7 +81,7 @@ pub struct Registration<T = &'static dyn Callsite> {
 }
 
 pub(crate) use self::inner::register_dispatch;
-pub use self::inner::{rebuild_interest_cache, register};
+pub use self::inner::{for_each, rebuild_interest_cache, register};
 
 #[cfg(feature = "std")]
 mod inner {
@@ -98,6 +117,13 @@ mod inner {
     /// implementation at runtime, then it **must** call this function after that
     /// value changes, in order for the change to be reflected.
     ///
+    /// This function is also an escape hatch for dynamically loaded libraries:
+    /// if a library's callsites were registered against a *different* copy of
+    /// the registry (because its copy of `tracing-core` was loaded before the
+    /// host's dispatchers could be shared with it), calling this function from
+    /// within the library after its dispatchers have been set brings every
+    /// previously-registered callsite up to date.
+    ///
     /// [`max_level_hint`]: crate::collect::Collect::max_level_hint
     /// [`Callsite`]: crate::callsite::Callsite
     /// [`enabled`]: crate::collect::Collect::enabled
@@ -113,17 +139,55 @@ mod inner {
     ///
     /// This should be called once per callsite after the callsite has been
     /// constructed.
+    ///
+    /// The new callsite's [`Interest`] is evaluated immediately, against every
+    /// dispatcher active at the time of the call — the global default as well
+    /// as any scoped dispatchers — so callsites that are first reached after a
+    /// collector has been installed (for example, from a dynamically loaded
+    /// library) do not require a separate [`rebuild_interest_cache`] call. The
+    /// dispatcher list is locked for the duration of the registration, so a
+    /// dispatcher added concurrently will either be seen here or will itself
+    /// re-evaluate this callsite once it is added.
     pub fn register(registration: &'static Registration) {
         let dispatchers = REGISTRY.dispatchers.read().unwrap();
         rebuild_callsite_interest(&dispatchers, registration.callsite);
         REGISTRY.callsites.push(registration);
     }
 
+    /// Invokes the given function with the [metadata] of every [`Callsite`]
+    /// currently in the registry.
+    ///
+    /// This can be used to enumerate every span and event a binary is able to
+    /// emit — for example, to generate documentation of its log targets. New
+    /// callsites may be registered concurrently; those registered after
+    /// iteration begins may or may not be observed.
+    ///
+    /// [metadata]: crate::metadata::Metadata
+    /// [`Callsite`]: crate::callsite::Callsite
+    pub fn for_each(mut f: impl FnMut(&'static Metadata<'static>)) {
+        REGISTRY
+            .callsites
+            .for_each(|reg| f(reg.callsite.metadata()));
+    }
+
     pub(crate) fn register_dispatch(dispatch: &Dispatch) {
         let mut dispatchers = REGISTRY.dispatchers.write().unwrap();
         let callsites = &REGISTRY.callsites;
 
-        dispatchers.push(dispatch.registrar());
+        // `set_global_default` registers the dispatcher it installs even
+        // though `Dispatch::new` usually has already done so. Don't add a
+        // second registrar for a collector that already has one: every
+        // registrar has `register_callsite` invoked once per callsite, and
+        // collectors may count those calls.
+        let already_registered = dispatchers.iter().any(|registrar| {
+            registrar
+                .upgrade()
+                .map(|d| d.same_collector(dispatch))
+                .unwrap_or(false)
+        });
+        if !already_registered {
+            dispatchers.push(dispatch.registrar());
+        }
 
         rebuild_interest(callsites, &mut dispatchers);
     }
@@ -214,6 +278,20 @@ mod inner {
         REGISTRY.push(registration);
     }
 
+    /// Invokes the given function with the [metadata] of every [`Callsite`]
+    /// currently in the registry.
+    ///
+    /// This can be used to enumerate every span and event a binary is able to
+    /// emit — for example, to generate documentation of its log targets. New
+    /// callsites may be registered concurrently; those registered after
+    /// iteration begins may or may not be observed.
+    ///
+    /// [metadata]: crate::metadata::Metadata
+    /// [`Callsite`]: crate::callsite::Callsite
+    pub fn for_each(mut f: impl FnMut(&'static Metadata<'static>)) {
+        REGISTRY.for_each(|reg| f(reg.callsite.metadata()));
+    }
+
     pub(crate) fn register_dispatch(dispatcher: &Dispatch) {
         // If the collector did not provide a max level hint, assume
         // that it may enable every level.
@@ -1,5 +1,5 @@
 //! Collectors collect and record trace data.
-use crate::{span, Event, LevelFilter, Metadata};
+use crate::{span, Dispatch, Event, LevelFilter, Metadata};
 
 use core::any::{Any, TypeId};
 use core::ptr::NonNull;
@@ -68,6 +68,25 @@ use core::ptr::NonNull;
 /// [`clone_span`]: Collect::clone_span
 /// [`try_close`]: Collect::try_close
 pub trait Collect: 'static {
+    /// Invoked when this collector becomes a [`Dispatch`].
+    ///
+    /// This method is invoked when the collector is used to create a new
+    /// [`Dispatch`] — by [`Dispatch::new`] or [`Dispatch::from_static`] — and
+    /// allows the collector to store a handle to the dispatcher it was
+    /// installed into. Collectors composed of multiple layers should forward
+    /// this call to every layer, so that each may capture the handle.
+    ///
+    /// To store the handle without keeping the collector alive forever (the
+    /// collector owning its own `Dispatch` would form a reference cycle),
+    /// [downgrade] it to a [`WeakDispatch`].
+    ///
+    /// [`Dispatch`]: crate::dispatch::Dispatch
+    /// [`WeakDispatch`]: crate::dispatch::WeakDispatch
+    /// [downgrade]: crate::dispatch::Dispatch::downgrade
+    fn on_register_dispatch(&self, collector: &Dispatch) {
+        let _ = collector;
+    }
+
     // === Span registry methods ==============================================
 
     /// Registers a new callsite with this collector, returning whether or not
@@ -278,6 +297,28 @@ pub trait Collect: 'static {
     /// follow from _b_), it may silently do nothing.
     fn record_follows_from(&self, span: &span::Id, follows: &span::Id);
 
+    /// Determines if an [`Event`] should be recorded.
+    ///
+    /// By default, this returns `true` and collectors can filter events in
+    /// [`event`][Self::event] without any penalty. However, when `event` is
+    /// more complicated, this can be used to determine if `event` should be
+    /// called at all, separating out the decision from the processing.
+    ///
+    /// Unlike [`enabled`], which makes its decision based on the event's
+    /// static [`Metadata`] alone, this method is passed the [`Event`] itself,
+    /// so the decision may consider the event's field *values* — for
+    /// example, a well-known field that overrides the event's static target.
+    /// This method is only called for events whose metadata was already
+    /// [`enabled`], so returning `true` here cannot enable an event that
+    /// filtering has rejected.
+    ///
+    /// [`enabled`]: Self::enabled
+    /// [`Event`]: super::event::Event
+    fn event_enabled(&self, event: &Event<'_>) -> bool {
+        let _ = event;
+        true
+    }
+
     /// Records that an [`Event`] has occurred.
     ///
     /// This method will be invoked when an Event is constructed by
@@ -555,6 +596,11 @@ impl Interest {
 
 #[cfg(feature = "alloc")]
 impl Collect for alloc::boxed::Box<dyn Collect + Send + Sync + 'static> {
+    #[inline]
+    fn on_register_dispatch(&self, collector: &Dispatch) {
+        self.as_ref().on_register_dispatch(collector)
+    }
+
     #[inline]
     fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
         self.as_ref().register_callsite(metadata)
@@ -585,6 +631,11 @@ impl Collect for alloc::boxed::Box<dyn Collect + Send + Sync + 'static> {
         self.as_ref().record_follows_from(span, follows)
     }
 
+    #[inline]
+    fn event_enabled(&self, event: &Event<'_>) -> bool {
+        self.as_ref().event_enabled(event)
+    }
+
     #[inline]
     fn event(&self, event: &Event<'_>) {
         self.as_ref().event(event)
@@ -626,6 +677,11 @@ impl Collect for alloc::boxed::Box<dyn Collect + Send + Sync + 'static> {
 
 #[cfg(feature = "alloc")]
 impl Collect for alloc::sync::Arc<dyn Collect + Send + Sync + 'static> {
+    #[inline]
+    fn on_register_dispatch(&self, collector: &Dispatch) {
+        self.as_ref().on_register_dispatch(collector)
+    }
+
     #[inline]
     fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
         self.as_ref().register_callsite(metadata)
@@ -656,6 +712,11 @@ impl Collect for alloc::sync::Arc<dyn Collect + Send + Sync + 'static> {
         self.as_ref().record_follows_from(span, follows)
     }
 
+    #[inline]
+    fn event_enabled(&self, event: &Event<'_>) -> bool {
+        self.as_ref().event_enabled(event)
+    }
+
     #[inline]
     fn event(&self, event: &Event<'_>) {
         self.as_ref().event(event)
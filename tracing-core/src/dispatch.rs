@@ -150,7 +150,6 @@ use core::{
 use std::{
     cell::{Cell, RefCell, RefMut},
     error,
-    sync::Weak,
 };
 
 #[cfg(feature = "alloc")]
@@ -176,6 +175,51 @@ enum Kind<T> {
     Scoped(T),
 }
 
+/// `WeakDispatch` is a version of [`Dispatch`] that holds a non-owning
+/// reference to a collector.
+///
+/// The collector may be accessed by calling [`WeakDispatch::upgrade`], which
+/// returns an `Option<Dispatch>`. If all [`Dispatch`] clones that point at the
+/// collector have been dropped, [`WeakDispatch::upgrade`] will return `None`.
+/// Otherwise, it will return `Some(Dispatch)`.
+///
+/// A `WeakDispatch` may be created from a [`Dispatch`] by calling the
+/// [`Dispatch::downgrade`] method. The primary use for creating a
+/// `WeakDispatch` is to allow a collector to hold a cyclical reference to
+/// itself without creating a memory leak. See [here] for details.
+///
+/// [here]: crate::collect::Collect::on_register_dispatch
+#[derive(Clone)]
+pub struct WeakDispatch {
+    #[cfg(feature = "alloc")]
+    collector: Kind<alloc::sync::Weak<dyn Collect + Send + Sync>>,
+
+    #[cfg(not(feature = "alloc"))]
+    collector: &'static (dyn Collect + Send + Sync),
+}
+
+impl WeakDispatch {
+    /// Attempts to upgrade this `WeakDispatch` to a [`Dispatch`].
+    ///
+    /// Returns `None` if the referenced `Dispatch` has already been dropped.
+    pub fn upgrade(&self) -> Option<Dispatch> {
+        #[cfg(feature = "alloc")]
+        let collector = match &self.collector {
+            Kind::Global(collector) => Some(Kind::Global(*collector)),
+            Kind::Scoped(collector) => collector.upgrade().map(Kind::Scoped),
+        };
+        #[cfg(not(feature = "alloc"))]
+        let collector = Some(self.collector);
+        collector.map(|collector| Dispatch { collector })
+    }
+}
+
+impl fmt::Debug for WeakDispatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("WeakDispatch(...)")
+    }
+}
+
 #[cfg(feature = "std")]
 thread_local! {
     static CURRENT_STATE: State = State {
@@ -332,6 +376,14 @@ pub fn set_global_default(dispatcher: Dispatch) -> Result<(), SetGlobalDefaultEr
         }
         GLOBAL_INIT.store(INITIALIZED, Ordering::SeqCst);
         EXISTS.store(true, Ordering::Release);
+        // The dispatcher may never have passed through `Dispatch::new` in
+        // *this* copy of `tracing-core` — for example, one shared by a host
+        // binary with a dynamically loaded library that statically links its
+        // own copy. Register it so that it participates in callsite interest
+        // evaluation and in the max-level calculation; for a dispatcher that
+        // `Dispatch::new` already registered, re-registering only re-evaluates
+        // interest with the same result.
+        crate::callsite::register_dispatch(get_global());
         Ok(())
     } else {
         Err(SetGlobalDefaultError { _no_construct: () })
@@ -464,7 +516,7 @@ pub(crate) fn get_global() -> &'static Dispatch {
 }
 
 #[cfg(feature = "std")]
-pub(crate) struct Registrar(Kind<Weak<dyn Collect + Send + Sync>>);
+pub(crate) struct Registrar(WeakDispatch);
 
 impl Dispatch {
     /// Returns a new `Dispatch` that discards events and spans.
@@ -491,6 +543,7 @@ impl Dispatch {
             collector: Kind::Scoped(Arc::new(collector)),
         };
         crate::callsite::register_dispatch(&me);
+        me.collector().on_register_dispatch(&me);
         me
     }
 
@@ -552,15 +605,45 @@ impl Dispatch {
         #[cfg(not(feature = "alloc"))]
         let me = Self { collector };
         crate::callsite::register_dispatch(&me);
+        me.collector().on_register_dispatch(&me);
         me
     }
 
+    /// Creates a [`WeakDispatch`] from this `Dispatch`.
+    ///
+    /// A [`WeakDispatch`] does not prevent the underlying collector from being
+    /// dropped. This is useful when a collector must hold a handle to itself
+    /// — for example, one captured by its own [`on_register_dispatch`]
+    /// callback — as a cycle of `Dispatch`es would otherwise keep the
+    /// collector alive forever.
+    ///
+    /// [`on_register_dispatch`]: crate::collect::Collect::on_register_dispatch
+    pub fn downgrade(&self) -> WeakDispatch {
+        #[cfg(feature = "alloc")]
+        let collector = match &self.collector {
+            Kind::Global(collector) => Kind::Global(*collector),
+            Kind::Scoped(collector) => Kind::Scoped(Arc::downgrade(collector)),
+        };
+        #[cfg(not(feature = "alloc"))]
+        let collector = self.collector;
+        WeakDispatch { collector }
+    }
+
     #[cfg(feature = "std")]
     pub(crate) fn registrar(&self) -> Registrar {
-        Registrar(match self.collector {
-            Kind::Scoped(ref s) => Kind::Scoped(Arc::downgrade(s)),
-            Kind::Global(s) => Kind::Global(s),
-        })
+        Registrar(self.downgrade())
+    }
+
+    /// Returns `true` if `self` and `other` forward to the same collector
+    /// instance.
+    #[cfg(feature = "std")]
+    pub(crate) fn same_collector(&self, other: &Dispatch) -> bool {
+        // Compare only the data pointers: the same collector may be reached
+        // through vtables with different addresses.
+        core::ptr::eq(
+            self.collector() as *const _ as *const (),
+            other.collector() as *const _ as *const (),
+        )
     }
 
     #[inline(always)]
@@ -671,7 +754,10 @@ impl Dispatch {
     /// [`event`]: super::collect::Collect::event
     #[inline]
     pub fn event(&self, event: &Event<'_>) {
-        self.collector().event(event)
+        let collector = self.collector();
+        if collector.event_enabled(event) {
+            collector.event(event);
+        }
     }
 
     /// Records that a span has been can_enter.
@@ -793,6 +879,76 @@ impl Dispatch {
     pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
         <dyn Collect>::downcast_ref(&*self.collector())
     }
+
+    /// Calls `f` with a reference to a *capability* of type `C` exposed by the
+    /// collector this `Dispatch` forwards to, returning the result if the
+    /// capability is present.
+    ///
+    /// Collectors (and subscribers composed into them) can expose additional
+    /// interfaces beyond the [`Collect`] trait to extension traits and helper
+    /// functions that hold only a `Dispatch`. A capability is an arbitrary
+    /// type — typically a struct of function pointers constructed where the
+    /// collector's concrete type is known — that the collector registers by
+    /// returning a pointer to it from [`Collect::downcast_raw`] when queried
+    /// for that type's [`TypeId`]:
+    ///
+    /// ```rust
+    /// use tracing_core::{collect::Collect, dispatch::Dispatch};
+    /// use std::{any::TypeId, ptr::NonNull};
+    /// # use tracing_core::{span, Event, Metadata};
+    ///
+    /// /// A capability exposed by collectors that can report how many
+    /// /// events they have observed.
+    /// pub struct EventCount(pub fn(&Dispatch) -> usize);
+    ///
+    /// struct MyCollector {
+    ///     event_count: EventCount,
+    ///     // ...
+    /// }
+    ///
+    /// impl Collect for MyCollector {
+    ///     unsafe fn downcast_raw(&self, id: TypeId) -> Option<NonNull<()>> {
+    ///         match id {
+    ///             id if id == TypeId::of::<Self>() => Some(NonNull::from(self).cast()),
+    ///             // Register the capability, so that callers can discover it
+    ///             // without naming `MyCollector`.
+    ///             id if id == TypeId::of::<EventCount>() => {
+    ///                 Some(NonNull::from(&self.event_count).cast())
+    ///             }
+    ///             _ => None,
+    ///         }
+    ///     }
+    ///     // ...
+    /// #   fn enabled(&self, _: &Metadata<'_>) -> bool { false }
+    /// #   fn new_span(&self, _: &span::Attributes<'_>) -> span::Id { span::Id::from_u64(1) }
+    /// #   fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+    /// #   fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+    /// #   fn event(&self, _: &Event<'_>) {}
+    /// #   fn enter(&self, _: &span::Id) {}
+    /// #   fn exit(&self, _: &span::Id) {}
+    /// #   fn current_span(&self) -> span::Current { span::Current::unknown() }
+    /// }
+    ///
+    /// // A caller holding only a `Dispatch` can then use the capability:
+    /// fn count_events(dispatch: &Dispatch) -> Option<usize> {
+    ///     dispatch.with_capability::<EventCount, _>(|count| (count.0)(dispatch))
+    /// }
+    /// ```
+    ///
+    /// As when implementing [`Collect::downcast_raw`] for ordinary
+    /// downcasting, the pointer returned for a capability's `TypeId` must
+    /// point to a valid instance of that type; the final cast is performed
+    /// here, so callers require no `unsafe`.
+    ///
+    /// Returns `None` if the collector does not expose a capability of type
+    /// `C`.
+    ///
+    /// [`Collect`]: super::collect::Collect
+    /// [`Collect::downcast_raw`]: super::collect::Collect::downcast_raw
+    /// [`TypeId`]: core::any::TypeId
+    pub fn with_capability<C: Any, R>(&self, f: impl FnOnce(&C) -> R) -> Option<R> {
+        self.downcast_ref::<C>().map(f)
+    }
 }
 
 impl Default for Dispatch {
@@ -852,14 +1008,7 @@ impl Collect for NoCollector {
 #[cfg(feature = "std")]
 impl Registrar {
     pub(crate) fn upgrade(&self) -> Option<Dispatch> {
-        match self.0 {
-            Kind::Global(s) => Some(Dispatch {
-                collector: Kind::Global(s),
-            }),
-            Kind::Scoped(ref s) => s.upgrade().map(|s| Dispatch {
-                collector: Kind::Scoped(s),
-            }),
-        }
+        self.0.upgrade()
     }
 }
 
@@ -1113,4 +1262,68 @@ mod test {
         let default_dispatcher = Dispatch::default();
         assert!(default_dispatcher.is::<NoCollector>());
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn weak_dispatch_does_not_keep_collector_alive() {
+        use alloc::sync::Arc;
+        use core::sync::atomic::AtomicBool;
+
+        struct DropCollector(Arc<AtomicBool>);
+        impl Collect for DropCollector {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+                span::Id::from_u64(0xAAAA)
+            }
+
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+            fn event(&self, _: &Event<'_>) {}
+
+            fn enter(&self, _: &span::Id) {}
+
+            fn exit(&self, _: &span::Id) {}
+
+            fn current_span(&self) -> span::Current {
+                span::Current::unknown()
+            }
+        }
+        impl Drop for DropCollector {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::Release);
+            }
+        }
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        let dispatch = Dispatch::new(DropCollector(dropped.clone()));
+        let weak = dispatch.downgrade();
+        assert!(
+            weak.upgrade().is_some(),
+            "weak dispatch should upgrade while a strong `Dispatch` exists"
+        );
+        assert!(!dropped.load(Ordering::Acquire));
+
+        drop(dispatch);
+        assert!(
+            dropped.load(Ordering::Acquire),
+            "holding a `WeakDispatch` should not keep the collector alive"
+        );
+        assert!(
+            weak.upgrade().is_none(),
+            "weak dispatch should not upgrade after the collector is dropped"
+        );
+    }
+
+    #[test]
+    fn weak_none_dispatch_always_upgrades() {
+        // The "none" dispatcher points at a static collector, so downgrading
+        // it can never invalidate the handle.
+        let weak = Dispatch::none().downgrade();
+        assert!(weak.upgrade().is_some());
+    }
 }
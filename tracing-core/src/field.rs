@@ -127,7 +127,8 @@ pub struct Iter {
 /// to be printed or stored in some other data structure.
 ///
 /// The `Visit` trait provides default implementations for `record_i64`,
-/// `record_u64`, `record_bool`, `record_str`, and `record_error`, which simply
+/// `record_u64`, `record_i128`, `record_u128`, `record_bool`, `record_str`,
+/// `record_bytes`, and `record_error`, which simply
 /// forward the recorded value to `record_debug`. Thus, `record_debug` is the
 /// only method which a `Visit` implementation *must* implement. However,
 /// visitors may override the default implementations of these functions in
@@ -189,6 +190,21 @@ pub trait Visit {
         self.record_debug(field, &value)
     }
 
+    /// Visit a signed 128-bit integer value.
+    fn record_i128(&mut self, field: &Field, value: i128) {
+        self.record_debug(field, &value)
+    }
+
+    /// Visit an unsigned 128-bit integer value.
+    fn record_u128(&mut self, field: &Field, value: u128) {
+        self.record_debug(field, &value)
+    }
+
+    /// Visit a double-precision floating point value.
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record_debug(field, &value)
+    }
+
     /// Visit a boolean value.
     fn record_bool(&mut self, field: &Field, value: bool) {
         self.record_debug(field, &value)
@@ -199,6 +215,16 @@ pub trait Visit {
         self.record_debug(field, &value)
     }
 
+    /// Visit a byte slice.
+    ///
+    /// By default, this formats the bytes with their `Debug` implementation,
+    /// rendering a value such as `[1, 2, 3]`. Visitors may override this to
+    /// render binary data in a more appropriate encoding, such as hex or
+    /// base64.
+    fn record_bytes(&mut self, field: &Field, value: &[u8]) {
+        self.record_debug(field, &value)
+    }
+
     /// Records a type implementing `Error`.
     ///
     /// <div class="example-wrap" style="display:inline-block">
@@ -333,6 +359,12 @@ macro_rules! impl_one_value {
     (bool, $op:expr, $record:ident) => {
         impl_one_value!(normal, bool, $op, $record);
     };
+    (f32, $op:expr, $record:ident) => {
+        impl_one_value!(normal, f32, $op, $record);
+    };
+    (f64, $op:expr, $record:ident) => {
+        impl_one_value!(normal, f64, $op, $record);
+    };
     ($value_ty:tt, $op:expr, $record:ident) => {
         impl_one_value!(normal, $value_ty, $op, $record);
         impl_one_value!(nonzero, $value_ty, $op, $record);
@@ -382,6 +414,10 @@ impl_values! {
     record_u64(usize, u32, u16, u8 as u64),
     record_i64(i64),
     record_i64(isize, i32, i16, i8 as i64),
+    record_u128(u128),
+    record_i128(i128),
+    record_f64(f64),
+    record_f64(f32 as f64),
     record_bool(bool)
 }
 
@@ -400,6 +436,22 @@ impl Value for str {
     }
 }
 
+impl crate::sealed::Sealed for [u8] {}
+
+impl Value for [u8] {
+    fn record(&self, key: &Field, visitor: &mut dyn Visit) {
+        visitor.record_bytes(key, self)
+    }
+}
+
+impl<const N: usize> crate::sealed::Sealed for [u8; N] {}
+
+impl<const N: usize> Value for [u8; N] {
+    fn record(&self, key: &Field, visitor: &mut dyn Visit) {
+        visitor.record_bytes(key, self)
+    }
+}
+
 #[cfg(feature = "std")]
 impl crate::sealed::Sealed for dyn std::error::Error + 'static {}
 
@@ -976,4 +1028,47 @@ mod test {
         });
         assert_eq!(result, String::from("123"));
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn record_bytes_falls_back_to_debug() {
+        let fields = TEST_META_1.fields();
+        let first = &b"abc"[..];
+        let second = [192u8, 255u8];
+        let values = &[
+            (&fields.field("foo").unwrap(), Some(&first as &dyn Value)),
+            (&fields.field("bar").unwrap(), Some(&second as &dyn Value)),
+            (&fields.field("baz").unwrap(), Some(&&b""[..] as &dyn Value)),
+        ];
+        let valueset = fields.value_set(values);
+        let mut result = String::new();
+        valueset.record(&mut |_: &Field, value: &dyn fmt::Debug| {
+            use core::fmt::Write;
+            write!(&mut result, "{:?};", value).unwrap();
+        });
+        assert_eq!(result, String::from("[97, 98, 99];[192, 255];[];"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn record_bytes_visitor() {
+        struct BytesVisitor(String);
+        impl Visit for BytesVisitor {
+            fn record_bytes(&mut self, field: &Field, value: &[u8]) {
+                use core::fmt::Write;
+                write!(&mut self.0, "{}={:?}", field.name(), value).unwrap();
+            }
+            fn record_debug(&mut self, _: &Field, _: &dyn fmt::Debug) {
+                panic!("byte slices should be recorded with `record_bytes`");
+            }
+        }
+
+        let fields = TEST_META_1.fields();
+        let value = &b"bytes"[..];
+        let values = &[(&fields.field("foo").unwrap(), Some(&value as &dyn Value))];
+        let valueset = fields.value_set(values);
+        let mut visitor = BytesVisitor(String::new());
+        valueset.record(&mut visitor);
+        assert_eq!(visitor.0, String::from("foo=[98, 121, 116, 101, 115]"));
+    }
 }
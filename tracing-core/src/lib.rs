@@ -103,6 +103,11 @@
 //!
 //! * `std`: Depend on the Rust standard library (enabled by default).
 //! * `alloc`: Depend on [`liballoc`] (enabled by "std").
+//! * `serde`: Implement [`serde`]'s `Serialize` and `Deserialize` traits for
+//!   [`Level`] and [`LevelFilter`].
+//!
+//! [`serde`]: https://serde.rs
+//! [`LevelFilter`]: metadata::LevelFilter
 //!
 //! [`liballoc`]: alloc
 //!
@@ -275,6 +280,46 @@ macro_rules! metadata {
             $kind,
         )
     };
+    (
+        name: $name:expr,
+        target: $target:expr,
+        level: $level:expr,
+        verbosity: $verbosity:expr,
+        fields: $fields:expr,
+        callsite: $callsite:expr,
+        kind: $kind:expr
+    ) => {
+        $crate::metadata! {
+            name: $name,
+            target: $target,
+            level: $level,
+            verbosity: $verbosity,
+            fields: $fields,
+            callsite: $callsite,
+            kind: $kind,
+        }
+    };
+    (
+        name: $name:expr,
+        target: $target:expr,
+        level: $level:expr,
+        verbosity: $verbosity:expr,
+        fields: $fields:expr,
+        callsite: $callsite:expr,
+        kind: $kind:expr,
+    ) => {
+        $crate::metadata::Metadata::new_with_verbosity(
+            $name,
+            $target,
+            $level,
+            $verbosity,
+            Some(file!()),
+            Some(line!()),
+            Some(module_path!()),
+            $crate::field::FieldSet::new($fields, $crate::identify_callsite!($callsite)),
+            $kind,
+        )
+    };
 }
 
 // std uses lazy_static from crates.io
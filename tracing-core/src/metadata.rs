@@ -86,6 +86,10 @@ pub struct Metadata<'a> {
 
     /// The kind of the callsite.
     kind: Kind,
+
+    /// A finer-grained numeric verbosity carried alongside the `Level`, if one
+    /// was specified.
+    verbosity: Option<u16>,
 }
 
 /// Indicates whether the callsite is a span or event.
@@ -271,6 +275,44 @@ impl<'a> Metadata<'a> {
             line,
             fields,
             kind,
+            verbosity: None,
+        }
+    }
+
+    /// Construct new metadata for a span or event, carrying a finer-grained
+    /// numeric `verbosity` in addition to its [`Level`].
+    ///
+    /// This is intended for bridges from foreign logging systems whose
+    /// severity scales do not map losslessly onto `tracing`'s five [`Level`]s:
+    /// the `Level` determines how the span or event is filtered, while the
+    /// verbosity preserves the original ordering for collectors that can make
+    /// use of it (such as syslog-style collectors mapping to native
+    /// severities). Smaller values should denote more severe diagnostics,
+    /// matching the ordering of [`Level`]s.
+    ///
+    /// See [`Metadata::verbosity`].
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new_with_verbosity(
+        name: &'static str,
+        target: &'a str,
+        level: Level,
+        verbosity: u16,
+        file: Option<&'a str>,
+        line: Option<u32>,
+        module_path: Option<&'a str>,
+        fields: field::FieldSet,
+        kind: Kind,
+    ) -> Self {
+        Metadata {
+            name,
+            target,
+            level,
+            module_path,
+            file,
+            line,
+            fields,
+            kind,
+            verbosity: Some(verbosity),
         }
     }
 
@@ -284,6 +326,21 @@ impl<'a> Metadata<'a> {
         &self.level
     }
 
+    /// Returns the finer-grained numeric verbosity of the described span or
+    /// event, if one was specified.
+    ///
+    /// Most callsites do not carry a verbosity, and most collectors only need
+    /// the [`Level`] returned by [`Metadata::level`]. A verbosity is present
+    /// when the metadata was constructed with
+    /// [`Metadata::new_with_verbosity`] (or the optional `verbosity:` key of
+    /// the [`metadata!`][crate::metadata!] macro), typically by a bridge from
+    /// a foreign logging system with more severities than `tracing` has
+    /// `Level`s. Collectors that can represent finer-grained severities may
+    /// prefer this value when it is present.
+    pub fn verbosity(&self) -> Option<u16> {
+        self.verbosity
+    }
+
     /// Returns the name of the span.
     pub fn name(&self) -> &'static str {
         self.name
@@ -341,6 +398,10 @@ impl<'a> fmt::Debug for Metadata<'a> {
             .field("target", &self.target)
             .field("level", &self.level);
 
+        if let Some(verbosity) = self.verbosity {
+            meta.field("verbosity", &verbosity);
+        }
+
         if let Some(path) = self.module_path() {
             meta.field("module_path", &path);
         }
@@ -642,6 +703,12 @@ impl LevelFilter {
     }
 }
 
+/// Formats the level filter as its lowercase name: `"off"`, `"error"`,
+/// `"warn"`, `"info"`, `"debug"`, or `"trace"`.
+///
+/// This representation is stable, and always round-trips through the
+/// [`FromStr`] implementation, so it may be used when persisting a level
+/// filter in configuration.
 impl fmt::Display for LevelFilter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
@@ -723,6 +790,148 @@ impl fmt::Display for ParseLevelFilterError {
 #[cfg(feature = "std")]
 impl std::error::Error for ParseLevelFilterError {}
 
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::{Level, LevelFilter};
+    use core::{convert::TryFrom, fmt};
+    use serde::{
+        de::{Error, Unexpected, Visitor},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    /// Serializes the level as its lowercase name: `"error"`, `"warn"`,
+    /// `"info"`, `"debug"`, or `"trace"`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    impl Serialize for Level {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let name = match *self {
+                Level::ERROR => "error",
+                Level::WARN => "warn",
+                Level::INFO => "info",
+                Level::DEBUG => "debug",
+                Level::TRACE => "trace",
+            };
+            serializer.serialize_str(name)
+        }
+    }
+
+    /// Deserializes a level from its name (compared case-insensitively) or
+    /// from the numbers 1 ([`ERROR`]) through 5 ([`TRACE`]), as either a
+    /// string or an integer.
+    ///
+    /// [`ERROR`]: Level::ERROR
+    /// [`TRACE`]: Level::TRACE
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    impl<'de> Deserialize<'de> for Level {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct LevelVisitor;
+
+            impl<'de> Visitor<'de> for LevelVisitor {
+                type Value = Level;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.pad("a level name or a number 1-5")
+                }
+
+                fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+                    value
+                        .parse()
+                        .map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+                }
+
+                fn visit_u64<E: Error>(self, value: u64) -> Result<Self::Value, E> {
+                    match value {
+                        1 => Ok(Level::ERROR),
+                        2 => Ok(Level::WARN),
+                        3 => Ok(Level::INFO),
+                        4 => Ok(Level::DEBUG),
+                        5 => Ok(Level::TRACE),
+                        _ => Err(E::invalid_value(Unexpected::Unsigned(value), &self)),
+                    }
+                }
+
+                fn visit_i64<E: Error>(self, value: i64) -> Result<Self::Value, E> {
+                    match u64::try_from(value) {
+                        Ok(value) => self.visit_u64(value),
+                        Err(_) => Err(E::invalid_value(Unexpected::Signed(value), &self)),
+                    }
+                }
+            }
+
+            deserializer.deserialize_any(LevelVisitor)
+        }
+    }
+
+    /// Serializes the level filter as its lowercase name — the same
+    /// representation produced by its `Display` implementation.
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    impl Serialize for LevelFilter {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_str(self)
+        }
+    }
+
+    /// Deserializes a level filter from its name (compared
+    /// case-insensitively) or from the numbers 0 ([`OFF`]) through 5
+    /// ([`TRACE`]), as either a string or an integer.
+    ///
+    /// [`OFF`]: LevelFilter::OFF
+    /// [`TRACE`]: LevelFilter::TRACE
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    impl<'de> Deserialize<'de> for LevelFilter {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct LevelFilterVisitor;
+
+            impl<'de> Visitor<'de> for LevelFilterVisitor {
+                type Value = LevelFilter;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.pad("a level filter name or a number 0-5")
+                }
+
+                fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+                    value
+                        .parse()
+                        .map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+                }
+
+                fn visit_u64<E: Error>(self, value: u64) -> Result<Self::Value, E> {
+                    match value {
+                        0 => Ok(LevelFilter::OFF),
+                        1 => Ok(LevelFilter::ERROR),
+                        2 => Ok(LevelFilter::WARN),
+                        3 => Ok(LevelFilter::INFO),
+                        4 => Ok(LevelFilter::DEBUG),
+                        5 => Ok(LevelFilter::TRACE),
+                        _ => Err(E::invalid_value(Unexpected::Unsigned(value), &self)),
+                    }
+                }
+
+                fn visit_i64<E: Error>(self, value: i64) -> Result<Self::Value, E> {
+                    match u64::try_from(value) {
+                        Ok(value) => self.visit_u64(value),
+                        Err(_) => Err(E::invalid_value(Unexpected::Signed(value), &self)),
+                    }
+                }
+            }
+
+            deserializer.deserialize_any(LevelFilterVisitor)
+        }
+    }
+}
+
 // ==== Level and LevelFilter comparisons ====
 
 // /!\ BIG, IMPORTANT WARNING /!\
@@ -952,6 +1161,59 @@ mod tests {
         assert!("0".parse::<Level>().is_err())
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn level_serde_round_trips() {
+        let levels = [
+            (Level::ERROR, "\"error\""),
+            (Level::WARN, "\"warn\""),
+            (Level::INFO, "\"info\""),
+            (Level::DEBUG, "\"debug\""),
+            (Level::TRACE, "\"trace\""),
+        ];
+        for (level, json) in levels.iter() {
+            assert_eq!(serde_json::to_string(level).unwrap(), *json);
+            assert_eq!(serde_json::from_str::<Level>(json).unwrap(), *level);
+        }
+
+        // Case-insensitive and numeric forms are accepted on deserialize.
+        assert_eq!(
+            serde_json::from_str::<Level>("\"WARN\"").unwrap(),
+            Level::WARN
+        );
+        assert_eq!(serde_json::from_str::<Level>("2").unwrap(), Level::WARN);
+        assert!(serde_json::from_str::<Level>("0").is_err());
+        assert!(serde_json::from_str::<Level>("\"everything\"").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn level_filter_serde_round_trips() {
+        let filters = [
+            (LevelFilter::OFF, "\"off\""),
+            (LevelFilter::ERROR, "\"error\""),
+            (LevelFilter::WARN, "\"warn\""),
+            (LevelFilter::INFO, "\"info\""),
+            (LevelFilter::DEBUG, "\"debug\""),
+            (LevelFilter::TRACE, "\"trace\""),
+        ];
+        for (filter, json) in filters.iter() {
+            assert_eq!(serde_json::to_string(filter).unwrap(), *json);
+            assert_eq!(serde_json::from_str::<LevelFilter>(json).unwrap(), *filter);
+        }
+
+        // Case-insensitive and numeric forms are accepted on deserialize.
+        assert_eq!(
+            serde_json::from_str::<LevelFilter>("\"Off\"").unwrap(),
+            LevelFilter::OFF
+        );
+        assert_eq!(
+            serde_json::from_str::<LevelFilter>("0").unwrap(),
+            LevelFilter::OFF
+        );
+        assert!(serde_json::from_str::<LevelFilter>("6").is_err());
+    }
+
     #[test]
     fn filter_level_conversion() {
         let mapping = [
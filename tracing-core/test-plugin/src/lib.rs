@@ -0,0 +1,30 @@
+//! A test fixture simulating an instrumented plugin loaded with `dlopen`.
+//!
+//! This cdylib statically links its own copy of `tracing-core`, so it has its
+//! own callsite registry and default dispatchers, entirely separate from the
+//! host's. The host shares its `Dispatch` through [`plugin_install`]; only
+//! then are events emitted by [`plugin_emit`] visible to the host's
+//! collector. The event callsite in `plugin_emit` first registers during that
+//! call — *after* the dispatcher was installed — which is exactly the
+//! late-registration path `tracing-core/tests/dlopen.rs` exercises.
+use tracing::dispatch::{self, Dispatch};
+
+/// Installs the host's dispatcher as this library's global default.
+///
+/// # Safety
+///
+/// `dispatch` must point to a valid [`Dispatch`] that was produced by a
+/// binary-identical build of `tracing-core` (the test builds both sides from
+/// the same workspace with the same compiler). This is a test-only ABI, not a
+/// supported way of sharing dispatchers across shared objects.
+#[no_mangle]
+pub unsafe extern "C" fn plugin_install(dispatch: *const Dispatch) {
+    dispatch::set_global_default((*dispatch).clone())
+        .expect("the plugin's global dispatcher should only be installed once");
+}
+
+/// Emits a single event with the target `dlopen_plugin`.
+#[no_mangle]
+pub extern "C" fn plugin_emit() {
+    tracing::info!(target: "dlopen_plugin", "an event from the plugin");
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "std")]
+use std::collections::HashSet;
+use std::sync::{Arc, Barrier};
+use tracing_core::{
+    callsite::{self, Callsite, Registration},
+    collect::Interest,
+    metadata,
+    metadata::{Kind, Level, Metadata},
+};
+
+macro_rules! make_callsite {
+    ($ty:ident, $cs:ident, $meta:ident, $reg:ident, $name:expr) => {
+        struct $ty;
+        impl Callsite for $ty {
+            fn set_interest(&self, _: Interest) {}
+            fn metadata(&self) -> &Metadata<'_> {
+                &$meta
+            }
+        }
+        static $cs: $ty = $ty;
+        static $meta: Metadata<'static> = metadata! {
+            name: $name,
+            target: "callsite_iter",
+            level: Level::DEBUG,
+            fields: &[],
+            callsite: &$cs,
+            kind: Kind::EVENT,
+        };
+        static $reg: Registration = Registration::new(&$cs);
+    };
+}
+
+make_callsite!(Cs1, CS1, META1, REG1, "cs1");
+make_callsite!(Cs2, CS2, META2, REG2, "cs2");
+make_callsite!(Cs3, CS3, META3, REG3, "cs3");
+make_callsite!(Cs4, CS4, META4, REG4, "cs4");
+make_callsite!(Cs5, CS5, META5, REG5, "cs5");
+make_callsite!(Cs6, CS6, META6, REG6, "cs6");
+make_callsite!(Cs7, CS7, META7, REG7, "cs7");
+make_callsite!(Cs8, CS8, META8, REG8, "cs8");
+
+#[test]
+fn for_each_observes_concurrent_registrations() {
+    static GROUPS: [&[&Registration]; 4] = [
+        &[&REG1, &REG2],
+        &[&REG3, &REG4],
+        &[&REG5, &REG6],
+        &[&REG7, &REG8],
+    ];
+    let barrier = Arc::new(Barrier::new(GROUPS.len() + 1));
+
+    let threads = GROUPS
+        .iter()
+        .map(|&registrations| {
+            let barrier = barrier.clone();
+            std::thread::spawn(move || {
+                barrier.wait();
+                for registration in registrations {
+                    callsite::register(registration);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // Iterate while the other threads are registering; this must not observe
+    // a torn list, and every pass must see a superset of the previous one.
+    barrier.wait();
+    let mut seen = HashSet::new();
+    while seen.len() < 8 {
+        let mut pass = HashSet::new();
+        callsite::for_each(|meta| {
+            if meta.target() == "callsite_iter" {
+                pass.insert(meta.name());
+            }
+        });
+        assert!(
+            pass.is_superset(&seen),
+            "a callsite observed by an earlier pass disappeared"
+        );
+        seen = pass;
+    }
+
+    for thread in threads {
+        thread.join().expect("registering thread should not panic");
+    }
+
+    // All threads have joined; a final pass must see every callsite.
+    let mut names = Vec::new();
+    callsite::for_each(|meta| {
+        if meta.target() == "callsite_iter" {
+            names.push(meta.name());
+        }
+    });
+    names.sort_unstable();
+    assert_eq!(
+        names,
+        ["cs1", "cs2", "cs3", "cs4", "cs5", "cs6", "cs7", "cs8"]
+    );
+}
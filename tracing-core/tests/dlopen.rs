@@ -0,0 +1,132 @@
+#![cfg(all(feature = "dlopen-tests", target_os = "linux"))]
+//! Tests that a collector installed in the host process observes events from
+//! an instrumented cdylib loaded with `dlopen` after the collector was
+//! installed.
+//!
+//! The plugin (the `tracing-dlopen-plugin` workspace member) statically links
+//! its own copy of `tracing-core`, with its own callsite registry and default
+//! dispatchers. The host shares its `Dispatch` with the plugin, and the
+//! plugin's event callsite registers *after* that dispatcher is installed —
+//! exercising the late-registration path in `callsite::register` across a
+//! dynamic loading boundary.
+//!
+//! Both sides are built from the same workspace by the same compiler, so
+//! their `tracing-core` layouts are identical; the raw-pointer handoff below
+//! is a test-only ABI, not a supported way of sharing dispatchers.
+use std::ffi::{CStr, CString};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tracing_core::{
+    collect::{Collect, Interest},
+    dispatch::Dispatch,
+    metadata::Metadata,
+    span, Event,
+};
+
+const PLUGIN_TARGET: &str = "dlopen_plugin";
+
+/// Counts the events with the plugin's target that reach this collector.
+struct CountingCollector(Arc<AtomicUsize>);
+
+impl Collect for CountingCollector {
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        if metadata.target() == PLUGIN_TARGET {
+            Interest::always()
+        } else {
+            Interest::never()
+        }
+    }
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target() == PLUGIN_TARGET
+    }
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+    fn event(&self, event: &Event<'_>) {
+        if event.metadata().target() == PLUGIN_TARGET {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+    fn enter(&self, _: &span::Id) {}
+    fn exit(&self, _: &span::Id) {}
+    fn current_span(&self) -> span::Current {
+        span::Current::unknown()
+    }
+}
+
+/// Builds the plugin cdylib and returns the path to the shared object.
+///
+/// A separate target directory avoids contending for the build lock held by
+/// the `cargo test` invocation running this test.
+fn build_plugin() -> PathBuf {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = manifest_dir
+        .parent()
+        .expect("tracing-core should live in the workspace root")
+        .join("target")
+        .join("dlopen-plugin");
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let status = Command::new(cargo)
+        .args(["build", "-p", "tracing-dlopen-plugin", "--target-dir"])
+        .arg(&target_dir)
+        .current_dir(&manifest_dir)
+        .status()
+        .expect("cargo should be runnable");
+    assert!(status.success(), "building the plugin fixture failed");
+    target_dir.join("debug").join("libtracing_dlopen_plugin.so")
+}
+
+unsafe fn symbol(handle: *mut libc::c_void, name: &CStr) -> *mut libc::c_void {
+    let sym = libc::dlsym(handle, name.as_ptr());
+    assert!(
+        !sym.is_null(),
+        "the plugin should export {:?}: {:?}",
+        name,
+        CStr::from_ptr(libc::dlerror())
+    );
+    sym
+}
+
+#[test]
+fn plugin_events_reach_an_already_installed_collector() {
+    let plugin = build_plugin();
+    let path = CString::new(plugin.to_str().expect("plugin path should be UTF-8"))
+        .expect("plugin path should not contain NUL");
+
+    let events = Arc::new(AtomicUsize::new(0));
+    let dispatch = Dispatch::new(CountingCollector(events.clone()));
+
+    unsafe {
+        let handle = libc::dlopen(path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL);
+        assert!(
+            !handle.is_null(),
+            "dlopen of {:?} failed: {:?}",
+            plugin,
+            CStr::from_ptr(libc::dlerror())
+        );
+
+        let install: unsafe extern "C" fn(*const Dispatch) = std::mem::transmute(symbol(
+            handle,
+            CStr::from_bytes_with_nul_unchecked(b"plugin_install\0"),
+        ));
+        let emit: unsafe extern "C" fn() = std::mem::transmute(symbol(
+            handle,
+            CStr::from_bytes_with_nul_unchecked(b"plugin_emit\0"),
+        ));
+
+        // The dispatcher is installed in the plugin *before* the plugin's
+        // event callsite exists; the callsite registers on the first `emit`.
+        install(&dispatch);
+        emit();
+        assert_eq!(events.load(Ordering::SeqCst), 1);
+        emit();
+        assert_eq!(events.load(Ordering::SeqCst), 2);
+
+        // The plugin is deliberately never `dlclose`d: the host may retain
+        // references to the plugin's static metadata.
+    }
+}
@@ -45,4 +45,24 @@ fn metadata_macro_api() {
         callsite: &CALLSITE,
         kind: Kind::EVENT
     };
+    let metadata = metadata! {
+        name: "test_metadata",
+        target: "test_target",
+        level: Level::INFO,
+        verbosity: 5,
+        fields: &[],
+        callsite: &CALLSITE,
+        kind: Kind::EVENT
+    };
+    assert_eq!(metadata.verbosity(), Some(5));
+    let metadata = metadata! {
+        name: "test_metadata",
+        target: "test_target",
+        level: Level::INFO,
+        verbosity: 2,
+        fields: &[],
+        callsite: &CALLSITE,
+        kind: Kind::SPAN,
+    };
+    assert_eq!(metadata.verbosity(), Some(2));
 }
@@ -0,0 +1,115 @@
+#![cfg(feature = "std")]
+//! Tests that callsites registered *after* dispatchers are installed have
+//! their interest evaluated immediately, without a `rebuild_interest_cache`
+//! call. This matters for callsites that are first reached late in a
+//! program's execution — for example, from a dynamically loaded library.
+use std::sync::atomic::{AtomicU8, Ordering};
+use tracing_core::{
+    callsite::{self, Callsite, Registration},
+    collect::{Collect, Interest},
+    dispatch::Dispatch,
+    metadata,
+    metadata::{Kind, Level, Metadata},
+    span, Event,
+};
+
+const NO_INTEREST: u8 = 0;
+const NEVER: u8 = 1;
+const SOMETIMES: u8 = 2;
+const ALWAYS: u8 = 3;
+
+/// A callsite that records the interest the registry assigns to it.
+struct TestCallsite {
+    interest: AtomicU8,
+    meta: &'static Metadata<'static>,
+}
+
+impl Callsite for TestCallsite {
+    fn set_interest(&self, interest: Interest) {
+        let interest = if interest.is_always() {
+            ALWAYS
+        } else if interest.is_sometimes() {
+            SOMETIMES
+        } else {
+            NEVER
+        };
+        self.interest.store(interest, Ordering::SeqCst);
+    }
+
+    fn metadata(&self) -> &Metadata<'_> {
+        self.meta
+    }
+}
+
+/// A collector whose `register_callsite` returns a fixed interest.
+struct InterestCollector(fn() -> Interest);
+
+impl Collect for InterestCollector {
+    fn register_callsite(&self, _: &Metadata<'_>) -> Interest {
+        (self.0)()
+    }
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+    fn event(&self, _: &Event<'_>) {}
+    fn enter(&self, _: &span::Id) {}
+    fn exit(&self, _: &span::Id) {}
+    fn current_span(&self) -> span::Current {
+        span::Current::unknown()
+    }
+}
+
+macro_rules! make_callsite {
+    ($cs:ident, $meta:ident, $reg:ident, $name:expr) => {
+        static $cs: TestCallsite = TestCallsite {
+            interest: AtomicU8::new(NO_INTEREST),
+            meta: &$meta,
+        };
+        static $meta: Metadata<'static> = metadata! {
+            name: $name,
+            target: "register_callsite",
+            level: Level::DEBUG,
+            fields: &[],
+            callsite: &$cs,
+            kind: Kind::EVENT,
+        };
+        static $reg: Registration = Registration::new(&$cs);
+    };
+}
+
+make_callsite!(CS1, META1, REG1, "live_dispatchers");
+make_callsite!(CS2, META2, REG2, "dropped_dispatchers");
+
+// A single test, as both phases share the process-global callsite registry:
+// a concurrently live dispatcher from another test would change the combined
+// interest.
+#[test]
+fn late_registration_evaluates_interest_immediately() {
+    // The dispatchers are merely created, not installed as the global
+    // default; they participate in interest evaluation all the same.
+    let always = Dispatch::new(InterestCollector(Interest::always));
+    let never = Dispatch::new(InterestCollector(Interest::never));
+
+    callsite::register(&REG1);
+
+    // Differing interests combine to `sometimes` — a value neither dispatcher
+    // returned on its own, so registration must have consulted both without a
+    // `rebuild_interest_cache` call.
+    assert_eq!(CS1.interest.load(Ordering::SeqCst), SOMETIMES);
+
+    drop(always);
+    drop(never);
+    drop(Dispatch::new(InterestCollector(Interest::never)));
+
+    callsite::register(&REG2);
+
+    // Every dispatcher created in this process is gone, so the new callsite
+    // falls back to `never` rather than observing a dropped dispatcher's
+    // interest.
+    assert_eq!(CS2.interest.load(Ordering::SeqCst), NEVER);
+}
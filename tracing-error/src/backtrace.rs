@@ -114,9 +114,7 @@ impl SpanTrace {
     /// [`Metadata`]: tracing::Metadata
     pub fn with_spans(&self, f: impl FnMut(&'static Metadata<'static>, &str) -> bool) {
         self.span.with_collector(|(id, s)| {
-            if let Some(getcx) = s.downcast_ref::<WithContext>() {
-                getcx.with_context(s, id, f);
-            }
+            s.with_capability::<WithContext, _>(|getcx| getcx.with_context(s, id, f));
         });
     }
 
@@ -132,7 +130,7 @@ impl SpanTrace {
         } else {
             let mut status = None;
             self.span.with_collector(|(_, s)| {
-                if s.downcast_ref::<WithContext>().is_some() {
+                if s.with_capability::<WithContext, _>(|_| ()).is_some() {
                     status = Some(SpanTraceStatusInner::Captured);
                 }
             });
@@ -179,6 +179,65 @@ where
     {
         self.map(Into::into)
     }
+
+    /// Borrows the wrapped error.
+    ///
+    /// Combined with downcasting the `TracedError` itself, this allows
+    /// recovering the concrete error type from a `dyn Error` trait object:
+    ///
+    /// ```rust
+    /// use std::error::Error;
+    /// use tracing_error::{InstrumentError, TracedError};
+    /// # #[derive(Debug)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// #         write!(f, "My Error")
+    /// #     }
+    /// # }
+    /// # impl std::error::Error for MyError {}
+    ///
+    /// let err: Box<dyn Error + 'static> = Box::new(MyError.in_current_span());
+    /// let err: &MyError = err
+    ///     .downcast_ref::<TracedError<MyError>>()
+    ///     .map(TracedError::inner)
+    ///     .unwrap();
+    /// ```
+    pub fn inner(&self) -> &E {
+        &self.inner.error
+    }
+
+    /// Borrows the `SpanTrace` attached to this error.
+    ///
+    /// This provides direct access to the captured span trace when the
+    /// concrete `TracedError` type is in hand, without walking the
+    /// [`Error::source`] chain and downcasting via [`ExtractSpanTrace`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tracing_error::{InstrumentError, TracedError};
+    /// # #[derive(Debug)]
+    /// # struct MyError;
+    /// # impl std::fmt::Display for MyError {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    /// #         write!(f, "My Error")
+    /// #     }
+    /// # }
+    /// # impl std::error::Error for MyError {}
+    ///
+    /// let err: TracedError<MyError> = MyError.in_current_span();
+    /// println!("{}", err.span_trace());
+    /// ```
+    pub fn span_trace(&self) -> &SpanTrace {
+        &self.inner.span_trace
+    }
+
+    /// Consumes the `TracedError`, returning the wrapped error and discarding
+    /// the attached `SpanTrace`.
+    pub fn into_inner(self) -> E {
+        self.inner.error
+    }
 }
 
 impl<E> From<E> for TracedError<E>
@@ -387,3 +446,81 @@ impl ExtractSpanTrace for dyn Error + 'static {
             .map(|inner| &inner.span_trace)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct TestError(&'static str);
+
+    impl Display for TestError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            Display::fmt(self.0, f)
+        }
+    }
+
+    impl Error for TestError {}
+
+    #[test]
+    fn downcasts_both_the_error_and_the_span_trace() {
+        let err: Box<dyn Error + 'static> = Box::new(TestError("boom").in_current_span());
+
+        // The concrete error type is still reachable by downcasting the
+        // `TracedError` itself...
+        let traced = err
+            .downcast_ref::<TracedError<TestError>>()
+            .expect("downcast to `TracedError<TestError>` should succeed");
+        assert_eq!(traced.inner(), &TestError("boom"));
+
+        // ...and the span trace is still reachable through the source chain.
+        let mut source = err.source();
+        let mut span_trace = None;
+        while let Some(err) = source {
+            span_trace = err.span_trace();
+            if span_trace.is_some() {
+                break;
+            }
+            source = err.source();
+        }
+        assert!(
+            span_trace.is_some(),
+            "no source in the chain had a span trace"
+        );
+    }
+
+    #[test]
+    fn into_inner_returns_the_wrapped_error() {
+        let traced = TestError("boom").in_current_span();
+        assert_eq!(traced.into_inner(), TestError("boom"));
+    }
+
+    #[test]
+    fn span_trace_includes_the_instrumented_span() {
+        use crate::ErrorSubscriber;
+        use tracing_subscriber::prelude::*;
+
+        #[tracing::instrument]
+        fn instrumented() -> Result<(), TracedError<TestError>> {
+            // The `?` conversion captures the span trace while
+            // `instrumented`'s span is still entered.
+            Err(TestError("boom"))?
+        }
+
+        let subscriber = tracing_subscriber::registry().with(ErrorSubscriber::default());
+        tracing::collect::with_default(subscriber, || {
+            let err = instrumented().unwrap_err();
+            let mut spans = Vec::new();
+            err.span_trace().with_spans(|metadata, _| {
+                spans.push(metadata.name());
+                true
+            });
+            assert!(
+                spans.contains(&"instrumented"),
+                "span trace spans {:?} did not include the instrumented \
+                 function's span",
+                spans
+            );
+        });
+    }
+}
@@ -87,6 +87,27 @@
 //! # }
 //! ```
 //!
+//! Errors converted to [`TracedError`] inside an `#[instrument]`ed function
+//! will include that function's span
+//! in the captured [`SpanTrace`] — for example, by returning
+//! `Result<T, TracedError<E>>` and using the `?` operator, or by calling
+//! [`in_current_span()`] at the error site. Note that `#[instrument(err)]`
+//! records an error *event* when the function returns `Err`, but it cannot
+//! attach the span trace on the caller's behalf: the generated span is exited
+//! before the error is returned, so the conversion must happen inside the
+//! function body.
+//!
+//! ```rust
+//! use tracing_error::TracedError;
+//!
+//! #[tracing::instrument(err)]
+//! fn read_config(path: &str) -> Result<String, TracedError<std::io::Error>> {
+//!     // The `?` conversion to `TracedError` captures the span trace while
+//!     // `read_config`'s span is still entered.
+//!     Ok(std::fs::read_to_string(path)?)
+//! }
+//! ```
+//!
 //! Once an error has been wrapped with with a [`TracedError`] the [`SpanTrace`]
 //! can be extracted one of 3 ways: either via [`TracedError`]'s
 //! `Display`/`Debug` implementations, or via the [`ExtractSpanTrace`] trait.
@@ -209,11 +230,15 @@
 mod backtrace;
 #[cfg(feature = "traced-error")]
 mod error;
+#[cfg(feature = "traced-error")]
+mod report;
 mod subscriber;
 
 pub use self::backtrace::{SpanTrace, SpanTraceStatus};
 #[cfg(feature = "traced-error")]
 pub use self::error::{ExtractSpanTrace, InstrumentError, InstrumentResult, TracedError};
+#[cfg(feature = "traced-error")]
+pub use self::report::Report;
 pub use self::subscriber::ErrorSubscriber;
 
 #[cfg(feature = "traced-error")]
@@ -0,0 +1,278 @@
+use crate::{ExtractSpanTrace, SpanTraceStatus};
+use std::error::Error;
+use std::fmt;
+
+/// A report that renders an error, its chain of sources, and its [`SpanTrace`]
+/// in a human-readable format.
+///
+/// `TracedError`'s `Display` and `Debug` implementations forward to the
+/// wrapped error, so printing one in a panic message or at the top level of
+/// `main` shows neither the chain of underlying causes nor the captured span
+/// trace. Wrapping the error in a `Report` and formatting it with `{:?}`
+/// renders the full picture:
+///
+/// ```text
+/// failed to handle request
+///
+/// Caused by:
+///    0: failed to load config
+///    1: No such file or directory (os error 2)
+///
+/// Span trace:
+///    0: server::request
+///         with method="GET"
+///           at src/server.rs:12
+///    1: server::connection
+///           at src/server.rs:5
+/// ```
+///
+/// The span trace is looked up through the error's [`source`] chain with
+/// [`ExtractSpanTrace`], so any error that has a [`TracedError`] anywhere in
+/// its chain of causes can be reported this way. If no span trace was
+/// captured, the report explains why — distinguishing an error that occurred
+/// outside of any spans from a collector without an [`ErrorSubscriber`]
+/// installed.
+///
+/// ANSI terminal colors may be enabled with [`with_ansi`]; they are disabled
+/// by default.
+///
+/// [`SpanTrace`]: crate::SpanTrace
+/// [`TracedError`]: crate::TracedError
+/// [`ErrorSubscriber`]: crate::ErrorSubscriber
+/// [`source`]: std::error::Error::source
+/// [`with_ansi`]: Report::with_ansi
+#[cfg_attr(docsrs, doc(cfg(feature = "traced-error")))]
+pub struct Report<E> {
+    error: E,
+    ansi: bool,
+}
+
+impl<E> Report<E>
+where
+    E: Error + 'static,
+{
+    /// Wraps an error in a `Report`.
+    pub fn new(error: E) -> Self {
+        Report { error, ansi: false }
+    }
+
+    /// Sets whether the report is formatted with ANSI terminal colors.
+    ///
+    /// Colors are disabled by default.
+    pub fn with_ansi(self, ansi: bool) -> Self {
+        Self { ansi, ..self }
+    }
+
+    /// Returns a reference to the wrapped error.
+    pub fn inner(&self) -> &E {
+        &self.error
+    }
+
+    /// Consumes the `Report`, returning the wrapped error.
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+
+    fn bold(&self) -> (&'static str, &'static str) {
+        if self.ansi {
+            ("\u{1b}[1m", "\u{1b}[0m")
+        } else {
+            ("", "")
+        }
+    }
+}
+
+impl<E> From<E> for Report<E>
+where
+    E: Error + 'static,
+{
+    fn from(error: E) -> Self {
+        Report::new(error)
+    }
+}
+
+impl<E> fmt::Display for Report<E>
+where
+    E: Error + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl<E> fmt::Debug for Report<E>
+where
+    E: Error + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (bold, reset) = self.bold();
+        write!(f, "{}{}{}", bold, self.error, reset)?;
+
+        // Walk the source chain, numbering the underlying causes. The
+        // type-erased carrier that holds the span trace forwards its own
+        // source and would otherwise show up as a duplicate entry rendering
+        // the trace inline, so it is skipped here and its trace rendered as a
+        // separate section below.
+        let mut span_trace = None;
+        let mut source = self.error.source();
+        let mut nth = 0;
+        while let Some(err) = source {
+            if let Some(trace) = err.span_trace() {
+                span_trace.get_or_insert(trace);
+            } else {
+                if nth == 0 {
+                    write!(f, "\n\n{}Caused by:{}", bold, reset)?;
+                }
+                write!(f, "\n{:>4}: {}", nth, err)?;
+                nth += 1;
+            }
+            source = err.source();
+        }
+
+        write!(f, "\n\n{}Span trace:{}", bold, reset)?;
+        match span_trace {
+            Some(trace) => {
+                let status = trace.status();
+                if status == SpanTraceStatus::CAPTURED {
+                    write!(f, "\n{}", trace)
+                } else if status == SpanTraceStatus::EMPTY {
+                    f.write_str("\nempty — the error occurred outside of any spans")
+                } else {
+                    f.write_str(
+                        "\nunavailable — the current collector does not support span traces \
+                         (is an `ErrorSubscriber` installed?)",
+                    )
+                }
+            }
+            None => f.write_str("\nunavailable — the error does not carry a span trace"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorSubscriber, InstrumentError};
+    use std::fmt;
+    use tracing::collect::with_default;
+    use tracing_subscriber::{prelude::*, registry::Registry};
+
+    #[derive(Debug)]
+    struct Inner;
+
+    impl fmt::Display for Inner {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("the disk caught fire")
+        }
+    }
+
+    impl Error for Inner {}
+
+    #[derive(Debug)]
+    struct Mid(Inner);
+
+    impl fmt::Display for Mid {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("failed to load config")
+        }
+    }
+
+    impl Error for Mid {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[derive(Debug)]
+    struct Outer(Mid);
+
+    impl fmt::Display for Outer {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("failed to handle request")
+        }
+    }
+
+    impl Error for Outer {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn report_formats_error_chain_and_span_trace() {
+        let collector = Registry::default().with(ErrorSubscriber::default());
+
+        let report = with_default(collector, || {
+            let outer = tracing::info_span!("outer", request = 1);
+            let _outer = outer.enter();
+            let inner = tracing::info_span!("inner");
+            let _inner = inner.enter();
+            Report::new(Outer(Mid(Inner)).in_current_span())
+        });
+
+        let rendered = format!("{:?}", report);
+        println!("{}", rendered);
+
+        // The error itself, then the numbered source chain...
+        assert!(rendered.starts_with("failed to handle request\n"));
+        assert!(rendered.contains("\nCaused by:\n"));
+        assert!(rendered.contains("\n   0: failed to load config\n"));
+        assert!(rendered.contains("\n   1: the disk caught fire\n"));
+        // ...then one entry per span, innermost first, with fields and
+        // source location.
+        assert!(rendered.contains("\nSpan trace:\n"));
+        assert!(rendered.contains("tracing_error::report::tests::inner"));
+        assert!(rendered.contains("tracing_error::report::tests::outer"));
+        assert!(rendered.contains("with request=1"));
+        assert!(rendered.contains("at tracing-error/src/report.rs:"));
+        // No colors unless requested.
+        assert!(!rendered.contains('\u{1b}'));
+    }
+
+    #[test]
+    fn report_with_ansi_brackets_headings() {
+        let collector = Registry::default().with(ErrorSubscriber::default());
+
+        let report = with_default(collector, || {
+            let span = tracing::info_span!("span");
+            let _guard = span.enter();
+            Report::new(Outer(Mid(Inner)).in_current_span()).with_ansi(true)
+        });
+
+        let rendered = format!("{:?}", report);
+        assert!(rendered.contains("\u{1b}[1mCaused by:\u{1b}[0m"));
+        assert!(rendered.contains("\u{1b}[1mSpan trace:\u{1b}[0m"));
+    }
+
+    #[test]
+    fn report_explains_empty_span_trace() {
+        let collector = Registry::default().with(ErrorSubscriber::default());
+
+        let report = with_default(collector, || Report::new(Inner.in_current_span()));
+
+        let rendered = format!("{:?}", report);
+        assert!(
+            rendered.contains("empty — the error occurred outside of any spans"),
+            "unexpected report: {}",
+            rendered
+        );
+    }
+
+    #[test]
+    fn report_explains_missing_error_subscriber() {
+        let collector = Registry::default();
+
+        let report = with_default(collector, || {
+            let span = tracing::info_span!("span");
+            let _guard = span.enter();
+            Report::new(Inner.in_current_span())
+        });
+
+        let rendered = format!("{:?}", report);
+        assert!(
+            rendered.contains("is an `ErrorSubscriber` installed?"),
+            "unexpected report: {}",
+            rendered
+        );
+    }
+}
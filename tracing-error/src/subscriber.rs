@@ -95,9 +95,13 @@ where
         let collector = dispatch
             .downcast_ref::<C>()
             .expect("collector should downcast to expected type; this is a bug!");
-        let span = collector
-            .span(id)
-            .expect("registry should have a span for the current ID");
+        // If the registry has no data for this ID, the span has already been
+        // closed (or the ID came from a different collector); produce an
+        // empty `SpanTrace` rather than panicking inside instrumentation.
+        let span = match collector.span(id) {
+            Some(span) => span,
+            None => return,
+        };
         for span in span.scope() {
             let cont = if let Some(fields) = span.extensions().get::<FormattedFields<F>>() {
                 f(span.metadata(), fields.fields.as_str())
@@ -0,0 +1,253 @@
+use crate::error::{Error, Kind};
+use crate::{START, THREAD_NAME};
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::sync::Mutex;
+use tracing::span;
+use tracing::Collect;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::subscribe::Context;
+use tracing_subscriber::Subscribe;
+
+/// A `Subscriber` that records span enter/exit events in the Chrome [Trace
+/// Event Format].
+///
+/// Unlike [`FlameSubscriber`], which collapses span timings into folded stack
+/// samples, `ChromeSubscriber` preserves the time dimension: every span enter
+/// is emitted as a `"ph": "B"` (begin) record and every exit as a `"ph": "E"`
+/// (end) record, with microsecond timestamps measured from the first use of
+/// the subscriber. The resulting file can be loaded directly into
+/// `chrome://tracing` or [Perfetto] to inspect the timeline of a program's
+/// spans.
+///
+/// Each record carries the process ID as `pid`, the thread name as `tid`, the
+/// span's name as `name`, and its module path as `cat`.
+///
+/// # Output Framing
+///
+/// Records are written as a newline-delimited JSON array that is only closed
+/// with a trailing `]` when the [`ChromeFlushGuard`] returned by
+/// [`flush_on_drop`] is dropped. The trace viewers above accept an unclosed
+/// array, so the output remains loadable even if the program exits without
+/// dropping the guard — but it will not parse as strict JSON until the guard
+/// has run.
+///
+/// [Trace Event Format]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+/// [Perfetto]: https://ui.perfetto.dev
+/// [`FlameSubscriber`]: crate::FlameSubscriber
+/// [`flush_on_drop`]: ChromeSubscriber::flush_on_drop()
+#[derive(Debug)]
+pub struct ChromeSubscriber<C, W> {
+    out: Arc<Mutex<W>>,
+    first: AtomicBool,
+    _inner: PhantomData<C>,
+}
+
+/// An RAII guard that closes the JSON array written by a [`ChromeSubscriber`]
+/// and flushes its writer.
+///
+/// This guard writes the trailing `]` that makes the output strict JSON, so
+/// it should be dropped (or kept until the end of `main`) even when the
+/// writer is unbuffered. Like [`FlushGuard`], it is also needed when using
+/// `tracing::collect::set_global_default`, which prevents the drop
+/// implementation of subscribers from running when the program exits.
+///
+/// [`FlushGuard`]: crate::FlushGuard
+#[must_use]
+#[derive(Debug)]
+pub struct ChromeFlushGuard<W>
+where
+    W: Write + 'static,
+{
+    out: Arc<Mutex<W>>,
+}
+
+impl<C, W> ChromeSubscriber<C, W>
+where
+    C: Collect + for<'span> LookupSpan<'span>,
+    W: Write + 'static,
+{
+    /// Returns a new `ChromeSubscriber` that writes Trace Event Format JSON
+    /// to the provided writer.
+    pub fn new(mut writer: W) -> Self {
+        // Initialize the start used by all threads when initializing the
+        // LAST_EVENT when constructing the subscriber
+        let _unused = *START;
+        let _ = writer.write_all(b"[");
+        Self {
+            out: Arc::new(Mutex::new(writer)),
+            first: AtomicBool::new(true),
+            _inner: PhantomData,
+        }
+    }
+
+    /// Returns a `ChromeFlushGuard` which will terminate the JSON array and
+    /// flush the `ChromeSubscriber`'s writer when it is dropped, or flush the
+    /// writer when `flush` is manually invoked on the guard.
+    pub fn flush_on_drop(&self) -> ChromeFlushGuard<W> {
+        ChromeFlushGuard {
+            out: self.out.clone(),
+        }
+    }
+
+    fn record(&self, phase: &str, span_name: &str, module_path: Option<&str>) {
+        let ts = START.elapsed().as_nanos() as f64 / 1_000.0;
+
+        let mut record = String::new();
+        record += "{\"ph\":\"";
+        record += phase;
+        record += "\",\"pid\":";
+        write!(&mut record, "{}", std::process::id())
+            .expect("expected: write to String never fails");
+        record += ",\"tid\":\"";
+        THREAD_NAME.with(|name| escape(&mut record, name));
+        record += "\",\"ts\":";
+        write!(&mut record, "{}", ts).expect("expected: write to String never fails");
+        record += ",\"name\":\"";
+        escape(&mut record, span_name);
+        record += "\"";
+        if let Some(module_path) = module_path {
+            record += ",\"cat\":\"";
+            escape(&mut record, module_path);
+            record += "\"";
+        }
+        record += "}";
+
+        let mut out = match self.out.lock() {
+            Ok(out) => out,
+            Err(e) => {
+                if !std::thread::panicking() {
+                    panic!("{}", e);
+                } else {
+                    return;
+                }
+            }
+        };
+        // Records are comma-separated so that the output is a valid JSON
+        // array once the `ChromeFlushGuard` appends the trailing `]`.
+        let sep = if self.first.swap(false, Ordering::Relaxed) {
+            "\n"
+        } else {
+            ",\n"
+        };
+        let _ = write!(*out, "{}{}", sep, record);
+    }
+}
+
+impl<C> ChromeSubscriber<C, BufWriter<File>>
+where
+    C: Collect + for<'span> LookupSpan<'span>,
+{
+    /// Constructs a `ChromeSubscriber` that outputs to a `BufWriter` to the given path, and a
+    /// `ChromeFlushGuard` to terminate the output and ensure the writer is flushed.
+    pub fn with_file(
+        path: impl AsRef<Path>,
+    ) -> Result<(Self, ChromeFlushGuard<BufWriter<File>>), Error> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .map_err(|source| Kind::CreateFile {
+                path: path.into(),
+                source,
+            })
+            .map_err(Error)?;
+        let writer = BufWriter::new(file);
+        let subscriber = Self::new(writer);
+        let guard = subscriber.flush_on_drop();
+        Ok((subscriber, guard))
+    }
+}
+
+impl<C, W> Subscribe<C> for ChromeSubscriber<C, W>
+where
+    C: Collect + for<'span> LookupSpan<'span>,
+    W: Write + 'static,
+{
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, C>) {
+        let span = match ctx.span_or_report(id) {
+            Some(span) => span,
+            None => return,
+        };
+        self.record("B", span.name(), span.metadata().module_path());
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, C>) {
+        let span = match ctx.span_or_report(id) {
+            Some(span) => span,
+            None => return,
+        };
+        self.record("E", span.name(), span.metadata().module_path());
+    }
+}
+
+impl<W> ChromeFlushGuard<W>
+where
+    W: Write + 'static,
+{
+    /// Flush the internal writer of the `ChromeSubscriber`, ensuring that all
+    /// intermediately buffered contents reach their destination.
+    ///
+    /// This does *not* terminate the JSON array; the trailing `]` is only
+    /// written when the guard is dropped.
+    pub fn flush(&self) -> Result<(), Error> {
+        let mut guard = match self.out.lock() {
+            Ok(guard) => guard,
+            Err(e) => {
+                if !std::thread::panicking() {
+                    panic!("{}", e);
+                } else {
+                    return Ok(());
+                }
+            }
+        };
+
+        guard.flush().map_err(Kind::FlushFile).map_err(Error)
+    }
+}
+
+impl<W> Drop for ChromeFlushGuard<W>
+where
+    W: Write + 'static,
+{
+    fn drop(&mut self) {
+        let terminate = || {
+            let mut out = match self.out.lock() {
+                Ok(out) => out,
+                Err(e) => {
+                    if !std::thread::panicking() {
+                        panic!("{}", e);
+                    } else {
+                        return Ok(());
+                    }
+                }
+            };
+            out.write_all(b"\n]\n")
+                .and_then(|()| out.flush())
+                .map_err(Kind::FlushFile)
+                .map_err(Error)
+        };
+        match terminate() {
+            Ok(_) => (),
+            Err(e) => e.report(),
+        }
+    }
+}
+
+/// Appends `s` to `dest`, escaping it for use inside a JSON string.
+fn escape(dest: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            '"' => dest.push_str("\\\""),
+            '\\' => dest.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                write!(dest, "\\u{:04x}", c as u32).expect("expected: write to String never fails")
+            }
+            c => dest.push(c),
+        }
+    }
+}
@@ -32,6 +32,9 @@ impl std::error::Error for Error {
         match &self.0 {
             Kind::CreateFile { ref source, .. } => Some(source),
             Kind::FlushFile(ref source) => Some(source),
+            Kind::ReadInput(ref source) => Some(source),
+            Kind::WriteOutput(ref source) => Some(source),
+            Kind::ParseSample(_) => None,
         }
     }
 }
@@ -43,6 +46,9 @@ pub(crate) enum Kind {
         path: PathBuf,
     },
     FlushFile(std::io::Error),
+    ReadInput(std::io::Error),
+    WriteOutput(std::io::Error),
+    ParseSample(String),
 }
 
 impl fmt::Display for Kind {
@@ -52,6 +58,11 @@ impl fmt::Display for Kind {
                 write!(f, "cannot create output file. path={}", path.display())
             }
             Self::FlushFile { .. } => write!(f, "cannot flush output buffer"),
+            Self::ReadInput { .. } => write!(f, "cannot read folded input"),
+            Self::WriteOutput { .. } => write!(f, "cannot write merged output"),
+            Self::ParseSample(line) => {
+                write!(f, "malformed folded sample line. line={:?}", line)
+            }
         }
     }
 }
@@ -24,6 +24,14 @@
 //! 2. Feed the textual representation into `inferno-flamegraph` to generate the
 //!    flamegraph or flamechart.
 //!
+//! As an alternative, the [`ChromeSubscriber`] records the same span enter and
+//! exit events as Chrome [Trace Event Format] JSON, which can be loaded into
+//! `chrome://tracing` or [Perfetto] to view span timings on a timeline instead
+//! of a flamegraph.
+//!
+//! [Trace Event Format]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+//! [Perfetto]: https://ui.perfetto.dev
+//!
 //! *Note*: when using a buffered writer as the writer for a `FlameSubscriber`, it is necessary to
 //! ensure that the buffer has been flushed before the data is passed into
 //! [`inferno-flamegraph`]. For more details on how to flush the internal writer
@@ -134,11 +142,14 @@
     while_true
 )]
 
+pub use chrome::{ChromeFlushGuard, ChromeSubscriber};
 pub use error::Error;
+pub use merge::merge;
+pub use writer::{MakeFlameWriter, PerThreadWriter};
 
 use error::Kind;
 use lazy_static::lazy_static;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::fmt;
 use std::fmt::Write as _;
 use std::fs::File;
@@ -148,24 +159,51 @@ use std::marker::PhantomData;
 use std::path::Path;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::sync::Once;
 use std::time::{Duration, Instant};
+use tracing::field;
 use tracing::span;
 use tracing::Collect;
+use tracing::Event;
 use tracing_subscriber::registry::LookupSpan;
 use tracing_subscriber::registry::SpanRef;
 use tracing_subscriber::subscribe::Context;
 use tracing_subscriber::Subscribe;
 
+mod chrome;
 mod error;
+mod merge;
+mod writer;
+
+lazy_static! {
+    pub(crate) static ref START: Instant = Instant::now();
+}
+
+/// Warn only the first time an out-of-order exit is observed; a program that
+/// reorders guards once will typically do so in a loop.
+static OUT_OF_ORDER_WARNING: Once = Once::new();
 
 lazy_static! {
-    static ref START: Instant = Instant::now();
+    /// Flush closures registered by [`FlushGuard::flush_on_exit`], run by the
+    /// process-exit and panic hooks.
+    static ref EXIT_FLUSHES: Mutex<Vec<Box<dyn Fn() + Send>>> = Mutex::new(Vec::new());
 }
 
+/// Installs the process-exit and panic hooks at most once.
+static EXIT_HOOKS: Once = Once::new();
+
 thread_local! {
     static LAST_EVENT: Cell<Instant> = Cell::new(*START);
 
-    static THREAD_NAME: String = {
+    /// The stack of spans entered (and not yet exited) on this thread, in the
+    /// order they were entered. Samples are attributed to this tracked stack
+    /// rather than to the registry's parent chain, so that the emitted stacks
+    /// reflect what was actually entered on the thread — the parent chain can
+    /// differ when a span is entered on a thread other than the one it was
+    /// created on, or when guards are held across nested calls.
+    static SPAN_STACK: RefCell<Vec<span::Id>> = RefCell::new(Vec::new());
+
+    pub(crate) static THREAD_NAME: String = {
         let thread = std::thread::current();
         let mut thread_name = format!("{:?}", thread.id());
         if let Some(name) = thread.name() {
@@ -224,11 +262,23 @@ struct Config {
     /// Don't include thread_id
     threads_collapsed: bool,
 
+    /// A prefix prepended to the thread root frame of every sample
+    thread_prefix: Option<String>,
+
     /// Don't display module_path
     module_path: bool,
 
     /// Don't display file and line
     file_and_line: bool,
+
+    /// Emit a zero-length `<close>` marker when a span closes
+    close_markers: bool,
+
+    /// Emit a marker sample with a synthetic leaf frame for each event
+    events: bool,
+
+    /// The fixed weight given to event marker samples
+    event_weight: Duration,
 }
 
 impl Default for Config {
@@ -236,8 +286,12 @@ impl Default for Config {
         Self {
             empty_samples: true,
             threads_collapsed: false,
+            thread_prefix: None,
             module_path: true,
             file_and_line: true,
+            close_markers: false,
+            events: false,
+            event_weight: Duration::from_nanos(0),
         }
     }
 }
@@ -288,6 +342,13 @@ where
     ///
     /// Defaults to `true`.
     ///
+    /// When enabled, time during which a thread has no span entered is
+    /// attributed to a synthetic `<idle>` frame under the thread root, so
+    /// that flamecharts show the gap between spans rather than attributing
+    /// the waiting time to the next stack. This is particularly useful for
+    /// async code, where a span frequently remains un-entered across an
+    /// `.await`.
+    ///
     /// Setting this feature to false can help with situations where no span is
     /// active for large periods of time. This can include time spent idling, or
     /// doing uninteresting work that isn't being measured.
@@ -316,6 +377,36 @@ where
         self
     }
 
+    /// Configures a prefix prepended to the thread root frame of every
+    /// sample.
+    ///
+    /// Thread IDs are only unique within a single process, so when folded
+    /// files from several processes are concatenated (or combined with
+    /// [`merge`]), samples from unrelated threads that happen to share an ID
+    /// are lumped together. A prefix such as the process ID or a service name
+    /// keeps each process's threads distinguishable:
+    ///
+    /// ```text
+    /// worker-1234-ThreadId(2); outer; inner 150
+    /// ```
+    ///
+    /// Since `;` separates frames and a space separates the stack from its
+    /// count in the folded format, those characters are replaced with `,`
+    /// and `_` in the prefix.
+    pub fn with_thread_prefix(mut self, prefix: impl Into<String>) -> Self {
+        let prefix = prefix
+            .into()
+            .chars()
+            .map(|c| match c {
+                ';' => ',',
+                c if c.is_whitespace() => '_',
+                c => c,
+            })
+            .collect();
+        self.config.thread_prefix = Some(prefix);
+        self
+    }
+
     /// Configures whether or not module paths should be included in the output.
     pub fn with_module_path(mut self, enabled: bool) -> Self {
         self.config.module_path = enabled;
@@ -327,6 +418,121 @@ where
         self.config.file_and_line = enabled;
         self
     }
+
+    /// Configures whether a zero-length `<close>` marker sample should be
+    /// emitted when a span closes.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// A span may be exited and re-entered many times before it truly ends;
+    /// only the close marks the end of its extent. When enabled, closing a
+    /// span emits one sample with the span's full ancestry, a synthetic
+    /// `<close>` leaf frame, and a count of zero. The marker does not affect
+    /// the measured times in a flamegraph (zero-length samples add no
+    /// weight), but in a flamechart it pins down where each span's lifetime
+    /// ended relative to the surrounding samples.
+    pub fn with_close_markers(mut self, enabled: bool) -> Self {
+        self.config.close_markers = enabled;
+        self
+    }
+
+    /// Configures whether events should be included in the output as marker
+    /// samples, similar to how perf shows tracepoints within a profile.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// When enabled, each event emits one sample with the stack of spans
+    /// entered on the thread plus a synthetic leaf frame named after the
+    /// event: its `message` field if it has one (truncated past
+    /// 32 characters), and its target otherwise. Since `;` and spaces
+    /// separate frames and counts in the folded format, they are replaced
+    /// with `,` and `_` in the frame name.
+    ///
+    /// The time elapsed since the previous sample is attributed to the
+    /// enclosing stack *without* the leaf, so marking events does not change
+    /// how time is attributed to the spans themselves. The leaf's own weight
+    /// is fixed and defaults to zero; see [`with_event_weight`].
+    ///
+    /// [`with_event_weight`]: FlameSubscriber::with_event_weight()
+    pub fn with_events(mut self, enabled: bool) -> Self {
+        self.config.events = enabled;
+        self
+    }
+
+    /// Configures the fixed weight, as a duration, given to event marker
+    /// samples.
+    ///
+    /// Defaults to zero, which adds no weight to the graph but may render
+    /// the marker invisibly thin. A small weight (say, a microsecond) makes
+    /// markers easier to spot, at the cost of slightly inflating the
+    /// enclosing stack.
+    pub fn with_event_weight(mut self, weight: Duration) -> Self {
+        self.config.event_weight = weight;
+        self
+    }
+
+    /// Spawns a background thread that flushes the writer at least once per
+    /// `interval`.
+    ///
+    /// By default, buffered contents only reach their destination when the
+    /// writer's buffer fills or when a [`FlushGuard`] flushes. When streaming
+    /// folded data to a live consumer — a `TcpStream` feeding speedscope or
+    /// inferno's diffing tools, or a pipe — samples should arrive while the
+    /// program is still running; this makes sure they do.
+    ///
+    /// The flusher thread holds only a weak handle to the writer, so it exits
+    /// once the subscriber (and any [`FlushGuard`]s) have been dropped.
+    ///
+    /// Samples are written to the writer under a mutex held by the
+    /// instrumented threads, so a sink with blocking writes (such as a bare
+    /// `TcpStream`) should be wrapped in a [`BufWriter`]: the instrumented
+    /// threads then only append to the in-memory buffer, and the periodic
+    /// flush pays the cost of the network write.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io::BufWriter;
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    /// use tracing_flame::FlameSubscriber;
+    /// use tracing_subscriber::{prelude::*, registry::Registry};
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8100").unwrap();
+    /// let flame_subscriber = FlameSubscriber::new(BufWriter::new(stream))
+    ///     .with_flush_interval(Duration::from_secs(1));
+    /// let collector = Registry::default().with(flame_subscriber);
+    /// # drop(collector);
+    /// ```
+    pub fn with_flush_interval(self, interval: Duration) -> Self
+    where
+        W: Send,
+    {
+        let out = Arc::downgrade(&self.out);
+        std::thread::Builder::new()
+            .name("tracing-flame-flush".into())
+            .spawn(move || loop {
+                std::thread::sleep(interval);
+                // If the subscriber has been dropped, there is nothing left
+                // to flush.
+                let out = match out.upgrade() {
+                    Some(out) => out,
+                    None => return,
+                };
+                let mut out = match out.lock() {
+                    Ok(out) => out,
+                    // A poisoned lock means a writing thread panicked; stop
+                    // rather than flushing a potentially half-written line.
+                    Err(_) => return,
+                };
+                if let Err(e) = out.flush() {
+                    Error(Kind::FlushFile(e)).report();
+                    return;
+                }
+            })
+            .expect("failed to spawn tracing-flame flush thread");
+        self
+    }
 }
 
 impl<W> FlushGuard<W>
@@ -351,6 +557,87 @@ where
     }
 }
 
+impl<W> FlushGuard<W>
+where
+    W: Write + Send + 'static,
+{
+    /// Additionally flushes the `FlameSubscriber`'s writer when the process
+    /// exits, returning the guard.
+    ///
+    /// `Drop` implementations do not run when the process exits through
+    /// [`std::process::exit`] or when a panic unwinds past `main`, so a
+    /// buffered writer can lose the tail of the folded output even when a
+    /// `FlushGuard` is held. This registers the writer with a process-exit
+    /// hook (and with the panic hook) that flushes whatever has been buffered
+    /// at that point.
+    ///
+    /// # Limitations
+    ///
+    /// Aborts do not run the hooks: [`std::process::abort`], double panics,
+    /// programs compiled with `panic = "abort"`, and fatal signals all
+    /// terminate the process immediately, losing any buffered data. If the
+    /// writer's lock is held when a hook runs — for example, because the
+    /// panic happened mid-write — the flush is skipped rather than risking a
+    /// deadlock.
+    pub fn flush_on_exit(self) -> Self {
+        install_exit_hooks();
+        let out = self.out.clone();
+        let flush: Box<dyn Fn() + Send> = Box::new(move || {
+            use std::sync::TryLockError;
+            match out.try_lock() {
+                Ok(mut writer) => {
+                    let _ = writer.flush();
+                }
+                // A panic while the writer was locked poisoned the mutex; the
+                // buffered data is still worth flushing.
+                Err(TryLockError::Poisoned(err)) => {
+                    let _ = err.into_inner().flush();
+                }
+                // The lock is held, possibly by the very thread running this
+                // hook; skip the flush rather than deadlock.
+                Err(TryLockError::WouldBlock) => {}
+            }
+        });
+        EXIT_FLUSHES
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(flush);
+        self
+    }
+}
+
+fn install_exit_hooks() {
+    EXIT_HOOKS.call_once(|| {
+        // `atexit` handlers run on normal process exit, including
+        // `std::process::exit`, which skips destructors.
+        #[cfg(unix)]
+        unsafe {
+            libc::atexit(exit_flush_handler);
+        }
+        // The panic hook runs at panic time, before any unwinding, so it
+        // covers panics that later unwind past `main`.
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            run_exit_flushes();
+            previous(info);
+        }));
+    });
+}
+
+#[cfg(unix)]
+extern "C" fn exit_flush_handler() {
+    run_exit_flushes();
+}
+
+fn run_exit_flushes() {
+    // `try_lock`: never block or panic inside an exit or panic hook.
+    if let Ok(flushes) = EXIT_FLUSHES.try_lock() {
+        for flush in flushes.iter() {
+            flush();
+        }
+    }
+}
+
 impl<W> Drop for FlushGuard<W>
 where
     W: Write + 'static,
@@ -384,6 +671,46 @@ where
     }
 }
 
+impl<C, M> FlameSubscriber<C, PerThreadWriter<M>>
+where
+    C: Collect + for<'span> LookupSpan<'span>,
+    M: MakeFlameWriter + 'static,
+{
+    /// Constructs a `FlameSubscriber` that writes each thread's samples to a
+    /// separate writer, and a `FlushGuard` that flushes all of them.
+    ///
+    /// `make` is called with the [escaped] name of a thread the first time
+    /// that thread produces a sample, and returns the writer that thread's
+    /// samples are sent to. Threads that never produce a sample never get a
+    /// writer. This is useful for processes with many worker threads, where
+    /// a single merged folded file makes it hard to generate per-thread
+    /// flamegraphs.
+    ///
+    /// # Examples
+    ///
+    /// One `.folded` file per thread:
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use std::io::BufWriter;
+    /// use tracing_flame::FlameSubscriber;
+    /// use tracing_subscriber::{prelude::*, registry::Registry};
+    ///
+    /// let (flame_subscriber, _guard) = FlameSubscriber::per_thread(|thread: &str| {
+    ///     File::create(format!("./flame-{}.folded", thread)).map(BufWriter::new)
+    /// });
+    /// let collector = Registry::default().with(flame_subscriber);
+    /// # drop(collector);
+    /// ```
+    ///
+    /// [escaped]: PerThreadWriter#key-escaping
+    pub fn per_thread(make: M) -> (Self, FlushGuard<PerThreadWriter<M>>) {
+        let subscriber = Self::new(PerThreadWriter::new(make));
+        let guard = subscriber.flush_on_drop();
+        (subscriber, guard)
+    }
+}
+
 impl<C, W> Subscribe<C> for FlameSubscriber<C, W>
 where
     C: Collect + for<'span> LookupSpan<'span>,
@@ -392,77 +719,92 @@ where
     fn on_enter(&self, id: &span::Id, ctx: Context<'_, C>) {
         let samples = self.time_since_last_event();
 
-        let first = ctx.span(id).expect("expected: span id exists in registry");
-
-        if !self.config.empty_samples && first.parent().is_none() {
+        // A missing span indicates a bug elsewhere in the collector
+        // composition; `span_or_report` emits a diagnostic rather than
+        // panicking inside instrumentation.
+        if ctx.span_or_report(id).is_none() {
             return;
         }
 
-        let mut stack = String::new();
+        // Attribute the elapsed time to the stack that was entered *before*
+        // this span.
+        self.write_sample(samples, &ctx);
 
-        if !self.config.threads_collapsed {
-            THREAD_NAME.with(|name| stack += name.as_str());
-        } else {
-            stack += "all-threads";
-        }
+        SPAN_STACK.with(|stack| stack.borrow_mut().push(id.clone()));
+    }
 
-        if let Some(second) = first.parent() {
-            for parent in second.scope().from_root() {
-                stack += "; ";
-                write(&mut stack, parent, &self.config)
-                    .expect("expected: write to String never fails");
-            }
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, C>) {
+        if !self.config.events {
+            return;
         }
 
-        write!(&mut stack, " {}", samples.as_nanos())
-            .expect("expected: write to String never fails");
+        // Attribute the elapsed interval to the enclosing stack, *without*
+        // the event leaf, so that marking events does not change the time
+        // attributed to the spans themselves.
+        let samples = self.time_since_last_event();
+        self.write_sample(samples, &ctx);
 
-        let _ = writeln!(*self.out.lock().unwrap(), "{}", stack);
+        self.write_event_marker(event, &ctx);
     }
 
     fn on_exit(&self, id: &span::Id, ctx: Context<'_, C>) {
-        let panicking = std::thread::panicking();
-        macro_rules! expect {
-            ($e:expr, $msg:literal) => {
-                if panicking {
-                    return;
-                } else {
-                    $e.expect($msg)
-                }
-            };
-            ($e:expr) => {
-                if panicking {
-                    return;
-                } else {
-                    $e.unwrap()
-                }
-            };
+        let samples = self.time_since_last_event();
+        if ctx.span_or_report(id).is_none() {
+            return;
         }
 
-        let samples = self.time_since_last_event();
-        let first = expect!(ctx.span(&id), "expected: span id exists in registry");
+        // The exiting span is still on the stack, so the elapsed time is
+        // attributed to it.
+        self.write_sample(samples, &ctx);
 
-        let mut stack = String::new();
-        if !self.config.threads_collapsed {
-            THREAD_NAME.with(|name| stack += name.as_str());
-        } else {
-            stack += "all-threads";
+        let out_of_order = SPAN_STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            match stack.iter().rposition(|entered| entered == id) {
+                Some(pos) => {
+                    let out_of_order = pos + 1 != stack.len();
+                    // Pop up to and including the exited span. If other spans
+                    // were still on top of it, their exits were reordered by
+                    // manually managed guards; discard them rather than
+                    // attributing further samples to a stack that no longer
+                    // exists.
+                    stack.truncate(pos);
+                    out_of_order
+                }
+                // An exit with no matching enter on this thread; leave the
+                // stack untouched.
+                None => false,
+            }
+        });
+
+        if out_of_order {
+            // Warn outside of the `SPAN_STACK` borrow: dispatching the event
+            // may itself re-enter this subscriber.
+            OUT_OF_ORDER_WARNING.call_once(|| {
+                tracing::warn!(
+                    "tracing-flame: spans were exited in non-LIFO order; \
+                     samples for the affected stacks may be approximate"
+                );
+            });
         }
+    }
 
-        for parent in first.scope().from_root() {
-            stack += "; ";
-            expect!(
-                write(&mut stack, parent, &self.config),
-                "expected: write to String never fails"
-            );
+    fn on_close(&self, id: span::Id, ctx: Context<'_, C>) {
+        if !self.config.close_markers {
+            return;
         }
+        let span = match ctx.span_or_report(&id) {
+            Some(span) => span,
+            None => return,
+        };
 
-        expect!(
-            write!(&mut stack, " {}", samples.as_nanos()),
-            "expected: write to String never fails"
-        );
+        // Flush the interval accumulated since the previous event first, so
+        // that the marker lands *after* all samples that precede the close;
+        // otherwise the next enter/exit would attribute that interval to a
+        // sample appearing below the marker.
+        let samples = self.time_since_last_event();
+        self.write_sample(samples, &ctx);
 
-        let _ = writeln!(*expect!(self.out.lock()), "{}", stack);
+        self.write_close_marker(span);
     }
 }
 
@@ -482,6 +824,178 @@ where
 
         now - prev
     }
+
+    /// Writes the thread root frame (with the configured prefix, if any) to
+    /// the start of a sample's stack.
+    fn write_thread_frame(&self, stack: &mut String) {
+        if let Some(ref prefix) = self.config.thread_prefix {
+            *stack += prefix;
+            *stack += "-";
+        }
+        if !self.config.threads_collapsed {
+            THREAD_NAME.with(|name| *stack += name.as_str());
+        } else {
+            *stack += "all-threads";
+        }
+    }
+
+    /// Emits one folded sample attributing `samples` to the stack of spans
+    /// currently entered on this thread.
+    fn write_sample(&self, samples: Duration, ctx: &Context<'_, C>) {
+        // A zero-length interval carries no information; don't emit it.
+        if samples.as_nanos() == 0 {
+            return;
+        }
+
+        let mut stack = String::new();
+        self.write_thread_frame(&mut stack);
+
+        let empty = SPAN_STACK.with(|spans| {
+            let spans = spans.borrow();
+            let mut last = None;
+            for id in spans.iter() {
+                // A span entered again while one of its own guards is still
+                // held appears on the stack twice; emit a single frame for
+                // adjacent duplicates rather than a nonsensical
+                // self-recursive stack.
+                if last == Some(id) {
+                    continue;
+                }
+                last = Some(id);
+
+                if let Some(span) = ctx.span(id) {
+                    stack += "; ";
+                    write(&mut stack, span, &self.config)
+                        .expect("expected: write to String never fails");
+                }
+            }
+            spans.is_empty()
+        });
+
+        if empty {
+            if !self.config.empty_samples {
+                return;
+            }
+            // The thread had no span entered since the last event, so the
+            // elapsed time was spent idle (or doing unmeasured work).
+            // Attribute it to a synthetic `<idle>` frame under the thread
+            // root, rather than folding it into the bare thread frame, so
+            // that flamecharts show the gap explicitly.
+            stack += "; <idle>";
+        }
+
+        write!(&mut stack, " {}", samples.as_nanos())
+            .expect("expected: write to String never fails");
+
+        let mut out = match self.out.lock() {
+            Ok(out) => out,
+            // Don't panic on a lock poisoned by another thread while this
+            // thread is already unwinding.
+            Err(_) if std::thread::panicking() => return,
+            Err(e) => panic!("{}", e),
+        };
+        let _ = writeln!(*out, "{}", stack);
+    }
+
+    /// Emits a zero-length sample marking the closing span's extent, with the
+    /// span's ancestry (from the registry, not the thread's entered stack —
+    /// the span is typically no longer entered when it closes) and a
+    /// synthetic `<close>` leaf frame.
+    fn write_close_marker(&self, span: SpanRef<'_, C>) {
+        let mut stack = String::new();
+        self.write_thread_frame(&mut stack);
+
+        for span in span.scope().from_root() {
+            stack += "; ";
+            write(&mut stack, span, &self.config).expect("expected: write to String never fails");
+        }
+        stack += "; <close> 0";
+
+        let mut out = match self.out.lock() {
+            Ok(out) => out,
+            Err(_) if std::thread::panicking() => return,
+            Err(e) => panic!("{}", e),
+        };
+        let _ = writeln!(*out, "{}", stack);
+    }
+
+    /// Emits a fixed-weight sample marking an event, with the stack of spans
+    /// entered on this thread and a synthetic leaf frame named after the
+    /// event.
+    fn write_event_marker(&self, event: &Event<'_>, ctx: &Context<'_, C>) {
+        let mut stack = String::new();
+        self.write_thread_frame(&mut stack);
+
+        SPAN_STACK.with(|spans| {
+            let spans = spans.borrow();
+            let mut last = None;
+            for id in spans.iter() {
+                if last == Some(id) {
+                    continue;
+                }
+                last = Some(id);
+
+                if let Some(span) = ctx.span(id) {
+                    stack += "; ";
+                    write(&mut stack, span, &self.config)
+                        .expect("expected: write to String never fails");
+                }
+            }
+        });
+
+        let mut name = EventName::default();
+        event.record(&mut name);
+        let name = name
+            .message
+            .unwrap_or_else(|| event.metadata().target().to_string());
+
+        stack += "; ";
+        // `;` separates frames and the trailing space separates the count;
+        // neither may appear inside a frame name.
+        for c in name.chars() {
+            stack.push(match c {
+                ';' => ',',
+                c if c.is_whitespace() => '_',
+                c => c,
+            });
+        }
+
+        write!(&mut stack, " {}", self.config.event_weight.as_nanos())
+            .expect("expected: write to String never fails");
+
+        let mut out = match self.out.lock() {
+            Ok(out) => out,
+            Err(_) if std::thread::panicking() => return,
+            Err(e) => panic!("{}", e),
+        };
+        let _ = writeln!(*out, "{}", stack);
+    }
+}
+
+/// The maximum length, in characters, of an event marker frame name taken
+/// from a `message` field.
+const MAX_EVENT_NAME_LEN: usize = 32;
+
+/// Extracts an event's `message` field, truncated to [`MAX_EVENT_NAME_LEN`]
+/// characters, for use as a marker frame name.
+#[derive(Default)]
+struct EventName {
+    message: Option<String>,
+}
+
+impl field::Visit for EventName {
+    fn record_debug(&mut self, field: &field::Field, value: &dyn fmt::Debug) {
+        if field.name() != "message" || self.message.is_some() {
+            return;
+        }
+        let message = format!("{:?}", value);
+        let mut chars = message.chars();
+        let mut truncated: String = chars.by_ref().take(MAX_EVENT_NAME_LEN).collect();
+        if chars.next().is_some() {
+            truncated.push('…');
+        }
+        self.message = Some(truncated);
+    }
 }
 
 fn write<C>(dest: &mut String, span: SpanRef<'_, C>, config: &Config) -> fmt::Result
@@ -0,0 +1,125 @@
+use crate::error::{Error, Kind};
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+
+/// Merges multiple folded stack files into one, summing the counts of
+/// identical stacks.
+///
+/// Folded files produced by separate processes (or separate runs) cannot
+/// simply be concatenated: lines from different files interleave
+/// arbitrarily, and identical stacks appear once per file rather than once
+/// with their combined weight. This function reads every input, sums the
+/// counts of lines whose stacks are identical, and writes each stack exactly
+/// once. The output is sorted by stack, so merging the same inputs always
+/// produces byte-identical output regardless of input order.
+///
+/// Note that thread IDs are only unique within a process; use
+/// [`with_thread_prefix`] when recording so that stacks from different
+/// processes remain distinguishable after the merge.
+///
+/// # Examples
+///
+/// Merging the folded files written by a set of worker processes:
+///
+/// ```no_run
+/// use std::fs::File;
+/// use std::io::BufReader;
+///
+/// # fn docs() -> Result<(), Box<dyn std::error::Error>> {
+/// let inputs = ["worker-0.folded", "worker-1.folded"]
+///     .iter()
+///     .map(|path| Ok(BufReader::new(File::open(path)?)))
+///     .collect::<Result<Vec<_>, std::io::Error>>()?;
+/// let out = File::create("merged.folded")?;
+/// tracing_flame::merge(inputs, out)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`with_thread_prefix`]: crate::FlameSubscriber::with_thread_prefix()
+pub fn merge<W: Write>(
+    inputs: impl IntoIterator<Item = impl BufRead>,
+    mut out: W,
+) -> Result<(), Error> {
+    // A `BTreeMap` keyed on the stack makes the output order deterministic,
+    // no matter how the inputs interleave.
+    let mut totals: BTreeMap<String, u128> = BTreeMap::new();
+    for input in inputs {
+        for line in input.lines() {
+            let line = line.map_err(|source| Error(Kind::ReadInput(source)))?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            // Everything up to the last space is the stack; the rest is the
+            // sample count.
+            let split = line
+                .rfind(' ')
+                .ok_or_else(|| Error(Kind::ParseSample(line.to_string())))?;
+            let (stack, count) = line.split_at(split);
+            let count = count
+                .trim_start()
+                .parse::<u128>()
+                .map_err(|_| Error(Kind::ParseSample(line.to_string())))?;
+            *totals.entry(stack.to_string()).or_insert(0) += count;
+        }
+    }
+    for (stack, count) in totals {
+        writeln!(out, "{} {}", stack, count).map_err(|source| Error(Kind::WriteOutput(source)))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge;
+    use std::io::Cursor;
+
+    fn merged(inputs: &[&str]) -> String {
+        let mut out = Vec::new();
+        merge(
+            inputs.iter().map(|input| Cursor::new(input.as_bytes())),
+            &mut out,
+        )
+        .expect("merging valid inputs should succeed");
+        String::from_utf8(out).expect("merged output should be UTF-8")
+    }
+
+    #[test]
+    fn overlapping_stacks_sum_their_counts() {
+        let out = merged(&[
+            "worker-1; outer; inner 100\nworker-1; outer 10\n",
+            "worker-1; outer; inner 50\n",
+        ]);
+        assert_eq!(out, "worker-1; outer 10\nworker-1; outer; inner 150\n");
+    }
+
+    #[test]
+    fn disjoint_stacks_are_kept_separate() {
+        let out = merged(&["a; x 1\n", "b; y 2\n"]);
+        assert_eq!(out, "a; x 1\nb; y 2\n");
+    }
+
+    #[test]
+    fn output_order_does_not_depend_on_input_order() {
+        let forward = merged(&["a; x 1\n", "b; y 2\n"]);
+        let backward = merged(&["b; y 2\n", "a; x 1\n"]);
+        assert_eq!(forward, backward);
+    }
+
+    #[test]
+    fn blank_lines_are_skipped() {
+        let out = merged(&["\na; x 1\n\n"]);
+        assert_eq!(out, "a; x 1\n");
+    }
+
+    #[test]
+    fn malformed_lines_are_an_error() {
+        let mut out = Vec::new();
+        let result = merge(
+            std::iter::once(Cursor::new(&b"no trailing count"[..])),
+            &mut out,
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,149 @@
+//! Writers that split the folded output into one destination per thread.
+use crate::THREAD_NAME;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Write};
+
+/// A factory for the writers used by a [`PerThreadWriter`].
+///
+/// The `key` passed to [`make_writer`] identifies the thread whose samples
+/// the returned writer will receive; it is the thread's name (and ID),
+/// [escaped] so that it can be used directly in a file name.
+///
+/// This trait is implemented for any `Fn(&str) -> io::Result<W>` closure
+/// where `W: Write`, so a factory is typically just a closure:
+///
+/// ```
+/// use std::fs::File;
+/// use tracing_flame::FlameSubscriber;
+/// use tracing_subscriber::registry::Registry;
+///
+/// # fn docs() {
+/// let (flame_subscriber, _guard) = FlameSubscriber::<Registry, _>::per_thread(|thread: &str| {
+///     File::create(format!("./flame-{}.folded", thread))
+/// });
+/// # }
+/// ```
+///
+/// [`make_writer`]: MakeFlameWriter::make_writer
+/// [escaped]: PerThreadWriter#key-escaping
+pub trait MakeFlameWriter {
+    /// The type of writer produced by this factory.
+    type Writer: Write;
+
+    /// Returns a new writer for the thread identified by `key`.
+    fn make_writer(&self, key: &str) -> io::Result<Self::Writer>;
+}
+
+impl<F, W> MakeFlameWriter for F
+where
+    F: Fn(&str) -> io::Result<W>,
+    W: Write,
+{
+    type Writer = W;
+
+    fn make_writer(&self, key: &str) -> io::Result<W> {
+        (self)(key)
+    }
+}
+
+/// A writer that forwards each thread's samples to a separate writer,
+/// created lazily by a [`MakeFlameWriter`] factory.
+///
+/// A `FlameSubscriber` writes every sample on the instrumented thread that
+/// produced it, so dispatching on the current thread's name splits the
+/// merged folded stream back into per-thread streams — e.g. one `.folded`
+/// file per worker thread, each of which can be fed to `inferno-flamegraph`
+/// on its own. Construct one with [`FlameSubscriber::per_thread`].
+///
+/// A thread's writer is created on the first sample from that thread;
+/// threads that never produce a sample never get a writer. Flushing this
+/// writer — e.g. through a [`FlushGuard`] — flushes every per-thread writer
+/// created so far.
+///
+/// # Key Escaping
+///
+/// The key passed to the factory is the same thread name (and ID) used for
+/// the thread root frame in the folded output, with path separators (`/`
+/// and `\`) replaced by `-` and whitespace replaced by `_`, so it can be
+/// spliced into a file name without creating directories.
+///
+/// [`FlameSubscriber::per_thread`]: crate::FlameSubscriber::per_thread
+/// [`FlushGuard`]: crate::FlushGuard
+pub struct PerThreadWriter<M>
+where
+    M: MakeFlameWriter,
+{
+    make: M,
+    writers: HashMap<String, M::Writer>,
+}
+
+thread_local! {
+    /// The current thread's name, pre-escaped for use as a writer key.
+    static ESCAPED_THREAD_NAME: String = THREAD_NAME.with(|name| escape_key(name));
+}
+
+/// Escapes a thread name for use as a writer key (and thus, typically, in a
+/// file name).
+fn escape_key(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' => '-',
+            c if c.is_whitespace() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+impl<M> PerThreadWriter<M>
+where
+    M: MakeFlameWriter,
+{
+    pub(crate) fn new(make: M) -> Self {
+        Self {
+            make,
+            writers: HashMap::new(),
+        }
+    }
+
+    /// Returns the writer for the current thread, creating it on the first
+    /// sample from this thread.
+    fn current(&mut self) -> io::Result<&mut M::Writer> {
+        let key = ESCAPED_THREAD_NAME.with(String::clone);
+        if !self.writers.contains_key(&key) {
+            let writer = self.make.make_writer(&key)?;
+            self.writers.insert(key.clone(), writer);
+        }
+        Ok(self
+            .writers
+            .get_mut(&key)
+            .expect("writer was just inserted"))
+    }
+}
+
+impl<M> Write for PerThreadWriter<M>
+where
+    M: MakeFlameWriter,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.current()?.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        for writer in self.writers.values_mut() {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl<M> fmt::Debug for PerThreadWriter<M>
+where
+    M: MakeFlameWriter,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PerThreadWriter")
+            .field("threads", &self.writers.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
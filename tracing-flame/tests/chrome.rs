@@ -0,0 +1,62 @@
+use std::thread::sleep;
+use std::time::Duration;
+use tempdir::TempDir;
+use tracing::{span, Level};
+use tracing_flame::ChromeSubscriber;
+use tracing_subscriber::{prelude::*, registry::Registry};
+
+#[test]
+fn chrome_begin_and_end_records_pair_up() {
+    let tmp_dir = TempDir::new("chrome").unwrap();
+    let path = tmp_dir.path().join("trace.json");
+    let (chrome_layer, guard) = ChromeSubscriber::with_file(&path).unwrap();
+
+    let subscriber = Registry::default().with(chrome_layer);
+
+    tracing::collect::with_default(subscriber, || {
+        let span = span!(Level::ERROR, "outer");
+        let _guard = span.enter();
+        sleep(Duration::from_millis(10));
+
+        {
+            let span = span!(Level::ERROR, "inner");
+            let _guard = span.enter();
+            sleep(Duration::from_millis(10));
+        }
+    });
+
+    // Dropping the guard terminates the JSON array and flushes the file.
+    drop(guard);
+
+    let trace = std::fs::read_to_string(&path).unwrap();
+    let records: Vec<serde_json::Value> = serde_json::from_str(&trace).unwrap();
+    assert_eq!(records.len(), 4, "expected a B and an E record per span");
+
+    let mut stack = Vec::new();
+    let mut last_ts = 0.0;
+    for record in &records {
+        let name = record["name"].as_str().expect("name must be a string");
+        assert_eq!(record["pid"].as_u64(), Some(std::process::id() as u64));
+        assert!(record["tid"].is_string(), "tid must be the thread name");
+        assert_eq!(
+            record["cat"].as_str(),
+            Some(module_path!()),
+            "cat must be the span's module path"
+        );
+
+        let ts = record["ts"].as_f64().expect("ts must be a number");
+        assert!(ts >= last_ts, "timestamps must be non-decreasing");
+        last_ts = ts;
+
+        match record["ph"].as_str() {
+            Some("B") => stack.push(name),
+            Some("E") => assert_eq!(
+                stack.pop(),
+                Some(name),
+                "an E record must close the most recently begun span"
+            ),
+            ph => panic!("unexpected phase: {:?}", ph),
+        }
+    }
+    assert!(stack.is_empty(), "every B record must have an E record");
+}
@@ -0,0 +1,119 @@
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::thread::sleep;
+use std::time::Duration;
+use tracing::{span, Level};
+use tracing_flame::FlameSubscriber;
+use tracing_subscriber::{prelude::*, registry::Registry};
+
+#[derive(Clone, Default)]
+struct CaptureWriter(Arc<Mutex<Vec<u8>>>);
+
+impl Write for CaptureWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn close_marker_is_emitted_once_per_span() {
+    let writer = CaptureWriter::default();
+    let flame_layer = FlameSubscriber::new(writer.clone()).with_close_markers(true);
+    let subscriber = Registry::default().with(flame_layer);
+
+    tracing::collect::with_default(subscriber, || {
+        let span = span!(Level::ERROR, "reentered");
+        // A span may be exited and re-entered many times; it only truly ends
+        // when it closes.
+        for _ in 0..2 {
+            let _guard = span.enter();
+            sleep(Duration::from_millis(5));
+        }
+        drop(span);
+    });
+
+    let output = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+    println!("{}", output);
+
+    let close_lines: Vec<&str> = output
+        .lines()
+        .filter(|line| line.contains("<close>"))
+        .collect();
+    // Two enter/exit cycles, one close: the marker must track the close, not
+    // the exits.
+    assert_eq!(close_lines.len(), 1);
+
+    let close = close_lines[0];
+    assert!(
+        close.contains("reentered"),
+        "the marker must carry the closing span's frame: {:?}",
+        close
+    );
+    assert!(
+        close.trim_end().ends_with("<close> 0"),
+        "the marker must be zero-length: {:?}",
+        close
+    );
+
+    // The close marker must come after the samples for both enter/exit
+    // cycles.
+    let lines: Vec<&str> = output.lines().collect();
+    let last_sample = lines
+        .iter()
+        .rposition(|line| line.contains("reentered") && !line.contains("<close>"))
+        .expect("expected samples for the `reentered` span");
+    let marker = lines
+        .iter()
+        .position(|line| line.contains("<close>"))
+        .unwrap();
+    assert!(marker > last_sample);
+}
+
+#[test]
+fn close_markers_are_off_by_default() {
+    let writer = CaptureWriter::default();
+    let flame_layer = FlameSubscriber::new(writer.clone());
+    let subscriber = Registry::default().with(flame_layer);
+
+    tracing::collect::with_default(subscriber, || {
+        span!(Level::ERROR, "quiet").in_scope(|| sleep(Duration::from_millis(5)));
+    });
+
+    let output = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+    assert!(!output.contains("<close>"));
+}
+
+#[test]
+fn close_marker_includes_ancestry() {
+    let writer = CaptureWriter::default();
+    let flame_layer = FlameSubscriber::new(writer.clone()).with_close_markers(true);
+    let subscriber = Registry::default().with(flame_layer);
+
+    tracing::collect::with_default(subscriber, || {
+        let outer = span!(Level::ERROR, "outer");
+        let _outer = outer.enter();
+        let inner = span!(Level::ERROR, "inner");
+        for _ in 0..2 {
+            let _inner = inner.enter();
+            sleep(Duration::from_millis(5));
+        }
+        drop(inner);
+    });
+
+    let output = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+    println!("{}", output);
+
+    let inner_close = output
+        .lines()
+        .find(|line| line.contains("<close>") && line.contains("inner"))
+        .expect("expected a close marker for `inner`");
+    // The marker stack is the span's full ancestry, root first.
+    let outer_pos = inner_close.find("outer").unwrap();
+    let inner_pos = inner_close.find("inner").unwrap();
+    assert!(outer_pos < inner_pos);
+}
@@ -0,0 +1,149 @@
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::thread::sleep;
+use std::time::Duration;
+use tracing::{span, Level};
+use tracing_flame::FlameSubscriber;
+use tracing_subscriber::{prelude::*, registry::Registry};
+
+#[derive(Clone, Default)]
+struct CaptureWriter(Arc<Mutex<Vec<u8>>>);
+
+impl Write for CaptureWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn event_adds_marker_line_without_changing_span_time() {
+    let writer = CaptureWriter::default();
+    let flame_layer = FlameSubscriber::new(writer.clone()).with_events(true);
+    let subscriber = Registry::default().with(flame_layer);
+
+    tracing::collect::with_default(subscriber, || {
+        let span = span!(Level::ERROR, "outer");
+        let _guard = span.enter();
+        sleep(Duration::from_millis(5));
+        tracing::info!("cache miss; cold");
+        sleep(Duration::from_millis(5));
+    });
+
+    let output = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+    println!("{}", output);
+
+    // `;` and spaces in the message must be escaped, since they are the
+    // folded format's separators.
+    let marker_lines: Vec<&str> = output
+        .lines()
+        .filter(|line| line.contains("cache_miss,_cold"))
+        .collect();
+    assert_eq!(marker_lines.len(), 1);
+
+    let marker = marker_lines[0];
+    assert!(
+        marker.contains("outer"),
+        "the marker must sit under the enclosing stack: {:?}",
+        marker
+    );
+    assert!(
+        marker.trim_end().ends_with(" 0"),
+        "the marker's weight must default to zero: {:?}",
+        marker
+    );
+
+    // Both sleeps are still attributed to the span itself: the marker's
+    // zero weight adds nothing, and the interval before the event goes to
+    // the enclosing stack without the leaf.
+    let span_time: u128 = output
+        .lines()
+        .filter(|line| line.contains("outer") && !line.contains("cache_miss"))
+        .map(|line| {
+            line.rsplit(' ')
+                .next()
+                .unwrap()
+                .parse::<u128>()
+                .expect("expected a folded line ending in a nanosecond count")
+        })
+        .sum();
+    assert!(
+        span_time >= Duration::from_millis(10).as_nanos(),
+        "expected at least 10ms attributed to `outer`, got {}ns",
+        span_time
+    );
+}
+
+#[test]
+fn event_markers_are_off_by_default() {
+    let writer = CaptureWriter::default();
+    let flame_layer = FlameSubscriber::new(writer.clone());
+    let subscriber = Registry::default().with(flame_layer);
+
+    tracing::collect::with_default(subscriber, || {
+        span!(Level::ERROR, "quiet").in_scope(|| {
+            sleep(Duration::from_millis(5));
+            tracing::info!("ping");
+            sleep(Duration::from_millis(5));
+        });
+    });
+
+    let output = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+    assert!(!output.contains("ping"));
+}
+
+#[test]
+fn event_without_message_is_named_after_its_target() {
+    let writer = CaptureWriter::default();
+    let flame_layer = FlameSubscriber::new(writer.clone()).with_events(true);
+    let subscriber = Registry::default().with(flame_layer);
+
+    tracing::collect::with_default(subscriber, || {
+        span!(Level::ERROR, "outer").in_scope(|| {
+            sleep(Duration::from_millis(5));
+            tracing::info!(count = 1);
+            sleep(Duration::from_millis(5));
+        });
+    });
+
+    let output = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+    println!("{}", output);
+
+    assert!(
+        output
+            .lines()
+            .any(|line| line.trim_end().ends_with("; event_markers 0")),
+        "expected a marker named after the event's target"
+    );
+}
+
+#[test]
+fn event_weight_is_configurable() {
+    let writer = CaptureWriter::default();
+    let flame_layer = FlameSubscriber::new(writer.clone())
+        .with_events(true)
+        .with_event_weight(Duration::from_micros(1));
+    let subscriber = Registry::default().with(flame_layer);
+
+    tracing::collect::with_default(subscriber, || {
+        span!(Level::ERROR, "outer").in_scope(|| {
+            sleep(Duration::from_millis(5));
+            tracing::info!("ping");
+            sleep(Duration::from_millis(5));
+        });
+    });
+
+    let output = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+    println!("{}", output);
+
+    assert!(
+        output
+            .lines()
+            .any(|line| line.trim_end().ends_with("; ping 1000")),
+        "expected the marker to carry the configured weight"
+    );
+}
@@ -0,0 +1,53 @@
+use std::io::{BufRead, BufReader, BufWriter};
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::thread::sleep;
+use std::time::Duration;
+use tracing::{span, Level};
+use tracing_flame::FlameSubscriber;
+use tracing_subscriber::{prelude::*, registry::Registry};
+
+#[test]
+fn periodic_flush_streams_samples_over_a_socket() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let stream = TcpStream::connect(addr).unwrap();
+    let (accepted, _) = listener.accept().unwrap();
+    accepted
+        .set_read_timeout(Some(Duration::from_secs(30)))
+        .unwrap();
+
+    let flame_layer =
+        FlameSubscriber::new(BufWriter::new(stream)).with_flush_interval(Duration::from_millis(25));
+    let subscriber = Registry::default().with(flame_layer);
+
+    tracing::collect::with_default(subscriber, || {
+        span!(Level::ERROR, "streamed").in_scope(|| sleep(Duration::from_millis(10)));
+
+        // No manual flush: the samples must arrive over the socket while the
+        // collector is still installed, pushed out by the periodic flusher.
+        let mut reader = BufReader::new(&accepted);
+        loop {
+            let mut line = String::new();
+            let read = reader
+                .read_line(&mut line)
+                .expect("the flusher should deliver samples before the read times out");
+            assert_ne!(read, 0, "the socket closed before a sample arrived");
+
+            // The first samples may be `<idle>` frames; wait for the span.
+            if line.contains("streamed") {
+                let samples = line
+                    .trim_end()
+                    .rsplit(' ')
+                    .next()
+                    .expect("samples are the last field of a folded line");
+                assert!(
+                    samples.parse::<u128>().is_ok(),
+                    "folded line should end with a sample count: {:?}",
+                    line
+                );
+                break;
+            }
+        }
+    });
+}
@@ -0,0 +1,50 @@
+#![cfg(unix)]
+use std::path::Path;
+use std::process::Command;
+use std::thread::sleep;
+use std::time::Duration;
+use tempdir::TempDir;
+use tracing::{span, Level};
+use tracing_flame::FlameSubscriber;
+use tracing_subscriber::{prelude::*, registry::Registry};
+
+/// Set in the child process to the path of the folded file it should write.
+const CHILD_ENV: &str = "TRACING_FLAME_FLUSH_ON_EXIT_CHILD";
+
+#[test]
+fn flush_on_exit_survives_process_exit() {
+    if let Ok(path) = std::env::var(CHILD_ENV) {
+        child(path.as_ref());
+    }
+
+    let tmp_dir = TempDir::new("flush_on_exit").expect("failed to create tempdir");
+    let path = tmp_dir.path().join("flamegraph.folded");
+    // Re-run this same test in a subprocess, which emits a span and then
+    // calls `std::process::exit` without dropping its `FlushGuard`.
+    let status = Command::new(std::env::current_exe().unwrap())
+        .arg("flush_on_exit_survives_process_exit")
+        .env(CHILD_ENV, &path)
+        .status()
+        .expect("failed to spawn child test process");
+    assert!(status.success());
+
+    let traces = std::fs::read_to_string(&path).expect("child produced no folded file");
+    assert!(
+        traces.lines().any(|line| line.contains("outer")),
+        "folded output is missing the span emitted before exit: {:?}",
+        traces
+    );
+}
+
+/// Emits a span, then exits without returning, so that neither the guard nor
+/// the subscriber is dropped; only the exit hook can flush the buffered
+/// samples.
+fn child(path: &Path) -> ! {
+    let (flame_layer, guard) = FlameSubscriber::with_file(path).unwrap();
+    let _guard = guard.flush_on_exit();
+    let subscriber = Registry::default().with(flame_layer);
+    tracing::collect::with_default(subscriber, || {
+        span!(Level::ERROR, "outer").in_scope(|| sleep(Duration::from_millis(10)));
+        std::process::exit(0);
+    })
+}
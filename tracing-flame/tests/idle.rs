@@ -0,0 +1,57 @@
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::thread::sleep;
+use std::time::Duration;
+use tracing::{span, Level};
+use tracing_flame::FlameSubscriber;
+use tracing_subscriber::{prelude::*, registry::Registry};
+
+#[derive(Clone, Default)]
+struct CaptureWriter(Arc<Mutex<Vec<u8>>>);
+
+impl Write for CaptureWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn idle_time_between_root_spans_is_attributed_to_an_idle_frame() {
+    let writer = CaptureWriter::default();
+    let flame_layer = FlameSubscriber::new(writer.clone());
+    let subscriber = Registry::default().with(flame_layer);
+
+    tracing::collect::with_default(subscriber, || {
+        span!(Level::ERROR, "first").in_scope(|| sleep(Duration::from_millis(10)));
+
+        // Idle time outside of any span.
+        sleep(Duration::from_millis(10));
+
+        span!(Level::ERROR, "second").in_scope(|| sleep(Duration::from_millis(10)));
+    });
+
+    let output = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+    println!("{}", output);
+
+    let first = output
+        .lines()
+        .position(|line| line.contains("first"))
+        .expect("expected a sample for the `first` span");
+    let second = output
+        .lines()
+        .position(|line| line.contains("second"))
+        .expect("expected a sample for the `second` span");
+    let idle_between = output
+        .lines()
+        .enumerate()
+        .any(|(i, line)| line.contains("; <idle> ") && first < i && i < second);
+    assert!(
+        idle_between,
+        "expected an `<idle>` sample between the two root spans"
+    );
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::thread::sleep;
+use std::time::Duration;
+use tracing::{span, Level};
+use tracing_flame::FlameSubscriber;
+use tracing_subscriber::{prelude::*, registry::Registry};
+
+#[derive(Clone, Default)]
+struct CaptureWriter(Arc<Mutex<Vec<u8>>>);
+
+impl Write for CaptureWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A factory that captures each thread's output in memory, recording the
+/// keys it was asked to create writers for.
+#[derive(Clone, Default)]
+struct CaptureFactory {
+    outputs: Arc<Mutex<HashMap<String, CaptureWriter>>>,
+}
+
+impl CaptureFactory {
+    fn make(&self) -> impl Fn(&str) -> std::io::Result<CaptureWriter> {
+        let outputs = self.outputs.clone();
+        move |key| {
+            let writer = CaptureWriter::default();
+            outputs
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), writer.clone());
+            Ok(writer)
+        }
+    }
+
+    fn outputs(&self) -> HashMap<String, String> {
+        self.outputs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(key, writer)| {
+                let bytes = writer.0.lock().unwrap().clone();
+                (key.clone(), String::from_utf8(bytes).unwrap())
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn threads_write_to_disjoint_outputs() {
+    let factory = CaptureFactory::default();
+    let (flame_subscriber, guard) = FlameSubscriber::per_thread(factory.make());
+    let dispatch = tracing::Dispatch::new(Registry::default().with(flame_subscriber));
+
+    let threads: Vec<_> = ["alpha", "beta"]
+        .iter()
+        .map(|name| {
+            let dispatch = dispatch.clone();
+            std::thread::Builder::new()
+                .name(name.to_string())
+                .spawn(move || {
+                    tracing::dispatch::with_default(&dispatch, || {
+                        let span = span!(Level::ERROR, "work");
+                        let _guard = span.enter();
+                        sleep(Duration::from_millis(10));
+                    })
+                })
+                .unwrap()
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    guard.flush().unwrap();
+
+    let outputs = factory.outputs();
+    assert_eq!(outputs.len(), 2, "one writer per thread: {:?}", outputs);
+
+    for name in &["alpha", "beta"] {
+        let (key, output) = outputs
+            .iter()
+            .find(|(key, _)| key.contains(name))
+            .unwrap_or_else(|| panic!("no writer created for {}: {:?}", name, outputs));
+        // Every line in a thread's file belongs to that thread, and the
+        // other thread's samples must not leak into it.
+        let other = if *name == "alpha" { "beta" } else { "alpha" };
+        assert!(
+            output.lines().all(|line| line.contains(name)),
+            "{}: {:?}",
+            key,
+            output
+        );
+        assert!(
+            !output.contains(other),
+            "{} contains samples from {}: {:?}",
+            key,
+            other,
+            output
+        );
+        assert!(
+            output.lines().any(|line| line.contains("work")),
+            "{}: {:?}",
+            key,
+            output
+        );
+    }
+}
+
+#[test]
+fn writers_are_created_lazily() {
+    let factory = CaptureFactory::default();
+    let (flame_subscriber, guard) = FlameSubscriber::per_thread(factory.make());
+    let dispatch = tracing::Dispatch::new(Registry::default().with(flame_subscriber));
+
+    // A thread that produces samples, and one that never does.
+    let sampling = {
+        let dispatch = dispatch.clone();
+        std::thread::Builder::new()
+            .name("sampling".into())
+            .spawn(move || {
+                tracing::dispatch::with_default(&dispatch, || {
+                    span!(Level::ERROR, "work").in_scope(|| sleep(Duration::from_millis(5)));
+                })
+            })
+            .unwrap()
+    };
+    let silent = std::thread::Builder::new()
+        .name("silent".into())
+        .spawn(|| {})
+        .unwrap();
+    sampling.join().unwrap();
+    silent.join().unwrap();
+    guard.flush().unwrap();
+
+    let outputs = factory.outputs();
+    assert_eq!(
+        outputs.len(),
+        1,
+        "only the sampling thread gets a writer: {:?}",
+        outputs
+    );
+    assert!(outputs.keys().all(|key| key.contains("sampling")));
+}
+
+#[test]
+fn keys_are_escaped_for_file_names() {
+    let factory = CaptureFactory::default();
+    let (flame_subscriber, guard) = FlameSubscriber::per_thread(factory.make());
+    let dispatch = tracing::Dispatch::new(Registry::default().with(flame_subscriber));
+
+    std::thread::Builder::new()
+        .name("pool/worker 1".into())
+        .spawn(move || {
+            tracing::dispatch::with_default(&dispatch, || {
+                span!(Level::ERROR, "work").in_scope(|| sleep(Duration::from_millis(5)));
+            })
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+    guard.flush().unwrap();
+
+    let outputs = factory.outputs();
+    let key = outputs.keys().next().expect("a writer should exist");
+    assert!(
+        key.contains("pool-worker_1"),
+        "separators and whitespace should be escaped: {:?}",
+        key
+    );
+    assert!(!key.contains('/') && !key.contains(' '), "{:?}", key);
+}
@@ -0,0 +1,120 @@
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::thread::sleep;
+use std::time::Duration;
+use tracing::{span, Level};
+use tracing_flame::FlameSubscriber;
+use tracing_subscriber::{prelude::*, registry::Registry};
+
+#[derive(Clone, Default)]
+struct CaptureWriter(Arc<Mutex<Vec<u8>>>);
+
+impl Write for CaptureWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl CaptureWriter {
+    fn output(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+#[test]
+fn reentering_a_span_does_not_duplicate_frames() {
+    let writer = CaptureWriter::default();
+    let flame_layer = FlameSubscriber::new(writer.clone());
+    let subscriber = Registry::default().with(flame_layer);
+
+    tracing::collect::with_default(subscriber, || {
+        let span = span!(Level::ERROR, "reentrant");
+        let _outer = span.enter();
+        sleep(Duration::from_millis(10));
+        {
+            // The same span is now entered twice on this thread.
+            let _inner = span.enter();
+            sleep(Duration::from_millis(10));
+        }
+        sleep(Duration::from_millis(10));
+    });
+
+    let output = writer.output();
+    println!("{}", output);
+
+    assert!(output.contains("reentrant"));
+    for line in output.lines() {
+        assert!(
+            line.matches("reentrant").count() <= 1,
+            "expected no duplicate adjacent frames, got: {}",
+            line
+        );
+    }
+}
+
+#[test]
+fn out_of_order_exits_produce_well_formed_stacks() {
+    let writer = CaptureWriter::default();
+    let flame_layer = FlameSubscriber::new(writer.clone());
+    let subscriber = Registry::default().with(flame_layer);
+
+    tracing::collect::with_default(subscriber, || {
+        let outer = span!(Level::ERROR, "outer_span");
+        let inner = span!(Level::ERROR, "inner_span");
+        let outer_guard = outer.enter();
+        let inner_guard = inner.enter();
+        sleep(Duration::from_millis(10));
+        // Exit the outer span while the inner one is still entered.
+        drop(outer_guard);
+        sleep(Duration::from_millis(10));
+        drop(inner_guard);
+    });
+
+    let output = writer.output();
+    println!("{}", output);
+
+    // The time while both spans were entered is attributed to the stack that
+    // actually existed.
+    let nested = output.lines().any(|line| {
+        let outer = match line.find("outer_span") {
+            Some(i) => i,
+            None => return false,
+        };
+        line[outer..].contains("inner_span")
+    });
+    assert!(
+        nested,
+        "expected a sample with `inner_span` below `outer_span`"
+    );
+
+    // Exiting `outer_span` pops `inner_span` as well, so no later sample may
+    // claim either span was still entered, and no sample may show the
+    // inverted stack.
+    for line in output.lines() {
+        if let Some(inner) = line.find("inner_span") {
+            assert!(
+                !line[inner..].contains("outer_span"),
+                "stack that never existed: {}",
+                line
+            );
+        }
+        if line.contains("<idle>") {
+            assert!(
+                !line.contains("outer_span") && !line.contains("inner_span"),
+                "idle sample should not contain span frames: {}",
+                line
+            );
+        }
+    }
+    let last = output.lines().last().expect("expected at least one sample");
+    assert!(
+        last.contains("<idle>"),
+        "time after the out-of-order exit should be idle, got: {}",
+        last
+    );
+}
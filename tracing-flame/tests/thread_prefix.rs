@@ -0,0 +1,75 @@
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::thread::sleep;
+use std::time::Duration;
+use tracing::{span, Level};
+use tracing_flame::FlameSubscriber;
+use tracing_subscriber::{prelude::*, registry::Registry};
+
+#[derive(Clone, Default)]
+struct CaptureWriter(Arc<Mutex<Vec<u8>>>);
+
+impl Write for CaptureWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn thread_prefix_is_prepended_and_escaped() {
+    let writer = CaptureWriter::default();
+    // `;` and spaces in the prefix must be escaped, since they are the
+    // folded format's separators.
+    let flame_layer = FlameSubscriber::new(writer.clone()).with_thread_prefix("svc 1;a");
+    let subscriber = Registry::default().with(flame_layer);
+
+    tracing::collect::with_default(subscriber, || {
+        let span = span!(Level::ERROR, "outer");
+        let _guard = span.enter();
+        sleep(Duration::from_millis(5));
+    });
+
+    let output = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+    println!("{}", output);
+
+    assert!(!output.is_empty(), "expected at least one sample");
+    for line in output.lines() {
+        assert!(
+            line.starts_with("svc_1,a-"),
+            "every thread frame must begin with the escaped prefix: {:?}",
+            line
+        );
+    }
+}
+
+#[test]
+fn thread_prefix_applies_to_collapsed_threads() {
+    let writer = CaptureWriter::default();
+    let flame_layer = FlameSubscriber::new(writer.clone())
+        .with_threads_collapsed(true)
+        .with_thread_prefix("worker-0");
+    let subscriber = Registry::default().with(flame_layer);
+
+    tracing::collect::with_default(subscriber, || {
+        let span = span!(Level::ERROR, "outer");
+        let _guard = span.enter();
+        sleep(Duration::from_millis(5));
+    });
+
+    let output = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+    println!("{}", output);
+
+    assert!(!output.is_empty(), "expected at least one sample");
+    for line in output.lines() {
+        assert!(
+            line.starts_with("worker-0-all-threads"),
+            "the prefix must also distinguish collapsed thread pools: {:?}",
+            line
+        );
+    }
+}
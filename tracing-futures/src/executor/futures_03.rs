@@ -1,4 +1,4 @@
-use crate::{Instrument, Instrumented, WithDispatch};
+use crate::{Instrument, Instrumented, WithCollector, WithDispatch};
 use futures_task::{FutureObj, LocalFutureObj, LocalSpawn, Spawn, SpawnError};
 
 impl<T> Spawn for Instrumented<T>
@@ -89,6 +89,42 @@ where
     }
 }
 
+/// Extension trait adding instrumented spawning to [`Spawn`] executors.
+///
+/// This trait is blanket-implemented for all types implementing [`Spawn`].
+pub trait SpawnExt: Spawn {
+    /// Spawns a future that inherits the current span and the current
+    /// dispatcher.
+    ///
+    /// Spawned tasks are polled outside the scope in which they were spawned,
+    /// so when a scoped (non-global) default collector is in use, their events
+    /// would otherwise be dispatched to the global default and vanish. This
+    /// method wraps the future with the [span] and [dispatcher] that are
+    /// current at the time it is spawned, before handing it to the underlying
+    /// executor.
+    ///
+    /// # Errors
+    ///
+    /// The executor may be unable to spawn tasks. Spawn errors should
+    /// represent relatively rare scenarios, such as the executor
+    /// having been shut down so that it is no longer able to accept
+    /// tasks.
+    ///
+    /// [span]: tracing::Span::current
+    /// [dispatcher]: crate::WithCollector::with_current_collector
+    fn spawn_instrumented<F>(&self, future: F) -> Result<(), SpawnError>
+    where
+        F: core::future::Future<Output = ()> + Send + 'static,
+    {
+        let future = future
+            .instrument(tracing::Span::current())
+            .with_current_collector();
+        self.spawn_obj(FutureObj::new(Box::new(future)))
+    }
+}
+
+impl<T: Spawn + ?Sized> SpawnExt for T {}
+
 impl<T> LocalSpawn for WithDispatch<T>
 where
     T: LocalSpawn,
@@ -117,3 +153,41 @@ where
         self.inner.status_local()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+    use futures::executor::LocalPool;
+    use tracing::{collect::with_default, Level};
+
+    #[test]
+    fn spawned_tasks_inherit_the_scoped_collector() {
+        let (collector, handle) = collector::mock()
+            .enter(span::mock().named("foo"))
+            .exit(span::mock().named("foo"))
+            .enter(span::mock().named("foo"))
+            .event(event::mock())
+            .exit(span::mock().named("foo"))
+            .drop_span(span::mock().named("foo"))
+            .done()
+            .run_with_handle();
+
+        let mut pool = LocalPool::new();
+        let spawner = pool.spawner();
+        with_default(collector, || {
+            let span = tracing::span!(Level::TRACE, "foo");
+            let _e = span.enter();
+            spawner
+                .spawn_instrumented(async {
+                    tracing::trace!("in the spawned task");
+                })
+                .expect("spawn should succeed");
+        });
+
+        // The task is polled outside of the `with_default` scope; its events
+        // must still reach the scoped collector.
+        pool.run();
+        handle.assert_finished();
+    }
+}
@@ -12,3 +12,54 @@ pub use self::futures_preview::*;
 mod futures_03;
 #[cfg(feature = "futures-03")]
 pub use self::futures_03::*;
+
+/// A boxed, pinned future, as handed to a spawn function by
+/// [`spawn_instrumented`].
+#[cfg(all(feature = "std", feature = "std-future"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "std", feature = "std-future"))))]
+pub type BoxFuture = core::pin::Pin<Box<dyn core::future::Future<Output = ()> + Send + 'static>>;
+
+/// Instruments a future with the current span and the current dispatcher,
+/// then passes it to a spawn function.
+///
+/// When tasks are spawned with a scoped (non-global) default collector, the
+/// spawned task is polled outside the scope of [`with_default`], so its events
+/// are dispatched to the global default instead — in tests, they typically
+/// vanish. Wrapping the future with this function before handing it to a spawn
+/// function such as `tokio::spawn` ensures that the task inherits both the
+/// span that was current when it was spawned (via [`Instrument`]) and the
+/// dispatcher that was current when it was spawned (via [`WithCollector`]):
+///
+/// ```rust
+/// use tracing_futures::executor::spawn_instrumented;
+///
+/// # async fn docs() {
+/// # fn spawn(_: tracing_futures::executor::BoxFuture) {}
+/// spawn_instrumented(
+///     async {
+///         tracing::info!("this reaches the collector that spawned the task");
+///     },
+///     // any `FnOnce(BoxFuture)` works here, e.g. `|task| { tokio::spawn(task); }`
+///     spawn,
+/// );
+/// # }
+/// ```
+///
+/// The spawn function's return value (e.g. a join handle) is passed through.
+///
+/// [`with_default`]: tracing::collect::with_default
+/// [`Instrument`]: crate::Instrument
+/// [`WithCollector`]: crate::WithCollector
+#[cfg(all(feature = "std", feature = "std-future"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "std", feature = "std-future"))))]
+pub fn spawn_instrumented<F, S, R>(future: F, spawn: S) -> R
+where
+    F: core::future::Future<Output = ()> + Send + 'static,
+    S: FnOnce(BoxFuture) -> R,
+{
+    use crate::{Instrument, WithCollector};
+    let future = future
+        .instrument(tracing::Span::current())
+        .with_current_collector();
+    spawn(Box::pin(future))
+}
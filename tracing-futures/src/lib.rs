@@ -107,6 +107,9 @@ use pin_project_lite::pin_project;
 #[cfg(feature = "std-future")]
 use core::{pin::Pin, task::Context};
 
+#[cfg(all(feature = "std-future", feature = "std"))]
+use std::time::{Duration, Instant};
+
 #[cfg(feature = "std")]
 use tracing::{dispatch, Dispatch};
 
@@ -119,7 +122,7 @@ pub mod executor;
 /// instrumented with a `tracing` [span].
 ///
 /// [span]: mod@tracing::span
-pub trait Instrument: Sized {
+pub trait Instrument {
     /// Instruments this type with the provided `Span`, returning an
     /// `Instrumented` wrapper.
     ///
@@ -148,7 +151,10 @@ pub trait Instrument: Sized {
     /// ```
     ///
     /// [entered]: tracing::span::Span::enter()
-    fn instrument(self, span: Span) -> Instrumented<Self> {
+    fn instrument(self, span: Span) -> Instrumented<Self>
+    where
+        Self: Sized,
+    {
         Instrumented { inner: self, span }
     }
 
@@ -185,9 +191,139 @@ pub trait Instrument: Sized {
     /// [current]: tracing::span::Span::current()
     /// [entered]: tracing::span::Span::enter()
     #[inline]
-    fn in_current_span(self) -> Instrumented<Self> {
+    fn in_current_span(self) -> Instrumented<Self>
+    where
+        Self: Sized,
+    {
         self.instrument(Span::current())
     }
+
+    /// Instruments an already-pinned borrow of this type with the provided
+    /// `Span`, returning an `InstrumentedPinned` wrapper.
+    ///
+    /// Unlike [`instrument`], this does not take ownership of the wrapped
+    /// type, so it can be used where only a `Pin<&mut Self>` is available —
+    /// for example, when instrumenting a `Pin<&mut dyn Future>` trait object
+    /// or a future pinned to the stack inside a manual poll loop. The wrapped
+    /// type may be unsized.
+    ///
+    /// The attached `Span` will be [entered] around every poll of the
+    /// borrowed future.
+    ///
+    /// Note that an *owned* `Pin<Box<dyn Future>>` is itself a future and may
+    /// simply be passed to [`instrument`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use core::{future::Future, pin::Pin};
+    /// use tracing_futures::Instrument;
+    ///
+    /// # async fn doc() {
+    /// let mut future: Pin<Box<dyn Future<Output = ()> + Send>> = Box::pin(async {
+    ///     // ...
+    /// });
+    ///
+    /// future
+    ///     .as_mut()
+    ///     .instrument_pinned(tracing::info_span!("my_future"))
+    ///     .await
+    /// # }
+    /// ```
+    ///
+    /// [`instrument`]: Instrument::instrument()
+    /// [entered]: tracing::span::Span::enter()
+    #[cfg(feature = "std-future")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std-future")))]
+    fn instrument_pinned(self: Pin<&mut Self>, span: Span) -> InstrumentedPinned<'_, Self> {
+        InstrumentedPinned { inner: self, span }
+    }
+
+    /// Instruments this type with a `Span` constructed lazily by the provided
+    /// closure, returning an `InstrumentedWith` wrapper.
+    ///
+    /// Unlike [`instrument`], which evaluates the span expression eagerly at
+    /// the instrumentation site, the closure is not invoked until the wrapped
+    /// future or stream is first polled. This means that if the span does not
+    /// have an explicit parent, its parent will be the span that is current
+    /// at the *poll* site, rather than the one current where the future was
+    /// constructed — which is usually what is wanted when instrumenting
+    /// futures that are handed off to a spawner.
+    ///
+    /// Once the span has been created, the wrapper behaves exactly like
+    /// [`Instrumented`]: the span is [entered] every time the wrapped type is
+    /// polled.
+    ///
+    /// [`instrument`]: Instrument::instrument()
+    /// [entered]: tracing::span::Span::enter()
+    #[cfg(feature = "std-future")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std-future")))]
+    fn instrument_with<F>(self, make_span: F) -> InstrumentedWith<Self, F>
+    where
+        Self: Sized,
+        F: FnOnce() -> Span,
+    {
+        InstrumentedWith {
+            inner: self,
+            make_span: Some(make_span),
+            span: None,
+        }
+    }
+
+    /// Instruments this type with the provided `Span`, additionally recording
+    /// poll statistics into the span.
+    ///
+    /// This behaves like [`instrument`], but also times every call to the
+    /// wrapped future's `poll`. When the future completes — or when it is
+    /// dropped without completing — the accumulated statistics are recorded
+    /// into the span:
+    ///
+    /// - `polls`: the total number of times the future was polled,
+    /// - `busy_ns`: the cumulative time spent inside `poll`, in nanoseconds,
+    /// - `max_poll_ns`: the duration of the longest single `poll`, in
+    ///   nanoseconds. A large value here indicates a future that blocks the
+    ///   executor, while a large `polls` count indicates a future that is
+    ///   woken excessively.
+    ///
+    /// Because [`Span::record`] only records fields that were declared when
+    /// the span was created, the span must pre-declare these fields (e.g.
+    /// with [`field::Empty`]) for the statistics to appear:
+    ///
+    /// ```rust,ignore
+    /// use tracing::field;
+    /// use tracing_futures::Instrument;
+    ///
+    /// let span = tracing::info_span!(
+    ///     "my_future",
+    ///     polls = field::Empty,
+    ///     busy_ns = field::Empty,
+    ///     max_poll_ns = field::Empty,
+    /// );
+    /// my_future.instrument_with_stats(span).await;
+    /// ```
+    ///
+    /// If the span is disabled, no timestamps are taken and polling proceeds
+    /// exactly as with [`instrument`], so this adds no overhead for spans
+    /// that no collector cares about.
+    ///
+    /// [`instrument`]: Instrument::instrument()
+    /// [`Span::record`]: tracing::span::Span::record()
+    /// [`field::Empty`]: tracing::field::Empty
+    #[cfg(all(feature = "std-future", feature = "std"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "std-future", feature = "std"))))]
+    fn instrument_with_stats(self, span: Span) -> InstrumentedWithStats<Self>
+    where
+        Self: Sized,
+    {
+        InstrumentedWithStats {
+            inner: self,
+            span,
+            polls: 0,
+            busy: Duration::from_nanos(0),
+            max_poll: Duration::from_nanos(0),
+            recorded: false,
+        }
+    }
 }
 
 /// Extension trait allowing futures, streams, and sinks to be instrumented with
@@ -258,6 +394,74 @@ pub struct Instrumented<T> {
     span: Span,
 }
 
+#[cfg(feature = "std-future")]
+pin_project! {
+    /// A future or stream that will be instrumented with a `tracing` span
+    /// constructed lazily on first poll.
+    ///
+    /// This is returned by [`Instrument::instrument_with`].
+    #[derive(Debug, Clone)]
+    pub struct InstrumentedWith<T, F> {
+        #[pin]
+        inner: T,
+        make_span: Option<F>,
+        span: Option<Span>,
+    }
+}
+
+/// An already-pinned, possibly unsized future that has been instrumented with
+/// a `tracing` span.
+///
+/// Unlike [`Instrumented`], this wraps a `Pin<&mut F>` rather than owning the
+/// instrumented type, so `F` need not be `Sized`; it is returned by
+/// [`Instrument::instrument_pinned`].
+#[cfg(feature = "std-future")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std-future")))]
+pub struct InstrumentedPinned<'a, F: ?Sized> {
+    inner: Pin<&'a mut F>,
+    span: Span,
+}
+
+#[cfg(all(feature = "std-future", feature = "std"))]
+pin_project! {
+    /// A future that has been instrumented with a `tracing` span and records
+    /// poll statistics into it.
+    ///
+    /// This is returned by [`Instrument::instrument_with_stats`].
+    #[derive(Debug)]
+    pub struct InstrumentedWithStats<T> {
+        #[pin]
+        inner: T,
+        span: Span,
+        polls: u64,
+        busy: Duration,
+        max_poll: Duration,
+        recorded: bool,
+    }
+
+    impl<T> PinnedDrop for InstrumentedWithStats<T> {
+        fn drop(this: Pin<&mut Self>) {
+            // If the future was dropped before completing (e.g. it was
+            // cancelled), record whatever was accumulated so far.
+            let this = this.project();
+            record_stats(this.span, *this.polls, *this.busy, *this.max_poll, this.recorded);
+        }
+    }
+}
+
+/// Records the accumulated poll statistics into `span`, unless they have
+/// already been recorded.
+#[cfg(all(feature = "std-future", feature = "std"))]
+fn record_stats(span: &Span, polls: u64, busy: Duration, max_poll: Duration, recorded: &mut bool) {
+    if *recorded {
+        return;
+    }
+    *recorded = true;
+    span.record("polls", &polls);
+    span.record("busy_ns", &(busy.as_nanos() as u64));
+    span.record("max_poll_ns", &(max_poll.as_nanos() as u64));
+}
+
 #[cfg(all(feature = "std", feature = "std-future"))]
 pin_project! {
     /// A future, stream, sink, or executor that has been instrumented with a
@@ -281,7 +485,7 @@ pub struct WithDispatch<T> {
     dispatch: Dispatch,
 }
 
-impl<T: Sized> Instrument for T {}
+impl<T: ?Sized> Instrument for T {}
 
 #[cfg(feature = "std-future")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std-future")))]
@@ -295,6 +499,105 @@ impl<T: core::future::Future> core::future::Future for Instrumented<T> {
     }
 }
 
+#[cfg(feature = "std-future")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std-future")))]
+impl<F: core::future::Future + ?Sized> core::future::Future for InstrumentedPinned<'_, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> core::task::Poll<Self::Output> {
+        // `InstrumentedPinned` contains only a `Pin<&mut F>` and a `Span`,
+        // both of which are `Unpin`, so the wrapper itself may be unpinned.
+        let this = self.get_mut();
+        let _enter = this.span.enter();
+        this.inner.as_mut().poll(cx)
+    }
+}
+
+#[cfg(feature = "std-future")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std-future")))]
+impl<T, F> core::future::Future for InstrumentedWith<T, F>
+where
+    T: core::future::Future,
+    F: FnOnce() -> Span,
+{
+    type Output = T::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> core::task::Poll<Self::Output> {
+        let this = self.project();
+        let make_span = this.make_span;
+        let span = this
+            .span
+            .get_or_insert_with(|| (make_span.take().expect("span closure polled twice"))());
+        let _enter = span.enter();
+        this.inner.poll(cx)
+    }
+}
+
+#[cfg(all(feature = "std-future", feature = "std"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "std-future", feature = "std"))))]
+impl<T: core::future::Future> core::future::Future for InstrumentedWithStats<T> {
+    type Output = T::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> core::task::Poll<Self::Output> {
+        let this = self.project();
+        let _enter = this.span.enter();
+
+        // If no collector cares about the span, skip the timestamps entirely.
+        if this.span.is_disabled() {
+            return this.inner.poll(cx);
+        }
+
+        let start = Instant::now();
+        let result = this.inner.poll(cx);
+        let elapsed = start.elapsed();
+
+        *this.polls += 1;
+        *this.busy += elapsed;
+        if elapsed > *this.max_poll {
+            *this.max_poll = elapsed;
+        }
+
+        if result.is_ready() {
+            record_stats(
+                this.span,
+                *this.polls,
+                *this.busy,
+                *this.max_poll,
+                this.recorded,
+            );
+        }
+
+        result
+    }
+}
+
+#[cfg(all(feature = "futures-03", feature = "std-future"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "futures-03", feature = "std-future"))))]
+impl<T, F> futures::Stream for InstrumentedWith<T, F>
+where
+    T: futures::Stream,
+    F: FnOnce() -> Span,
+{
+    type Item = T::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> futures::task::Poll<Option<Self::Item>> {
+        let this = self.project();
+        let make_span = this.make_span;
+        let span = this
+            .span
+            .get_or_insert_with(|| (make_span.take().expect("span closure polled twice"))());
+        let _enter = span.enter();
+        T::poll_next(this.inner, cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
 #[cfg(feature = "futures-01")]
 #[cfg_attr(docsrs, doc(cfg(feature = "futures-01")))]
 impl<T: futures_01::Future> futures_01::Future for Instrumented<T> {
@@ -352,6 +655,26 @@ impl<T: futures::Stream> futures::Stream for Instrumented<T> {
         let _enter = this.span.enter();
         T::poll_next(this.inner, cx)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(all(feature = "futures-03", feature = "std-future"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "futures-03", feature = "std-future"))))]
+impl<T: futures::future::FusedFuture> futures::future::FusedFuture for Instrumented<T> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_terminated()
+    }
+}
+
+#[cfg(all(feature = "futures-03", feature = "std-future"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "futures-03", feature = "std-future"))))]
+impl<T: futures::stream::FusedStream> futures::stream::FusedStream for Instrumented<T> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_terminated()
+    }
 }
 
 #[cfg(all(feature = "futures-03", feature = "std-future"))]
@@ -439,6 +762,101 @@ impl<T> Instrumented<T> {
     }
 }
 
+#[cfg(feature = "std-future")]
+impl<'a, F: ?Sized> InstrumentedPinned<'a, F> {
+    /// Borrows the `Span` that this type is instrumented by.
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Mutably borrows the `Span` that this type is instrumented by.
+    pub fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+
+    /// Get a pinned mutable reference to the wrapped type.
+    pub fn inner_pin_mut(&mut self) -> Pin<&mut F> {
+        self.inner.as_mut()
+    }
+
+    /// Consumes the `InstrumentedPinned`, returning the wrapped pinned
+    /// borrow.
+    ///
+    /// Note that this drops the span.
+    pub fn into_inner(self) -> Pin<&'a mut F> {
+        self.inner
+    }
+}
+
+// Derived `Debug` would require `F: Debug`, which would rule out trait
+// objects such as `dyn Future`.
+#[cfg(feature = "std-future")]
+impl<F: ?Sized> core::fmt::Debug for InstrumentedPinned<'_, F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InstrumentedPinned")
+            .field("span", &self.span)
+            .finish()
+    }
+}
+
+#[cfg(feature = "std-future")]
+impl<T, F> InstrumentedWith<T, F> {
+    /// Borrows the `Span` that this type is instrumented by, if it has been
+    /// created yet.
+    ///
+    /// This returns `None` until the wrapped type has been polled for the
+    /// first time.
+    pub fn span(&self) -> Option<&Span> {
+        self.span.as_ref()
+    }
+
+    /// Borrows the wrapped type.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Mutably borrows the wrapped type.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Get a pinned reference to the wrapped type.
+    pub fn inner_pin_ref(self: Pin<&Self>) -> Pin<&T> {
+        self.project_ref().inner
+    }
+
+    /// Get a pinned mutable reference to the wrapped type.
+    pub fn inner_pin_mut(self: Pin<&mut Self>) -> Pin<&mut T> {
+        self.project().inner
+    }
+
+    /// Consumes the `InstrumentedWith`, returning the wrapped type.
+    ///
+    /// Note that this drops the span (and the closure, if the span has not
+    /// yet been created).
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+#[cfg(all(feature = "std-future", feature = "std"))]
+impl<T> InstrumentedWithStats<T> {
+    /// Borrows the `Span` that this type is instrumented by.
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Borrows the wrapped type.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Mutably borrows the wrapped type.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
 #[cfg(feature = "std")]
 impl<T: Sized> WithCollector for T {}
 
@@ -467,6 +885,100 @@ impl<T: core::future::Future> core::future::Future for WithDispatch<T> {
     }
 }
 
+#[cfg(all(feature = "futures-03", feature = "std-future", feature = "std"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "futures-03", feature = "std-future", feature = "std")))
+)]
+impl<T: futures::Stream> futures::Stream for WithDispatch<T> {
+    type Item = T::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> futures::task::Poll<Option<Self::Item>> {
+        let this = self.project();
+        let dispatch = this.dispatch;
+        let stream = this.inner;
+        dispatch::with_default(dispatch, || T::poll_next(stream, cx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(all(feature = "futures-03", feature = "std-future", feature = "std"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "futures-03", feature = "std-future", feature = "std")))
+)]
+impl<I, T> futures::Sink<I> for WithDispatch<T>
+where
+    T: futures::Sink<I>,
+{
+    type Error = T::Error;
+
+    fn poll_ready(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> futures::task::Poll<Result<(), Self::Error>> {
+        let this = self.project();
+        let dispatch = this.dispatch;
+        let sink = this.inner;
+        dispatch::with_default(dispatch, || T::poll_ready(sink, cx))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: I) -> Result<(), Self::Error> {
+        let this = self.project();
+        let dispatch = this.dispatch;
+        let sink = this.inner;
+        dispatch::with_default(dispatch, || T::start_send(sink, item))
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> futures::task::Poll<Result<(), Self::Error>> {
+        let this = self.project();
+        let dispatch = this.dispatch;
+        let sink = this.inner;
+        dispatch::with_default(dispatch, || T::poll_flush(sink, cx))
+    }
+
+    fn poll_close(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> futures::task::Poll<Result<(), Self::Error>> {
+        let this = self.project();
+        let dispatch = this.dispatch;
+        let sink = this.inner;
+        dispatch::with_default(dispatch, || T::poll_close(sink, cx))
+    }
+}
+
+#[cfg(all(feature = "futures-03", feature = "std-future", feature = "std"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "futures-03", feature = "std-future", feature = "std")))
+)]
+impl<T: futures::future::FusedFuture> futures::future::FusedFuture for WithDispatch<T> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_terminated()
+    }
+}
+
+#[cfg(all(feature = "futures-03", feature = "std-future", feature = "std"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "futures-03", feature = "std-future", feature = "std")))
+)]
+impl<T: futures::stream::FusedStream> futures::stream::FusedStream for WithDispatch<T> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_terminated()
+    }
+}
+
 #[cfg(feature = "std")]
 impl<T> WithDispatch<T> {
     /// Wrap a future, stream, sink or executor with the same subscriber as this WithDispatch.
@@ -513,12 +1025,7 @@ impl<T> WithDispatch<T> {
 }
 
 #[cfg(test)]
-pub(crate) use self::support as test_support;
-// This has to have the same name as the module in `tracing`.
-#[path = "../../tracing/tests/support/mod.rs"]
-#[cfg(test)]
-#[allow(unreachable_pub)]
-pub(crate) mod support;
+pub(crate) use tracing_mock as test_support;
 
 #[cfg(test)]
 mod tests {
@@ -694,6 +1201,80 @@ mod tests {
             handle.assert_finished();
         }
 
+        #[test]
+        fn instrument_with_creates_the_span_at_the_poll_site() {
+            let (collector, handle) = collector::mock()
+                .enter(span::mock().named("spawn_site"))
+                .exit(span::mock().named("spawn_site"))
+                .enter(span::mock().named("poll_site"))
+                .new_span(
+                    span::mock()
+                        .named("lazy")
+                        .with_contextual_parent(Some("poll_site")),
+                )
+                .enter(span::mock().named("lazy"))
+                .exit(span::mock().named("lazy"))
+                .exit(span::mock().named("poll_site"))
+                .done()
+                .run_with_handle();
+            with_default(collector, || {
+                // The span closure must not run here, in the "spawn site"
+                // span...
+                let future = tracing::trace_span!("spawn_site").in_scope(|| {
+                    future::ready(()).instrument_with(|| tracing::trace_span!("lazy"))
+                });
+                // ...but when the future is first polled, in the "poll site"
+                // span.
+                tracing::trace_span!("poll_site").in_scope(|| future.now_or_never().unwrap());
+            });
+            handle.assert_finished();
+        }
+
+        #[test]
+        fn stream_size_hint_is_preserved() {
+            let stream =
+                Instrument::instrument(stream::iter(&[1, 2, 3]), tracing::trace_span!("foo"));
+            assert_eq!(futures::Stream::size_hint(&stream), (3, Some(3)));
+        }
+
+        #[test]
+        fn fused_stream_termination_is_forwarded() {
+            use futures::stream::FusedStream;
+            let mut stream =
+                Instrument::instrument(stream::iter(&[1, 2]).fuse(), tracing::trace_span!("foo"));
+            assert!(!stream.is_terminated());
+            (&mut stream).for_each(|_| future::ready(())).now_or_never();
+            assert!(stream.is_terminated());
+        }
+
+        #[cfg(feature = "std")]
+        #[test]
+        fn with_dispatch_stream_sets_dispatch_on_poll() {
+            use core::task::Poll;
+
+            let (collector, handle) = collector::mock()
+                .event(event::mock())
+                .event(event::mock())
+                .event(event::mock())
+                .done()
+                .run_with_handle();
+            let mut count = 0;
+            let stream = stream::poll_fn(move |_| {
+                if count < 3 {
+                    count += 1;
+                    tracing::info!("polled");
+                    Poll::Ready(Some(count))
+                } else {
+                    Poll::Ready(None)
+                }
+            });
+            WithCollector::with_collector(stream, collector)
+                .for_each(|_| future::ready(()))
+                .now_or_never()
+                .unwrap();
+            handle.assert_finished();
+        }
+
         #[test]
         fn sink_enter_exit_is_reasonable() {
             let (collector, handle) = collector::mock()
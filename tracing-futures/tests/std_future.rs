@@ -37,3 +37,239 @@ fn error_ends_span() {
     });
     handle.assert_finished();
 }
+
+#[test]
+fn pinned_dyn_future_enter_exit_is_reasonable() {
+    use std::{future::Future, pin::Pin};
+    use tracing_futures::Instrument as _;
+
+    let (collector, handle) = collector::mock()
+        .enter(span::mock().named("foo"))
+        .exit(span::mock().named("foo"))
+        .enter(span::mock().named("foo"))
+        .exit(span::mock().named("foo"))
+        .drop_span(span::mock().named("foo"))
+        .done()
+        .run_with_handle();
+    with_default(collector, || {
+        let mut future: Pin<Box<dyn Future<Output = Result<(), ()>> + Send>> =
+            Box::pin(PollN::new_ok(2));
+        let instrumented = future
+            .as_mut()
+            .instrument_pinned(tracing::span!(Level::TRACE, "foo"));
+        block_on_future(instrumented).unwrap();
+    });
+    handle.assert_finished();
+}
+
+#[test]
+fn pinned_future_survives_the_wrapper() {
+    use std::{future::Future, pin::Pin, task::Poll};
+    use tracing_futures::Instrument as _;
+
+    let (collector, handle) = collector::mock()
+        .enter(span::mock().named("a"))
+        .exit(span::mock().named("a"))
+        .drop_span(span::mock().named("a"))
+        .enter(span::mock().named("b"))
+        .exit(span::mock().named("b"))
+        .drop_span(span::mock().named("b"))
+        .done()
+        .run_with_handle();
+    with_default(collector, || {
+        let mut future: Pin<Box<dyn Future<Output = Result<(), ()>>>> = Box::pin(PollN::new_ok(2));
+        // The wrapper only borrows the future, so it can be dropped and the
+        // same future re-instrumented with a different span.
+        let mut task = tokio_test::task::spawn(
+            future
+                .as_mut()
+                .instrument_pinned(tracing::span!(Level::TRACE, "a")),
+        );
+        assert!(task.poll().is_pending());
+        drop(task);
+        let mut task = tokio_test::task::spawn(
+            future
+                .as_mut()
+                .instrument_pinned(tracing::span!(Level::TRACE, "b")),
+        );
+        assert_eq!(task.poll(), Poll::Ready(Ok(())));
+    });
+    handle.assert_finished();
+}
+
+mod stats {
+    use super::*;
+    use std::{
+        collections::HashMap,
+        future::Future,
+        pin::Pin,
+        sync::{Arc, Mutex},
+        task::{Context, Poll},
+        time::Duration,
+    };
+    use tracing::field;
+    use tracing_core::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Collect, Event, Metadata,
+    };
+    use tracing_futures::Instrument as _;
+
+    /// A collector that captures `u64` values recorded into spans, so the
+    /// tests can make inequality assertions about timing fields.
+    #[derive(Clone, Default)]
+    struct StatsCollector {
+        recorded: Arc<Mutex<HashMap<String, u64>>>,
+    }
+
+    struct Visitor<'a>(&'a Mutex<HashMap<String, u64>>);
+
+    impl Visit for Visitor<'_> {
+        fn record_u64(&mut self, field: &Field, value: u64) {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(field.name().to_string(), value);
+        }
+
+        fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+    }
+
+    impl Collect for StatsCollector {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, values: &Record<'_>) {
+            values.record(&mut Visitor(&self.recorded));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, _: &Event<'_>) {}
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+        fn current_span(&self) -> tracing_core::span::Current {
+            tracing_core::span::Current::unknown()
+        }
+    }
+
+    fn stats_span() -> tracing::Span {
+        tracing::span!(
+            Level::TRACE,
+            "stats",
+            polls = field::Empty,
+            busy_ns = field::Empty,
+            max_poll_ns = field::Empty,
+        )
+    }
+
+    /// A future that blocks in `poll` for `sleep` before returning `Pending`,
+    /// completing on the `finish_at`th poll.
+    struct SleepyPoll {
+        sleep: Duration,
+        finish_at: usize,
+        polls: usize,
+    }
+
+    impl Future for SleepyPoll {
+        type Output = ();
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            let this = self.get_mut();
+            std::thread::sleep(this.sleep);
+            this.polls += 1;
+            if this.polls == this.finish_at {
+                Poll::Ready(())
+            } else {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn stats_are_recorded_on_completion() {
+        let sleep = Duration::from_millis(10);
+        let collector = StatsCollector::default();
+        let recorded = collector.recorded.clone();
+
+        with_default(collector, || {
+            block_on_future(
+                SleepyPoll {
+                    sleep,
+                    finish_at: 3,
+                    polls: 0,
+                }
+                .instrument_with_stats(stats_span()),
+            );
+        });
+
+        let recorded = recorded.lock().unwrap();
+        assert_eq!(recorded["polls"], 3);
+        // Each poll slept synchronously, so the longest single poll must be
+        // at least as long as the sleep, and the cumulative time at least as
+        // long as all three.
+        assert!(recorded["max_poll_ns"] >= sleep.as_nanos() as u64);
+        assert!(recorded["busy_ns"] >= (sleep * 3).as_nanos() as u64);
+        assert!(recorded["busy_ns"] >= recorded["max_poll_ns"]);
+    }
+
+    #[test]
+    fn stats_are_recorded_when_dropped_before_completion() {
+        let collector = StatsCollector::default();
+        let recorded = collector.recorded.clone();
+
+        with_default(collector, || {
+            let mut task = tokio_test::task::spawn(
+                SleepyPoll {
+                    sleep: Duration::from_millis(1),
+                    finish_at: usize::MAX,
+                    polls: 0,
+                }
+                .instrument_with_stats(stats_span()),
+            );
+            assert!(task.poll().is_pending());
+            assert!(task.poll().is_pending());
+            // Cancel the future; the stats must be recorded on drop.
+        });
+
+        let recorded = recorded.lock().unwrap();
+        assert_eq!(recorded["polls"], 2);
+        assert!(recorded["busy_ns"] >= recorded["max_poll_ns"]);
+    }
+}
+
+#[test]
+fn spawn_instrumented_inherits_scoped_collector() {
+    use tracing_futures::executor::spawn_instrumented;
+
+    let (collector, handle) = collector::mock()
+        .enter(span::mock().named("foo"))
+        .exit(span::mock().named("foo"))
+        .enter(span::mock().named("foo"))
+        .event(event::mock())
+        .exit(span::mock().named("foo"))
+        .drop_span(span::mock().named("foo"))
+        .done()
+        .run_with_handle();
+
+    let task = with_default(collector, || {
+        let span = tracing::span!(Level::TRACE, "foo");
+        let _e = span.enter();
+        // "Spawn" by handing the instrumented task back to the caller...
+        spawn_instrumented(
+            async {
+                tracing::trace!("in the spawned task");
+            },
+            |task| task,
+        )
+    });
+
+    // ...and poll it outside of the `with_default` scope; its events must
+    // still reach the scoped collector.
+    block_on_future(task);
+    handle.assert_finished();
+}
@@ -1,15 +1,10 @@
-// this is the only way to make the path attribute play nice with `in
-// crate::support`...
-#[allow(clippy::module_inception)]
-#[path = "../../tracing/tests/support/mod.rs"]
-mod support;
 use std::{
     future::Future,
     pin::Pin,
     task::{Context, Poll},
 };
-pub use support::*;
 use tokio_test::task;
+pub use tracing_mock::*;
 
 pub struct PollN<T, E> {
     and_return: Option<Result<T, E>>,
@@ -66,6 +66,13 @@ use tracing_subscriber::{registry::LookupSpan, subscribe::Context};
 ///
 /// Note that the naming scheme differs slightly for the latter half.
 ///
+/// If an event's [`Metadata`] carries a finer-grained [verbosity] in the
+/// syslog priority range (0–7) — typically set by bridges from logging
+/// systems with more severities than tracing has levels — it is used as the
+/// `PRIORITY` directly, in preference to the level mapping above.
+///
+/// [verbosity]: tracing_core::Metadata::verbosity
+///
 /// The standard journald `CODE_LINE` and `CODE_FILE` fields are automatically emitted. A `TARGET`
 /// field is emitted containing the event's target. Enclosing spans are numbered counting up from
 /// the root, and their fields and metadata are included in fields prefixed by `Sn_` where `n` is
@@ -74,7 +81,11 @@ use tracing_subscriber::{registry::LookupSpan, subscribe::Context};
 /// User-defined fields other than the event `message` field have a prefix applied by default to
 /// prevent collision with standard fields.
 ///
+/// Payloads that exceed the datagram size limit are passed to journald as a sealed memory-mapped
+/// file instead, as the [journal protocol] requires.
+///
 /// [journald conventions]: https://www.freedesktop.org/software/systemd/man/systemd.journal-fields.html
+/// [journal protocol]: https://systemd.io/JOURNAL_NATIVE_PROTOCOL/
 pub struct Subscriber {
     #[cfg(unix)]
     socket: UnixDatagram,
@@ -87,20 +98,36 @@ impl Subscriber {
     /// Fails if the journald socket couldn't be opened. Returns a `NotFound` error unconditionally
     /// in non-Unix environments.
     pub fn new() -> io::Result<Self> {
+        Self::with_socket_path("/run/systemd/journal/socket")
+    }
+
+    /// Construct a journald subscriber sending entries to the [journal
+    /// protocol] socket at `path` rather than the default
+    /// `/run/systemd/journal/socket`.
+    ///
+    /// This is primarily useful for testing against a local datagram socket.
+    /// Fails if the socket couldn't be opened. Returns a `NotFound` error
+    /// unconditionally in non-Unix environments.
+    ///
+    /// [journal protocol]: https://systemd.io/JOURNAL_NATIVE_PROTOCOL/
+    pub fn with_socket_path(path: impl AsRef<std::path::Path>) -> io::Result<Self> {
         #[cfg(unix)]
         {
             let socket = UnixDatagram::unbound()?;
-            socket.connect("/run/systemd/journal/socket")?;
+            socket.connect(path)?;
             Ok(Self {
                 socket,
                 field_prefix: Some("F".into()),
             })
         }
         #[cfg(not(unix))]
-        Err(io::Error::new(
-            io::ErrorKind::NotFound,
-            "journald does not exist in this environment",
-        ))
+        {
+            let _ = path;
+            Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "journald does not exist in this environment",
+            ))
+        }
     }
 
     /// Sets the prefix to apply to names of user-defined fields other than the event `message`
@@ -111,6 +138,44 @@ impl Subscriber {
     }
 }
 
+#[cfg(unix)]
+impl Subscriber {
+    fn send_payload(&self, payload: &[u8]) -> io::Result<usize> {
+        self.socket.send(payload).or_else(|error| {
+            if error.raw_os_error() == Some(libc::EMSGSIZE) {
+                self.send_large_payload(payload)
+            } else {
+                Err(error)
+            }
+        })
+    }
+
+    /// Send a large payload to journald through a sealed memfd, as the
+    /// [journal protocol] requires for entries exceeding the datagram size
+    /// limit.
+    ///
+    /// [journal protocol]: https://systemd.io/JOURNAL_NATIVE_PROTOCOL/
+    #[cfg(target_os = "linux")]
+    fn send_large_payload(&self, payload: &[u8]) -> io::Result<usize> {
+        use std::os::unix::prelude::AsRawFd;
+        // Write the whole payload to a memfd, then fully seal it to signal to
+        // journald that its backing data won't change anymore and is thus
+        // safe to mmap.
+        let mut mem = memfd::create_sealable()?;
+        mem.write_all(payload)?;
+        memfd::seal_fully(mem.as_raw_fd())?;
+        socket::send_one_fd(&self.socket, mem.as_raw_fd())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn send_large_payload(&self, _payload: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "large payloads are not supported on non-Linux unixes",
+        ))
+    }
+}
+
 /// Construct a journald subscriber
 ///
 /// Fails if the journald socket couldn't be opened.
@@ -176,7 +241,7 @@ where
 
         // What could we possibly do on error?
         #[cfg(unix)]
-        let _ = self.socket.send(&buf);
+        let _ = self.send_payload(&buf);
     }
 }
 
@@ -226,17 +291,24 @@ impl Visit for EventVisitor<'_> {
 
 fn put_metadata(buf: &mut Vec<u8>, meta: &Metadata, span: Option<usize>) {
     if span.is_none() {
-        put_field(
-            buf,
-            "PRIORITY",
-            match *meta.level() {
-                Level::ERROR => b"3",
-                Level::WARN => b"4",
-                Level::INFO => b"5",
-                Level::DEBUG => b"6",
-                Level::TRACE => b"7",
-            },
-        );
+        // Prefer a finer-grained verbosity when the callsite carries one in
+        // the syslog priority range. Bridges from foreign logging systems
+        // with more severities than tracing has `Level`s use this to preserve
+        // ordering that the `Level` mapping below would lose.
+        match meta.verbosity() {
+            Some(verbosity @ 0..=7) => writeln!(buf, "PRIORITY={}", verbosity).unwrap(),
+            _ => put_field(
+                buf,
+                "PRIORITY",
+                match *meta.level() {
+                    Level::ERROR => b"3",
+                    Level::WARN => b"4",
+                    Level::INFO => b"5",
+                    Level::DEBUG => b"6",
+                    Level::TRACE => b"7",
+                },
+            ),
+        }
     }
     if let Some(n) = span {
         write!(buf, "S{}_", n).unwrap();
@@ -292,3 +364,222 @@ fn put_value(buf: &mut Vec<u8>, value: &[u8]) {
     buf.extend_from_slice(value);
     buf.push(b'\n');
 }
+
+#[cfg(target_os = "linux")]
+mod memfd {
+    use libc::*;
+    use std::fs::File;
+    use std::io::{Error, Result};
+    use std::os::raw::c_uint;
+    use std::os::unix::prelude::{FromRawFd, RawFd};
+
+    pub(crate) fn create_sealable() -> Result<File> {
+        create(MFD_ALLOW_SEALING | MFD_CLOEXEC)
+    }
+
+    pub(crate) fn seal_fully(fd: RawFd) -> Result<()> {
+        let all_seals = F_SEAL_SHRINK | F_SEAL_GROW | F_SEAL_WRITE | F_SEAL_SEAL;
+        let result = unsafe { fcntl(fd, F_ADD_SEALS, all_seals) };
+        if result < 0 {
+            Err(Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn create(flags: c_uint) -> Result<File> {
+        let fd = memfd_create_syscall(flags);
+        if fd < 0 {
+            Err(Error::last_os_error())
+        } else {
+            Ok(unsafe { File::from_raw_fd(fd as RawFd) })
+        }
+    }
+
+    /// Make the `memfd_create` syscall ourselves instead of going through
+    /// `libc`; some libc implementations (notably musl) don't provide a
+    /// wrapper for it.
+    fn memfd_create_syscall(flags: c_uint) -> c_int {
+        unsafe {
+            syscall(
+                SYS_memfd_create,
+                "tracing-journald\0".as_ptr() as *const c_char,
+                flags,
+            ) as c_int
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod socket {
+    use libc::*;
+    use std::io::{Error, Result};
+    use std::mem::{size_of, zeroed};
+    use std::os::unix::net::UnixDatagram;
+    use std::os::unix::prelude::{AsRawFd, RawFd};
+    use std::ptr;
+
+    const CMSG_BUFSIZE: usize = 64;
+
+    /// A buffer for a single control message, aligned as `cmsghdr` requires.
+    #[repr(C)]
+    union AlignedBuffer {
+        buffer: [u8; CMSG_BUFSIZE],
+        align: cmsghdr,
+    }
+
+    /// Send a single file descriptor over the connected datagram `socket` as
+    /// a `SCM_RIGHTS` control message, with an empty message body.
+    pub(crate) fn send_one_fd(socket: &UnixDatagram, fd: RawFd) -> Result<usize> {
+        let cmsg_space = unsafe { CMSG_SPACE(size_of::<RawFd>() as u32) };
+        assert!(
+            cmsg_space as usize <= CMSG_BUFSIZE,
+            "control message buffer too small (< {}) to hold a single fd",
+            cmsg_space
+        );
+
+        let mut msg: msghdr = unsafe { zeroed() };
+        // The socket is already connected to the journal, so no `msg_name`.
+        let mut cmsg_buffer = AlignedBuffer {
+            buffer: [0; CMSG_BUFSIZE],
+        };
+        msg.msg_control = unsafe { cmsg_buffer.buffer.as_mut_ptr() as _ };
+        msg.msg_controllen = cmsg_space as _;
+
+        let cmsg: &mut cmsghdr =
+            unsafe { CMSG_FIRSTHDR(&msg).as_mut() }.expect("control message buffer exhausted");
+        cmsg.cmsg_level = SOL_SOCKET;
+        cmsg.cmsg_type = SCM_RIGHTS;
+        cmsg.cmsg_len = unsafe { CMSG_LEN(size_of::<RawFd>() as u32) } as _;
+        unsafe { ptr::write(CMSG_DATA(cmsg) as *mut RawFd, fd) };
+
+        let result = unsafe { sendmsg(socket.as_raw_fd(), &msg, 0) };
+        if result < 0 {
+            Err(Error::last_os_error())
+        } else {
+            // sendmsg returns the number of bytes of message *data* sent,
+            // which is zero here; the interesting payload is the fd.
+            Ok(result as usize)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sanitized(name: &str) -> String {
+        let mut buf = Vec::new();
+        sanitize_name(name, &mut buf);
+        String::from_utf8(buf).unwrap()
+    }
+
+    /// Frame a key-value pair the way journald's native protocol expects:
+    /// name, newline, little-endian length, value, newline.
+    fn framed(name: &str, value: &[u8]) -> Vec<u8> {
+        let mut buf = name.as_bytes().to_vec();
+        buf.push(b'\n');
+        buf.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        buf.extend_from_slice(value);
+        buf.push(b'\n');
+        buf
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|w| w == needle)
+    }
+
+    #[test]
+    fn field_names_are_sanitized() {
+        assert_eq!(sanitized("message"), "MESSAGE");
+        assert_eq!(sanitized("log.target"), "LOG_TARGET");
+        assert_eq!(sanitized("_private"), "PRIVATE");
+        assert_eq!(sanitized("__very_private"), "VERY_PRIVATE");
+        assert_eq!(sanitized("nested.field.name"), "NESTED_FIELD_NAME");
+        // Non-ASCII-alphanumeric characters other than `_` are stripped.
+        assert_eq!(sanitized("weird-fïeld!"), "WEIRDFELD");
+    }
+
+    #[test]
+    fn values_use_length_prefixed_framing() {
+        let mut buf = Vec::new();
+        put_field(&mut buf, "MESSAGE", b"hello\nworld");
+        assert_eq!(buf, framed("MESSAGE", b"hello\nworld"));
+    }
+
+    #[test]
+    fn debug_values_are_framed_with_sanitized_names() {
+        let mut buf = Vec::new();
+        put_debug(&mut buf, "my.field", &"quoted");
+        // The `Debug` representation of a `&str` includes the quotes.
+        assert_eq!(buf, framed("MY_FIELD", b"\"quoted\""));
+    }
+
+    #[cfg(unix)]
+    mod wire {
+        use super::*;
+        use std::os::unix::net::UnixDatagram;
+        use std::path::PathBuf;
+        use tracing_subscriber::subscribe::CollectExt;
+
+        /// Binds a datagram socket standing in for the journal, at a path
+        /// unique to the calling test.
+        fn journal_stand_in(name: &str) -> (UnixDatagram, PathBuf) {
+            let path = std::env::temp_dir().join(format!(
+                "tracing-journald-test-{}-{}",
+                std::process::id(),
+                name
+            ));
+            let _ = std::fs::remove_file(&path);
+            let server = UnixDatagram::bind(&path).expect("bind stand-in socket");
+            (server, path)
+        }
+
+        #[test]
+        fn events_are_framed_onto_the_socket() {
+            let (server, path) = journal_stand_in("events");
+            let subscriber = Subscriber::with_socket_path(&path).expect("connect");
+            let collector = tracing_subscriber::registry().with(subscriber);
+            tracing::collect::with_default(collector, || {
+                tracing::info!(answer = 42, "hello");
+            });
+
+            let mut datagram = [0; 2048];
+            let len = server.recv(&mut datagram).expect("no datagram received");
+            let payload = &datagram[..len];
+
+            // `INFO` maps to syslog Notice.
+            assert!(contains(payload, &framed("PRIORITY", b"5")));
+            assert!(contains(
+                payload,
+                &framed("TARGET", module_path!().as_bytes())
+            ));
+            assert!(contains(payload, &framed("MESSAGE", b"hello")));
+            // User-defined fields get the default `F` prefix.
+            assert!(contains(payload, &framed("F_ANSWER", b"42")));
+
+            let _ = std::fs::remove_file(&path);
+        }
+
+        #[cfg(target_os = "linux")]
+        #[test]
+        fn large_payloads_fall_back_to_a_memfd() {
+            let (server, path) = journal_stand_in("large");
+            let subscriber = Subscriber::with_socket_path(&path).expect("connect");
+            let collector = tracing_subscriber::registry().with(subscriber);
+            let huge = "x".repeat(1024 * 1024);
+            tracing::collect::with_default(collector, || {
+                tracing::info!(huge = huge.as_str(), "oversized");
+            });
+
+            // The payload exceeds the datagram size limit, so it must arrive
+            // as an (empty) datagram carrying a sealed memfd instead of being
+            // silently dropped.
+            let mut datagram = [0; 2048];
+            let len = server.recv(&mut datagram).expect("no datagram received");
+            assert_eq!(len, 0, "memfd datagrams carry no inline payload");
+
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use tracing::{span, Event, Id, Metadata};
+use tracing_core::span::Current;
+
+/// A subscriber that is enabled but otherwise does nothing.
+struct EnabledSubscriber;
+
+impl tracing::Collect for EnabledSubscriber {
+    fn new_span(&self, span: &span::Attributes<'_>) -> Id {
+        let _ = span;
+        Id::from_u64(0xDEAD_FACE)
+    }
+
+    fn event(&self, event: &Event<'_>) {
+        let _ = event;
+    }
+
+    fn record(&self, span: &Id, values: &span::Record<'_>) {
+        let _ = (span, values);
+    }
+
+    fn record_follows_from(&self, span: &Id, follows: &Id) {
+        let _ = (span, follows);
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        let _ = metadata;
+        true
+    }
+
+    fn enter(&self, span: &Id) {
+        let _ = span;
+    }
+
+    fn exit(&self, span: &Id) {
+        let _ = span;
+    }
+
+    fn current_span(&self) -> Current {
+        Current::unknown()
+    }
+}
+
+fn bench_logtracer(c: &mut Criterion) {
+    let _ = tracing_log::LogTracer::builder()
+        .ignore_crate("hyper")
+        .ignore_target_prefix("mio::poll")
+        .init();
+
+    let mut group = c.benchmark_group("logtracer");
+
+    group.bench_function("bridged", |b| {
+        tracing::collect::with_default(EnabledSubscriber, || {
+            b.iter(|| {
+                log::info!(target: "accepted", "hi");
+            })
+        });
+    });
+
+    group.bench_function("ignored", |b| {
+        tracing::collect::with_default(EnabledSubscriber, || {
+            b.iter(|| {
+                log::info!(target: "hyper::proto", "hi");
+            })
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_logtracer);
+criterion_main!(benches);
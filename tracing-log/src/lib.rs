@@ -49,6 +49,13 @@
 //! records emitted by dependencies which use `log` within the context of a
 //! trace.
 //!
+//! For `env_logger`-style one-call setup — installing `LogTracer`, building a
+//! filter from an environment variable, and setting a global formatting
+//! collector together — use `tracing_subscriber::fmt()` and its `init` or
+//! `try_init` methods, which perform all three when `tracing-subscriber`'s
+//! "tracing-log" feature is enabled. (That initializer cannot live in this
+//! crate, as `tracing-subscriber` already depends on it.)
+//!
 //! ## Convert tracing `Event`s to logs
 //!
 //! Enabling the ["log" and "log-always" feature flags][flags] on the `tracing`
@@ -72,6 +79,16 @@
 //! * `log-tracer`: enables the `LogTracer` type (on by default)
 //! * `env_logger`: enables the `env_logger` module, with helpers for working
 //!   with the [`env_logger` crate].
+//! * `kv_unstable`: enables support for bridging the [structured key-values]
+//!   attached to `log` records into the bridged `tracing` events. Because
+//!   `tracing` callsites register their field names statically while `log`'s
+//!   keys are only known at runtime, each pair is recorded as a `key=value`
+//!   string in one of a fixed set of generic `log.kv.*` fields, and pairs
+//!   past the last slot are folded into it. This depends on the unstable
+//!   `log/kv_unstable` feature, and may break in minor releases of the `log`
+//!   crate.
+//!
+//! [structured key-values]: https://docs.rs/log/latest/log/kv/index.html
 //!
 //! ## Supported Rust Versions
 //!
@@ -179,6 +196,23 @@ pub(crate) fn dispatch_record(record: &log::Record<'_>) {
         let file = log_file.as_ref().map(|s| s as &dyn field::Value);
         let line = log_line.as_ref().map(|s| s as &dyn field::Value);
 
+        #[cfg(feature = "kv_unstable")]
+        let kv_values = {
+            let mut visitor = KvVisitor::default();
+            // `Source::visit` only fails if the visitor does, and ours is
+            // infallible.
+            let _ = record.key_values().visit(&mut visitor);
+            visitor.values
+        };
+        #[cfg(feature = "kv_unstable")]
+        let kv = {
+            let mut kv: [Option<&str>; MAX_KV_FIELDS] = [None; MAX_KV_FIELDS];
+            for (slot, value) in kv.iter_mut().zip(kv_values.iter()) {
+                *slot = Some(value.as_str());
+            }
+            kv
+        };
+
         dispatch.event(&Event::new(
             meta,
             &meta.fields().value_set(&[
@@ -187,11 +221,64 @@ pub(crate) fn dispatch_record(record: &log::Record<'_>) {
                 (&keys.module, module),
                 (&keys.file, file),
                 (&keys.line, line),
+                #[cfg(feature = "kv_unstable")]
+                (&keys.kv[0], kv[0].as_ref().map(|s| s as &dyn field::Value)),
+                #[cfg(feature = "kv_unstable")]
+                (&keys.kv[1], kv[1].as_ref().map(|s| s as &dyn field::Value)),
+                #[cfg(feature = "kv_unstable")]
+                (&keys.kv[2], kv[2].as_ref().map(|s| s as &dyn field::Value)),
+                #[cfg(feature = "kv_unstable")]
+                (&keys.kv[3], kv[3].as_ref().map(|s| s as &dyn field::Value)),
+                #[cfg(feature = "kv_unstable")]
+                (&keys.kv[4], kv[4].as_ref().map(|s| s as &dyn field::Value)),
+                #[cfg(feature = "kv_unstable")]
+                (&keys.kv[5], kv[5].as_ref().map(|s| s as &dyn field::Value)),
+                #[cfg(feature = "kv_unstable")]
+                (&keys.kv[6], kv[6].as_ref().map(|s| s as &dyn field::Value)),
+                #[cfg(feature = "kv_unstable")]
+                (&keys.kv[7], kv[7].as_ref().map(|s| s as &dyn field::Value)),
             ]),
         ));
     });
 }
 
+/// The number of generic `log.kv.*` field slots available for carrying a log
+/// record's structured key-values.
+///
+/// `tracing` callsites must register their field names statically, while the
+/// `log` crate's keys are only known at runtime, so a bridged record's
+/// key-values cannot become first-class `tracing` fields named after their
+/// keys. Instead, each pair is recorded as a preformatted `key=value` string
+/// in one of these generic slots; any pairs beyond the slot count are folded
+/// into the final slot rather than dropped.
+#[cfg(feature = "kv_unstable")]
+const MAX_KV_FIELDS: usize = 8;
+
+/// Visits a `log` record's key-values, formatting each pair into a value for
+/// one of the generic `log.kv.*` field slots.
+#[cfg(feature = "kv_unstable")]
+#[derive(Default)]
+struct KvVisitor {
+    values: Vec<String>,
+}
+
+#[cfg(feature = "kv_unstable")]
+impl<'kv> log::kv::Visitor<'kv> for KvVisitor {
+    fn visit_pair(
+        &mut self,
+        key: log::kv::Key<'kv>,
+        value: log::kv::Value<'kv>,
+    ) -> Result<(), log::kv::Error> {
+        use fmt::Write;
+        if self.values.len() < MAX_KV_FIELDS {
+            self.values.push(format!("{}={}", key, value));
+        } else if let Some(last) = self.values.last_mut() {
+            let _ = write!(last, " {}={}", key, value);
+        }
+        Ok(())
+    }
+}
+
 /// Trait implemented for `tracing` types that can be converted to a `log`
 /// equivalent.
 pub trait AsLog: crate::sealed::Sealed {
@@ -246,14 +333,34 @@ struct Fields {
     module: field::Field,
     file: field::Field,
     line: field::Field,
+    #[cfg(feature = "kv_unstable")]
+    kv: [field::Field; MAX_KV_FIELDS],
 }
 
+#[cfg(not(feature = "kv_unstable"))]
+static FIELD_NAMES: &[&str] = &[
+    "message",
+    "log.target",
+    "log.module_path",
+    "log.file",
+    "log.line",
+];
+
+#[cfg(feature = "kv_unstable")]
 static FIELD_NAMES: &[&str] = &[
     "message",
     "log.target",
     "log.module_path",
     "log.file",
     "log.line",
+    "log.kv.0",
+    "log.kv.1",
+    "log.kv.2",
+    "log.kv.3",
+    "log.kv.4",
+    "log.kv.5",
+    "log.kv.6",
+    "log.kv.7",
 ];
 
 impl Fields {
@@ -264,12 +371,25 @@ impl Fields {
         let module = fieldset.field("log.module_path").unwrap();
         let file = fieldset.field("log.file").unwrap();
         let line = fieldset.field("log.line").unwrap();
+        #[cfg(feature = "kv_unstable")]
+        let kv = [
+            fieldset.field("log.kv.0").unwrap(),
+            fieldset.field("log.kv.1").unwrap(),
+            fieldset.field("log.kv.2").unwrap(),
+            fieldset.field("log.kv.3").unwrap(),
+            fieldset.field("log.kv.4").unwrap(),
+            fieldset.field("log.kv.5").unwrap(),
+            fieldset.field("log.kv.6").unwrap(),
+            fieldset.field("log.kv.7").unwrap(),
+        ];
         Fields {
             message,
             target,
             module,
             file,
             line,
+            #[cfg(feature = "kv_unstable")]
+            kv,
         }
     }
 }
@@ -31,6 +31,8 @@ use tracing_core::dispatch;
 /// A simple "logger" that converts all log records into `tracing` `Event`s.
 #[derive(Debug)]
 pub struct LogTracer {
+    /// The list of ignored target prefixes, sorted so that lookups can bail
+    /// out as soon as an entry sorts after the target being checked.
     ignore_crates: Box<[String]>,
 }
 
@@ -148,6 +150,22 @@ impl LogTracer {
     pub fn init() -> Result<(), SetLoggerError> {
         Self::builder().init()
     }
+
+    /// Returns whether a record with the given target should be ignored.
+    fn ignores(&self, target: &str) -> bool {
+        for ignored in &self.ignore_crates[..] {
+            if target.starts_with(&ignored[..]) {
+                return true;
+            }
+            // The list is sorted, so once we see an entry that sorts after
+            // the target, no subsequent entry can be a prefix of it: any
+            // prefix of `target` compares less than or equal to `target`.
+            if &ignored[..] > target {
+                break;
+            }
+        }
+        false
+    }
 }
 
 impl Default for LogTracer {
@@ -167,15 +185,8 @@ impl log::Log for LogTracer {
 
         // Okay, it wasn't disabled by the max level — do we have any specific
         // modules to ignore?
-        if !self.ignore_crates.is_empty() {
-            // If we are ignoring certain module paths, ensure that the metadata
-            // does not start with one of those paths.
-            let target = metadata.target();
-            for ignored in &self.ignore_crates[..] {
-                if target.starts_with(ignored) {
-                    return false;
-                }
-            }
+        if self.ignores(metadata.target()) {
+            return false;
         }
 
         // Finally, check if the current `tracing` dispatcher cares about this.
@@ -183,6 +194,15 @@ impl log::Log for LogTracer {
     }
 
     fn log(&self, record: &log::Record<'_>) {
+        // The `log` macros don't consult `enabled` before calling `log`, so
+        // check the max level and the ignore list here as well, before any
+        // `tracing` machinery is involved in dispatching the record.
+        let metadata = record.metadata();
+        if metadata.level().as_trace() > tracing_core::LevelFilter::current()
+            || self.ignores(metadata.target())
+        {
+            return;
+        }
         crate::dispatch_record(record);
     }
 
@@ -233,13 +253,31 @@ impl Builder {
         crates.into_iter().fold(self, Self::ignore_crate)
     }
 
+    /// Configures the `LogTracer` to ignore all log records whose target
+    /// starts with the given prefix.
+    ///
+    /// This behaves identically to [`ignore_crate`], but makes it explicit
+    /// that the prefix need not be a crate name: it may name a single noisy
+    /// module within a crate, such as `"mio::poll"` or `"hyper::proto"`.
+    /// Ignored records are discarded before any `tracing` dispatch occurs,
+    /// so this is cheaper than filtering them in a collector.
+    ///
+    /// [`ignore_crate`]: Builder::ignore_crate()
+    pub fn ignore_target_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.ignore_crates.push(prefix.into());
+        self
+    }
+
     /// Constructs a new `LogTracer` with the provided configuration and sets it
     /// as the default logger.
     ///
     /// Setting a global logger can only be done once.
     #[cfg(feature = "std")]
     #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
-    pub fn init(self) -> Result<(), SetLoggerError> {
+    pub fn init(mut self) -> Result<(), SetLoggerError> {
+        // Sort the ignore list so that lookups can short-circuit once they
+        // pass the target's position in the ordering.
+        self.ignore_crates.sort_unstable();
         let ignore_crates = self.ignore_crates.into_boxed_slice();
         let logger = Box::new(LogTracer { ignore_crates });
         log::set_boxed_logger(logger)?;
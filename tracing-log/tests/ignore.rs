@@ -0,0 +1,68 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tracing::collect::with_default;
+use tracing_core::span::{Attributes, Record};
+use tracing_core::{span, Collect, Event, Metadata};
+use tracing_log::LogTracer;
+
+struct EventCount(Arc<AtomicUsize>);
+
+impl Collect for EventCount {
+    fn enabled(&self, _meta: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> span::Id {
+        span::Id::from_u64(42)
+    }
+
+    fn record(&self, _span: &span::Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, _event: &Event<'_>) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+
+    fn current_span(&self) -> span::Current {
+        span::Current::unknown()
+    }
+}
+
+#[test]
+fn ignored_targets_are_not_dispatched() {
+    LogTracer::builder()
+        .ignore_crate("noisy")
+        .ignore_target_prefix("mio::poll")
+        .with_max_level(log::LevelFilter::Info)
+        .init()
+        .unwrap();
+
+    // `with_max_level` should also configure the `log` crate's max level.
+    assert_eq!(log::max_level(), log::LevelFilter::Info);
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let events = count.clone();
+
+    with_default(EventCount(count), || {
+        log::info!(target: "noisy", "ignored");
+        log::info!(target: "noisy::submodule", "ignored");
+        log::info!(target: "mio::poll", "ignored");
+        assert_eq!(events.load(Ordering::Relaxed), 0);
+
+        // A target that merely sorts near an ignored prefix is not ignored.
+        log::info!(target: "mio::net", "dispatched");
+        log::info!(target: "quiet", "dispatched");
+        assert_eq!(events.load(Ordering::Relaxed), 2);
+
+        // Records more verbose than the max level are dropped entirely.
+        log::debug!(target: "quiet", "dropped");
+        assert_eq!(events.load(Ordering::Relaxed), 2);
+    })
+}
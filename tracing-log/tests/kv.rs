@@ -0,0 +1,112 @@
+#![cfg(feature = "kv_unstable")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::collect::with_default;
+use tracing_core::span::{Attributes, Record};
+use tracing_core::{span, Collect, Event, Field, Metadata};
+use tracing_log::LogTracer;
+
+struct State {
+    last_event_fields: Mutex<Option<HashMap<String, String>>>,
+}
+
+struct TestSubscriber(Arc<State>);
+
+impl Collect for TestSubscriber {
+    fn enabled(&self, _meta: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> span::Id {
+        span::Id::from_u64(42)
+    }
+
+    fn record(&self, _span: &span::Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        struct Fields(HashMap<String, String>);
+        impl tracing_core::field::Visit for Fields {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0
+                    .insert(field.name().to_string(), format!("{:?}", value));
+            }
+
+            fn record_str(&mut self, field: &Field, value: &str) {
+                self.0.insert(field.name().to_string(), value.to_string());
+            }
+        }
+        let mut fields = Fields(HashMap::new());
+        event.record(&mut fields);
+        *self.0.last_event_fields.lock().unwrap() = Some(fields.0);
+    }
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+
+    fn current_span(&self) -> span::Current {
+        span::Current::unknown()
+    }
+}
+
+#[test]
+fn key_values_are_bridged() {
+    LogTracer::init().unwrap();
+    let me = Arc::new(State {
+        last_event_fields: Mutex::new(None),
+    });
+    let state = me.clone();
+
+    with_default(TestSubscriber(me), || {
+        let kvs: &[(&str, &dyn log::kv::ToValue)] = &[("question", &"life"), ("answer", &42_i64)];
+        let log = log::Record::builder()
+            .args(format_args!("the answer"))
+            .level(log::Level::Info)
+            .target("kv_target")
+            .key_values(&kvs)
+            .build();
+        log::logger().log(&log);
+
+        let fields = state
+            .last_event_fields
+            .lock()
+            .unwrap()
+            .take()
+            .expect("no event was recorded");
+        assert_eq!(
+            fields.get("log.kv.0").map(String::as_str),
+            Some("question=life")
+        );
+        assert_eq!(
+            fields.get("log.kv.1").map(String::as_str),
+            Some("answer=42")
+        );
+        assert_eq!(fields.get("log.kv.2"), None);
+
+        // Pairs past the last generic slot are folded into it, not dropped.
+        let kvs: Vec<(String, i64)> = (0..10).map(|i| (format!("key{}", i), i)).collect();
+        let log = log::Record::builder()
+            .args(format_args!("too many keys"))
+            .level(log::Level::Info)
+            .target("kv_target")
+            .key_values(&kvs)
+            .build();
+        log::logger().log(&log);
+
+        let fields = state
+            .last_event_fields
+            .lock()
+            .unwrap()
+            .take()
+            .expect("no event was recorded");
+        assert_eq!(fields.get("log.kv.0").map(String::as_str), Some("key0=0"));
+        assert_eq!(fields.get("log.kv.6").map(String::as_str), Some("key6=6"));
+        assert_eq!(
+            fields.get("log.kv.7").map(String::as_str),
+            Some("key7=7 key8=8 key9=9")
+        );
+    })
+}
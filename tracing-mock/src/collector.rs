@@ -0,0 +1,830 @@
+#![allow(missing_docs)]
+use super::{
+    event::MockEvent,
+    field as mock_field,
+    span::{MockSpan, NewSpan},
+    Parent,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+};
+use tracing::{
+    collect::Interest,
+    level_filters::LevelFilter,
+    span::{self, Attributes, Id},
+    Collect, Event, Metadata,
+};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Expect {
+    Event(MockEvent),
+    Enter(MockSpan),
+    Exit(MockSpan),
+    CloneSpan(MockSpan),
+    DropSpan(MockSpan),
+    TryClose(MockSpan),
+    Visit(MockSpan, mock_field::Expect),
+    NewSpan(NewSpan),
+    Unordered(Vec<Expect>),
+    Nothing,
+}
+
+/// The queue (or queues) of expectations a running mock collector matches
+/// notifications against.
+#[derive(Clone)]
+enum Expectations {
+    /// All threads share a single ordered queue of expectations.
+    Shared(Arc<Mutex<VecDeque<Expect>>>),
+    /// Each thread that generates notifications is given its own copy of the
+    /// expectation sequence, matched independently of other threads.
+    PerThread(Arc<PerThread>),
+}
+
+struct PerThread {
+    sequence: VecDeque<Expect>,
+    threads: Mutex<HashMap<thread::ThreadId, Arc<Mutex<VecDeque<Expect>>>>>,
+}
+
+/// The result of looking up the next expectation for a notification.
+enum NextExpect {
+    /// An expectation matched the notification, and was removed from the
+    /// queue.
+    Expect(Expect),
+    /// The queue is non-empty, but the next expectation does not match the
+    /// notification. The queue is left unchanged.
+    Unmatched,
+    /// No expectations remain.
+    Empty,
+}
+
+impl Expectations {
+    fn for_current_thread(&self) -> Arc<Mutex<VecDeque<Expect>>> {
+        match self {
+            Expectations::Shared(queue) => queue.clone(),
+            Expectations::PerThread(per_thread) => per_thread
+                .threads
+                .lock()
+                .unwrap()
+                .entry(thread::current().id())
+                .or_insert_with(|| Arc::new(Mutex::new(per_thread.sequence.clone())))
+                .clone(),
+        }
+    }
+
+    /// Like `for_current_thread`, but returns `None` rather than panicking if
+    /// the thread map's lock cannot be acquired. This is used on the span
+    /// close path, which may run in `Drop` impls while panicking.
+    fn try_for_current_thread(&self) -> Option<Arc<Mutex<VecDeque<Expect>>>> {
+        match self {
+            Expectations::Shared(queue) => Some(queue.clone()),
+            Expectations::PerThread(per_thread) => {
+                let mut threads = per_thread.threads.try_lock().ok()?;
+                Some(
+                    threads
+                        .entry(thread::current().id())
+                        .or_insert_with(|| Arc::new(Mutex::new(per_thread.sequence.clone())))
+                        .clone(),
+                )
+            }
+        }
+    }
+}
+
+/// Removes and returns the next expectation matched by `matches`.
+///
+/// Ordinarily, this is the expectation at the front of the queue. If the
+/// front of the queue is an unordered group, the first expectation in the
+/// group matched by `matches` is removed instead, and the group itself is
+/// popped once all of its expectations have been matched.
+fn next_expect(expected: &mut VecDeque<Expect>, matches: impl Fn(&Expect) -> bool) -> NextExpect {
+    let matched = match expected.front_mut() {
+        None => return NextExpect::Empty,
+        Some(Expect::Unordered(group)) => {
+            return match group.iter().position(&matches) {
+                Some(i) => {
+                    let expect = group.remove(i);
+                    if group.is_empty() {
+                        expected.pop_front();
+                    }
+                    NextExpect::Expect(expect)
+                }
+                None => NextExpect::Unmatched,
+            };
+        }
+        Some(expect) => matches(expect),
+    };
+    if matched {
+        NextExpect::Expect(expected.pop_front().unwrap())
+    } else {
+        NextExpect::Unmatched
+    }
+}
+
+struct SpanState {
+    name: &'static str,
+    refs: usize,
+    meta: &'static Metadata<'static>,
+}
+
+struct Running<F: Fn(&Metadata<'_>) -> bool> {
+    spans: Mutex<HashMap<Id, SpanState>>,
+    expected: Expectations,
+    current: Mutex<HashMap<thread::ThreadId, Vec<Id>>>,
+    ids: AtomicUsize,
+    max_level: Option<LevelFilter>,
+    filter: F,
+    name: String,
+}
+
+pub struct MockCollector<F: Fn(&Metadata<'_>) -> bool> {
+    expected: VecDeque<Expect>,
+    unordered: bool,
+    per_thread: bool,
+    max_level: Option<LevelFilter>,
+    filter: F,
+    name: String,
+}
+
+pub struct MockHandle(Expectations, String);
+
+pub fn mock() -> MockCollector<fn(&Metadata<'_>) -> bool> {
+    MockCollector {
+        expected: VecDeque::new(),
+        unordered: false,
+        per_thread: false,
+        filter: (|_: &Metadata<'_>| true) as for<'r, 's> fn(&'r Metadata<'s>) -> _,
+        max_level: None,
+        name: thread::current()
+            .name()
+            .unwrap_or("mock_subscriber")
+            .to_string(),
+    }
+}
+
+impl<F> MockCollector<F>
+where
+    F: Fn(&Metadata<'_>) -> bool + 'static,
+{
+    /// Overrides the name printed by the mock subscriber's debugging output.
+    ///
+    /// The debugging output is displayed if the test panics, or if the test is
+    /// run with `--nocapture`.
+    ///
+    /// By default, the mock subscriber's name is the  name of the test
+    /// (*technically*, the name of the thread where it was created, which is
+    /// the name of the test unless tests are run with `--test-threads=1`).
+    /// When a test has only one mock subscriber, this is sufficient. However,
+    /// some tests may include multiple subscribers, in order to test
+    /// interactions between multiple subscribers. In that case, it can be
+    /// helpful to give each subscriber a separate name to distinguish where the
+    /// debugging output comes from.
+    pub fn named(self, name: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            ..self
+        }
+    }
+
+    pub fn enter(mut self, span: MockSpan) -> Self {
+        self.expect(Expect::Enter(span));
+        self
+    }
+
+    pub fn event(mut self, event: MockEvent) -> Self {
+        self.expect(Expect::Event(event));
+        self
+    }
+
+    pub fn exit(mut self, span: MockSpan) -> Self {
+        self.expect(Expect::Exit(span));
+        self
+    }
+
+    pub fn clone_span(mut self, span: MockSpan) -> Self {
+        self.expect(Expect::CloneSpan(span));
+        self
+    }
+
+    #[allow(deprecated)]
+    pub fn drop_span(mut self, span: MockSpan) -> Self {
+        self.expect(Expect::DropSpan(span));
+        self
+    }
+
+    /// Expects the span to be closed, i.e. for `try_close` to be called with
+    /// its ID after all clones of the ID have been dropped.
+    pub fn try_close(mut self, span: MockSpan) -> Self {
+        self.expect(Expect::TryClose(span));
+        self
+    }
+
+    /// Begins a group of expectations that may be matched in any order.
+    ///
+    /// Expectations added after calling this method are collected into a
+    /// single group. Once the group is reached, each notification may match
+    /// any remaining expectation in the group, and the group is finished when
+    /// all of its expectations have been matched. Call [`in_order`] to close
+    /// the group and return to ordered matching.
+    ///
+    /// This is primarily intended for tests where multiple threads generate
+    /// notifications whose relative order is not deterministic; see also
+    /// [`per_thread`], which matches each thread's notifications against its
+    /// own sequence instead.
+    ///
+    /// [`in_order`]: MockCollector::in_order
+    /// [`per_thread`]: MockCollector::per_thread
+    pub fn unordered(mut self) -> Self {
+        assert!(!self.unordered, "unordered groups may not be nested");
+        self.expected.push_back(Expect::Unordered(Vec::new()));
+        self.unordered = true;
+        self
+    }
+
+    /// Closes an unordered group begun by [`unordered`], returning to ordered
+    /// matching for subsequent expectations.
+    ///
+    /// [`unordered`]: MockCollector::unordered
+    pub fn in_order(mut self) -> Self {
+        self.unordered = false;
+        self
+    }
+
+    /// Matches each thread's notifications against its own copy of the
+    /// expectation sequence.
+    ///
+    /// By default, all threads pop expectations from a single shared queue,
+    /// so notifications from multiple threads must interleave in exactly the
+    /// expected order. In per-thread mode, each thread that generates a
+    /// notification is instead given its own copy of the full expectation
+    /// sequence the first time the collector sees it, and matches against
+    /// that sequence independently.
+    ///
+    /// The handle's [`assert_finished`] requires every observed thread to
+    /// have matched its entire sequence, and at least one thread to have been
+    /// observed.
+    ///
+    /// [`assert_finished`]: MockHandle::assert_finished
+    pub fn per_thread(mut self) -> Self {
+        self.per_thread = true;
+        self
+    }
+
+    pub fn done(mut self) -> Self {
+        self.unordered = false;
+        self.expected.push_back(Expect::Nothing);
+        self
+    }
+
+    pub fn record<I>(mut self, span: MockSpan, fields: I) -> Self
+    where
+        I: Into<mock_field::Expect>,
+    {
+        self.expect(Expect::Visit(span, fields.into()));
+        self
+    }
+
+    pub fn new_span<I>(mut self, new_span: I) -> Self
+    where
+        I: Into<NewSpan>,
+    {
+        self.expect(Expect::NewSpan(new_span.into()));
+        self
+    }
+
+    /// Adds an expectation, either to the open unordered group or to the end
+    /// of the ordered queue.
+    fn expect(&mut self, expect: Expect) {
+        if self.unordered {
+            match self.expected.back_mut() {
+                Some(Expect::Unordered(group)) => group.push(expect),
+                _ => unreachable!("`unordered` should have pushed an open group"),
+            }
+        } else {
+            self.expected.push_back(expect);
+        }
+    }
+
+    pub fn with_filter<G>(self, filter: G) -> MockCollector<G>
+    where
+        G: Fn(&Metadata<'_>) -> bool + 'static,
+    {
+        MockCollector {
+            expected: self.expected,
+            unordered: self.unordered,
+            per_thread: self.per_thread,
+            filter,
+            max_level: self.max_level,
+            name: self.name,
+        }
+    }
+
+    pub fn with_max_level_hint(self, hint: impl Into<LevelFilter>) -> Self {
+        Self {
+            max_level: Some(hint.into()),
+            ..self
+        }
+    }
+
+    pub fn run(self) -> impl Collect {
+        let (collector, _) = self.run_with_handle();
+        collector
+    }
+
+    pub fn run_with_handle(self) -> (impl Collect, MockHandle) {
+        let expected = if self.per_thread {
+            Expectations::PerThread(Arc::new(PerThread {
+                sequence: self.expected,
+                threads: Mutex::new(HashMap::new()),
+            }))
+        } else {
+            Expectations::Shared(Arc::new(Mutex::new(self.expected)))
+        };
+        let handle = MockHandle(expected.clone(), self.name.clone());
+        let collector = Running {
+            spans: Mutex::new(HashMap::new()),
+            expected,
+            current: Mutex::new(HashMap::new()),
+            ids: AtomicUsize::new(1),
+            filter: self.filter,
+            max_level: self.max_level,
+            name: self.name,
+        };
+        (collector, handle)
+    }
+}
+
+impl<F> Collect for Running<F>
+where
+    F: Fn(&Metadata<'_>) -> bool + 'static,
+{
+    fn enabled(&self, meta: &Metadata<'_>) -> bool {
+        println!("[{}] enabled: {:#?}", self.name, meta);
+        let enabled = (self.filter)(meta);
+        println!("[{}] enabled -> {}", self.name, enabled);
+        enabled
+    }
+
+    fn register_callsite(&self, meta: &'static Metadata<'static>) -> Interest {
+        println!("[{}] register_callsite: {:#?}", self.name, meta);
+        if self.enabled(meta) {
+            Interest::always()
+        } else {
+            Interest::never()
+        }
+    }
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        self.max_level
+    }
+
+    fn record(&self, id: &Id, values: &span::Record<'_>) {
+        let spans = self.spans.lock().unwrap();
+        let queue = self.expected.for_current_thread();
+        let mut expected = queue.lock().unwrap();
+        let span = spans
+            .get(id)
+            .unwrap_or_else(|| panic!("[{}] no span for ID {:?}", self.name, id));
+        println!(
+            "[{}] record: {}; id={:?}; values={:?};",
+            self.name, span.name, id, values
+        );
+        let next = next_expect(&mut expected, |e| {
+            matches!(e, Expect::Visit(expected_span, _)
+                if expected_span.name().map_or(true, |name| name == span.name))
+        });
+        if let NextExpect::Expect(Expect::Visit(expected_span, mut expected_values)) = next {
+            if let Some(name) = expected_span.name() {
+                assert_eq!(name, span.name);
+            }
+            let mut checker = expected_values.checker(format!("span {}: ", span.name));
+            values.record(&mut checker);
+            checker.finish();
+        }
+    }
+
+    fn event(&self, event: &Event<'_>) {
+        let name = event.metadata().name();
+        println!("[{}] event: {};", self.name, name);
+        let queue = self.expected.for_current_thread();
+        let mut queue = queue.lock().unwrap();
+        match next_expect(&mut queue, |e| {
+            matches!(e, Expect::Event(expected)
+                if expected.metadata.matched_by(event.metadata()))
+        }) {
+            NextExpect::Empty => {}
+            NextExpect::Expect(Expect::Event(mut expected)) => {
+                let spans = self.spans.lock().unwrap();
+                expected.check(event);
+                match expected.parent {
+                    Some(Parent::ExplicitRoot) => {
+                        assert!(
+                            event.is_root(),
+                            "[{}] expected {:?} to be an explicit root event",
+                            self.name,
+                            name
+                        );
+                    }
+                    Some(Parent::Explicit(expected_parent)) => {
+                        let actual_parent =
+                            event.parent().and_then(|id| spans.get(id)).map(|s| s.name);
+                        assert_eq!(
+                            Some(expected_parent.as_ref()),
+                            actual_parent,
+                            "[{}] expected {:?} to have explicit parent {:?}",
+                            self.name,
+                            name,
+                            expected_parent,
+                        );
+                    }
+                    Some(Parent::ContextualRoot) => {
+                        assert!(
+                            event.is_contextual(),
+                            "[{}] expected {:?} to have a contextual parent",
+                            self.name,
+                            name
+                        );
+                        assert!(
+                            self.current_id().is_none(),
+                            "[{}] expected {:?} to be a root, but we were inside a span",
+                            self.name,
+                            name
+                        );
+                    }
+                    Some(Parent::Contextual(expected_parent)) => {
+                        assert!(
+                            event.is_contextual(),
+                            "[{}] expected {:?} to have a contextual parent",
+                            self.name,
+                            name
+                        );
+                        let actual_parent = self
+                            .current_id()
+                            .and_then(|id| spans.get(&id).map(|s| s.name));
+                        assert_eq!(
+                            Some(expected_parent.as_ref()),
+                            actual_parent,
+                            "[{}] expected {:?} to have contextual parent {:?}",
+                            self.name,
+                            name,
+                            expected_parent,
+                        );
+                    }
+                    None => {}
+                }
+            }
+            NextExpect::Expect(_) => unreachable!(),
+            NextExpect::Unmatched => queue.pop_front().unwrap().bad(
+                &self.name,
+                format_args!("[{}] observed event {:?}", self.name, event),
+            ),
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {
+        // TODO: it should be possible to expect spans to follow from other spans
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let meta = span.metadata();
+        let id = self.ids.fetch_add(1, Ordering::SeqCst);
+        let id = Id::from_u64(id as u64);
+        println!(
+            "[{}] new_span: name={:?}; target={:?}; id={:?};",
+            self.name,
+            meta.name(),
+            meta.target(),
+            id
+        );
+        let queue = self.expected.for_current_thread();
+        let mut expected = queue.lock().unwrap();
+        let next = next_expect(&mut expected, |e| {
+            matches!(e, Expect::NewSpan(expected)
+                if expected.span.metadata.matched_by(meta))
+        });
+        let mut spans = self.spans.lock().unwrap();
+        if let NextExpect::Expect(Expect::NewSpan(mut expected)) = next {
+            let name = meta.name();
+            expected
+                .span
+                .metadata
+                .check(meta, format_args!("span `{}`", name));
+            let mut checker = expected.fields.checker(name.to_string());
+            span.record(&mut checker);
+            checker.finish();
+            match expected.parent {
+                Some(Parent::ExplicitRoot) => {
+                    assert!(
+                        span.is_root(),
+                        "[{}] expected {:?} to be an explicit root span",
+                        self.name,
+                        name
+                    );
+                }
+                Some(Parent::Explicit(expected_parent)) => {
+                    let actual_parent = span.parent().and_then(|id| spans.get(id)).map(|s| s.name);
+                    assert_eq!(
+                        Some(expected_parent.as_ref()),
+                        actual_parent,
+                        "[{}] expected {:?} to have explicit parent {:?}",
+                        self.name,
+                        name,
+                        expected_parent,
+                    );
+                }
+                Some(Parent::ContextualRoot) => {
+                    assert!(
+                        span.is_contextual(),
+                        "[{}] expected {:?} to have a contextual parent",
+                        self.name,
+                        name
+                    );
+                    assert!(
+                        self.current_id().is_none(),
+                        "[{}] expected {:?} to be a root, but we were inside a span",
+                        self.name,
+                        name
+                    );
+                }
+                Some(Parent::Contextual(expected_parent)) => {
+                    assert!(
+                        span.is_contextual(),
+                        "[{}] expected {:?} to have a contextual parent",
+                        self.name,
+                        name
+                    );
+                    let actual_parent = self
+                        .current_id()
+                        .and_then(|id| spans.get(&id).map(|s| s.name));
+                    assert_eq!(
+                        Some(expected_parent.as_ref()),
+                        actual_parent,
+                        "[{}] expected {:?} to have contextual parent {:?}",
+                        self.name,
+                        name,
+                        expected_parent,
+                    );
+                }
+                None => {}
+            }
+        }
+        spans.insert(
+            id.clone(),
+            SpanState {
+                name: meta.name(),
+                refs: 1,
+                meta,
+            },
+        );
+        id
+    }
+
+    fn enter(&self, id: &Id) {
+        let spans = self.spans.lock().unwrap();
+        if let Some(span) = spans.get(id) {
+            println!("[{}] enter: {}; id={:?};", self.name, span.name, id);
+            let queue = self.expected.for_current_thread();
+            let mut queue = queue.lock().unwrap();
+            match next_expect(&mut queue, |e| {
+                matches!(e, Expect::Enter(expected_span)
+                    if expected_span.name().map_or(true, |name| name == span.name))
+            }) {
+                NextExpect::Empty | NextExpect::Expect(_) => {}
+                NextExpect::Unmatched => queue
+                    .pop_front()
+                    .unwrap()
+                    .bad(&self.name, format_args!("entered span {:?}", span.name)),
+            }
+        };
+        self.current
+            .lock()
+            .unwrap()
+            .entry(thread::current().id())
+            .or_default()
+            .push(id.clone());
+    }
+
+    fn exit(&self, id: &Id) {
+        if std::thread::panicking() {
+            // `exit()` can be called in `drop` impls, so we must guard against
+            // double panics.
+            println!("[{}] exit {:?} while panicking", self.name, id);
+            return;
+        }
+        let spans = self.spans.lock().unwrap();
+        let span = spans
+            .get(id)
+            .unwrap_or_else(|| panic!("[{}] no span for ID {:?}", self.name, id));
+        println!("[{}] exit: {}; id={:?};", self.name, span.name, id);
+        let queue = self.expected.for_current_thread();
+        let mut queue = queue.lock().unwrap();
+        match next_expect(&mut queue, |e| {
+            matches!(e, Expect::Exit(expected_span)
+                if expected_span.name().map_or(true, |name| name == span.name))
+        }) {
+            NextExpect::Empty => {}
+            NextExpect::Expect(_) => {
+                let curr = self
+                    .current
+                    .lock()
+                    .unwrap()
+                    .get_mut(&thread::current().id())
+                    .and_then(|stack| stack.pop());
+                assert_eq!(
+                    Some(id),
+                    curr.as_ref(),
+                    "[{}] exited span {:?}, but the current span was {:?}",
+                    self.name,
+                    span.name,
+                    curr.as_ref().and_then(|id| spans.get(id)).map(|s| s.name)
+                );
+            }
+            NextExpect::Unmatched => queue
+                .pop_front()
+                .unwrap()
+                .bad(&self.name, format_args!("exited span {:?}", span.name)),
+        };
+    }
+
+    fn clone_span(&self, id: &Id) -> Id {
+        let name = self.spans.lock().unwrap().get_mut(id).map(|span| {
+            let name = span.name;
+            println!(
+                "[{}] clone_span: {}; id={:?}; refs={:?};",
+                self.name, name, id, span.refs
+            );
+            span.refs += 1;
+            name
+        });
+        if name.is_none() {
+            println!("[{}] clone_span: id={:?};", self.name, id);
+        }
+        let queue = self.expected.for_current_thread();
+        let mut queue = queue.lock().unwrap();
+        match next_expect(&mut queue, |e| {
+            matches!(e, Expect::CloneSpan(span)
+                if span.name().map_or(true, |n| Some(n) == name))
+        }) {
+            NextExpect::Empty | NextExpect::Expect(_) => {}
+            NextExpect::Unmatched => {
+                if let Some(Expect::CloneSpan(ref span)) = queue.front() {
+                    assert_eq!(
+                        name,
+                        span.name(),
+                        "[{}] expected to clone a span named {:?}",
+                        self.name,
+                        span.name()
+                    );
+                }
+            }
+        }
+        id.clone()
+    }
+
+    fn try_close(&self, id: Id) -> bool {
+        let mut is_event = false;
+        let mut closed = false;
+        let name = if let Ok(mut spans) = self.spans.try_lock() {
+            spans.get_mut(&id).map(|span| {
+                let name = span.name;
+                if name.contains("event") {
+                    is_event = true;
+                }
+                println!(
+                    "[{}] try_close: {}; id={:?}; refs={:?};",
+                    self.name, name, id, span.refs
+                );
+                span.refs -= 1;
+                closed = span.refs == 0;
+                name
+            })
+        } else {
+            None
+        };
+        if name.is_none() {
+            println!("[{}] try_close: id={:?}", self.name, id);
+        }
+        if let Some(queue) = self.expected.try_for_current_thread() {
+            if let Ok(mut expected) = queue.try_lock() {
+                // Don't assert if this function was called while panicking, as
+                // failing the assertion can cause a double panic. Instead,
+                // leave the expectation in the queue for `assert_finished` to
+                // report.
+                if !::std::thread::panicking() {
+                    next_expect(&mut expected, |e| match e {
+                        Expect::DropSpan(span) | Expect::TryClose(span) => {
+                            span.name().map_or(true, |n| Some(n) == name)
+                        }
+                        Expect::Event(_) => is_event,
+                        _ => false,
+                    });
+                }
+            }
+        }
+        closed
+    }
+
+    fn current_span(&self) -> tracing_core::span::Current {
+        let current = self.current.lock().unwrap();
+        match current
+            .get(&thread::current().id())
+            .and_then(|stack| stack.last())
+        {
+            Some(id) => {
+                let spans = self.spans.lock().unwrap();
+                let state = spans.get(id).expect("state for current span");
+                tracing_core::span::Current::new(id.clone(), state.meta)
+            }
+            None => tracing_core::span::Current::none(),
+        }
+    }
+}
+
+impl<F> Running<F>
+where
+    F: Fn(&Metadata<'_>) -> bool,
+{
+    /// Returns the ID at the top of the current thread's span stack, if any.
+    fn current_id(&self) -> Option<Id> {
+        self.current
+            .lock()
+            .unwrap()
+            .get(&thread::current().id())
+            .and_then(|stack| stack.last())
+            .cloned()
+    }
+}
+
+impl MockHandle {
+    pub fn assert_finished(&self) {
+        match &self.0 {
+            Expectations::Shared(queue) => {
+                if let Ok(ref expected) = queue.lock() {
+                    assert!(
+                        !expected.iter().any(|thing| thing != &Expect::Nothing),
+                        "[{}] more notifications expected: {:?}",
+                        self.1,
+                        **expected
+                    );
+                }
+            }
+            Expectations::PerThread(per_thread) => {
+                let threads = per_thread.threads.lock().unwrap();
+                assert!(
+                    !threads.is_empty()
+                        || per_thread.sequence.iter().all(|e| e == &Expect::Nothing),
+                    "[{}] no threads produced any notifications",
+                    self.1,
+                );
+                for (thread_id, queue) in threads.iter() {
+                    if let Ok(ref expected) = queue.lock() {
+                        assert!(
+                            !expected.iter().any(|thing| thing != &Expect::Nothing),
+                            "[{}] more notifications expected on {:?}: {:?}",
+                            self.1,
+                            thread_id,
+                            **expected
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Expect {
+    fn bad(&self, name: impl AsRef<str>, what: fmt::Arguments<'_>) {
+        let name = name.as_ref();
+        match self {
+            Expect::Event(e) => panic!("[{}] expected event {}, but {} instead", name, e, what,),
+            Expect::Enter(e) => panic!("[{}] expected to enter {} but {} instead", name, e, what,),
+            Expect::Exit(e) => panic!("[{}] expected to exit {} but {} instead", name, e, what,),
+            Expect::CloneSpan(e) => {
+                panic!("[{}] expected to clone {} but {} instead", name, e, what,)
+            }
+            Expect::DropSpan(e) => {
+                panic!("[{}] expected to drop {} but {} instead", name, e, what,)
+            }
+            Expect::TryClose(e) => {
+                panic!("[{}] expected to close {} but {} instead", name, e, what,)
+            }
+            Expect::Visit(e, fields) => panic!(
+                "[{}] expected {} to record {} but {} instead",
+                name, e, fields, what,
+            ),
+            Expect::NewSpan(e) => panic!("[{}] expected {} but {} instead", name, e, what),
+            Expect::Unordered(expects) => panic!(
+                "[{}] expected one of {:?} (in any order) but {} instead",
+                name, expects, what,
+            ),
+            Expect::Nothing => panic!(
+                "[{}] expected nothing else to happen, but {} instead",
+                name, what,
+            ),
+        }
+    }
+}
@@ -7,11 +7,11 @@ use std::fmt;
 ///
 /// This is intended for use with the mock subscriber API in the
 /// `subscriber` module.
-#[derive(Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct MockEvent {
     pub fields: Option<field::Expect>,
-    pub(in crate::support) parent: Option<Parent>,
-    metadata: metadata::Expect,
+    pub(crate) parent: Option<Parent>,
+    pub(crate) metadata: metadata::Expect,
 }
 
 pub fn mock() -> MockEvent {
@@ -78,7 +78,7 @@ impl MockEvent {
         }
     }
 
-    pub(in crate::support) fn check(&mut self, event: &tracing::Event<'_>) {
+    pub(crate) fn check(&mut self, event: &tracing::Event<'_>) {
         let meta = event.metadata();
         let name = meta.name();
         self.metadata
@@ -5,9 +5,9 @@ use tracing::{
     metadata::Kind,
 };
 
-use std::{collections::HashMap, fmt};
+use std::{collections::HashMap, convert::TryFrom, fmt};
 
-#[derive(Default, Debug, Eq, PartialEq)]
+#[derive(Clone, Default, Debug, Eq, PartialEq)]
 pub struct Expect {
     fields: HashMap<String, MockValue>,
     only: bool,
@@ -19,16 +19,49 @@ pub struct MockField {
     value: MockValue,
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug)]
 pub enum MockValue {
     I64(i64),
     U64(u64),
+    I128(i128),
+    U128(u128),
     Bool(bool),
     Str(String),
     Debug(String),
     Any,
 }
 
+impl PartialEq for MockValue {
+    fn eq(&self, other: &Self) -> bool {
+        // Integer values are compared numerically, regardless of width, so
+        // that (e.g.) an expectation recorded from an `i32` literal matches a
+        // `u128` value recorded by the collector.
+        fn as_int(value: &MockValue) -> Option<i128> {
+            match value {
+                MockValue::I64(v) => Some(i128::from(*v)),
+                MockValue::U64(v) => Some(i128::from(*v)),
+                MockValue::I128(v) => Some(*v),
+                MockValue::U128(v) => i128::try_from(*v).ok(),
+                _ => None,
+            }
+        }
+
+        match (self, other) {
+            (MockValue::U128(this), MockValue::U128(that)) => this == that,
+            (MockValue::Bool(this), MockValue::Bool(that)) => this == that,
+            (MockValue::Str(this), MockValue::Str(that)) => this == that,
+            (MockValue::Debug(this), MockValue::Debug(that)) => this == that,
+            (MockValue::Any, MockValue::Any) => true,
+            (this, that) => match (as_int(this), as_int(that)) {
+                (Some(this), Some(that)) => this == that,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Eq for MockValue {}
+
 pub fn mock<K>(name: K) -> MockField
 where
     String: From<K>,
@@ -122,6 +155,8 @@ impl fmt::Display for MockValue {
         match self {
             MockValue::I64(v) => write!(f, "i64 = {:?}", v),
             MockValue::U64(v) => write!(f, "u64 = {:?}", v),
+            MockValue::I128(v) => write!(f, "i128 = {:?}", v),
+            MockValue::U128(v) => write!(f, "u128 = {:?}", v),
             MockValue::Bool(v) => write!(f, "bool = {:?}", v),
             MockValue::Str(v) => write!(f, "&str = {:?}", v),
             MockValue::Debug(v) => write!(f, "&fmt::Debug = {:?}", v),
@@ -146,6 +181,16 @@ impl<'a> Visit for CheckVisitor<'a> {
             .compare_or_panic(field.name(), &value, &self.ctx[..])
     }
 
+    fn record_i128(&mut self, field: &Field, value: i128) {
+        self.expect
+            .compare_or_panic(field.name(), &value, &self.ctx[..])
+    }
+
+    fn record_u128(&mut self, field: &Field, value: u128) {
+        self.expect
+            .compare_or_panic(field.name(), &value, &self.ctx[..])
+    }
+
     fn record_bool(&mut self, field: &Field, value: bool) {
         self.expect
             .compare_or_panic(field.name(), &value, &self.ctx[..])
@@ -188,6 +233,14 @@ impl<'a> From<&'a dyn Value> for MockValue {
                 self.value = Some(MockValue::U64(value));
             }
 
+            fn record_i128(&mut self, _: &Field, value: i128) {
+                self.value = Some(MockValue::I128(value));
+            }
+
+            fn record_u128(&mut self, _: &Field, value: u128) {
+                self.value = Some(MockValue::U128(value));
+            }
+
             fn record_bool(&mut self, _: &Field, value: bool) {
                 self.value = Some(MockValue::Bool(value));
             }
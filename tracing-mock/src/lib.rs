@@ -0,0 +1,35 @@
+//! An implementation of the [`Collect`] trait to make assertions about the
+//! spans and events a library emits, for use in tests.
+//!
+//! The mock collector is constructed with a sequence of *expectations*: a
+//! [`collector::MockCollector`] built with [`collector::mock`] asserts that
+//! spans and events are observed in the order the expectations were added,
+//! panicking with a message describing the mismatch otherwise. Expectations
+//! can assert on a span's fields at creation time (via
+//! [`span::MockSpan::with_field`]), on values recorded after creation (via
+//! [`collector::MockCollector::record`]), and on span lifecycle events such
+//! as `enter`, `exit`, and `drop_span`.
+//!
+//! [`collector::MockCollector::run_with_handle`] returns the collector
+//! together with a [`collector::MockHandle`]; calling
+//! [`collector::MockHandle::assert_finished`] at the end of the test asserts
+//! that every expectation was satisfied.
+//!
+//! This crate is not published; it is an internal testing utility for the
+//! `tracing` crates.
+//!
+//! [`Collect`]: tracing::collect::Collect
+#![allow(dead_code)]
+pub mod collector;
+pub mod event;
+pub mod field;
+mod metadata;
+pub mod span;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Parent {
+    ContextualRoot,
+    Contextual(String),
+    ExplicitRoot,
+    Explicit(String),
+}
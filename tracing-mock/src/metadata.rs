@@ -9,7 +9,7 @@ pub struct Expect {
 }
 
 impl Expect {
-    pub(in crate::support) fn check(&self, actual: &Metadata<'_>, ctx: fmt::Arguments<'_>) {
+    pub(crate) fn check(&self, actual: &Metadata<'_>, ctx: fmt::Arguments<'_>) {
         if let Some(ref expected_name) = self.name {
             let name = actual.name();
             assert!(
@@ -43,6 +43,25 @@ impl Expect {
             )
         }
     }
+
+    /// Returns `true` if `actual` satisfies this expectation, without
+    /// panicking on a mismatch.
+    ///
+    /// This is used to select a candidate from an unordered group of
+    /// expectations before the panicking assertions in `check` are run.
+    pub(crate) fn matched_by(&self, actual: &Metadata<'_>) -> bool {
+        self.name
+            .as_deref()
+            .map_or(true, |name| name == actual.name())
+            && self
+                .level
+                .as_ref()
+                .map_or(true, |level| level == actual.level())
+            && self
+                .target
+                .as_deref()
+                .map_or(true, |target| target == actual.target())
+    }
 }
 
 impl fmt::Display for Expect {
@@ -1,6 +1,6 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use opentelemetry::{
-    sdk::trace::{Tracer, TracerProvider},
+    sdk::trace::{config, Sampler, Tracer, TracerProvider},
     trace::{SpanBuilder, Tracer as _, TracerProvider as _},
     Context,
 };
@@ -53,6 +53,68 @@ fn many_children(c: &mut Criterion) {
     }
 }
 
+fn sampled_spans(c: &mut Criterion) {
+    let mut group = c.benchmark_group("otel_sampled_spans");
+
+    // Span throughput at different sampling rates. Spans the sampler drops
+    // should be much cheaper than sampled ones, as only a marker with the
+    // pre-sampled context is stored for them.
+    for &(name, ratio) in &[("0_pct", 0.0), ("1_pct", 0.01), ("100_pct", 1.0)] {
+        let provider = TracerProvider::builder()
+            .with_config(config().with_sampler(Sampler::TraceIdRatioBased(ratio)))
+            .build();
+        let tracer = provider.get_tracer("bench", None);
+        let otel_layer = tracing_opentelemetry::subscriber()
+            .with_tracer(tracer)
+            .with_tracked_inactivity(false);
+        let _subscriber = tracing_subscriber::registry()
+            .with(otel_layer)
+            .set_default();
+
+        group.bench_function(name, |b| b.iter(tracing_harness));
+    }
+}
+
+fn expensive_attributes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("otel_expensive_attributes");
+
+    // A span attribute whose `Debug` representation is costly to build.
+    // Spans the sampler drops skip formatting their attributes entirely, so
+    // the 0% case should not pay for the payload at all.
+    for &(name, ratio) in &[("0_pct", 0.0), ("100_pct", 1.0)] {
+        let provider = TracerProvider::builder()
+            .with_config(config().with_sampler(Sampler::TraceIdRatioBased(ratio)))
+            .build();
+        let tracer = provider.get_tracer("bench", None);
+        let otel_layer = tracing_opentelemetry::subscriber()
+            .with_tracer(tracer)
+            .with_tracked_inactivity(false);
+        let _subscriber = tracing_subscriber::registry()
+            .with(otel_layer)
+            .set_default();
+
+        let payload = HeavyDebug(vec![0xAB; 4096]);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let span = trace_span!("request", payload = ?payload);
+                let _enter = span.enter();
+            })
+        });
+    }
+}
+
+/// A value whose `Debug` implementation formats a large buffer byte by byte.
+struct HeavyDebug(Vec<u8>);
+
+impl std::fmt::Debug for HeavyDebug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
 struct NoDataSpan;
 struct RegistryAccessCollector;
 
@@ -125,5 +187,5 @@ fn tracing_harness() {
     dummy();
 }
 
-criterion_group!(benches, many_children);
+criterion_group!(benches, many_children, sampled_spans, expensive_attributes);
 criterion_main!(benches);
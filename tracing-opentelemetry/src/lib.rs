@@ -99,6 +99,9 @@
     issue_tracker_base_url = "https://github.com/tokio-rs/tracing/issues/"
 )]
 
+/// Implementation of the metrics::Subscriber converting metric-prefixed event
+/// fields into OpenTelemetry instruments.
+mod metrics;
 /// Span extension which enables OpenTelemetry context management.
 mod span_ext;
 /// Implementation of the trace::Subscriber as a source of OpenTelemetry data.
@@ -106,6 +109,7 @@ mod subscriber;
 /// Protocols for OpenTelemetry Tracers that are compatible with Tracing
 mod tracer;
 
-pub use span_ext::OpenTelemetrySpanExt;
+pub use metrics::MetricsSubscriber;
+pub use span_ext::{extract_to_span, inject_current, OpenTelemetrySpanExt};
 pub use subscriber::{subscriber, OpenTelemetrySubscriber};
 pub use tracer::PreSampledTracer;
@@ -0,0 +1,215 @@
+use std::{collections::HashMap, fmt, sync::RwLock};
+
+use opentelemetry::metrics::{Counter, Meter, UpDownCounter, ValueRecorder};
+use tracing_core::{field, Collect, Event};
+use tracing_subscriber::{registry::LookupSpan, subscribe::Context, Subscribe};
+
+const METRIC_PREFIX_MONOTONIC_COUNTER: &str = "monotonic_counter.";
+const METRIC_PREFIX_COUNTER: &str = "counter.";
+const METRIC_PREFIX_HISTOGRAM: &str = "histogram.";
+
+/// Instruments created by the [`MetricsSubscriber`], cached by metric name.
+///
+/// Creating an instrument requires registering it with the meter's backing
+/// registry, so instruments are created lazily the first time their name is
+/// seen and reused for all subsequent events.
+#[derive(Default)]
+struct Instruments {
+    u64_counter: RwLock<HashMap<&'static str, Counter<u64>>>,
+    f64_counter: RwLock<HashMap<&'static str, Counter<f64>>>,
+    i64_up_down_counter: RwLock<HashMap<&'static str, UpDownCounter<i64>>>,
+    f64_up_down_counter: RwLock<HashMap<&'static str, UpDownCounter<f64>>>,
+    i64_value_recorder: RwLock<HashMap<&'static str, ValueRecorder<i64>>>,
+    u64_value_recorder: RwLock<HashMap<&'static str, ValueRecorder<u64>>>,
+    f64_value_recorder: RwLock<HashMap<&'static str, ValueRecorder<f64>>>,
+}
+
+impl Instruments {
+    fn update_or_insert<T>(
+        map: &RwLock<HashMap<&'static str, T>>,
+        name: &'static str,
+        insert: impl FnOnce() -> T,
+        update: impl FnOnce(&T),
+    ) {
+        {
+            let lock = map.read().expect("metric map poisoned");
+            if let Some(instrument) = lock.get(name) {
+                update(instrument);
+                return;
+            }
+        }
+        let mut lock = map.write().expect("metric map poisoned");
+        // Another thread may have created the instrument while the read lock
+        // was released; `entry` keeps the first one either way.
+        update(lock.entry(name).or_insert_with(insert));
+    }
+}
+
+struct MetricVisitor<'a> {
+    meter: &'a Meter,
+    instruments: &'a Instruments,
+}
+
+impl<'a> field::Visit for MetricVisitor<'a> {
+    fn record_u64(&mut self, field: &field::Field, value: u64) {
+        if let Some(metric_name) = field.name().strip_prefix(METRIC_PREFIX_MONOTONIC_COUNTER) {
+            Instruments::update_or_insert(
+                &self.instruments.u64_counter,
+                metric_name,
+                || self.meter.u64_counter(metric_name).init(),
+                |counter| counter.add(value, &[]),
+            );
+        } else if let Some(metric_name) = field.name().strip_prefix(METRIC_PREFIX_COUNTER) {
+            Instruments::update_or_insert(
+                &self.instruments.i64_up_down_counter,
+                metric_name,
+                || self.meter.i64_up_down_counter(metric_name).init(),
+                |counter| counter.add(value as i64, &[]),
+            );
+        } else if let Some(metric_name) = field.name().strip_prefix(METRIC_PREFIX_HISTOGRAM) {
+            Instruments::update_or_insert(
+                &self.instruments.u64_value_recorder,
+                metric_name,
+                || self.meter.u64_value_recorder(metric_name).init(),
+                |recorder| recorder.record(value, &[]),
+            );
+        }
+    }
+
+    fn record_i64(&mut self, field: &field::Field, value: i64) {
+        if let Some(metric_name) = field.name().strip_prefix(METRIC_PREFIX_MONOTONIC_COUNTER) {
+            Instruments::update_or_insert(
+                &self.instruments.u64_counter,
+                metric_name,
+                || self.meter.u64_counter(metric_name).init(),
+                |counter| counter.add(value as u64, &[]),
+            );
+        } else if let Some(metric_name) = field.name().strip_prefix(METRIC_PREFIX_COUNTER) {
+            Instruments::update_or_insert(
+                &self.instruments.i64_up_down_counter,
+                metric_name,
+                || self.meter.i64_up_down_counter(metric_name).init(),
+                |counter| counter.add(value, &[]),
+            );
+        } else if let Some(metric_name) = field.name().strip_prefix(METRIC_PREFIX_HISTOGRAM) {
+            Instruments::update_or_insert(
+                &self.instruments.i64_value_recorder,
+                metric_name,
+                || self.meter.i64_value_recorder(metric_name).init(),
+                |recorder| recorder.record(value, &[]),
+            );
+        }
+    }
+
+    fn record_f64(&mut self, field: &field::Field, value: f64) {
+        if let Some(metric_name) = field.name().strip_prefix(METRIC_PREFIX_MONOTONIC_COUNTER) {
+            Instruments::update_or_insert(
+                &self.instruments.f64_counter,
+                metric_name,
+                || self.meter.f64_counter(metric_name).init(),
+                |counter| counter.add(value, &[]),
+            );
+        } else if let Some(metric_name) = field.name().strip_prefix(METRIC_PREFIX_COUNTER) {
+            Instruments::update_or_insert(
+                &self.instruments.f64_up_down_counter,
+                metric_name,
+                || self.meter.f64_up_down_counter(metric_name).init(),
+                |counter| counter.add(value, &[]),
+            );
+        } else if let Some(metric_name) = field.name().strip_prefix(METRIC_PREFIX_HISTOGRAM) {
+            Instruments::update_or_insert(
+                &self.instruments.f64_value_recorder,
+                metric_name,
+                || self.meter.f64_value_recorder(metric_name).init(),
+                |recorder| recorder.record(value, &[]),
+            );
+        }
+    }
+
+    fn record_debug(&mut self, field: &field::Field, _value: &dyn fmt::Debug) {
+        // A metric-prefixed field with a non-numeric value is almost
+        // certainly a mistake at the instrumentation site; note it rather
+        // than silently dropping the data. The event emitted here has no
+        // metric-prefixed fields, so it cannot recurse further.
+        if field.name().starts_with(METRIC_PREFIX_MONOTONIC_COUNTER)
+            || field.name().starts_with(METRIC_PREFIX_COUNTER)
+            || field.name().starts_with(METRIC_PREFIX_HISTOGRAM)
+        {
+            tracing::debug!(
+                field = field.name(),
+                "non-numeric value for metric field; ignoring it"
+            );
+        }
+    }
+}
+
+/// A subscriber that publishes metrics to an [OpenTelemetry] meter based on
+/// event fields with well-known prefixes.
+///
+/// When an event carries a field whose name starts with one of the prefixes
+/// below, the prefix is stripped to form the instrument name and the field's
+/// numeric value is recorded on that instrument. Instruments are created
+/// lazily from the provided [`Meter`] the first time each name is seen and
+/// cached for reuse. `u64`, `i64`, and `f64` values are all supported;
+/// fields with a metric prefix but a non-numeric value are ignored (with a
+/// debug-level event noting the mistake).
+///
+/// - `monotonic_counter.`: a monotonically increasing [`Counter`].
+/// - `counter.`: an [`UpDownCounter`], which may also decrease.
+/// - `histogram.`: a [`ValueRecorder`] tracking the value distribution.
+///
+/// Fields without one of these prefixes are untouched, so metric fields can
+/// be mixed freely with ordinary ones.
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::metrics::MeterProvider as _;
+/// use tracing_opentelemetry::MetricsSubscriber;
+/// use tracing_subscriber::subscribe::CollectExt;
+/// use tracing_subscriber::Registry;
+///
+/// let meter = opentelemetry::metrics::noop::NoopMeterProvider::new().meter("app", None);
+/// let collector = Registry::default().with(MetricsSubscriber::new(meter));
+///
+/// tracing::collect::with_default(collector, || {
+///     tracing::info!(monotonic_counter.requests = 1, "handled a request");
+///     tracing::info!(histogram.request_size = 512u64);
+/// });
+/// ```
+///
+/// [OpenTelemetry]: https://opentelemetry.io
+pub struct MetricsSubscriber {
+    meter: Meter,
+    instruments: Instruments,
+}
+
+impl MetricsSubscriber {
+    /// Returns a new `MetricsSubscriber` recording metrics on instruments
+    /// created from the given [`Meter`].
+    pub fn new(meter: Meter) -> Self {
+        Self {
+            meter,
+            instruments: Instruments::default(),
+        }
+    }
+}
+
+impl<C> Subscribe<C> for MetricsSubscriber
+where
+    C: Collect + for<'span> LookupSpan<'span>,
+{
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, C>) {
+        let mut visitor = MetricVisitor {
+            meter: &self.meter,
+            instruments: &self.instruments,
+        };
+        event.record(&mut visitor);
+    }
+}
+
+impl fmt::Debug for MetricsSubscriber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetricsSubscriber").finish()
+    }
+}
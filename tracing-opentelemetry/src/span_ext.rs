@@ -1,5 +1,8 @@
-use crate::subscriber::WithContext;
-use opentelemetry::Context;
+use crate::subscriber::{OtelSpanRef, WithContext};
+use opentelemetry::{
+    propagation::{Extractor, Injector, TextMapPropagator},
+    trace, Context, Key, KeyValue, Value,
+};
 
 /// Utility functions to allow tracing [`Span`]s to accept and return
 /// [OpenTelemetry] [`Context`]s.
@@ -70,32 +73,232 @@ pub trait OpenTelemetrySpanExt {
     /// make_request(Span::current().context())
     /// ```
     fn context(&self) -> Context;
+
+    /// Adds a link from `self` to the span described by the provided
+    /// [`SpanContext`], e.g. one extracted from a remote message header.
+    ///
+    /// If `self` is disabled, or its collector does not contain an
+    /// [`OpenTelemetrySubscriber`], this is a no-op.
+    ///
+    /// [`SpanContext`]: opentelemetry::trace::SpanContext
+    /// [`OpenTelemetrySubscriber`]: crate::OpenTelemetrySubscriber
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use opentelemetry::trace::SpanContext;
+    /// use tracing_opentelemetry::OpenTelemetrySpanExt;
+    ///
+    /// // Generate a tracing span as usual
+    /// let app_root = tracing::span!(tracing::Level::INFO, "app_start");
+    ///
+    /// // Link the span to a span in another trace
+    /// # let remote_cx = SpanContext::empty_context();
+    /// app_root.add_link(remote_cx);
+    /// ```
+    fn add_link(&self, cx: trace::SpanContext);
+
+    /// Sets an OpenTelemetry attribute directly on `self`, for callers that
+    /// only have the [`Span`] handle after the span was created (e.g.
+    /// middleware that learns an HTTP status code late).
+    ///
+    /// If `self` is disabled, or its collector does not contain an
+    /// [`OpenTelemetrySubscriber`], this is a no-op.
+    ///
+    /// [`Span`]: tracing::Span
+    /// [`OpenTelemetrySubscriber`]: crate::OpenTelemetrySubscriber
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tracing_opentelemetry::OpenTelemetrySpanExt;
+    ///
+    /// // Generate a tracing span as usual
+    /// let app_root = tracing::span!(tracing::Level::INFO, "app_start");
+    ///
+    /// // Set an OpenTelemetry attribute after creating the span
+    /// app_root.set_attribute("http.status_code", 503_i64);
+    /// ```
+    fn set_attribute(&self, key: impl Into<Key>, value: impl Into<Value>);
+
+    /// Sets the OpenTelemetry status of `self`.
+    ///
+    /// If `self` is disabled, or its collector does not contain an
+    /// [`OpenTelemetrySubscriber`], this is a no-op.
+    ///
+    /// [`OpenTelemetrySubscriber`]: crate::OpenTelemetrySubscriber
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use opentelemetry::trace::StatusCode;
+    /// use tracing_opentelemetry::OpenTelemetrySpanExt;
+    ///
+    /// // Generate a tracing span as usual
+    /// let app_root = tracing::span!(tracing::Level::INFO, "app_start");
+    ///
+    /// // Mark the span as failed
+    /// app_root.set_status(StatusCode::Error, "service unavailable".to_string());
+    /// ```
+    fn set_status(&self, code: trace::StatusCode, message: String);
 }
 
 impl OpenTelemetrySpanExt for tracing::Span {
     fn set_parent(&self, cx: Context) {
         let mut cx = Some(cx);
         self.with_collector(move |(id, collector)| {
-            if let Some(get_context) = collector.downcast_ref::<WithContext>() {
-                get_context.with_context(collector, id, move |builder, _tracer| {
-                    if let Some(cx) = cx.take() {
-                        builder.parent_context = cx;
+            collector.with_capability::<WithContext, _>(|get_context| {
+                get_context.with_context(collector, id, move |span_ref, _tracer| {
+                    // A span the sampler has already dropped cannot be
+                    // re-parented; its sampling decision is final.
+                    if let OtelSpanRef::Builder(builder) = span_ref {
+                        if let Some(cx) = cx.take() {
+                            builder.parent_context = cx;
+                        }
                     }
                 });
-            }
+            });
         });
     }
 
     fn context(&self) -> Context {
         let mut cx = None;
         self.with_collector(|(id, collector)| {
-            if let Some(get_context) = collector.downcast_ref::<WithContext>() {
-                get_context.with_context(collector, id, |builder, tracer| {
-                    cx = Some(tracer.sampled_context(builder));
+            collector.with_capability::<WithContext, _>(|get_context| {
+                get_context.with_context(collector, id, |span_ref, tracer| {
+                    cx = Some(match span_ref {
+                        OtelSpanRef::Builder(builder) => tracer.sampled_context(builder),
+                        // A dropped span's pre-sampled context is retained
+                        // precisely so it can still be propagated.
+                        OtelSpanRef::Dropped(cx) => cx.clone(),
+                    });
                 })
-            }
+            });
         });
 
         cx.unwrap_or_default()
     }
+
+    fn add_link(&self, cx: trace::SpanContext) {
+        let mut cx = Some(cx);
+        self.with_collector(move |(id, collector)| {
+            collector.with_capability::<WithContext, _>(|get_context| {
+                get_context.with_context(collector, id, move |span_ref, _tracer| {
+                    if let OtelSpanRef::Builder(builder) = span_ref {
+                        if let Some(cx) = cx.take() {
+                            let link = trace::Link::new(cx, Vec::new());
+                            if let Some(ref mut links) = builder.links {
+                                links.push(link);
+                            } else {
+                                builder.links = Some(vec![link]);
+                            }
+                        }
+                    }
+                });
+            });
+        });
+    }
+
+    fn set_attribute(&self, key: impl Into<Key>, value: impl Into<Value>) {
+        let mut attribute = Some(KeyValue::new(key.into(), value.into()));
+        self.with_collector(move |(id, collector)| {
+            collector.with_capability::<WithContext, _>(|get_context| {
+                get_context.with_context(collector, id, move |span_ref, _tracer| {
+                    if let OtelSpanRef::Builder(builder) = span_ref {
+                        if let Some(attribute) = attribute.take() {
+                            if let Some(ref mut attributes) = builder.attributes {
+                                attributes.push(attribute);
+                            } else {
+                                builder.attributes = Some(vec![attribute]);
+                            }
+                        }
+                    }
+                });
+            });
+        });
+    }
+
+    fn set_status(&self, code: trace::StatusCode, message: String) {
+        let mut message = Some(message);
+        self.with_collector(move |(id, collector)| {
+            collector.with_capability::<WithContext, _>(|get_context| {
+                get_context.with_context(collector, id, move |span_ref, _tracer| {
+                    if let OtelSpanRef::Builder(builder) = span_ref {
+                        builder.status_code = Some(code);
+                        if let Some(message) = message.take() {
+                            builder.status_message = Some(message.into());
+                        }
+                    }
+                });
+            });
+        });
+    }
+}
+
+/// Injects the [`Context`] of the current [`Span`] into a carrier, e.g. the
+/// headers of an outgoing request.
+///
+/// This is equivalent to `propagator.inject_context(&Span::current().context(),
+/// injector)`: the current span's context is sampled eagerly, so the injected
+/// values are valid even though the span has not yet closed. If there is no
+/// current span, or its collector does not contain an
+/// [`OpenTelemetrySubscriber`], nothing is injected.
+///
+/// [`Context`]: opentelemetry::Context
+/// [`Span`]: tracing::Span
+/// [`OpenTelemetrySubscriber`]: crate::OpenTelemetrySubscriber
+///
+/// # Examples
+///
+/// ```rust
+/// use opentelemetry::sdk::propagation::TraceContextPropagator;
+/// use std::collections::HashMap;
+///
+/// let propagator = TraceContextPropagator::new();
+/// // Example carrier, could be a framework header map that impls otel's
+/// // `Injector`.
+/// let mut carrier = HashMap::new();
+///
+/// let request = tracing::info_span!("outgoing_request");
+/// let _guard = request.enter();
+///
+/// // Inject the current trace into the outgoing request's headers.
+/// tracing_opentelemetry::inject_current(&propagator, &mut carrier);
+/// ```
+pub fn inject_current(propagator: &dyn TextMapPropagator, injector: &mut dyn Injector) {
+    propagator.inject_context(&tracing::Span::current().context(), injector);
+}
+
+/// Extracts a remote [`Context`] from a carrier, e.g. the headers of an
+/// incoming request, and sets it as the parent of the given [`Span`].
+///
+/// This is equivalent to `span.set_parent(propagator.extract(extractor))`. If
+/// the carrier contains no trace information, or the span's collector does not
+/// contain an [`OpenTelemetrySubscriber`], this is a no-op.
+///
+/// [`Context`]: opentelemetry::Context
+/// [`Span`]: tracing::Span
+/// [`OpenTelemetrySubscriber`]: crate::OpenTelemetrySubscriber
+///
+/// # Examples
+///
+/// ```rust
+/// use opentelemetry::sdk::propagation::TraceContextPropagator;
+/// use std::collections::HashMap;
+///
+/// let propagator = TraceContextPropagator::new();
+/// // Example carrier, could be a framework header map that impls otel's
+/// // `Extractor`.
+/// let carrier: HashMap<String, String> = HashMap::new();
+///
+/// // Continue the trace started by the remote caller.
+/// let request = tracing::info_span!("incoming_request");
+/// tracing_opentelemetry::extract_to_span(&propagator, &carrier, &request);
+/// ```
+pub fn extract_to_span(
+    propagator: &dyn TextMapPropagator,
+    extractor: &dyn Extractor,
+    span: &tracing::Span,
+) {
+    span.set_parent(propagator.extract(extractor));
 }
@@ -1,14 +1,17 @@
 use crate::PreSampledTracer;
+use opentelemetry::sdk::trace::{SamplingDecision, SamplingResult};
 use opentelemetry::{trace as otel, trace::TraceContextExt, Context as OtelContext, Key, KeyValue};
+use std::convert::TryFrom;
 use std::fmt;
 use std::marker;
-use std::time::{Instant, SystemTime};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 use std::{any::TypeId, ptr::NonNull};
 use tracing_core::span::{self, Attributes, Id, Record};
-use tracing_core::{field, Collect, Event};
+use tracing_core::{field, Collect, Event, Metadata};
 #[cfg(feature = "tracing-log")]
 use tracing_log::NormalizeEvent;
-use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::registry::{LookupSpan, Timings};
 use tracing_subscriber::subscribe::Context;
 use tracing_subscriber::Subscribe;
 
@@ -17,6 +20,14 @@ const SPAN_KIND_FIELD: &str = "otel.kind";
 const SPAN_STATUS_CODE_FIELD: &str = "otel.status_code";
 const SPAN_STATUS_MESSAGE_FIELD: &str = "otel.status_message";
 
+const SPAN_LINK_TRACE_ID_FIELD: &str = "otel.link.trace_id";
+const SPAN_LINK_SPAN_ID_FIELD: &str = "otel.link.span_id";
+
+const ERROR_FIELD: &str = "error";
+const EVENT_TIMESTAMP_FIELD: &str = "event.timestamp";
+const EXCEPTION_EVENT_NAME: &str = "exception";
+const EXCEPTION_MESSAGE_KEY: &str = "exception.message";
+
 /// An [OpenTelemetry] propagation subscriber for use in a project that uses
 /// [tracing].
 ///
@@ -25,10 +36,17 @@ const SPAN_STATUS_MESSAGE_FIELD: &str = "otel.status_message";
 pub struct OpenTelemetrySubscriber<C, T> {
     tracer: T,
     tracked_inactivity: bool,
+    timing_handler: Option<TimingHandler>,
+    max_events_per_span: Option<usize>,
+    max_attributes_per_span: Option<usize>,
     get_context: WithContext,
     _registry: marker::PhantomData<C>,
 }
 
+/// A callback invoked with a closing span's metadata and its busy and idle
+/// durations. See [`OpenTelemetrySubscriber::with_timing_handler`].
+type TimingHandler = Arc<dyn Fn(&'static Metadata<'static>, Duration, Duration) + Send + Sync>;
+
 impl<C> Default for OpenTelemetrySubscriber<C, otel::NoopTracer>
 where
     C: Collect + for<'span> LookupSpan<'span>,
@@ -66,11 +84,7 @@ where
 //
 // See https://github.com/tokio-rs/tracing/blob/4dad420ee1d4607bad79270c1520673fa6266a3d/tracing-error/src/layer.rs
 pub(crate) struct WithContext(
-    fn(
-        &tracing::Dispatch,
-        &span::Id,
-        f: &mut dyn FnMut(&mut otel::SpanBuilder, &dyn PreSampledTracer),
-    ),
+    fn(&tracing::Dispatch, &span::Id, f: &mut dyn FnMut(OtelSpanRef<'_>, &dyn PreSampledTracer)),
 );
 
 impl WithContext {
@@ -80,12 +94,79 @@ impl WithContext {
         &self,
         dispatch: &'a tracing::Dispatch,
         id: &span::Id,
-        mut f: impl FnMut(&mut otel::SpanBuilder, &dyn PreSampledTracer),
+        mut f: impl FnMut(OtelSpanRef<'_>, &dyn PreSampledTracer),
     ) {
         (self.0)(dispatch, id, &mut f)
     }
 }
 
+/// A reference to the OpenTelemetry data stored in a span's extensions:
+/// either the pending [`SpanBuilder`] of a span that will be exported, or the
+/// pre-sampled context of a span the sampler decided to drop.
+///
+/// [`SpanBuilder`]: opentelemetry::trace::SpanBuilder
+pub(crate) enum OtelSpanRef<'a> {
+    Builder(&'a mut otel::SpanBuilder),
+    Dropped(&'a OtelContext),
+}
+
+/// Marker extension stored in place of a [`SpanBuilder`] for spans that the
+/// sampler has decided to drop.
+///
+/// Dropped spans are never exported, so there is no reason to keep a full
+/// builder — with its attribute, event, and link vectors — alive until the
+/// span closes. Only the pre-sampled context is retained, so that child spans
+/// can still be parented to the dropped span and its trace state can be
+/// propagated to downstream requests.
+///
+/// [`SpanBuilder`]: opentelemetry::trace::SpanBuilder
+struct DroppedSpan(OtelContext);
+
+/// Per-span counts of events and attributes discarded because a configured
+/// limit was reached.
+///
+/// Stored in the span's extensions and reported as synthetic
+/// `events_dropped` / `attributes_dropped` attributes when the span closes,
+/// so that truncation is visible in the exported data.
+#[derive(Default)]
+struct DroppedCounts {
+    events: u64,
+    attributes: u64,
+}
+
+/// Returns the explicit timestamp carried by the event's `event.timestamp`
+/// field, if one is present.
+///
+/// This matches the convention defined by the `tracing::time` module; see its
+/// documentation for details.
+fn event_timestamp(event: &Event<'_>) -> Option<SystemTime> {
+    struct TimestampVisitor(Option<SystemTime>);
+
+    impl field::Visit for TimestampVisitor {
+        fn record_u64(&mut self, field: &field::Field, value: u64) {
+            if field.name() == EVENT_TIMESTAMP_FIELD {
+                self.0 = Some(SystemTime::UNIX_EPOCH + Duration::from_nanos(value));
+            }
+        }
+
+        fn record_debug(&mut self, _: &field::Field, _: &dyn fmt::Debug) {}
+    }
+
+    let mut visitor = TimestampVisitor(None);
+    event.record(&mut visitor);
+    visitor.0
+}
+
+/// Converts a recorded byte slice to an OpenTelemetry array value.
+///
+/// OpenTelemetry has no dedicated type for binary data, so byte slices are
+/// represented as arrays of integers.
+fn bytes_to_array(bytes: &[u8]) -> opentelemetry::Value {
+    opentelemetry::Value::Array(opentelemetry::Array::I64(
+        bytes.iter().map(|&byte| byte as i64).collect(),
+    ))
+}
+
 fn str_to_span_kind(s: &str) -> Option<otel::SpanKind> {
     match s {
         s if s.eq_ignore_ascii_case("server") => Some(otel::SpanKind::Server),
@@ -106,7 +187,37 @@ fn str_to_status_code(s: &str) -> Option<otel::StatusCode> {
     }
 }
 
-struct SpanEventVisitor<'a>(&'a mut otel::Event);
+/// Formats an error and its chain of [`source`]s as a single message, e.g.
+/// `"request failed: connection reset"`.
+///
+/// [`source`]: std::error::Error::source
+fn chained_error_message(err: &(dyn std::error::Error + 'static)) -> String {
+    use std::fmt::Write;
+
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        let _ = write!(message, ": {}", err);
+        source = err.source();
+    }
+    message
+}
+
+struct SpanEventVisitor<'a> {
+    event: &'a mut otel::Event,
+    /// Set when an `error` field is recorded, so that the span's status can be
+    /// updated once the span extensions are locked.
+    error_msg: Option<String>,
+}
+
+impl<'a> SpanEventVisitor<'a> {
+    fn new(event: &'a mut otel::Event) -> Self {
+        Self {
+            event,
+            error_msg: None,
+        }
+    }
+}
 
 impl<'a> field::Visit for SpanEventVisitor<'a> {
     /// Record events on the underlying OpenTelemetry [`Span`] from `bool` values.
@@ -114,12 +225,12 @@ impl<'a> field::Visit for SpanEventVisitor<'a> {
     /// [`Span`]: opentelemetry::trace::Span
     fn record_bool(&mut self, field: &field::Field, value: bool) {
         match field.name() {
-            "message" => self.0.name = value.to_string().into(),
+            "message" => self.event.name = value.to_string().into(),
             // Skip fields that are actually log metadata that have already been handled
             #[cfg(feature = "tracing-log")]
             name if name.starts_with("log.") => (),
             name => {
-                self.0.attributes.push(KeyValue::new(name, value));
+                self.event.attributes.push(KeyValue::new(name, value));
             }
         }
     }
@@ -129,61 +240,255 @@ impl<'a> field::Visit for SpanEventVisitor<'a> {
     /// [`Span`]: opentelemetry::trace::Span
     fn record_i64(&mut self, field: &field::Field, value: i64) {
         match field.name() {
-            "message" => self.0.name = value.to_string().into(),
+            "message" => self.event.name = value.to_string().into(),
             // Skip fields that are actually log metadata that have already been handled
             #[cfg(feature = "tracing-log")]
             name if name.starts_with("log.") => (),
             name => {
-                self.0.attributes.push(KeyValue::new(name, value));
+                self.event.attributes.push(KeyValue::new(name, value));
             }
         }
     }
 
+    /// Record events on the underlying OpenTelemetry [`Span`] from `f64` values.
+    ///
+    /// [`Span`]: opentelemetry::trace::Span
+    fn record_f64(&mut self, field: &field::Field, value: f64) {
+        match field.name() {
+            "message" => self.event.name = value.to_string().into(),
+            // Skip fields that are actually log metadata that have already been handled
+            #[cfg(feature = "tracing-log")]
+            name if name.starts_with("log.") => (),
+            name => {
+                self.event.attributes.push(KeyValue::new(name, value));
+            }
+        }
+    }
+
+    /// Record events on the underlying OpenTelemetry [`Span`] from `u64`
+    /// values.
+    ///
+    /// OpenTelemetry integers are signed; values that do not fit in an `i64`
+    /// saturate to `i64::MAX` rather than falling back to their string
+    /// representation, so that the attribute stays numeric for backends that
+    /// query on it.
+    ///
+    /// [`Span`]: opentelemetry::trace::Span
+    fn record_u64(&mut self, field: &field::Field, value: u64) {
+        match field.name() {
+            // The explicit event timestamp was already consumed when the
+            // OpenTelemetry event was constructed; don't record it again.
+            EVENT_TIMESTAMP_FIELD => (),
+            _ => self.record_i64(field, i64::try_from(value).unwrap_or(i64::MAX)),
+        }
+    }
+
+    /// Record events on the underlying OpenTelemetry [`Span`] from `i128`
+    /// values.
+    ///
+    /// Values that fit in an `i64` are recorded as OpenTelemetry integers;
+    /// larger values are recorded as strings.
+    ///
+    /// [`Span`]: opentelemetry::trace::Span
+    fn record_i128(&mut self, field: &field::Field, value: i128) {
+        match i64::try_from(value) {
+            Ok(value) => self.record_i64(field, value),
+            Err(_) => self.record_debug(field, &value),
+        }
+    }
+
+    /// Record events on the underlying OpenTelemetry [`Span`] from `u128`
+    /// values.
+    ///
+    /// Values that fit in an `i64` are recorded as OpenTelemetry integers;
+    /// larger values are recorded as strings.
+    ///
+    /// [`Span`]: opentelemetry::trace::Span
+    fn record_u128(&mut self, field: &field::Field, value: u128) {
+        match i64::try_from(value) {
+            Ok(value) => self.record_i64(field, value),
+            Err(_) => self.record_debug(field, &value),
+        }
+    }
+
     /// Record events on the underlying OpenTelemetry [`Span`] from `&str` values.
     ///
     /// [`Span`]: opentelemetry::trace::Span
     fn record_str(&mut self, field: &field::Field, value: &str) {
         match field.name() {
-            "message" => self.0.name = value.to_string().into(),
+            "message" => self.event.name = value.to_string().into(),
             // Skip fields that are actually log metadata that have already been handled
             #[cfg(feature = "tracing-log")]
             name if name.starts_with("log.") => (),
             name => {
-                self.0
+                self.event
                     .attributes
                     .push(KeyValue::new(name, value.to_string()));
             }
         }
     }
 
+    /// Record events on the underlying OpenTelemetry [`Span`] from byte
+    /// slices.
+    ///
+    /// OpenTelemetry has no dedicated type for binary data, so the bytes are
+    /// recorded as an array of integers.
+    ///
+    /// [`Span`]: opentelemetry::trace::Span
+    fn record_bytes(&mut self, field: &field::Field, value: &[u8]) {
+        match field.name() {
+            // Skip fields that are actually log metadata that have already been handled
+            #[cfg(feature = "tracing-log")]
+            name if name.starts_with("log.") => (),
+            name => {
+                self.event
+                    .attributes
+                    .push(KeyValue::new(name, bytes_to_array(value)));
+            }
+        }
+    }
+
     /// Record events on the underlying OpenTelemetry [`Span`] from values that
     /// implement Debug.
     ///
     /// [`Span`]: opentelemetry::trace::Span
     fn record_debug(&mut self, field: &field::Field, value: &dyn fmt::Debug) {
         match field.name() {
-            "message" => self.0.name = format!("{:?}", value).into(),
+            "message" => self.event.name = format!("{:?}", value).into(),
             // Skip fields that are actually log metadata that have already been handled
             #[cfg(feature = "tracing-log")]
             name if name.starts_with("log.") => (),
             name => {
-                self.0
+                self.event
                     .attributes
                     .push(KeyValue::new(name, format!("{:?}", value)));
             }
         }
     }
+
+    /// Record events on the underlying OpenTelemetry [`Span`] from values that
+    /// implement `std::error::Error`, mapping `error` fields to the
+    /// OpenTelemetry `exception` semantic conventions.
+    ///
+    /// [`Span`]: opentelemetry::trace::Span
+    fn record_error(&mut self, field: &field::Field, value: &(dyn std::error::Error + 'static)) {
+        let message = chained_error_message(value);
+        match field.name() {
+            ERROR_FIELD => {
+                self.event.name = EXCEPTION_EVENT_NAME.into();
+                self.event
+                    .attributes
+                    .push(Key::new(EXCEPTION_MESSAGE_KEY).string(message.clone()));
+                self.error_msg = Some(message);
+            }
+            name => {
+                self.event.attributes.push(KeyValue::new(name, message));
+            }
+        }
+    }
 }
 
-struct SpanAttributeVisitor<'a>(&'a mut otel::SpanBuilder);
+/// Captures only the `otel.name` and `otel.kind` fields from a span's
+/// attributes, ignoring every other field.
+///
+/// This visitor runs before the sampling decision is made, so that a span
+/// the sampler drops never pays to `Debug`-format its attribute values,
+/// while the name and kind — which the sampler sees, and which children of
+/// a dropped span may still need for parenting — are captured regardless.
+struct SpanPreSamplingVisitor<'a> {
+    builder: &'a mut otel::SpanBuilder,
+}
+
+impl<'a> field::Visit for SpanPreSamplingVisitor<'a> {
+    fn record_str(&mut self, field: &field::Field, value: &str) {
+        match field.name() {
+            SPAN_NAME_FIELD => self.builder.name = value.to_string().into(),
+            SPAN_KIND_FIELD => self.builder.span_kind = str_to_span_kind(value),
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, field: &field::Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            SPAN_NAME_FIELD => self.builder.name = format!("{:?}", value).into(),
+            SPAN_KIND_FIELD => self.builder.span_kind = str_to_span_kind(&format!("{:?}", value)),
+            _ => {}
+        }
+    }
+}
+
+struct SpanAttributeVisitor<'a> {
+    builder: &'a mut otel::SpanBuilder,
+    /// The IDs of a span link recorded via `otel.link.*` fields, pending until
+    /// both halves have been seen.
+    link_trace_id: Option<otel::TraceId>,
+    link_span_id: Option<otel::SpanId>,
+    invalid_links: usize,
+}
 
 impl<'a> SpanAttributeVisitor<'a> {
+    fn new(builder: &'a mut otel::SpanBuilder) -> Self {
+        Self {
+            builder,
+            link_trace_id: None,
+            link_span_id: None,
+            invalid_links: 0,
+        }
+    }
+
     fn record(&mut self, attribute: KeyValue) {
-        debug_assert!(self.0.attributes.is_some());
-        if let Some(v) = self.0.attributes.as_mut() {
+        debug_assert!(self.builder.attributes.is_some());
+        if let Some(v) = self.builder.attributes.as_mut() {
             v.push(attribute);
         }
     }
+
+    fn record_link_trace_id(&mut self, value: &str) {
+        match u128::from_str_radix(value, 16) {
+            Ok(trace_id) if trace_id != 0 => {
+                self.link_trace_id = Some(otel::TraceId::from_u128(trace_id))
+            }
+            _ => self.invalid_links += 1,
+        }
+    }
+
+    fn record_link_span_id(&mut self, value: &str) {
+        match u64::from_str_radix(value, 16) {
+            Ok(span_id) if span_id != 0 => {
+                self.link_span_id = Some(otel::SpanId::from_u64(span_id))
+            }
+            _ => self.invalid_links += 1,
+        }
+    }
+
+    /// Completes the visit, adding any pending span link to the builder.
+    ///
+    /// Returns the number of `otel.link.*` fields that did not form a valid
+    /// link, so that the caller can report them once the span's extensions are
+    /// no longer locked.
+    fn finish(mut self) -> usize {
+        match (self.link_trace_id.take(), self.link_span_id.take()) {
+            (Some(trace_id), Some(span_id)) => {
+                let link_context = otel::SpanContext::new(
+                    trace_id,
+                    span_id,
+                    otel::TraceFlags::default(),
+                    true,
+                    Default::default(),
+                );
+                let link = otel::Link::new(link_context, Vec::new());
+                if let Some(ref mut links) = self.builder.links {
+                    links.push(link);
+                } else {
+                    self.builder.links = Some(vec![link]);
+                }
+            }
+            (None, None) => {}
+            // Half of a link is as unusable as an invalid ID.
+            _ => self.invalid_links += 1,
+        }
+        self.invalid_links
+    }
 }
 
 impl<'a> field::Visit for SpanAttributeVisitor<'a> {
@@ -201,36 +506,118 @@ impl<'a> field::Visit for SpanAttributeVisitor<'a> {
         self.record(KeyValue::new(field.name(), value));
     }
 
+    /// Set attributes on the underlying OpenTelemetry [`Span`] from `f64` values.
+    ///
+    /// [`Span`]: opentelemetry::trace::Span
+    fn record_f64(&mut self, field: &field::Field, value: f64) {
+        self.record(KeyValue::new(field.name(), value));
+    }
+
+    /// Set attributes on the underlying OpenTelemetry [`Span`] from `u64`
+    /// values.
+    ///
+    /// OpenTelemetry integers are signed; values that do not fit in an `i64`
+    /// saturate to `i64::MAX` rather than falling back to their string
+    /// representation, so that the attribute stays numeric for backends that
+    /// query on it.
+    ///
+    /// [`Span`]: opentelemetry::trace::Span
+    fn record_u64(&mut self, field: &field::Field, value: u64) {
+        self.record_i64(field, i64::try_from(value).unwrap_or(i64::MAX));
+    }
+
+    /// Set attributes on the underlying OpenTelemetry [`Span`] from `i128`
+    /// values.
+    ///
+    /// Values that fit in an `i64` are recorded as OpenTelemetry integers;
+    /// larger values are recorded as strings.
+    ///
+    /// [`Span`]: opentelemetry::trace::Span
+    fn record_i128(&mut self, field: &field::Field, value: i128) {
+        match i64::try_from(value) {
+            Ok(value) => self.record_i64(field, value),
+            Err(_) => self.record_debug(field, &value),
+        }
+    }
+
+    /// Set attributes on the underlying OpenTelemetry [`Span`] from `u128`
+    /// values.
+    ///
+    /// Values that fit in an `i64` are recorded as OpenTelemetry integers;
+    /// larger values are recorded as strings.
+    ///
+    /// [`Span`]: opentelemetry::trace::Span
+    fn record_u128(&mut self, field: &field::Field, value: u128) {
+        match i64::try_from(value) {
+            Ok(value) => self.record_i64(field, value),
+            Err(_) => self.record_debug(field, &value),
+        }
+    }
+
     /// Set attributes on the underlying OpenTelemetry [`Span`] from `&str` values.
     ///
     /// [`Span`]: opentelemetry::trace::Span
     fn record_str(&mut self, field: &field::Field, value: &str) {
         match field.name() {
-            SPAN_NAME_FIELD => self.0.name = value.to_string().into(),
-            SPAN_KIND_FIELD => self.0.span_kind = str_to_span_kind(value),
-            SPAN_STATUS_CODE_FIELD => self.0.status_code = str_to_status_code(value),
-            SPAN_STATUS_MESSAGE_FIELD => self.0.status_message = Some(value.to_owned().into()),
+            SPAN_NAME_FIELD => self.builder.name = value.to_string().into(),
+            SPAN_KIND_FIELD => self.builder.span_kind = str_to_span_kind(value),
+            SPAN_STATUS_CODE_FIELD => self.builder.status_code = str_to_status_code(value),
+            SPAN_STATUS_MESSAGE_FIELD => {
+                self.builder.status_message = Some(value.to_owned().into())
+            }
+            SPAN_LINK_TRACE_ID_FIELD => self.record_link_trace_id(value),
+            SPAN_LINK_SPAN_ID_FIELD => self.record_link_span_id(value),
             _ => self.record(KeyValue::new(field.name(), value.to_string())),
         }
     }
 
+    /// Set attributes on the underlying OpenTelemetry [`Span`] from byte
+    /// slices.
+    ///
+    /// OpenTelemetry has no dedicated type for binary data, so the bytes are
+    /// recorded as an array of integers.
+    ///
+    /// [`Span`]: opentelemetry::trace::Span
+    fn record_bytes(&mut self, field: &field::Field, value: &[u8]) {
+        self.record(KeyValue::new(field.name(), bytes_to_array(value)));
+    }
+
     /// Set attributes on the underlying OpenTelemetry [`Span`] from values that
     /// implement Debug.
     ///
     /// [`Span`]: opentelemetry::trace::Span
     fn record_debug(&mut self, field: &field::Field, value: &dyn fmt::Debug) {
         match field.name() {
-            SPAN_NAME_FIELD => self.0.name = format!("{:?}", value).into(),
-            SPAN_KIND_FIELD => self.0.span_kind = str_to_span_kind(&format!("{:?}", value)),
+            SPAN_NAME_FIELD => self.builder.name = format!("{:?}", value).into(),
+            SPAN_KIND_FIELD => self.builder.span_kind = str_to_span_kind(&format!("{:?}", value)),
             SPAN_STATUS_CODE_FIELD => {
-                self.0.status_code = str_to_status_code(&format!("{:?}", value))
+                self.builder.status_code = str_to_status_code(&format!("{:?}", value))
             }
             SPAN_STATUS_MESSAGE_FIELD => {
-                self.0.status_message = Some(format!("{:?}", value).into())
+                self.builder.status_message = Some(format!("{:?}", value).into())
             }
+            SPAN_LINK_TRACE_ID_FIELD => self.record_link_trace_id(&format!("{:?}", value)),
+            SPAN_LINK_SPAN_ID_FIELD => self.record_link_span_id(&format!("{:?}", value)),
             _ => self.record(Key::new(field.name()).string(format!("{:?}", value))),
         }
     }
+
+    /// Set attributes on the underlying OpenTelemetry [`Span`] from values that
+    /// implement `std::error::Error`, mapping `error` fields to the
+    /// OpenTelemetry `exception` semantic conventions.
+    ///
+    /// [`Span`]: opentelemetry::trace::Span
+    fn record_error(&mut self, field: &field::Field, value: &(dyn std::error::Error + 'static)) {
+        let message = chained_error_message(value);
+        match field.name() {
+            ERROR_FIELD => {
+                self.record(Key::new(EXCEPTION_MESSAGE_KEY).string(message.clone()));
+                self.builder.status_code = Some(otel::StatusCode::Error);
+                self.builder.status_message = Some(message.into());
+            }
+            name => self.record(Key::new(name).string(message)),
+        }
+    }
 }
 
 impl<C, T> OpenTelemetrySubscriber<C, T>
@@ -269,6 +656,9 @@ where
         OpenTelemetrySubscriber {
             tracer,
             tracked_inactivity: true,
+            timing_handler: None,
+            max_events_per_span: None,
+            max_attributes_per_span: None,
             get_context: WithContext(Self::get_context),
             _registry: marker::PhantomData,
         }
@@ -307,6 +697,9 @@ where
         OpenTelemetrySubscriber {
             tracer,
             tracked_inactivity: self.tracked_inactivity,
+            timing_handler: self.timing_handler,
+            max_events_per_span: self.max_events_per_span,
+            max_attributes_per_span: self.max_attributes_per_span,
             get_context: WithContext(OpenTelemetrySubscriber::<C, Tracer>::get_context),
             _registry: self._registry,
         }
@@ -322,6 +715,60 @@ where
         }
     }
 
+    /// Sets a handler that is invoked when a span closes, with that span's
+    /// metadata and its busy and idle durations, so that timings can be fed
+    /// to a metrics system without a second timing subscriber.
+    ///
+    /// The handler is only invoked when inactivity tracking is enabled (see
+    /// [`with_tracked_inactivity`]).
+    ///
+    /// [`with_tracked_inactivity`]: Self::with_tracked_inactivity
+    pub fn with_timing_handler(
+        self,
+        handler: impl Fn(&'static Metadata<'static>, Duration, Duration) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            timing_handler: Some(Arc::new(handler)),
+            ..self
+        }
+    }
+
+    /// Limits the number of OpenTelemetry [`Event`]s recorded on each span.
+    ///
+    /// A long-lived span accumulates every `tracing` event that occurs inside
+    /// it until it closes, which can grow without bound and exceed exporter
+    /// message size limits. Once a span has recorded `max_events` events,
+    /// further events are dropped (newest first) without paying the cost of
+    /// building the OpenTelemetry event, and an `events_dropped` attribute
+    /// recording the number discarded is added when the span closes.
+    ///
+    /// By default, the number of events per span is unlimited.
+    ///
+    /// [`Event`]: opentelemetry::trace::Event
+    pub fn with_max_events_per_span(self, max_events: usize) -> Self {
+        Self {
+            max_events_per_span: Some(max_events),
+            ..self
+        }
+    }
+
+    /// Limits the number of [`attributes`] recorded on each span.
+    ///
+    /// Once a span has `max_attributes` attributes, further values recorded
+    /// on it are dropped (newest first), and an `attributes_dropped`
+    /// attribute recording the number discarded is added when the span
+    /// closes.
+    ///
+    /// By default, the number of attributes per span is unlimited.
+    ///
+    /// [`attributes`]: opentelemetry::trace::SpanBuilder::attributes
+    pub fn with_max_attributes_per_span(self, max_attributes: usize) -> Self {
+        Self {
+            max_attributes_per_span: Some(max_attributes),
+            ..self
+        }
+    }
+
     /// Retrieve the parent OpenTelemetry [`Context`] from the current tracing
     /// [`span`] through the [`Registry`]. This [`Context`] links spans to their
     /// parent for proper hierarchical visualization.
@@ -332,21 +779,15 @@ where
     fn parent_context(&self, attrs: &Attributes<'_>, ctx: &Context<'_, C>) -> OtelContext {
         // If a span is specified, it _should_ exist in the underlying `Registry`.
         if let Some(parent) = attrs.parent() {
-            let span = ctx.span(parent).expect("Span not found, this is a bug");
-            let mut extensions = span.extensions_mut();
-            extensions
-                .get_mut::<otel::SpanBuilder>()
-                .map(|builder| self.tracer.sampled_context(builder))
+            // If the parent span is missing from the registry, treat the new
+            // span as a root rather than panicking inside instrumentation.
+            ctx.span_or_report(parent)
+                .and_then(|span| Self::sampled_context_for(&self.tracer, &span))
                 .unwrap_or_default()
         // Else if the span is inferred from context, look up any available current span.
         } else if attrs.is_contextual() {
             ctx.lookup_current()
-                .and_then(|span| {
-                    let mut extensions = span.extensions_mut();
-                    extensions
-                        .get_mut::<otel::SpanBuilder>()
-                        .map(|builder| self.tracer.sampled_context(builder))
-                })
+                .and_then(|span| Self::sampled_context_for(&self.tracer, &span))
                 .unwrap_or_else(OtelContext::current)
         // Explicit root spans should have no parent context.
         } else {
@@ -354,24 +795,46 @@ where
         }
     }
 
+    /// Returns the pre-sampled OpenTelemetry context of the given span,
+    /// whether it will be exported or was dropped by the sampler.
+    fn sampled_context_for(
+        tracer: &T,
+        span: &tracing_subscriber::registry::SpanRef<'_, C>,
+    ) -> Option<OtelContext> {
+        let mut extensions = span.extensions_mut();
+        if let Some(builder) = extensions.get_mut::<otel::SpanBuilder>() {
+            Some(tracer.sampled_context(builder))
+        } else {
+            extensions
+                .get_mut::<DroppedSpan>()
+                .map(|dropped| dropped.0.clone())
+        }
+    }
+
     fn get_context(
         dispatch: &tracing::Dispatch,
         id: &span::Id,
-        f: &mut dyn FnMut(&mut otel::SpanBuilder, &dyn PreSampledTracer),
+        f: &mut dyn FnMut(OtelSpanRef<'_>, &dyn PreSampledTracer),
     ) {
         let subscriber = dispatch
             .downcast_ref::<C>()
             .expect("subscriber should downcast to expected type; this is a bug!");
-        let span = subscriber
-            .span(id)
-            .expect("registry should have a span for the current ID");
+        // If the registry has no data for this ID, the span has already been
+        // closed (or the ID came from a different collector); skip the update
+        // rather than panicking inside instrumentation.
+        let span = match subscriber.span(id) {
+            Some(span) => span,
+            None => return,
+        };
         let subscriber = dispatch
             .downcast_ref::<OpenTelemetrySubscriber<C, T>>()
             .expect("subscriber should downcast to expected type; this is a bug!");
 
         let mut extensions = span.extensions_mut();
         if let Some(builder) = extensions.get_mut::<otel::SpanBuilder>() {
-            f(builder, &subscriber.tracer);
+            f(OtelSpanRef::Builder(builder), &subscriber.tracer);
+        } else if let Some(dropped) = extensions.get_mut::<DroppedSpan>() {
+            f(OtelSpanRef::Dropped(&dropped.0), &subscriber.tracer);
         }
     }
 }
@@ -386,7 +849,10 @@ where
     /// [OpenTelemetry `Span`]: opentelemetry::trace::Span
     /// [tracing `Span`]: tracing::Span
     fn new_span(&self, attrs: &Attributes<'_>, id: &span::Id, ctx: Context<'_, C>) {
-        let span = ctx.span(id).expect("Span not found, this is a bug");
+        let span = match ctx.span_or_report(id) {
+            Some(span) => span,
+            None => return,
+        };
         let mut extensions = span.extensions_mut();
 
         if self.tracked_inactivity && extensions.get_mut::<Timings>().is_none() {
@@ -424,8 +890,53 @@ where
             builder_attrs.push(KeyValue::new("code.lineno", line as i64));
         }
 
-        attrs.record(&mut SpanAttributeVisitor(&mut builder));
-        extensions.insert(builder);
+        // Apply only the span's name and kind before sampling: the sampler
+        // can use them, and children of a dropped span still need the name
+        // for parenting. The remaining attributes are visited only for spans
+        // the sampler keeps, so a dropped span never pays to `Debug`-format
+        // its attribute values.
+        let mut visitor = SpanPreSamplingVisitor {
+            builder: &mut builder,
+        };
+        attrs.record(&mut visitor);
+
+        // Come to a sampling decision eagerly. If the sampler drops the
+        // span, there is no need to keep the builder (and its attribute
+        // vectors) alive until the span closes — retain only the pre-sampled
+        // context, which children and propagation still need.
+        let sampled_context = self.tracer.sampled_context(&mut builder);
+        let sampled_away = matches!(
+            builder.sampling_result,
+            Some(SamplingResult {
+                decision: SamplingDecision::Drop,
+                ..
+            })
+        );
+        let mut invalid_links = 0;
+        if sampled_away {
+            extensions.insert(DroppedSpan(sampled_context));
+        } else {
+            // The eager decision only chooses the storage representation. A
+            // kept span's parent may still change via `set_parent`, so drop
+            // the cached result and let `sampled_context` re-sample against
+            // the final parent when the context is read or the span closes.
+            builder.sampling_result = None;
+
+            let mut visitor = SpanAttributeVisitor::new(&mut builder);
+            attrs.record(&mut visitor);
+            invalid_links = visitor.finish();
+            extensions.insert(builder);
+        }
+
+        // Report discarded links only after releasing the extensions lock, as
+        // emitting an event will re-enter the subscriber.
+        drop(extensions);
+        if invalid_links > 0 {
+            tracing::debug!(
+                invalid_links,
+                "discarded `otel.link` fields that did not form a valid span link"
+            );
+        }
     }
 
     fn on_enter(&self, id: &span::Id, ctx: Context<'_, C>) {
@@ -433,13 +944,14 @@ where
             return;
         }
 
-        let span = ctx.span(id).expect("Span not found, this is a bug");
+        let span = match ctx.span_or_report(id) {
+            Some(span) => span,
+            None => return,
+        };
         let mut extensions = span.extensions_mut();
 
         if let Some(timings) = extensions.get_mut::<Timings>() {
-            let now = Instant::now();
-            timings.idle += (now - timings.last).as_nanos() as i64;
-            timings.last = now;
+            timings.enter();
         }
     }
 
@@ -448,13 +960,14 @@ where
             return;
         }
 
-        let span = ctx.span(id).expect("Span not found, this is a bug");
+        let span = match ctx.span_or_report(id) {
+            Some(span) => span,
+            None => return,
+        };
         let mut extensions = span.extensions_mut();
 
         if let Some(timings) = extensions.get_mut::<Timings>() {
-            let now = Instant::now();
-            timings.busy += (now - timings.last).as_nanos() as i64;
-            timings.last = now;
+            timings.exit();
         }
     }
 
@@ -462,34 +975,75 @@ where
     ///
     /// [`attributes`]: opentelemetry::trace::SpanBuilder::attributes
     fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, C>) {
-        let span = ctx.span(id).expect("Span not found, this is a bug");
+        let span = match ctx.span_or_report(id) {
+            Some(span) => span,
+            None => return,
+        };
         let mut extensions = span.extensions_mut();
+        let mut invalid_links = 0;
+        let mut dropped_attributes = 0;
         if let Some(builder) = extensions.get_mut::<otel::SpanBuilder>() {
-            values.record(&mut SpanAttributeVisitor(builder));
+            let mut visitor = SpanAttributeVisitor::new(builder);
+            values.record(&mut visitor);
+            invalid_links = visitor.finish();
+
+            // Enforce the attribute limit, dropping the most recently recorded
+            // values first.
+            if let (Some(max_attributes), Some(attributes)) =
+                (self.max_attributes_per_span, builder.attributes.as_mut())
+            {
+                if attributes.len() > max_attributes {
+                    dropped_attributes = (attributes.len() - max_attributes) as u64;
+                    attributes.truncate(max_attributes);
+                }
+            }
+        }
+        if dropped_attributes > 0 {
+            if let Some(counts) = extensions.get_mut::<DroppedCounts>() {
+                counts.attributes += dropped_attributes;
+            } else {
+                extensions.insert(DroppedCounts {
+                    attributes: dropped_attributes,
+                    ..Default::default()
+                });
+            }
+        }
+
+        // Report discarded links only after releasing the extensions lock, as
+        // emitting an event will re-enter the subscriber.
+        drop(extensions);
+        if invalid_links > 0 {
+            tracing::debug!(
+                invalid_links,
+                "discarded `otel.link` fields that did not form a valid span link"
+            );
         }
     }
 
     fn on_follows_from(&self, id: &Id, follows: &Id, ctx: Context<C>) {
-        let span = ctx.span(id).expect("Span not found, this is a bug");
+        let follows_span = match ctx.span_or_report(follows) {
+            Some(span) => span,
+            None => return,
+        };
+        // The followed span's context is available whether or not the sampler
+        // dropped it.
+        let follows_context = match Self::sampled_context_for(&self.tracer, &follows_span) {
+            Some(follows_context) => follows_context.span().span_context().clone(),
+            None => return,
+        };
+
+        let span = match ctx.span_or_report(id) {
+            Some(span) => span,
+            None => return,
+        };
         let mut extensions = span.extensions_mut();
-        let builder = extensions
-            .get_mut::<otel::SpanBuilder>()
-            .expect("Missing SpanBuilder span extensions");
-
-        let follows_span = ctx
-            .span(follows)
-            .expect("Span to follow not found, this is a bug");
-        let mut follows_extensions = follows_span.extensions_mut();
-        let follows_builder = follows_extensions
-            .get_mut::<otel::SpanBuilder>()
-            .expect("Missing SpanBuilder span extensions");
-
-        let follows_context = self
-            .tracer
-            .sampled_context(follows_builder)
-            .span()
-            .span_context()
-            .clone();
+        // A span the sampler dropped will never be exported, so there is no
+        // builder to record the link on.
+        let builder = match extensions.get_mut::<otel::SpanBuilder>() {
+            Some(builder) => builder,
+            None => return,
+        };
+
         let follows_link = otel::Link::new(follows_context, Vec::new());
         if let Some(ref mut links) = builder.links {
             links.push(follows_link);
@@ -507,8 +1061,39 @@ where
     /// [`ERROR`]: tracing::Level::ERROR
     /// [`Error`]: opentelemetry::trace::StatusCode::Error
     fn on_event(&self, event: &Event<'_>, ctx: Context<'_, C>) {
-        // Ignore events that are not in the context of a span
-        if let Some(span) = ctx.lookup_current() {
+        // Ignore events that are not in the context of a span. Note that this
+        // respects an explicit `parent:` on the event, which may differ from
+        // the contextually-current span.
+        if let Some(span) = ctx.event_span(event) {
+            // A span the sampler dropped records no events; skip constructing
+            // the OpenTelemetry event (and visiting the fields) entirely.
+            if span.extensions().get::<DroppedSpan>().is_some() {
+                return;
+            }
+
+            // Check the event limit *before* building the OpenTelemetry event,
+            // so that dropped events do not pay the cost of visiting and
+            // formatting their fields.
+            if let Some(max_events) = self.max_events_per_span {
+                let mut extensions = span.extensions_mut();
+                let at_limit = extensions
+                    .get_mut::<otel::SpanBuilder>()
+                    .is_some_and(|builder| {
+                        builder.events.as_ref().map_or(0, Vec::len) >= max_events
+                    });
+                if at_limit {
+                    if let Some(counts) = extensions.get_mut::<DroppedCounts>() {
+                        counts.events += 1;
+                    } else {
+                        extensions.insert(DroppedCounts {
+                            events: 1,
+                            ..Default::default()
+                        });
+                    }
+                    return;
+                }
+            }
+
             // Performing read operations before getting a write lock to avoid a deadlock
             // See https://github.com/tokio-rs/tracing/issues/763
             #[cfg(feature = "tracing-log")]
@@ -519,14 +1104,16 @@ where
             let meta = event.metadata();
             let mut otel_event = otel::Event::new(
                 String::new(),
-                SystemTime::now(),
+                event_timestamp(event).unwrap_or_else(SystemTime::now),
                 vec![
                     Key::new("level").string(meta.level().to_string()),
                     Key::new("target").string(meta.target().to_string()),
                 ],
                 0,
             );
-            event.record(&mut SpanEventVisitor(&mut otel_event));
+            let mut visitor = SpanEventVisitor::new(&mut otel_event);
+            event.record(&mut visitor);
+            let error_msg = visitor.error_msg;
 
             let mut extensions = span.extensions_mut();
             if let Some(builder) = extensions.get_mut::<otel::SpanBuilder>() {
@@ -534,6 +1121,13 @@ where
                     builder.status_code = Some(otel::StatusCode::Error);
                 }
 
+                // An `error` field sets the span status to `Error`, with the
+                // chained error message as the status description.
+                if let Some(error_msg) = error_msg {
+                    builder.status_code = Some(otel::StatusCode::Error);
+                    builder.status_message = Some(error_msg.into());
+                }
+
                 if let Some(ref mut events) = builder.events {
                     events.push(otel_event);
                 } else {
@@ -547,22 +1141,58 @@ where
     ///
     /// [`Span`]: opentelemetry::trace::Span
     fn on_close(&self, id: span::Id, ctx: Context<'_, C>) {
-        let span = ctx.span(&id).expect("Span not found, this is a bug");
+        let span = match ctx.span_or_report(&id) {
+            Some(span) => span,
+            None => return,
+        };
         let mut extensions = span.extensions_mut();
 
+        let timings = if self.tracked_inactivity {
+            extensions
+                .get_mut::<Timings>()
+                .map(|timings| (timings.busy(), timings.idle()))
+        } else {
+            None
+        };
+
+        // The timing handler sees every span, including ones the sampler
+        // dropped; sampling out a span should not bias timing metrics.
+        if let (Some((busy, idle)), Some(handler)) = (timings, self.timing_handler.as_ref()) {
+            handler(span.metadata(), busy, idle);
+        }
+
+        // Spans the sampler dropped have no builder and are simply discarded.
         if let Some(mut builder) = extensions.remove::<otel::SpanBuilder>() {
-            if self.tracked_inactivity {
-                // Append busy/idle timings when enabled.
-                if let Some(timings) = extensions.get_mut::<Timings>() {
-                    let busy_ns = KeyValue::new("busy_ns", timings.busy);
-                    let idle_ns = KeyValue::new("idle_ns", timings.idle);
-
-                    if let Some(ref mut attributes) = builder.attributes {
-                        attributes.push(busy_ns);
-                        attributes.push(idle_ns);
-                    } else {
-                        builder.attributes = Some(vec![busy_ns, idle_ns]);
-                    }
+            // Append busy/idle timings when enabled.
+            if let Some((busy, idle)) = timings {
+                let busy_ns = KeyValue::new("busy_ns", busy.as_nanos() as i64);
+                let idle_ns = KeyValue::new("idle_ns", idle.as_nanos() as i64);
+
+                if let Some(ref mut attributes) = builder.attributes {
+                    attributes.push(busy_ns);
+                    attributes.push(idle_ns);
+                } else {
+                    builder.attributes = Some(vec![busy_ns, idle_ns]);
+                }
+            }
+
+            // Make any truncation performed by the configured limits visible
+            // in the exported span.
+            if let Some(counts) = extensions.remove::<DroppedCounts>() {
+                let mut dropped = Vec::with_capacity(2);
+                if counts.events > 0 {
+                    dropped.push(KeyValue::new("events_dropped", counts.events as i64));
+                }
+                if counts.attributes > 0 {
+                    dropped.push(KeyValue::new(
+                        "attributes_dropped",
+                        counts.attributes as i64,
+                    ));
+                }
+                if let Some(ref mut attributes) = builder.attributes {
+                    attributes.extend(dropped);
+                } else {
+                    builder.attributes = Some(dropped);
                 }
             }
 
@@ -584,22 +1214,6 @@ where
     }
 }
 
-struct Timings {
-    idle: i64,
-    busy: i64,
-    last: Instant,
-}
-
-impl Timings {
-    fn new() -> Self {
-        Self {
-            idle: 0,
-            busy: 0,
-            last: Instant::now(),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -728,6 +1342,190 @@ mod tests {
         assert_eq!(recorded_status_message, Some(message.into()))
     }
 
+    #[test]
+    fn numeric_fields_are_recorded_as_native_value_types() {
+        let tracer = TestTracer(Arc::new(Mutex::new(None)));
+        let subscriber =
+            tracing_subscriber::registry().with(subscriber().with_tracer(tracer.clone()));
+
+        tracing::collect::with_default(subscriber, || {
+            tracing::debug_span!(
+                "request",
+                ratio = 0.5_f64,
+                count = 42_u64,
+                oversized = u64::MAX,
+                offset = -7_i64,
+                enabled = true,
+                name = "alice",
+            );
+        });
+
+        let builder = tracer.0.lock().unwrap().take().unwrap();
+        let attributes = builder.attributes.as_ref().unwrap();
+        let value = |name: &str| {
+            attributes
+                .iter()
+                .find(|kv| kv.key.as_str() == name)
+                .map(|kv| kv.value.clone())
+                .unwrap_or_else(|| panic!("span should have a {:?} attribute", name))
+        };
+
+        assert_eq!(value("ratio"), opentelemetry::Value::F64(0.5));
+        assert_eq!(value("count"), opentelemetry::Value::I64(42));
+        // `u64` values too large for an `i64` saturate rather than degrading
+        // to strings.
+        assert_eq!(value("oversized"), opentelemetry::Value::I64(i64::MAX));
+        assert_eq!(value("offset"), opentelemetry::Value::I64(-7));
+        assert_eq!(value("enabled"), opentelemetry::Value::Bool(true));
+        assert_eq!(value("name"), opentelemetry::Value::String("alice".into()));
+    }
+
+    #[test]
+    fn explicit_event_timestamp() {
+        let tracer = TestTracer(Arc::new(Mutex::new(None)));
+        let subscriber =
+            tracing_subscriber::registry().with(subscriber().with_tracer(tracer.clone()));
+
+        let timestamp = SystemTime::UNIX_EPOCH + Duration::from_secs(1614223200);
+        tracing::collect::with_default(subscriber, || {
+            tracing::debug_span!("request").in_scope(|| {
+                tracing::debug!(event.timestamp = 1_614_223_200_000_000_000_u64, "replayed");
+            });
+        });
+
+        let events = tracer
+            .0
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .events
+            .clone()
+            .unwrap();
+        assert_eq!(events[0].timestamp, timestamp);
+        // The timestamp field should be consumed, not recorded as an attribute.
+        assert!(events[0]
+            .attributes
+            .iter()
+            .all(|kv| kv.key.as_str() != EVENT_TIMESTAMP_FIELD));
+    }
+
+    #[test]
+    fn event_with_explicit_parent_attaches_to_that_span() {
+        let tracer = TestTracer(Arc::new(Mutex::new(None)));
+        let subscriber =
+            tracing_subscriber::registry().with(subscriber().with_tracer(tracer.clone()));
+
+        tracing::collect::with_default(subscriber, || {
+            let target = tracing::debug_span!("target");
+            tracing::debug_span!("current").in_scope(|| {
+                // The event names `target` as its parent, so it must not be
+                // attached to the contextually-current `current` span.
+                tracing::debug!(parent: &target, "explicit parent");
+            });
+        });
+
+        let builder = tracer.0.lock().unwrap().as_ref().unwrap().clone();
+        assert_eq!(builder.name, "target");
+        assert_eq!(builder.events.unwrap().len(), 1);
+    }
+
+    #[derive(Debug)]
+    struct TestError {
+        message: &'static str,
+        source: Option<Box<TestError>>,
+    }
+
+    impl fmt::Display for TestError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.message)
+        }
+    }
+
+    impl std::error::Error for TestError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            self.source
+                .as_ref()
+                .map(|source| source as &(dyn std::error::Error + 'static))
+        }
+    }
+
+    #[test]
+    fn error_events_use_exception_semantic_conventions() {
+        let tracer = TestTracer(Arc::new(Mutex::new(None)));
+        let subscriber =
+            tracing_subscriber::registry().with(subscriber().with_tracer(tracer.clone()));
+
+        let error = TestError {
+            message: "request failed",
+            source: Some(Box::new(TestError {
+                message: "connection reset",
+                source: None,
+            })),
+        };
+
+        tracing::collect::with_default(subscriber, || {
+            let _guard = tracing::debug_span!("request").entered();
+            tracing::error!(error = &error as &(dyn std::error::Error + 'static));
+        });
+
+        let builder = tracer.0.lock().unwrap().take().unwrap();
+
+        let event = builder
+            .events
+            .as_ref()
+            .and_then(|events| events.first())
+            .expect("builder should have an event");
+        assert_eq!(event.name, EXCEPTION_EVENT_NAME.to_string());
+        let message = event
+            .attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == EXCEPTION_MESSAGE_KEY)
+            .map(|kv| kv.value.as_str().into_owned())
+            .expect("event should have an exception.message attribute");
+        assert_eq!(message, "request failed: connection reset");
+
+        assert_eq!(builder.status_code, Some(otel::StatusCode::Error));
+        assert_eq!(
+            builder.status_message,
+            Some("request failed: connection reset".into())
+        );
+    }
+
+    #[test]
+    fn error_span_fields_use_exception_semantic_conventions() {
+        let tracer = TestTracer(Arc::new(Mutex::new(None)));
+        let subscriber =
+            tracing_subscriber::registry().with(subscriber().with_tracer(tracer.clone()));
+
+        let error = TestError {
+            message: "request failed",
+            source: None,
+        };
+
+        tracing::collect::with_default(subscriber, || {
+            tracing::debug_span!(
+                "request",
+                error = &error as &(dyn std::error::Error + 'static)
+            );
+        });
+
+        let builder = tracer.0.lock().unwrap().take().unwrap();
+
+        let message = builder
+            .attributes
+            .as_ref()
+            .unwrap()
+            .iter()
+            .find(|kv| kv.key.as_str() == EXCEPTION_MESSAGE_KEY)
+            .map(|kv| kv.value.as_str().into_owned())
+            .expect("span should have an exception.message attribute");
+        assert_eq!(message, "request failed");
+
+        assert_eq!(builder.status_code, Some(otel::StatusCode::Error));
+        assert_eq!(builder.status_message, Some("request failed".into()));
+    }
+
     #[test]
     fn trace_id_from_existing_context() {
         let tracer = TestTracer(Arc::new(Mutex::new(None)));
@@ -760,6 +1558,183 @@ mod tests {
         assert_eq!(recorded_trace_id, trace_id)
     }
 
+    #[test]
+    fn links_from_fields() {
+        let tracer = TestTracer(Arc::new(Mutex::new(None)));
+        let subscriber =
+            tracing_subscriber::registry().with(subscriber().with_tracer(tracer.clone()));
+
+        tracing::collect::with_default(subscriber, || {
+            tracing::debug_span!(
+                "process",
+                otel.link.trace_id = "0000000000000000000000000000002a",
+                otel.link.span_id = "0000000000000001",
+            );
+        });
+
+        let links = tracer
+            .0
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .links
+            .clone()
+            .expect("span should have links");
+        assert_eq!(links.len(), 1);
+        let link_context = links[0].span_context();
+        assert_eq!(link_context.trace_id(), otel::TraceId::from_u128(42));
+        assert_eq!(link_context.span_id(), otel::SpanId::from_u64(1));
+        assert!(link_context.is_remote());
+    }
+
+    #[test]
+    fn invalid_link_ids_are_dropped() {
+        let tracer = TestTracer(Arc::new(Mutex::new(None)));
+        let subscriber =
+            tracing_subscriber::registry().with(subscriber().with_tracer(tracer.clone()));
+
+        tracing::collect::with_default(subscriber, || {
+            tracing::debug_span!(
+                "process",
+                otel.link.trace_id = "not hex",
+                otel.link.span_id = "0000000000000001",
+            );
+        });
+
+        let links = tracer.0.lock().unwrap().as_ref().unwrap().links.clone();
+        assert!(links.is_none());
+    }
+
+    #[test]
+    fn links_from_span_extension() {
+        use crate::OpenTelemetrySpanExt;
+
+        let tracer = TestTracer(Arc::new(Mutex::new(None)));
+        let subscriber =
+            tracing_subscriber::registry().with(subscriber().with_tracer(tracer.clone()));
+
+        let link_context = otel::SpanContext::new(
+            otel::TraceId::from_u128(42),
+            otel::SpanId::from_u64(1),
+            TraceFlags::default(),
+            true,
+            Default::default(),
+        );
+
+        tracing::collect::with_default(subscriber, || {
+            let span = tracing::debug_span!("process");
+            span.add_link(link_context.clone());
+        });
+
+        let links = tracer
+            .0
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .links
+            .clone()
+            .expect("span should have links");
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].span_context(), &link_context);
+    }
+
+    #[test]
+    fn set_attribute_from_span_extension() {
+        use crate::OpenTelemetrySpanExt;
+
+        let tracer = TestTracer(Arc::new(Mutex::new(None)));
+        let subscriber =
+            tracing_subscriber::registry().with(subscriber().with_tracer(tracer.clone()));
+
+        tracing::collect::with_default(subscriber, || {
+            let span = tracing::debug_span!("request");
+            span.set_attribute("http.status_code", 503_i64);
+        });
+
+        let attribute = tracer
+            .0
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .attributes
+            .as_ref()
+            .unwrap()
+            .iter()
+            .find(|kv| kv.key.as_str() == "http.status_code")
+            .cloned()
+            .expect("span should have an http.status_code attribute");
+        assert_eq!(attribute.value, opentelemetry::Value::I64(503));
+    }
+
+    #[test]
+    fn set_status_from_span_extension() {
+        use crate::OpenTelemetrySpanExt;
+
+        let tracer = TestTracer(Arc::new(Mutex::new(None)));
+        let subscriber =
+            tracing_subscriber::registry().with(subscriber().with_tracer(tracer.clone()));
+
+        tracing::collect::with_default(subscriber, || {
+            let span = tracing::debug_span!("request");
+            span.set_status(otel::StatusCode::Error, "service unavailable".to_string());
+        });
+
+        let builder = tracer.0.lock().unwrap().take().unwrap();
+        assert_eq!(builder.status_code, Some(otel::StatusCode::Error));
+        assert_eq!(builder.status_message, Some("service unavailable".into()));
+    }
+
+    #[test]
+    fn span_extension_methods_are_noops_without_subscriber() {
+        use crate::OpenTelemetrySpanExt;
+
+        // A disabled span, or one whose collector has no
+        // `OpenTelemetrySubscriber`, must silently ignore these calls.
+        let span = tracing::Span::none();
+        span.set_attribute("http.status_code", 503_i64);
+        span.set_status(otel::StatusCode::Error, "service unavailable".to_string());
+    }
+
+    #[test]
+    fn dropped_spans_keep_context_for_propagation() {
+        use crate::OpenTelemetrySpanExt;
+        use opentelemetry::sdk::trace::{config, Sampler, TracerProvider};
+        use opentelemetry::trace::TracerProvider as _;
+
+        let provider = TracerProvider::builder()
+            .with_config(config().with_sampler(Sampler::AlwaysOff))
+            .build();
+        let tracer = provider.get_tracer("test", None);
+        let subscriber = tracing_subscriber::registry().with(subscriber().with_tracer(tracer));
+
+        tracing::collect::with_default(subscriber, || {
+            let root = tracing::debug_span!("root");
+
+            // Although the sampler dropped the span, its context must remain
+            // available for propagation to downstream requests...
+            let root_cx = root.context();
+            let root_context = root_cx.span().span_context().clone();
+            assert!(root_context.is_valid());
+            assert!(!root_context.is_sampled());
+
+            // ...and child spans must still be parented into the same trace.
+            let child_trace_id = root.in_scope(|| {
+                tracing::debug_span!("child")
+                    .context()
+                    .span()
+                    .span_context()
+                    .trace_id()
+            });
+            assert_eq!(child_trace_id, root_context.trace_id());
+
+            // Late attributes on a dropped span are silently discarded.
+            root.set_attribute("http.status_code", 503_i64);
+        });
+    }
+
     #[test]
     fn includes_timings() {
         let tracer = TestTracer(Arc::new(Mutex::new(None)));
@@ -789,5 +1764,133 @@ mod tests {
             .collect::<Vec<&str>>();
         assert!(keys.contains(&"idle_ns"));
         assert!(keys.contains(&"busy_ns"));
+
+        // Timings must be recorded as numeric values, not strings, so that
+        // backends can build histograms from them.
+        for attr in attributes {
+            let key = attr.key.as_str();
+            if key == "idle_ns" || key == "busy_ns" {
+                assert!(
+                    matches!(attr.value, opentelemetry::Value::I64(_)),
+                    "expected {} to be an i64, but it was {:?}",
+                    key,
+                    attr.value,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn timing_handler_called_on_close() {
+        let timings = Arc::new(Mutex::new(None));
+        let tracer = TestTracer(Arc::new(Mutex::new(None)));
+        let handler_timings = timings.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            subscriber()
+                .with_tracer(tracer.clone())
+                .with_tracked_inactivity(true)
+                .with_timing_handler(move |metadata, busy, idle| {
+                    *handler_timings.lock().unwrap() = Some((metadata.name(), busy, idle));
+                }),
+        );
+
+        tracing::collect::with_default(subscriber, || {
+            tracing::debug_span!("request").in_scope(|| {});
+        });
+
+        let (name, _busy, _idle) = timings
+            .lock()
+            .unwrap()
+            .take()
+            .expect("timing handler should have been called");
+        assert_eq!(name, "request");
+    }
+
+    #[test]
+    fn timing_handler_not_called_when_inactivity_untracked() {
+        let timings: Arc<Mutex<Option<(&str, Duration, Duration)>>> = Arc::new(Mutex::new(None));
+        let tracer = TestTracer(Arc::new(Mutex::new(None)));
+        let handler_timings = timings.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            subscriber()
+                .with_tracer(tracer.clone())
+                .with_tracked_inactivity(false)
+                .with_timing_handler(move |metadata, busy, idle| {
+                    *handler_timings.lock().unwrap() = Some((metadata.name(), busy, idle));
+                }),
+        );
+
+        tracing::collect::with_default(subscriber, || {
+            tracing::debug_span!("request").in_scope(|| {});
+        });
+
+        assert!(timings.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn event_limit_drops_newest_and_reports_count() {
+        let tracer = TestTracer(Arc::new(Mutex::new(None)));
+        let subscriber = tracing_subscriber::registry().with(
+            subscriber()
+                .with_tracer(tracer.clone())
+                .with_max_events_per_span(2),
+        );
+
+        tracing::collect::with_default(subscriber, || {
+            tracing::debug_span!("request").in_scope(|| {
+                for i in 0..5 {
+                    tracing::debug!(i, "event");
+                }
+            });
+        });
+
+        let builder = tracer.0.lock().unwrap().take().expect("span was exported");
+        let events = builder.events.expect("events were recorded");
+        assert_eq!(events.len(), 2, "only the first two events are kept");
+        let attributes = builder.attributes.expect("attributes were recorded");
+        let dropped = attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == "events_dropped")
+            .expect("the drop counter is reported on close");
+        assert_eq!(dropped.value, opentelemetry::Value::I64(3));
+    }
+
+    #[test]
+    fn attribute_limit_truncates_recorded_values() {
+        let tracer = TestTracer(Arc::new(Mutex::new(None)));
+        let subscriber = tracing_subscriber::registry().with(
+            subscriber()
+                .with_tracer(tracer.clone())
+                .with_tracked_inactivity(false)
+                // The limit also counts the `code.*` attributes recorded when
+                // the span is created, so leave room for those three.
+                .with_max_attributes_per_span(5),
+        );
+
+        tracing::collect::with_default(subscriber, || {
+            let span = tracing::debug_span!(
+                "request",
+                a = 1,
+                b = tracing::field::Empty,
+                c = tracing::field::Empty
+            );
+            span.record("b", &2);
+            span.record("c", &3);
+        });
+
+        let builder = tracer.0.lock().unwrap().take().expect("span was exported");
+        let attributes = builder.attributes.expect("attributes were recorded");
+        let keys = attributes
+            .iter()
+            .map(|kv| kv.key.as_str())
+            .collect::<Vec<_>>();
+        assert!(keys.contains(&"a"));
+        assert!(keys.contains(&"b"));
+        assert!(!keys.contains(&"c"), "the newest attribute is dropped");
+        let dropped = attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == "attributes_dropped")
+            .expect("the drop counter is reported on close");
+        assert_eq!(dropped.value, opentelemetry::Value::I64(1));
     }
 }
@@ -72,7 +72,7 @@ impl PreSampledTracer for Tracer {
         let parent_cx = &builder.parent_context;
 
         // Gather trace state
-        let (no_parent, trace_id, remote_parent, parent_trace_flags) =
+        let (no_parent, trace_id, remote_parent, parent_trace_flags, parent_trace_state) =
             current_trace_state(&builder, &parent_cx, &provider);
 
         // Sample or defer to existing sampling decisions
@@ -99,7 +99,9 @@ impl PreSampledTracer for Tracer {
                 parent_cx.span().span_context().trace_state().clone(),
             ))
         }
-        .unwrap_or_default();
+        // A dropped span still carries its parent's `tracestate`, so that it
+        // survives injection into downstream requests.
+        .unwrap_or((Default::default(), parent_trace_state));
 
         let span_id = builder.span_id.unwrap_or_else(SpanId::invalid);
         let span_context = SpanContext::new(trace_id, span_id, flags, false, trace_state);
@@ -123,11 +125,17 @@ fn current_trace_state(
     builder: &SpanBuilder,
     parent_cx: &OtelContext,
     provider: &TracerProvider,
-) -> (bool, TraceId, bool, TraceFlags) {
+) -> (bool, TraceId, bool, TraceFlags, TraceState) {
     if parent_cx.has_active_span() {
         let span = parent_cx.span();
         let sc = span.span_context();
-        (false, sc.trace_id(), sc.is_remote(), sc.trace_flags())
+        (
+            false,
+            sc.trace_id(),
+            sc.is_remote(),
+            sc.trace_flags(),
+            sc.trace_state().clone(),
+        )
     } else {
         (
             true,
@@ -136,6 +144,7 @@ fn current_trace_state(
                 .unwrap_or_else(|| provider.config().id_generator.new_trace_id()),
             false,
             Default::default(),
+            Default::default(),
         )
     }
 }
@@ -0,0 +1,102 @@
+use std::{collections::HashMap, time::Duration};
+
+use opentelemetry::{
+    metrics::MeterProvider as _,
+    sdk::{
+        export::metrics::{CheckpointSet, ExportKindSelector, Sum as _},
+        metrics::{aggregators::SumAggregator, controllers, selectors::simple::Selector},
+    },
+};
+use tracing_opentelemetry::MetricsSubscriber;
+use tracing_subscriber::{subscribe::CollectExt, Registry};
+
+fn collect_sums(controller: &mut controllers::PullController) -> HashMap<String, f64> {
+    controller.collect().expect("collection should succeed");
+    let mut sums = HashMap::new();
+    controller
+        .try_for_each(&ExportKindSelector::Cumulative, &mut |record| {
+            if let Some(aggregator) = record.aggregator() {
+                if let Some(sum) = aggregator.as_any().downcast_ref::<SumAggregator>() {
+                    let number = sum.sum()?;
+                    sums.insert(
+                        record.descriptor().name().to_string(),
+                        number.to_f64(record.descriptor().number_kind()),
+                    );
+                }
+            }
+            Ok(())
+        })
+        .expect("checkpoint iteration should succeed");
+    sums
+}
+
+#[test]
+fn events_with_metric_fields_update_instruments() {
+    let mut controller = controllers::pull(
+        Box::new(Selector::Inexpensive),
+        Box::new(ExportKindSelector::Cumulative),
+    )
+    .with_cache_period(Duration::from_secs(0))
+    .with_memory(true)
+    .build();
+    let meter = controller.provider().meter("metrics_test", None);
+
+    let collector = Registry::default().with(MetricsSubscriber::new(meter));
+
+    tracing::collect::with_default(collector, || {
+        // The same instrument must be reused across repeated events.
+        tracing::info!(monotonic_counter.requests = 1u64);
+        tracing::info!(monotonic_counter.requests = 2u64);
+        tracing::info!(monotonic_counter.requests = 3u64);
+
+        // Up-down counters accept signed values...
+        tracing::info!(counter.in_flight = 5i64);
+        tracing::info!(counter.in_flight = -2i64);
+
+        // ...and floating-point values go to floating-point instruments.
+        tracing::info!(monotonic_counter.bytes_out = 0.5f64);
+
+        // Non-numeric and unprefixed fields must not create instruments.
+        tracing::info!(monotonic_counter.broken = "not a number", "oops");
+        tracing::info!(unrelated = 42u64, "an ordinary event");
+    });
+
+    let sums = collect_sums(&mut controller);
+    assert_eq!(sums.get("requests"), Some(&6.0));
+    assert_eq!(sums.get("in_flight"), Some(&3.0));
+    assert_eq!(sums.get("bytes_out"), Some(&0.5));
+    assert!(!sums.contains_key("broken"));
+    assert!(!sums.contains_key("unrelated"));
+    assert!(!sums.keys().any(|name| name.contains('.')));
+}
+
+#[test]
+fn histogram_fields_record_value_distribution() {
+    let mut controller = controllers::pull(
+        Box::new(Selector::Inexpensive),
+        Box::new(ExportKindSelector::Cumulative),
+    )
+    .with_cache_period(Duration::from_secs(0))
+    .with_memory(true)
+    .build();
+    let meter = controller.provider().meter("metrics_test", None);
+
+    let collector = Registry::default().with(MetricsSubscriber::new(meter));
+
+    tracing::collect::with_default(collector, || {
+        tracing::info!(histogram.request_size = 100u64);
+        tracing::info!(histogram.request_size = 300u64);
+    });
+
+    controller.collect().expect("collection should succeed");
+    let mut seen = Vec::new();
+    controller
+        .try_for_each(&ExportKindSelector::Cumulative, &mut |record| {
+            seen.push(record.descriptor().name().to_string());
+            Ok(())
+        })
+        .expect("checkpoint iteration should succeed");
+    // Both recordings land on a single cached instrument, so the checkpoint
+    // contains exactly one record for the metric.
+    assert_eq!(seen, vec!["request_size".to_string()]);
+}
@@ -83,6 +83,60 @@ fn inject_context_into_outgoing_requests() {
     assert_carrier_attrs_eq(&carrier, &outgoing_req_carrier);
 }
 
+#[test]
+fn inject_context_with_unsampled_parent() {
+    let (_tracer, _provider, _exporter, subscriber) = test_tracer();
+    let propagator = test_propagator();
+    let mut carrier = test_carrier();
+    // An unsampled remote parent; the default parent-based sampler will drop
+    // the child spans, but the `tracestate` must still be propagated.
+    carrier.insert(
+        "traceparent".to_string(),
+        "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-00".to_string(),
+    );
+    let cx = propagator.extract(&carrier);
+    let mut outgoing_req_carrier = HashMap::new();
+
+    tracing::collect::with_default(subscriber, || {
+        let root = tracing::debug_span!("root");
+        root.set_parent(cx);
+        let _g = root.enter();
+        let child = tracing::debug_span!("child");
+        propagator.inject_context(&child.context(), &mut outgoing_req_carrier);
+    });
+
+    assert_carrier_attrs_eq(&carrier, &outgoing_req_carrier);
+}
+
+#[test]
+fn propagate_context_between_collectors() {
+    // Two separate registries with separate tracers, simulating a client and
+    // a server process communicating only through the carrier.
+    let (_, client_provider, client_exporter, client_subscriber) = test_tracer();
+    let (_, server_provider, server_exporter, server_subscriber) = test_tracer();
+    let propagator = test_propagator();
+    let mut carrier = HashMap::new();
+
+    tracing::collect::with_default(client_subscriber, || {
+        let request = tracing::debug_span!("client_request");
+        let _g = request.enter();
+        tracing_opentelemetry::inject_current(&propagator, &mut carrier);
+    });
+
+    tracing::collect::with_default(server_subscriber, || {
+        let request = tracing::debug_span!("server_request");
+        tracing_opentelemetry::extract_to_span(&propagator, &carrier, &request);
+    });
+
+    drop(client_provider); // flush all spans
+    drop(server_provider);
+    let client_spans = client_exporter.0.lock().unwrap();
+    let server_spans = server_exporter.0.lock().unwrap();
+    assert_eq!(client_spans.len(), 1);
+    assert_eq!(server_spans.len(), 1);
+    assert_shared_attrs_eq(&client_spans[0].span_context, &server_spans[0].span_context);
+}
+
 fn assert_shared_attrs_eq(sc_a: &SpanContext, sc_b: &SpanContext) {
     assert_eq!(sc_a.trace_id(), sc_b.trace_id());
     assert_eq!(sc_a.trace_state(), sc_b.trace_state());
@@ -0,0 +1,122 @@
+#![cfg(feature = "std")]
+use serde_json::json;
+use tracing_core::{
+    callsite::Callsite,
+    field::{self, Value},
+    metadata,
+    metadata::{Kind, Level, Metadata},
+    Event, Interest,
+};
+use tracing_serde::{fields::AsMap, AsSerde};
+
+struct TestCallsite;
+impl Callsite for TestCallsite {
+    fn set_interest(&self, _: Interest) {}
+    fn metadata(&self) -> &Metadata<'_> {
+        &META
+    }
+}
+
+static CS: TestCallsite = TestCallsite;
+static META: Metadata<'static> = metadata! {
+    name: "fields",
+    target: "tracing_serde_test",
+    level: Level::INFO,
+    fields: &["message", "answer", "signed", "pi", "enabled", "error", "nested"],
+    callsite: &CS,
+    kind: Kind::EVENT,
+};
+
+/// Serializes an event's field map with every value kind a `Visit` can
+/// receive, and checks each is rendered as the matching JSON type.
+#[test]
+fn field_map_serializes_all_value_kinds() {
+    let fields = META.fields();
+    let message = fields.field("message").unwrap();
+    let answer = fields.field("answer").unwrap();
+    let signed = fields.field("signed").unwrap();
+    let pi = fields.field("pi").unwrap();
+    let enabled = fields.field("enabled").unwrap();
+    let error = fields.field("error").unwrap();
+
+    let io_error = std::io::Error::new(std::io::ErrorKind::Other, "the disk caught fire");
+    let dyn_error: &(dyn std::error::Error + 'static) = &io_error;
+
+    // The value set borrows its values, so the whole event must be built and
+    // serialized within a single statement, exactly as the macros do.
+    let map = serde_json::to_value(
+        Event::new(
+            &META,
+            &fields.value_set(&[
+                (
+                    &message,
+                    Some(&format_args!("a \"quoted\" message") as &dyn Value),
+                ),
+                (&answer, Some(&42u64 as &dyn Value)),
+                (&signed, Some(&-42i64 as &dyn Value)),
+                (&pi, Some(&3.5f64 as &dyn Value)),
+                (&enabled, Some(&true as &dyn Value)),
+                (&error, Some(&dyn_error as &dyn Value)),
+            ]),
+        )
+        .field_map(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        map,
+        json!({
+            "message": "a \"quoted\" message",
+            "answer": 42,
+            "signed": -42,
+            "pi": 3.5,
+            "enabled": true,
+            "error": "the disk caught fire",
+        })
+    );
+    // `u64`, `i64`, `f64`, and `bool` must come through as JSON numbers and
+    // booleans, not stringified Debug output.
+    assert!(map["answer"].is_u64());
+    assert!(map["signed"].is_i64());
+    assert!(map["pi"].is_f64());
+    assert!(map["enabled"].is_boolean());
+}
+
+/// Debug-recorded values are formatted with `{:?}`; any quotes and
+/// backslashes in the Debug output must survive JSON string escaping.
+#[test]
+fn debug_output_is_escaped() {
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Nested {
+        path: &'static str,
+    }
+
+    let nested_value = Nested {
+        path: "C:\\logs\\\"today\"",
+    };
+    let expected = format!("{:?}", nested_value);
+
+    let fields = META.fields();
+    let nested = fields.field("nested").unwrap();
+
+    let json = serde_json::to_string(
+        &Event::new(
+            &META,
+            &fields.value_set(&[(&nested, Some(&field::debug(&nested_value) as &dyn Value))]),
+        )
+        .field_map(),
+    )
+    .unwrap();
+
+    let map: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(map["nested"], serde_json::Value::String(expected));
+}
+
+#[test]
+fn metadata_serializes_name_target_and_level() {
+    let metadata = serde_json::to_value(META.as_serde()).unwrap();
+    assert_eq!(metadata["name"], "fields");
+    assert_eq!(metadata["target"], "tracing_serde_test");
+    assert_eq!(metadata["level"], json!("INFO"));
+}
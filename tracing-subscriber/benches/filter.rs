@@ -493,5 +493,47 @@ fn bench_mixed(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_static, bench_dynamic, bench_mixed);
+fn bench_cached(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cached");
+    group.bench_function("no_directives", |b| {
+        let filter = EnvFilter::default();
+        tracing::collect::with_default(EnabledSubscriber.with(filter), || {
+            b.iter(|| {
+                tracing::info!(target: "cached_filter", "hi");
+            })
+        });
+    });
+    group.bench_function("static_10", |b| {
+        let filter = "crate0=info,crate1=debug,crate2=trace,crate3=warn,crate4=error,\
+                      crate5=info,crate6=debug,crate7=trace,crate8=warn,cached_filter=info"
+            .parse::<EnvFilter>()
+            .expect("should parse");
+        tracing::collect::with_default(EnabledSubscriber.with(filter), || {
+            b.iter(|| {
+                tracing::info!(target: "cached_filter", "hi");
+            })
+        });
+    });
+    group.bench_function("dynamic_5", |b| {
+        let filter = "[span0]=info,[span1]=debug,[span2]=trace,[span3]=warn,[span4]=error"
+            .parse::<EnvFilter>()
+            .expect("should parse");
+        tracing::collect::with_default(EnabledSubscriber.with(filter), || {
+            let span = tracing::info_span!("span0");
+            let _enter = span.enter();
+            b.iter(|| {
+                tracing::info!(target: "cached_filter", "hi");
+            })
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_static,
+    bench_dynamic,
+    bench_mixed,
+    bench_cached
+);
 criterion_main!(benches);
@@ -0,0 +1,101 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use tracing::{Collect, Event, Metadata};
+use tracing_core::Interest;
+use tracing_subscriber::{
+    prelude::*,
+    registry::Registry,
+    subscribe::{Context, Subscribe},
+    EnvFilter,
+};
+
+/// A subscriber that observes events but imposes no filtering of its own.
+struct NopSubscriber;
+
+impl<C: Collect> Subscribe<C> for NopSubscriber {
+    fn on_event(&self, event: &Event<'_>, _: Context<'_, C>) {
+        let _ = event;
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, _: Context<'_, C>) -> bool {
+        let _ = metadata;
+        true
+    }
+}
+
+/// A subscriber whose interest is always `sometimes`, so that — like a
+/// dynamic filter — it must be asked to `enabled` every event.
+struct SometimesSubscriber;
+
+impl<C: Collect> Subscribe<C> for SometimesSubscriber {
+    fn register_callsite(&self, _: &'static Metadata<'static>) -> Interest {
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, _: Context<'_, C>) -> bool {
+        let _ = metadata;
+        true
+    }
+}
+
+fn bench_stack(c: &mut Criterion) {
+    let mut group = c.benchmark_group("layered");
+
+    // Five pass-through subscribers with no filter: every event is
+    // dispatched through the whole stack.
+    group.bench_function("5_layers/enabled", |b| {
+        let collector = Registry::default()
+            .with(NopSubscriber)
+            .with(NopSubscriber)
+            .with(NopSubscriber)
+            .with(NopSubscriber)
+            .with(NopSubscriber);
+        tracing::collect::with_default(collector, || {
+            b.iter(|| {
+                tracing::info!(target: "layered_bench", "hi");
+            })
+        });
+    });
+
+    // The same stack behind a filter that disables everything: the
+    // composed interest is `never`, so events should cost close to
+    // nothing — the composed decision is cached per callsite rather than
+    // re-walking the five layers.
+    group.bench_function("5_layers/all_disabled", |b| {
+        let collector = Registry::default()
+            .with(NopSubscriber)
+            .with(NopSubscriber)
+            .with(NopSubscriber)
+            .with(NopSubscriber)
+            .with(NopSubscriber)
+            .with(EnvFilter::new("off"));
+        tracing::collect::with_default(collector, || {
+            b.iter(|| {
+                tracing::info!(target: "layered_bench", "hi");
+            })
+        });
+    });
+
+    // The same stack behind a filter whose interest is `sometimes`: the
+    // composed decision cannot be cached, so every event must still walk
+    // the stack's `enabled` chain. This is the common `EnvFilter`-with-
+    // dynamic-directives shape, and it must not pay for the cache it
+    // cannot use.
+    group.bench_function("5_layers/sometimes", |b| {
+        let collector = Registry::default()
+            .with(NopSubscriber)
+            .with(NopSubscriber)
+            .with(NopSubscriber)
+            .with(NopSubscriber)
+            .with(SometimesSubscriber);
+        tracing::collect::with_default(collector, || {
+            b.iter(|| {
+                tracing::info!(target: "layered_bench", "hi");
+            })
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_stack);
+criterion_main!(benches);
@@ -0,0 +1,490 @@
+//! Filtering directives shared by the [`EnvFilter`] and [`Targets`]
+//! filters.
+//!
+//! [`EnvFilter`]: crate::filter::EnvFilter
+//! [`Targets`]: crate::filter::Targets
+use crate::filter::level::{self, LevelFilter};
+use std::{cmp::Ordering, error::Error, fmt, iter::FromIterator, str::FromStr};
+use tracing_core::Metadata;
+
+#[cfg(feature = "smallvec")]
+pub(crate) type FilterVec<T> = smallvec::SmallVec<[T; 8]>;
+#[cfg(not(feature = "smallvec"))]
+pub(crate) type FilterVec<T> = Vec<T>;
+
+/// A directive which will statically enable or disable a given callsite.
+///
+/// Unlike a dynamic directive, this can be cached by the callsite.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct StaticDirective {
+    pub(in crate::filter) target: Option<String>,
+    pub(in crate::filter) field_names: FilterVec<String>,
+    pub(in crate::filter) level: LevelFilter,
+}
+
+pub(crate) trait Match {
+    fn cares_about(&self, meta: &Metadata<'_>) -> bool;
+    fn level(&self) -> &LevelFilter;
+}
+
+/// A set of static filtering directives.
+pub(crate) type Statics = DirectiveSet<StaticDirective>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DirectiveSet<T> {
+    pub(in crate::filter) directives: Vec<T>,
+    pub(crate) max_level: LevelFilter,
+}
+
+/// Indicates that a string could not be parsed as a filtering directive.
+#[derive(Debug)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    offset: Option<usize>,
+}
+
+#[derive(Debug)]
+enum ParseErrorKind {
+    #[cfg(feature = "env-filter")]
+    Field(Box<dyn Error + Send + Sync>),
+    Level(level::ParseError),
+    Other,
+    /// Multiple directives in a filter string failed to parse.
+    #[cfg(feature = "env-filter")]
+    Multiple(Vec<ParseError>),
+}
+
+// === impl DirectiveSet ===
+
+impl<T> DirectiveSet<T> {
+    #[cfg(feature = "env-filter")]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.directives.is_empty()
+    }
+
+    pub(crate) fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.directives.iter()
+    }
+
+    pub(crate) fn into_vec(self) -> Vec<T> {
+        self.directives
+    }
+}
+
+impl<T: Ord> Default for DirectiveSet<T> {
+    fn default() -> Self {
+        Self {
+            directives: Vec::new(),
+            max_level: LevelFilter::OFF,
+        }
+    }
+}
+
+impl<T: Match + Ord> DirectiveSet<T> {
+    pub(in crate::filter) fn directives_for<'a>(
+        &'a self,
+        metadata: &'a Metadata<'a>,
+    ) -> impl Iterator<Item = &'a T> + 'a {
+        self.directives
+            .iter()
+            .filter(move |d| d.cares_about(metadata))
+    }
+
+    /// Removes all directives for which `matches` returns `true`, returning
+    /// the number of directives that were removed.
+    #[cfg(feature = "env-filter")]
+    pub(crate) fn remove_matching(&mut self, matches: impl Fn(&T) -> bool) -> usize {
+        let before = self.directives.len();
+        self.directives.retain(|directive| !matches(directive));
+        let removed = before - self.directives.len();
+        if removed > 0 {
+            // One of the removed directives may have determined the set's max
+            // level; recompute it from the directives that remain.
+            self.max_level = self
+                .directives
+                .iter()
+                .map(|directive| *directive.level())
+                .max()
+                .unwrap_or(LevelFilter::OFF);
+        }
+        removed
+    }
+
+    pub(crate) fn add(&mut self, directive: T) {
+        // does this directive enable a more verbose level than the current
+        // max? if so, update the max level.
+        let level = *directive.level();
+        if level > self.max_level {
+            self.max_level = level;
+        }
+        // insert the directive into the vec of directives, ordered by
+        // specificity (length of target + number of field filters). this
+        // ensures that, when finding a directive to match a span or event, we
+        // search the directive set in most specific first order.
+        match self.directives.binary_search(&directive) {
+            Ok(i) => self.directives[i] = directive,
+            Err(i) => self.directives.insert(i, directive),
+        }
+    }
+}
+
+impl<T: Match + Ord> FromIterator<T> for DirectiveSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut this = Self::default();
+        this.extend(iter);
+        this
+    }
+}
+
+impl<T: Match + Ord> Extend<T> for DirectiveSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for directive in iter.into_iter() {
+            self.add(directive);
+        }
+    }
+}
+
+// === impl Statics ===
+
+impl Statics {
+    pub(crate) fn enabled(&self, meta: &Metadata<'_>) -> bool {
+        let level = meta.level();
+        match self.directives_for(meta).next() {
+            Some(d) => d.level >= *level,
+            None => false,
+        }
+    }
+
+    /// Like [`enabled`], but matching directives against the given `target`
+    /// rather than the metadata's static target.
+    ///
+    /// This is used for events carrying a [`log.target` field], whose
+    /// effective target is not known until their field values are recorded.
+    ///
+    /// [`enabled`]: Statics::enabled
+    /// [`log.target` field]: crate::filter::EnvFilter#dynamic-targets
+    #[cfg(feature = "env-filter")]
+    pub(crate) fn target_enabled(&self, target: &str, meta: &Metadata<'_>) -> bool {
+        let level = meta.level();
+        match self
+            .directives
+            .iter()
+            .find(|d| d.cares_about_target(target, meta))
+        {
+            Some(d) => d.level >= *level,
+            None => false,
+        }
+    }
+}
+
+// ===== impl StaticDirective =====
+
+impl StaticDirective {
+    pub(crate) fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    /// Like [`Match::cares_about`], but matching the given `target` in place
+    /// of the metadata's static target.
+    #[cfg(feature = "env-filter")]
+    fn cares_about_target(&self, target: &str, meta: &Metadata<'_>) -> bool {
+        if let Some(ref prefix) = self.target.as_ref() {
+            if !target.starts_with(&prefix[..]) {
+                return false;
+            }
+        }
+
+        if meta.is_event() && !self.field_names.is_empty() {
+            let fields = meta.fields();
+            for name in &self.field_names {
+                if fields.field(name).is_none() {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+impl Ord for StaticDirective {
+    fn cmp(&self, other: &StaticDirective) -> Ordering {
+        // We attempt to order directives by how "specific" they are. This
+        // ensures that we try the most specific directives first when
+        // attempting to match a piece of metadata.
+
+        // First, we compare based on whether a target is specified, and the
+        // lengths of those targets if both have targets.
+        let ordering = self
+            .target
+            .as_ref()
+            .map(String::len)
+            .cmp(&other.target.as_ref().map(String::len))
+            // Then we compare how many field names are matched by each directive.
+            .then_with(|| self.field_names.len().cmp(&other.field_names.len()))
+            // Finally, we fall back to lexicographical ordering if the directives are
+            // equally specific. Although this is no longer semantically important,
+            // we need to define a total ordering to determine the directive's place
+            // in the BTreeMap.
+            .then_with(|| {
+                self.target
+                    .cmp(&other.target)
+                    .then_with(|| self.field_names[..].cmp(&other.field_names[..]))
+            })
+            .reverse();
+
+        #[cfg(debug_assertions)]
+        {
+            if ordering == Ordering::Equal {
+                debug_assert_eq!(
+                    self.target, other.target,
+                    "invariant violated: Ordering::Equal must imply a.target == b.target"
+                );
+                debug_assert_eq!(
+                    self.field_names, other.field_names,
+                    "invariant violated: Ordering::Equal must imply a.field_names == b.field_names"
+                );
+            }
+        }
+
+        ordering
+    }
+}
+
+impl PartialOrd for StaticDirective {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Match for StaticDirective {
+    fn cares_about(&self, meta: &Metadata<'_>) -> bool {
+        // Does this directive have a target filter, and does it match the
+        // metadata's target?
+        if let Some(ref target) = self.target.as_ref() {
+            if !meta.target().starts_with(&target[..]) {
+                return false;
+            }
+        }
+
+        if meta.is_event() && !self.field_names.is_empty() {
+            let fields = meta.fields();
+            for name in &self.field_names {
+                if fields.field(name).is_none() {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    fn level(&self) -> &LevelFilter {
+        &self.level
+    }
+}
+
+impl Default for StaticDirective {
+    fn default() -> Self {
+        StaticDirective {
+            target: None,
+            field_names: FilterVec::new(),
+            level: LevelFilter::ERROR,
+        }
+    }
+}
+
+impl fmt::Display for StaticDirective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut wrote_any = false;
+        if let Some(ref target) = self.target {
+            fmt::Display::fmt(target, f)?;
+            wrote_any = true;
+        }
+
+        if !self.field_names.is_empty() {
+            f.write_str("[")?;
+
+            let mut fields = self.field_names.iter();
+            if let Some(field) = fields.next() {
+                write!(f, "{{{}", field)?;
+                for field in fields {
+                    write!(f, ",{}", field)?;
+                }
+                f.write_str("}")?;
+            }
+
+            f.write_str("]")?;
+            wrote_any = true;
+        }
+
+        if wrote_any {
+            f.write_str("=")?;
+        }
+
+        fmt::Display::fmt(&self.level, f)
+    }
+}
+
+impl FromStr for StaticDirective {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // This method parses a filtering directive in one of the following
+        // formats:
+        //
+        // * `foo=trace` (TARGET=LEVEL)
+        // * `foo` (bare TARGET, enables all levels for that target)
+        // * `trace` (bare LEVEL, enables that level for all targets)
+        //
+        // The span and field syntax accepted by `EnvFilter` is *not*
+        // supported here.
+        if s.contains('[') || s.contains('{') {
+            return Err(ParseError::new());
+        }
+
+        let mut split = s.split('=');
+        let part0 = split.next().ok_or_else(ParseError::new)?;
+        if part0.is_empty() {
+            return Err(ParseError::new());
+        }
+
+        // Directive includes an `=`:
+        // * `foo=trace`
+        if let Some(part1) = split.next() {
+            if split.next().is_some() {
+                return Err(ParseError::new());
+            }
+
+            return Ok(Self {
+                target: Some(part0.to_string()),
+                field_names: FilterVec::new(),
+                level: part1.parse()?,
+            });
+        }
+
+        // Directive is either a bare level or a bare target:
+        // * `trace`
+        // * `foo`
+        Ok(match part0.parse::<LevelFilter>() {
+            Ok(level) => Self {
+                target: None,
+                field_names: FilterVec::new(),
+                level,
+            },
+            Err(_) => Self {
+                target: Some(part0.to_string()),
+                field_names: FilterVec::new(),
+                level: LevelFilter::TRACE,
+            },
+        })
+    }
+}
+
+// ===== impl ParseError =====
+
+impl ParseError {
+    pub(crate) fn new() -> Self {
+        ParseError {
+            kind: ParseErrorKind::Other,
+            offset: None,
+        }
+    }
+
+    #[cfg(feature = "env-filter")]
+    pub(crate) fn multiple(errors: Vec<ParseError>) -> Self {
+        ParseError {
+            kind: ParseErrorKind::Multiple(errors),
+            offset: None,
+        }
+    }
+
+    #[cfg(feature = "env-filter")]
+    pub(crate) fn at_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Returns the byte offset into the original filter string at which the
+    /// directive that failed to parse began, if known.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// Returns an iterator over the individual parse errors in this error.
+    ///
+    /// When multiple directives in a filter string fail to parse, this yields
+    /// one error per invalid directive; otherwise, it yields this error
+    /// itself.
+    pub fn errors(&self) -> impl Iterator<Item = &ParseError> {
+        match self.kind {
+            #[cfg(feature = "env-filter")]
+            ParseErrorKind::Multiple(ref errors) => errors.iter(),
+            _ => std::slice::from_ref(self).iter(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::Other => f.pad("invalid filter directive")?,
+            ParseErrorKind::Level(ref l) => l.fmt(f)?,
+            #[cfg(feature = "env-filter")]
+            ParseErrorKind::Field(ref e) => write!(f, "invalid field filter: {}", e)?,
+            #[cfg(feature = "env-filter")]
+            ParseErrorKind::Multiple(ref errors) => {
+                let mut errors = errors.iter();
+                if let Some(error) = errors.next() {
+                    error.fmt(f)?;
+                    for error in errors {
+                        write!(f, "; {}", error)?;
+                    }
+                }
+                return Ok(());
+            }
+        }
+        if let Some(offset) = self.offset {
+            write!(f, " (at byte {})", offset)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {
+    fn description(&self) -> &str {
+        "invalid filter directive"
+    }
+
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self.kind {
+            ParseErrorKind::Other => None,
+            ParseErrorKind::Level(ref l) => Some(l),
+            #[cfg(feature = "env-filter")]
+            ParseErrorKind::Field(ref n) => Some(n.as_ref()),
+            #[cfg(feature = "env-filter")]
+            ParseErrorKind::Multiple(ref errors) => {
+                errors.first().map(|e| e as &(dyn Error + 'static))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "env-filter")]
+impl From<Box<dyn Error + Send + Sync>> for ParseError {
+    fn from(e: Box<dyn Error + Send + Sync>) -> Self {
+        Self {
+            kind: ParseErrorKind::Field(e),
+            offset: None,
+        }
+    }
+}
+
+impl From<level::ParseError> for ParseError {
+    fn from(l: level::ParseError) -> Self {
+        Self {
+            kind: ParseErrorKind::Level(l),
+            offset: None,
+        }
+    }
+}
@@ -16,12 +16,20 @@ use crate::{
     subscribe::{Context, Subscribe},
     sync::RwLock,
 };
-use std::{cell::RefCell, collections::HashMap, env, error::Error, fmt, str::FromStr};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    env,
+    error::Error,
+    fmt,
+    str::FromStr,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
 use tracing_core::{
     callsite,
     collect::{Collect, Interest},
-    field::Field,
-    span, Metadata,
+    field::{Field, Visit},
+    span, Event, Level, Metadata,
 };
 
 /// A [`Subscriber`] which filters spans and events based on a set of filter
@@ -87,6 +95,30 @@ use tracing_core::{
 ///    - which has a field named `name` with value `bob`,
 ///    - at _any_ level.
 ///
+/// ## Dynamic Targets
+///
+/// An event's target is part of its static metadata, so it cannot normally be
+/// determined at runtime. Events that need a runtime-determined target — such
+/// as diagnostics routed on behalf of dynamically loaded plugins — may record
+/// the well-known `log.target` field instead:
+///
+/// ```rust
+/// let plugin_name = "plugin_foo";
+/// tracing::info!(log.target = plugin_name, "plugin loaded");
+/// ```
+///
+/// When an event has a `log.target` field, target directives match against
+/// that field's value in place of the event's static target. This is the same
+/// field used by the [`tracing-log`] bridge to carry the `log` crate's
+/// dynamic targets, so events forwarded from `log` and native `tracing`
+/// events are filtered uniformly.
+///
+/// Since the effective target is not known until the event's field values are
+/// recorded, such events bypass the callsite interest cache, making them
+/// somewhat more expensive to filter than events with static targets.
+///
+/// [`tracing-log`]: https://docs.rs/tracing-log
+///
 /// [`Subscriber`]: Subscribe
 /// [`env_logger`]: https://docs.rs/env_logger/0.7.1/env_logger/#enabling-logging
 /// [`Span`]: tracing_core::span
@@ -101,20 +133,163 @@ pub struct EnvFilter {
     statics: directive::Statics,
     dynamics: directive::Dynamics,
     has_dynamics: bool,
+    /// A unique ID identifying this filter's current set of directives in
+    /// per-thread [`EnabledCache`]s. Reassigned whenever the directives
+    /// change, so that stale cached decisions are never returned.
+    id: u64,
     by_id: RwLock<HashMap<span::Id, directive::SpanMatcher>>,
     by_cs: RwLock<HashMap<callsite::Identifier, directive::CallsiteMatcher>>,
+    /// Event callsites declaring a [`TARGET_FIELD`], whose `enabled` decision
+    /// must be deferred until their field values are recorded.
+    target_overrides: RwLock<HashSet<callsite::Identifier>>,
+    /// The number of `register_callsite` calls each directive has matched,
+    /// keyed by the directive's `Display` form. Used by
+    /// [`matched_directives`] and [`check_unmatched`].
+    ///
+    /// [`matched_directives`]: EnvFilter::matched_directives
+    /// [`check_unmatched`]: EnvFilter::check_unmatched
+    callsite_matches: RwLock<HashMap<String, usize>>,
+    /// Whether [`check_unmatched`] has already run.
+    ///
+    /// [`check_unmatched`]: EnvFilter::check_unmatched
+    warned_unmatched: AtomicBool,
 }
 
 thread_local! {
     static SCOPE: RefCell<Vec<LevelFilter>> = RefCell::new(Vec::new());
+    static ENABLED_CACHE: RefCell<EnabledCache> = RefCell::new(EnabledCache::default());
 }
 
 type FieldMap<T> = HashMap<Field, T>;
 
-#[cfg(feature = "smallvec")]
-type FilterVec<T> = smallvec::SmallVec<[T; 8]>;
-#[cfg(not(feature = "smallvec"))]
-type FilterVec<T> = Vec<T>;
+use crate::filter::directive::FilterVec;
+
+/// The maximum number of per-callsite `enabled` decisions cached by each
+/// thread.
+///
+/// This matches the inline capacity of `FilterVec`, so the cache never
+/// allocates when the `smallvec` feature is enabled.
+const ENABLED_CACHE_CAPACITY: usize = 8;
+
+/// A per-thread cache of `enabled` decisions for event callsites.
+///
+/// When a filter has dynamic (span-scoped) directives, deciding whether an
+/// event is enabled requires checking every level in the current scope. That
+/// decision only changes when a span is entered or exited, so a thread
+/// emitting many events between scope changes — such as a tight loop — would
+/// otherwise re-evaluate the same directives for every event. Each cached
+/// entry records the `epoch` at which it was computed; pushing to or popping
+/// from the thread's scope bumps the epoch, implicitly invalidating every
+/// cached decision. Entries are also keyed by the owning filter's ID, which
+/// is reassigned whenever that filter's directives change, so mutating a
+/// filter (e.g. through a `reload` handle) invalidates its entries as well.
+///
+/// The cache is a short most-recently-used list rather than a map: with at
+/// most [`ENABLED_CACHE_CAPACITY`] entries, a linear scan is cheaper than
+/// hashing, and hot callsites stay at the front.
+#[derive(Debug, Default)]
+struct EnabledCache {
+    epoch: u64,
+    entries: FilterVec<CacheEntry>,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    filter: u64,
+    callsite: callsite::Identifier,
+    epoch: u64,
+    enabled: bool,
+}
+
+impl EnabledCache {
+    /// Returns the decision cached for `callsite` by the filter identified by
+    /// `filter`, if one was recorded during the current epoch.
+    fn get(&mut self, filter: u64, callsite: &callsite::Identifier) -> Option<bool> {
+        let idx = self
+            .entries
+            .iter()
+            .position(|entry| entry.filter == filter && entry.callsite == *callsite)?;
+        if self.entries[idx].epoch != self.epoch {
+            // The scope has changed since this decision was recorded.
+            self.entries.remove(idx);
+            return None;
+        }
+        // Move the entry to the front, so that the least recently used entry
+        // is the one evicted when the cache fills up.
+        if idx != 0 {
+            let entry = self.entries.remove(idx);
+            self.entries.insert(0, entry);
+        }
+        Some(self.entries[0].enabled)
+    }
+
+    /// Caches a decision for `callsite` in the current epoch.
+    fn insert(&mut self, filter: u64, callsite: callsite::Identifier, enabled: bool) {
+        if self.entries.len() == ENABLED_CACHE_CAPACITY {
+            self.entries.pop();
+        }
+        self.entries.insert(
+            0,
+            CacheEntry {
+                filter,
+                callsite,
+                epoch: self.epoch,
+                enabled,
+            },
+        );
+    }
+
+    /// Invalidates every decision cached on this thread.
+    fn bump_epoch(&mut self) {
+        self.epoch = self.epoch.wrapping_add(1);
+    }
+}
+
+/// Returns a fresh ID identifying a filter's current set of directives in
+/// per-thread [`EnabledCache`]s.
+fn next_cache_id() -> u64 {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// The well-known event field which, when present, overrides the event's
+/// static target for filtering.
+///
+/// This is the same field used by the `tracing-log` bridge to record the
+/// `log` crate's dynamic targets.
+const TARGET_FIELD: &str = "log.target";
+
+/// Extracts the value of an event's [`TARGET_FIELD`], if one was recorded.
+struct TargetVisitor {
+    field: Field,
+    target: Option<String>,
+}
+
+impl Visit for TargetVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field == &self.field {
+            self.target = Some(value.to_owned());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field == &self.field {
+            self.target = Some(format!("{:?}", value));
+        }
+    }
+}
+
+/// Returns `true` if a span in the current thread's scope enables `level`.
+fn scope_enabled(level: &Level) -> bool {
+    SCOPE.with(|scope| {
+        for filter in scope.borrow().iter() {
+            if filter >= level {
+                return true;
+            }
+        }
+        false
+    })
+}
 
 /// Indicates that an error occurred while parsing a `EnvFilter` from an
 /// environment variable.
@@ -147,6 +322,24 @@ impl EnvFilter {
         env::var(env.as_ref()).map(Self::new).unwrap_or_default()
     }
 
+    /// Returns a new `EnvFilter` from the value of the given environment
+    /// variable, falling back to the given default directives if the variable
+    /// is unset. Invalid directives are ignored.
+    ///
+    /// This is useful for `env_logger`-style initialization, where an
+    /// application uses a baseline filter unless the user has configured one:
+    ///
+    /// ```rust
+    /// use tracing_subscriber::filter::EnvFilter;
+    ///
+    /// let filter = EnvFilter::from_env_or("MY_APP_LOG", "info");
+    /// ```
+    pub fn from_env_or<A: AsRef<str>, S: AsRef<str>>(env: A, default: S) -> Self {
+        env::var(env.as_ref())
+            .map(Self::new)
+            .unwrap_or_else(|_| Self::new(default))
+    }
+
     /// Returns a new `EnvFilter` from the directives in the given string,
     /// ignoring any that are invalid.
     pub fn new<S: AsRef<str>>(dirs: S) -> Self {
@@ -162,13 +355,27 @@ impl EnvFilter {
 
     /// Returns a new `EnvFilter` from the directives in the given string,
     /// or an error if any are invalid.
+    ///
+    /// If multiple directives fail to parse, the returned [`ParseError`]
+    /// includes all of them; each can be inspected via
+    /// [`ParseError::errors`], and [`ParseError::offset`] reports the byte
+    /// offset in `dirs` at which the invalid directive began.
     pub fn try_new<S: AsRef<str>>(dirs: S) -> Result<Self, ParseError> {
-        let directives = dirs
-            .as_ref()
-            .split(',')
-            .map(|s| s.parse())
-            .collect::<Result<Vec<_>, _>>()?;
-        Ok(Self::from_directives(directives))
+        let mut directives = Vec::new();
+        let mut errors = Vec::new();
+        let mut offset = 0;
+        for part in dirs.as_ref().split(',') {
+            match part.parse::<Directive>() {
+                Ok(directive) => directives.push(directive),
+                Err(err) => errors.push(err.at_offset(offset)),
+            }
+            offset += part.len() + 1;
+        }
+        match errors.len() {
+            0 => Ok(Self::from_directives(directives)),
+            1 => Err(errors.pop().expect("errors is not empty")),
+            _ => Err(ParseError::multiple(errors)),
+        }
     }
 
     /// Returns a new `EnvFilter` from the value of the `RUST_LOG` environment
@@ -185,6 +392,96 @@ impl EnvFilter {
         env::var(env.as_ref())?.parse().map_err(Into::into)
     }
 
+    /// Returns an iterator over the filtering [`Directive`]s this `EnvFilter`
+    /// was parsed from (or which were added with [`add_directive`]).
+    ///
+    /// The returned directives may not be in the order in which they appeared
+    /// in the original filter string, but [`Display`]ing them, joined by
+    /// commas, produces a string that parses back to an equivalent filter.
+    ///
+    /// [`add_directive`]: EnvFilter::add_directive
+    /// [`Display`]: fmt::Display
+    pub fn directives(&self) -> impl Iterator<Item = Directive> + '_ {
+        self.statics
+            .iter()
+            .map(Directive::from)
+            .chain(self.dynamics.iter().cloned())
+    }
+
+    /// Returns each of this filter's directives, together with the number of
+    /// registered callsites it has matched so far.
+    ///
+    /// A directive that has matched nothing after the application's
+    /// instrumented crates are loaded is usually a misspelled target; see
+    /// [`check_unmatched`] for a diagnostic that surfaces such directives
+    /// automatically.
+    ///
+    /// [`check_unmatched`]: EnvFilter::check_unmatched
+    pub fn matched_directives(&self) -> Vec<(Directive, usize)> {
+        let matches = try_lock!(self.callsite_matches.read(), else return Vec::new());
+        self.directives()
+            .map(|directive| {
+                let count = matches
+                    .get(&directive.to_string())
+                    .copied()
+                    .unwrap_or_default();
+                (directive, count)
+            })
+            .collect()
+    }
+
+    /// Emits a single `WARN` event listing directives that have not matched
+    /// any registered callsite, if there are any.
+    ///
+    /// A typo in a filter directive — `my_crate::sevrer=debug`, say — fails
+    /// silently: the directive simply never matches anything. Calling this
+    /// method once the application's instrumented crates have been loaded
+    /// (for example, after startup has completed) surfaces such directives.
+    ///
+    /// Only the first call on a filter emits the warning; subsequent calls do
+    /// nothing, so this may safely be invoked from a periodic or per-event
+    /// hook. The warning is dispatched like any other event and is subject to
+    /// this (and every other) filter.
+    pub fn check_unmatched(&self) {
+        // Setting the flag *before* emitting doubles as a reentrancy guard:
+        // the warning passes through the normal dispatch, and thus possibly
+        // back through this very filter.
+        if self.warned_unmatched.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let unmatched = self
+            .matched_directives()
+            .into_iter()
+            .filter(|(_, count)| *count == 0)
+            .map(|(directive, _)| directive.to_string())
+            .collect::<Vec<_>>();
+        if !unmatched.is_empty() {
+            tracing::warn!(
+                unmatched_directives = %unmatched.join(","),
+                "some filter directives matched no known callsites; are they misspelled?"
+            );
+        }
+    }
+
+    /// Returns the maximum verbosity level that this filter will enable for
+    /// any target, or `None` if there is no maximum.
+    ///
+    /// If the filter matches on span field *values*, this returns
+    /// [`LevelFilter::TRACE`], as spans must be enabled for their field values
+    /// to be recorded.
+    pub fn max_level_hint(&self) -> Option<LevelFilter> {
+        if self.dynamics.has_value_filters() {
+            // If we perform any filtering on span field *values*, we will
+            // enable *all* spans, because their field values are not known
+            // until recording.
+            return Some(LevelFilter::TRACE);
+        }
+        std::cmp::max(
+            self.statics.max_level.into(),
+            self.dynamics.max_level.into(),
+        )
+    }
+
     /// Add a filtering directive to this `EnvFilter`.
     ///
     /// The added directive will be used in addition to any previously set
@@ -199,7 +496,13 @@ impl EnvFilter {
     /// and events as a previous filter, but sets a different level for those
     /// spans and events, the previous directive is overwritten.
     ///
+    /// To add a directive to a filter behind a mutable reference — such as
+    /// one that has already been installed and is being modified through a
+    /// [`reload::Handle`] — use [`add_directive_mut`] instead.
+    ///
     /// [`Level`]: tracing_core::Level
+    /// [`reload::Handle`]: crate::reload::Handle
+    /// [`add_directive_mut`]: EnvFilter::add_directive_mut
     ///
     /// # Examples
     ///
@@ -233,13 +536,112 @@ impl EnvFilter {
     /// # }
     /// ```
     pub fn add_directive(mut self, directive: Directive) -> Self {
+        self.add_directive_mut(directive);
+        self
+    }
+
+    /// Adds a filtering directive to this `EnvFilter` in place.
+    ///
+    /// This is equivalent to [`add_directive`], but takes the filter by
+    /// mutable reference rather than by value, so it can modify a filter that
+    /// has already been installed. Combined with a [`reload::Handle`], this
+    /// allows enabling additional targets at runtime without reconstructing
+    /// the filter from its original directive string:
+    ///
+    /// ```rust
+    /// use tracing_subscriber::{filter::EnvFilter, prelude::*, reload};
+    ///
+    /// let filter = EnvFilter::new("info");
+    /// let (filter, handle) = reload::Subscriber::new(filter);
+    /// let collector = tracing_subscriber::registry().with(filter);
+    /// # let _ = collector;
+    ///
+    /// // ...later on, enable verbose logging for a single crate:
+    /// handle
+    ///     .modify(|filter| {
+    ///         filter.add_directive_mut("my_crate=trace".parse().unwrap());
+    ///     })
+    ///     .expect("collector should still exist");
+    /// ```
+    ///
+    /// Any filter state cached from the previous directives is invalidated,
+    /// so the new directive takes effect for subsequent spans and events.
+    /// Note that if the filter has already been installed, the callsite
+    /// interest cache must also be rebuilt for the change to be observed;
+    /// [`reload::Handle::modify`] does this automatically.
+    ///
+    /// [`add_directive`]: EnvFilter::add_directive
+    /// [`reload::Handle`]: crate::reload::Handle
+    /// [`reload::Handle::modify`]: crate::reload::Handle::modify
+    pub fn add_directive_mut(&mut self, directive: Directive) {
         if let Some(stat) = directive.to_static() {
             self.statics.add(stat)
         } else {
             self.has_dynamics = true;
             self.dynamics.add(directive);
         }
-        self
+        self.invalidate_caches();
+    }
+
+    /// Removes all directives that apply to exactly the given target,
+    /// returning the number of directives that were removed.
+    ///
+    /// Only directives whose target is precisely `target` are removed; a
+    /// directive naming a prefix of `target` (such as the crate containing a
+    /// module) is unaffected, as are directives with no target at all. If
+    /// every directive is removed, the filter behaves like one parsed from an
+    /// empty string, enabling the `ERROR` level for all targets.
+    ///
+    /// Like [`add_directive_mut`], this invalidates cached filter state, and
+    /// is intended for use through a [`reload::Handle`]'s [`modify`] method,
+    /// which rebuilds the callsite interest cache after the mutation. Spans
+    /// that have already been created retain the dynamic filters they were
+    /// created with until they close.
+    ///
+    /// [`add_directive_mut`]: EnvFilter::add_directive_mut
+    /// [`reload::Handle`]: crate::reload::Handle
+    /// [`modify`]: crate::reload::Handle::modify
+    pub fn remove_directives_for_target(&mut self, target: &str) -> usize {
+        let removed = self
+            .statics
+            .remove_matching(|directive| directive.target() == Some(target))
+            + self
+                .dynamics
+                .remove_matching(|directive| directive.target() == Some(target));
+        if removed > 0 {
+            self.has_dynamics = !self.dynamics.is_empty();
+            // An `EnvFilter` with no directives at all implicitly enables the
+            // `ERROR` level, as when parsed from an empty string; preserve
+            // that invariant if the last directive was just removed.
+            if self.statics.is_empty() && !self.has_dynamics {
+                self.statics.add(directive::StaticDirective::default());
+            }
+            self.invalidate_caches();
+        }
+        removed
+    }
+
+    /// Discards any cached filter state computed from a previous set of
+    /// directives.
+    fn invalidate_caches(&mut self) {
+        // The matchers cached per-callsite were constructed from the old
+        // dynamic directives; drop them so they are rebuilt by
+        // `register_callsite` when the interest cache is next rebuilt. The
+        // span matchers in `by_id` are deliberately left in place: spans that
+        // are currently executing keep the filters they were created with,
+        // and clearing them would unbalance the `SCOPE` stack maintained by
+        // `on_enter`/`on_exit`.
+        //
+        // Note that this does *not* rebuild the callsite interest cache:
+        // doing so re-registers every callsite with the subscriber, which
+        // would deadlock when this filter is mutated through a
+        // `reload::Handle` holding its lock. `reload::Handle::modify`
+        // rebuilds the interest cache itself once the lock is released.
+        try_lock!(self.by_cs.get_mut(), else return).clear();
+        // Reassigning the filter's ID orphans any `enabled` decisions this
+        // filter has recorded in per-thread caches, so they will be
+        // recomputed against the new directives.
+        self.id = next_cache_id();
     }
 
     fn from_directives(directives: impl IntoIterator<Item = Directive>) -> Self {
@@ -343,8 +745,12 @@ impl EnvFilter {
             statics,
             dynamics,
             has_dynamics,
+            id: next_cache_id(),
             by_id: RwLock::new(HashMap::new()),
             by_cs: RwLock::new(HashMap::new()),
+            target_overrides: RwLock::new(HashSet::new()),
+            callsite_matches: RwLock::new(HashMap::new()),
+            warned_unmatched: AtomicBool::new(false),
         }
     }
 
@@ -353,6 +759,44 @@ impl EnvFilter {
         spans.contains_key(span)
     }
 
+    /// Records which directives care about a newly registered callsite, for
+    /// [`matched_directives`] and [`check_unmatched`].
+    ///
+    /// [`matched_directives`]: EnvFilter::matched_directives
+    /// [`check_unmatched`]: EnvFilter::check_unmatched
+    fn record_callsite_matches(&self, metadata: &Metadata<'_>) {
+        use directive::Match;
+        let mut matches = try_lock!(self.callsite_matches.write(), else return);
+        for directive in self.statics.iter() {
+            if directive.cares_about(metadata) {
+                *matches
+                    .entry(Directive::from(directive).to_string())
+                    .or_insert(0) += 1;
+            }
+        }
+        for directive in self.dynamics.iter() {
+            if directive.cares_about(metadata) {
+                *matches.entry(directive.to_string()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// Returns `true` if the given callsite was registered as an event with a
+    /// [`TARGET_FIELD`].
+    fn has_target_override(&self, callsite: &callsite::Identifier) -> bool {
+        try_lock!(self.target_overrides.read(), else return false).contains(callsite)
+    }
+
+    /// Returns `true` if an event with the given metadata is enabled by its
+    /// static target — that is, as though it carried no target override.
+    fn enabled_by_static_target(&self, metadata: &Metadata<'_>) -> bool {
+        let level = metadata.level();
+        if self.has_dynamics && self.dynamics.max_level >= *level && scope_enabled(level) {
+            return true;
+        }
+        self.statics.max_level >= *level && self.statics.enabled(metadata)
+    }
+
     fn base_interest(&self) -> Interest {
         if self.has_dynamics {
             Interest::sometimes()
@@ -364,6 +808,18 @@ impl EnvFilter {
 
 impl<C: Collect> Subscribe<C> for EnvFilter {
     fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        self.record_callsite_matches(metadata);
+
+        if metadata.is_event() && metadata.fields().field(TARGET_FIELD).is_some() {
+            // The event's effective target is the *value* of its `log.target`
+            // field, which is not known until the event occurs; the decision
+            // is deferred to `event_enabled`.
+            let mut overrides =
+                try_lock!(self.target_overrides.write(), else return Interest::sometimes());
+            overrides.insert(metadata.callsite());
+            return Interest::sometimes();
+        }
+
         if self.has_dynamics && metadata.is_span() {
             // If this metadata describes a span, first, check if there is a
             // dynamic filter that should be constructed for it. If so, it
@@ -384,21 +840,27 @@ impl<C: Collect> Subscribe<C> for EnvFilter {
     }
 
     fn max_level_hint(&self) -> Option<LevelFilter> {
-        if self.dynamics.has_value_filters() {
-            // If we perform any filtering on span field *values*, we will
-            // enable *all* spans, because their field values are not known
-            // until recording.
-            return Some(LevelFilter::TRACE);
-        }
-        std::cmp::max(
-            self.statics.max_level.into(),
-            self.dynamics.max_level.into(),
-        )
+        EnvFilter::max_level_hint(self)
     }
 
     fn enabled(&self, metadata: &Metadata<'_>, _: Context<'_, C>) -> bool {
         let level = metadata.level();
 
+        if metadata.is_event()
+            && metadata.fields().field(TARGET_FIELD).is_some()
+            && self.has_target_override(&metadata.callsite())
+        {
+            // The real decision is made by `event_enabled` once the target
+            // override's value is known; here, just check whether *any*
+            // directive could enable an event at this level. This only
+            // applies to *registered* callsites: the `tracing-log` bridge
+            // checks `enabled` with unregistered per-record metadata whose
+            // target is already the record's dynamic target, which can be
+            // matched directly below.
+            return self.statics.max_level >= *level
+                || (self.has_dynamics && self.dynamics.max_level >= *level);
+        }
+
         // is it possible for a dynamic filter directive to enable this event?
         // if not, we can avoid the thread local access + iterating over the
         // spans in the current scope.
@@ -414,17 +876,35 @@ impl<C: Collect> Subscribe<C> for EnvFilter {
                 if enabled_by_cs {
                     return true;
                 }
-            }
-
-            let enabled_by_scope = SCOPE.with(|scope| {
-                for filter in scope.borrow().iter() {
-                    if filter >= level {
-                        return true;
+            } else {
+                // For events, the decision depends only on the callsite and
+                // the current scope, so it can be looked up in this thread's
+                // cache. Span callsites are not cached, as their decisions
+                // also depend on the `by_cs` map, which is repopulated
+                // whenever the callsite interest cache is rebuilt.
+                // However, metadata carrying a `log.target` field — such as
+                // the per-record metadata the `tracing-log` bridge filters
+                // with — shares a single callsite across many targets, so its
+                // decisions cannot be cached.
+                let cacheable = metadata.fields().field(TARGET_FIELD).is_none();
+                let callsite = metadata.callsite();
+                if cacheable {
+                    if let Some(enabled) =
+                        ENABLED_CACHE.with(|cache| cache.borrow_mut().get(self.id, &callsite))
+                    {
+                        return enabled;
                     }
                 }
-                false
-            });
-            if enabled_by_scope {
+                let enabled = scope_enabled(level)
+                    || (self.statics.max_level >= *level && self.statics.enabled(metadata));
+                if cacheable {
+                    ENABLED_CACHE
+                        .with(|cache| cache.borrow_mut().insert(self.id, callsite, enabled));
+                }
+                return enabled;
+            }
+
+            if scope_enabled(level) {
                 return true;
             }
         }
@@ -439,6 +919,35 @@ impl<C: Collect> Subscribe<C> for EnvFilter {
         false
     }
 
+    fn event_enabled(&self, event: &Event<'_>, _: Context<'_, C>) -> bool {
+        let metadata = event.metadata();
+        let field = match metadata.fields().field(TARGET_FIELD) {
+            Some(field) => field,
+            // Without a target override, the decision made by `enabled`
+            // stands.
+            None => return true,
+        };
+
+        let mut visitor = TargetVisitor {
+            field,
+            target: None,
+        };
+        event.record(&mut visitor);
+        let target = match visitor.target {
+            // The callsite declares the field, but this event did not record
+            // a value for it, so its static target applies after all.
+            None => return self.enabled_by_static_target(metadata),
+            Some(target) => target,
+        };
+
+        let level = metadata.level();
+        if self.has_dynamics && self.dynamics.max_level >= *level && scope_enabled(level) {
+            return true;
+        }
+
+        self.statics.max_level >= *level && self.statics.target_enabled(&target, metadata)
+    }
+
     fn new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, _: Context<'_, C>) {
         let by_cs = try_lock!(self.by_cs.read());
         if let Some(cs) = by_cs.get(&attrs.metadata().callsite()) {
@@ -459,12 +968,14 @@ impl<C: Collect> Subscribe<C> for EnvFilter {
         // But that might be much less efficient...
         if let Some(span) = try_lock!(self.by_id.read()).get(id) {
             SCOPE.with(|scope| scope.borrow_mut().push(span.level()));
+            ENABLED_CACHE.with(|cache| cache.borrow_mut().bump_epoch());
         }
     }
 
     fn on_exit(&self, id: &span::Id, _: Context<'_, C>) {
         if self.cares_about_span(id) {
             SCOPE.with(|scope| scope.borrow_mut().pop());
+            ENABLED_CACHE.with(|cache| cache.borrow_mut().bump_epoch());
         }
     }
 
@@ -705,4 +1216,121 @@ mod tests {
         assert_eq!(f1.statics, f2.statics);
         assert_eq!(f1.dynamics, f2.dynamics);
     }
+
+    #[test]
+    fn directives_iterator_roundtrip() {
+        let filter =
+            EnvFilter::try_new("crate1::mod1=error,crate2=debug,[span1{foo=1}]=warn").unwrap();
+
+        let joined = filter
+            .directives()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let reparsed = EnvFilter::try_new(joined).unwrap();
+        assert_eq!(filter.statics, reparsed.statics);
+        assert_eq!(filter.dynamics, reparsed.dynamics);
+    }
+
+    #[test]
+    fn directives_iterator_accessors() {
+        let filter = EnvFilter::try_new("crate1::mod1=error,[span1{foo=1}]=warn").unwrap();
+        let directives: Vec<_> = filter.directives().collect();
+        assert_eq!(directives.len(), 2);
+
+        let static_directive = directives
+            .iter()
+            .find(|d| d.span_name().is_none())
+            .expect("static directive should be present");
+        assert_eq!(static_directive.target(), Some("crate1::mod1"));
+        assert_eq!(*static_directive.level(), LevelFilter::ERROR);
+
+        let span_directive = directives
+            .iter()
+            .find(|d| d.span_name().is_some())
+            .expect("span directive should be present");
+        assert_eq!(span_directive.span_name(), Some("span1"));
+        assert_eq!(span_directive.field_names().collect::<Vec<_>>(), ["foo"]);
+        assert_eq!(*span_directive.level(), LevelFilter::WARN);
+    }
+
+    #[test]
+    fn max_level_hint_accessor() {
+        let filter = EnvFilter::try_new("crate1::mod1=error,crate2=debug").unwrap();
+        assert_eq!(filter.max_level_hint(), Some(LevelFilter::DEBUG));
+
+        // Filtering on span field values requires enabling all levels.
+        let filter = EnvFilter::try_new("[{foo=1}]=warn").unwrap();
+        assert_eq!(filter.max_level_hint(), Some(LevelFilter::TRACE));
+    }
+
+    #[test]
+    fn try_new_returns_all_errors_with_offsets() {
+        let err = EnvFilter::try_new("crate1=badlevel,crate2=debug,=,crate3=warn")
+            .expect_err("parsing should fail");
+
+        let errors: Vec<_> = err.errors().collect();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].offset(), Some(0));
+        assert_eq!(
+            errors[1].offset(),
+            Some("crate1=badlevel,crate2=debug,".len())
+        );
+    }
+
+    #[test]
+    fn try_new_single_error_has_offset() {
+        let err =
+            EnvFilter::try_new("crate1=debug,crate2=badlevel").expect_err("parsing should fail");
+        assert_eq!(err.errors().count(), 1);
+        assert_eq!(err.offset(), Some("crate1=debug,".len()));
+    }
+
+    #[test]
+    fn enabled_cache_bumping_epoch_invalidates_entries() {
+        static CS: Cs = Cs;
+        let callsite = callsite::Identifier(&CS);
+        let mut cache = EnabledCache::default();
+
+        cache.insert(1, callsite.clone(), true);
+        assert_eq!(cache.get(1, &callsite), Some(true));
+        // A different filter must not observe this filter's decisions.
+        assert_eq!(cache.get(2, &callsite), None);
+
+        cache.bump_epoch();
+        // Entering or exiting a span invalidates every cached decision.
+        assert_eq!(cache.get(1, &callsite), None);
+    }
+
+    #[test]
+    fn enabled_cache_evicts_least_recently_used() {
+        // `Identifier`s compare by address, and every element of an array of
+        // zero-sized callsites shares one, so these must not be zero-sized.
+        #[derive(Clone, Copy)]
+        struct FatCs(#[allow(dead_code)] u8);
+        impl Callsite for FatCs {
+            fn set_interest(&self, _interest: Interest) {}
+            fn metadata(&self) -> &Metadata<'_> {
+                unimplemented!()
+            }
+        }
+
+        static CSS: [FatCs; ENABLED_CACHE_CAPACITY + 1] = [FatCs(0); ENABLED_CACHE_CAPACITY + 1];
+        let mut cache = EnabledCache::default();
+        for cs in CSS[..ENABLED_CACHE_CAPACITY].iter() {
+            cache.insert(1, callsite::Identifier(cs), true);
+        }
+
+        // Refresh the oldest entry, then fill the cache past its capacity;
+        // the second-oldest entry is now the least recently used, and must be
+        // the one that is evicted.
+        assert_eq!(cache.get(1, &callsite::Identifier(&CSS[0])), Some(true));
+        cache.insert(1, callsite::Identifier(&CSS[ENABLED_CACHE_CAPACITY]), false);
+        assert_eq!(cache.get(1, &callsite::Identifier(&CSS[1])), None);
+        assert_eq!(cache.get(1, &callsite::Identifier(&CSS[0])), Some(true));
+        assert_eq!(
+            cache.get(1, &callsite::Identifier(&CSS[ENABLED_CACHE_CAPACITY])),
+            Some(false)
+        );
+    }
 }
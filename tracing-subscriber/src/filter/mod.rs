@@ -2,11 +2,15 @@
 //! subscriber.
 //!
 //! [`Subscriber`]: crate::fmt::Subscriber
+mod directive;
 #[cfg(feature = "env-filter")]
 mod env;
 mod level;
+mod targets;
 
+pub use self::directive::ParseError;
 pub use self::level::{LevelFilter, ParseError as LevelParseError};
+pub use self::targets::Targets;
 
 #[cfg(feature = "env-filter")]
 #[cfg_attr(docsrs, doc(cfg(feature = "env-filter")))]
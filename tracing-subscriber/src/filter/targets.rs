@@ -0,0 +1,350 @@
+//! A filter which enables spans and events by comparing their [target]s to a
+//! set of statically configured per-target verbosity levels.
+//!
+//! [target]: tracing_core::Metadata::target
+use crate::{
+    filter::{
+        directive::{DirectiveSet, FilterVec, ParseError, StaticDirective},
+        LevelFilter,
+    },
+    subscribe::Context,
+    Subscribe,
+};
+use std::{iter::FromIterator, str::FromStr};
+use tracing_core::{
+    collect::{Collect, Interest},
+    Metadata,
+};
+
+/// A filter which enables spans and events by comparing their [target]s to a
+/// set of statically configured per-target verbosity levels.
+///
+/// This is a lighter-weight alternative to [`EnvFilter`] for the common case
+/// where filtering is configured programmatically rather than parsed from an
+/// environment variable: it supports *only* per-target level filtering, so it
+/// does not depend on regular expressions or the span and field matching
+/// syntax, and it is available without the "env-filter" feature flag.
+///
+/// # Matching
+///
+/// Like `env_logger`-style filters, a directive for a given target enables
+/// spans and events whose targets begin with that target as a prefix of Rust
+/// module path segments. For example, a directive for `hyper` applies to
+/// spans and events with the targets `hyper`, `hyper::proto`, and so on. When
+/// multiple directives apply, the most specific (longest) one wins. A default
+/// level, set by [`with_default`], applies to targets matched by no other
+/// directive; if no default is set, such targets are disabled.
+///
+/// # Examples
+///
+/// ```
+/// use tracing_subscriber::{filter::{LevelFilter, Targets}, prelude::*};
+///
+/// let filter = Targets::new()
+///     // Disable `hyper`'s verbose internals entirely...
+///     .with_target("hyper", LevelFilter::OFF)
+///     // ...enable the `DEBUG` level for the application itself...
+///     .with_target("my_app", LevelFilter::DEBUG)
+///     // ...and the `WARN` level for everything else.
+///     .with_default(LevelFilter::WARN);
+///
+/// let subscriber = tracing_subscriber::registry()
+///     .with(tracing_subscriber::fmt::subscriber())
+///     .with(filter);
+/// ```
+///
+/// The same configuration can be parsed from a string using the
+/// `target=level` syntax accepted by `RUST_LOG`, without spans or fields:
+///
+/// ```
+/// use tracing_subscriber::filter::Targets;
+///
+/// let filter = "hyper=off,my_app=debug,warn"
+///     .parse::<Targets>()
+///     .expect("filter should parse");
+/// ```
+///
+/// [target]: tracing_core::Metadata::target
+/// [`EnvFilter`]: crate::filter::EnvFilter
+/// [`with_default`]: Targets::with_default
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Targets(DirectiveSet<StaticDirective>);
+
+impl Targets {
+    /// Returns a new `Targets` filter.
+    ///
+    /// This filter enables no targets. Call [`with_target`] or
+    /// [`with_default`] to add enabled targets.
+    ///
+    /// [`with_target`]: Targets::with_target
+    /// [`with_default`]: Targets::with_default
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables spans and events with the given [target] at the given
+    /// [`LevelFilter`], and all levels below it.
+    ///
+    /// The target acts as a prefix: a directive for `hyper` also applies to
+    /// `hyper::proto`, `hyper::client::conn`, and so on. If a directive for
+    /// the same target was already added, it is replaced.
+    ///
+    /// [target]: tracing_core::Metadata::target
+    pub fn with_target(mut self, target: impl Into<String>, level: impl Into<LevelFilter>) -> Self {
+        self.0.add(StaticDirective {
+            target: Some(target.into()),
+            field_names: FilterVec::new(),
+            level: level.into(),
+        });
+        self
+    }
+
+    /// Adds [target]s from an iterator of target-[`LevelFilter`] pairs to
+    /// this filter.
+    ///
+    /// [target]: tracing_core::Metadata::target
+    pub fn with_targets<T, L>(mut self, targets: impl IntoIterator<Item = (T, L)>) -> Self
+    where
+        String: From<T>,
+        LevelFilter: From<L>,
+    {
+        self.extend(targets);
+        self
+    }
+
+    /// Sets the default level to enable for [target]s not matched by any
+    /// other directive.
+    ///
+    /// If a default was already set, it is replaced. When no default is set,
+    /// unmatched targets are disabled.
+    ///
+    /// [target]: tracing_core::Metadata::target
+    pub fn with_default(mut self, level: impl Into<LevelFilter>) -> Self {
+        self.0.add(StaticDirective {
+            target: None,
+            field_names: FilterVec::new(),
+            level: level.into(),
+        });
+        self
+    }
+
+    /// Returns an iterator over the [target]-[`LevelFilter`] pairs in this
+    /// filter, ordered most specific target first.
+    ///
+    /// The default level, if one was set by [`with_default`], is yielded as a
+    /// pair with a target of `None`.
+    ///
+    /// [target]: tracing_core::Metadata::target
+    /// [`with_default`]: Targets::with_default
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.0.iter())
+    }
+}
+
+impl<T, L> Extend<(T, L)> for Targets
+where
+    String: From<T>,
+    LevelFilter: From<L>,
+{
+    fn extend<I: IntoIterator<Item = (T, L)>>(&mut self, iter: I) {
+        for (target, level) in iter.into_iter() {
+            self.0.add(StaticDirective {
+                target: Some(String::from(target)),
+                field_names: FilterVec::new(),
+                level: LevelFilter::from(level),
+            });
+        }
+    }
+}
+
+impl<T, L> FromIterator<(T, L)> for Targets
+where
+    String: From<T>,
+    LevelFilter: From<L>,
+{
+    fn from_iter<I: IntoIterator<Item = (T, L)>>(iter: I) -> Self {
+        let mut this = Self::new();
+        this.extend(iter);
+        this
+    }
+}
+
+impl FromStr for Targets {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|directive| !directive.is_empty())
+            .map(StaticDirective::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map(|directives| Self(directives.into_iter().collect()))
+    }
+}
+
+impl std::fmt::Display for Targets {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut directives = self.0.iter();
+        if let Some(directive) = directives.next() {
+            std::fmt::Display::fmt(directive, f)?;
+            for directive in directives {
+                write!(f, ",{}", directive)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl IntoIterator for Targets {
+    type Item = (Option<String>, LevelFilter);
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self.0.into_vec().into_iter())
+    }
+}
+
+impl<'a> IntoIterator for &'a Targets {
+    type Item = (Option<&'a str>, LevelFilter);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<C: Collect> Subscribe<C> for Targets {
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        // Because this filter has no dynamic (per-span) state, `enabled`
+        // decisions are final, and the callsite can cache them.
+        if self.0.enabled(metadata) {
+            Interest::always()
+        } else {
+            Interest::never()
+        }
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, _: Context<'_, C>) -> bool {
+        self.0.enabled(metadata)
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(self.0.max_level)
+    }
+}
+
+/// A borrowing iterator over the target-level pairs of a [`Targets`] filter,
+/// returned by [`Targets::iter`].
+#[derive(Debug)]
+pub struct Iter<'a>(std::slice::Iter<'a, StaticDirective>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (Option<&'a str>, LevelFilter);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .next()
+            .map(|directive| (directive.target(), directive.level))
+    }
+}
+
+/// An owning iterator over the target-level pairs of a [`Targets`] filter,
+/// returned by [`Targets::into_iter`].
+///
+/// [`Targets::into_iter`]: struct.Targets.html#impl-IntoIterator
+#[derive(Debug)]
+pub struct IntoIter(std::vec::IntoIter<StaticDirective>);
+
+impl Iterator for IntoIter {
+    type Item = (Option<String>, LevelFilter);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .next()
+            .map(|directive| (directive.target.clone(), directive.level))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expect_parse(s: &str) -> Targets {
+        s.parse()
+            .unwrap_or_else(|err| panic!("filter {:?} should parse: {}", s, err))
+    }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        let filter = expect_parse("hyper=off,my_app=debug,warn");
+        let formatted = filter.to_string();
+        assert_eq!(
+            filter,
+            expect_parse(&formatted),
+            "round-tripped through {:?}",
+            formatted
+        );
+    }
+
+    #[test]
+    fn builder_and_parser_agree() {
+        let parsed = expect_parse("hyper=off,my_app=debug,warn");
+        let built = Targets::new()
+            .with_target("hyper", LevelFilter::OFF)
+            .with_target("my_app", LevelFilter::DEBUG)
+            .with_default(LevelFilter::WARN);
+        assert_eq!(parsed, built);
+    }
+
+    #[test]
+    fn parse_bare_target_enables_all_levels() {
+        let filter = expect_parse("my_app");
+        assert_eq!(
+            filter.iter().collect::<Vec<_>>(),
+            vec![(Some("my_app"), LevelFilter::TRACE)]
+        );
+    }
+
+    #[test]
+    fn iteration_is_most_specific_first() {
+        let filter = expect_parse("warn,foo=info,foo::bar=debug");
+        assert_eq!(
+            filter.iter().collect::<Vec<_>>(),
+            vec![
+                (Some("foo::bar"), LevelFilter::DEBUG),
+                (Some("foo"), LevelFilter::INFO),
+                (None, LevelFilter::WARN),
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicate_directives_are_replaced() {
+        let filter = Targets::new()
+            .with_target("foo", LevelFilter::INFO)
+            .with_target("foo", LevelFilter::DEBUG)
+            .with_default(LevelFilter::ERROR)
+            .with_default(LevelFilter::WARN);
+        assert_eq!(
+            filter.iter().collect::<Vec<_>>(),
+            vec![(Some("foo"), LevelFilter::DEBUG), (None, LevelFilter::WARN)]
+        );
+    }
+
+    #[test]
+    fn size_hint_is_max_directive_level() {
+        let filter = expect_parse("hyper=off,my_app=debug,warn");
+        assert_eq!(
+            crate::Subscribe::<crate::registry::Registry>::max_level_hint(&filter),
+            Some(LevelFilter::DEBUG)
+        );
+    }
+
+    #[test]
+    fn parse_rejects_span_and_field_syntax() {
+        assert!("my_app[span]=debug".parse::<Targets>().is_err());
+        assert!("my_app[{field}]=debug".parse::<Targets>().is_err());
+        assert!("my_app=debug=info".parse::<Targets>().is_err());
+        assert!("my_app=not_a_level".parse::<Targets>().is_err());
+    }
+}
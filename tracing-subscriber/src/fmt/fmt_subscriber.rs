@@ -269,10 +269,35 @@ where
         }
     }
 
-    /// Enable ANSI terminal colors for formatted output.
+    /// Configure ANSI terminal colors for formatted output.
+    ///
+    /// This accepts either a `bool`, unconditionally enabling or disabling
+    /// escape sequences, or an [`Ansi`] mode. In [`Ansi::Auto`] mode, escape
+    /// sequences are emitted only if the configured [`MakeWriter`] [reports
+    /// that it writes to a terminal][is_terminal] and the `NO_COLOR`
+    /// environment variable is not set. This allows enabling colors for, say,
+    /// a terminal on stderr while keeping a log file on stdout free of escape
+    /// sequences.
+    ///
+    /// Note that in `Auto` mode, the writer is consulted when this method is
+    /// called: configure the writer with [`with_writer`] *before* calling
+    /// `with_ansi`.
+    ///
+    /// [`Ansi`]: format::Ansi
+    /// [`Ansi::Auto`]: format::Ansi::Auto
+    /// [`MakeWriter`]: super::writer::MakeWriter
+    /// [is_terminal]: super::writer::MakeWriter::is_terminal
+    /// [`with_writer`]: Subscriber::with_writer
     #[cfg(feature = "ansi")]
     #[cfg_attr(docsrs, doc(cfg(feature = "ansi")))]
-    pub fn with_ansi(self, ansi: bool) -> Subscriber<C, N, format::Format<L, T>, W> {
+    pub fn with_ansi(
+        self,
+        ansi: impl Into<format::Ansi>,
+    ) -> Subscriber<C, N, format::Format<L, T>, W>
+    where
+        W: for<'writer> MakeWriter<'writer>,
+    {
+        let ansi = ansi.into().is_enabled(|| self.make_writer.is_terminal());
         Subscriber {
             fmt_event: self.fmt_event.with_ansi(ansi),
             ..self
@@ -287,6 +312,20 @@ where
         }
     }
 
+    /// Sets which spans' fields are included when an event's span context is
+    /// displayed.
+    ///
+    /// See [`format::SpanFields`].
+    pub fn with_span_fields(
+        self,
+        span_fields: format::SpanFields,
+    ) -> Subscriber<C, N, format::Format<L, T>, W> {
+        Subscriber {
+            fmt_event: self.fmt_event.with_span_fields(span_fields),
+            ..self
+        }
+    }
+
     /// Sets whether or not an event's level is displayed.
     pub fn with_level(self, display_level: bool) -> Subscriber<C, N, format::Format<L, T>, W> {
         Subscriber {
@@ -295,6 +334,25 @@ where
         }
     }
 
+    /// Sets whether or not an event's source code file path is displayed.
+    pub fn with_file(self, display_filename: bool) -> Subscriber<C, N, format::Format<L, T>, W> {
+        Subscriber {
+            fmt_event: self.fmt_event.with_file(display_filename),
+            ..self
+        }
+    }
+
+    /// Sets whether or not an event's source code line number is displayed.
+    pub fn with_line_number(
+        self,
+        display_line_number: bool,
+    ) -> Subscriber<C, N, format::Format<L, T>, W> {
+        Subscriber {
+            fmt_event: self.fmt_event.with_line_number(display_line_number),
+            ..self
+        }
+    }
+
     /// Sets whether or not the [thread ID] of the current thread is displayed
     /// when formatting events
     ///
@@ -337,6 +395,32 @@ where
         }
     }
 
+    /// Sets the subscriber being built to use a [format selected at
+    /// runtime](format::FormatMode).
+    ///
+    /// Unlike [`compact`] and [`pretty`], which select the formatter at the
+    /// type level, the mode may be computed at runtime — for example, from a
+    /// CLI flag. This also installs a [`ModalFields`] field formatter, so that
+    /// [`FormatMode::Pretty`] formats span and event fields with the pretty
+    /// multi-line field format.
+    ///
+    /// [`compact`]: Subscriber::compact
+    /// [`pretty`]: Subscriber::pretty
+    /// [`ModalFields`]: format::ModalFields
+    /// [`FormatMode::Pretty`]: format::FormatMode::Pretty
+    pub fn with_format_mode(
+        self,
+        mode: format::FormatMode,
+    ) -> Subscriber<C, format::ModalFields, format::Format<format::FormatMode, T>, W> {
+        Subscriber {
+            fmt_event: self.fmt_event.with_format_mode(mode),
+            fmt_fields: format::ModalFields::new(mode),
+            fmt_span: self.fmt_span,
+            make_writer: self.make_writer,
+            _inner: self._inner,
+        }
+    }
+
     /// Sets the subscriber being built to use an [excessively pretty, human-readable formatter](crate::fmt::format::Pretty).
     #[cfg(feature = "ansi")]
     #[cfg_attr(docsrs, doc(cfg(feature = "ansi")))]
@@ -979,6 +1063,34 @@ mod test {
         );
     }
 
+    #[test]
+    fn synthesize_span_close_with_location() {
+        let make_writer = MockMakeWriter::default();
+        let subscriber = crate::fmt::Collector::builder()
+            .with_writer(make_writer.clone())
+            .with_level(false)
+            .with_ansi(false)
+            .with_timer(MockTime)
+            .with_file(true)
+            .with_line_number(true)
+            .with_span_events(FmtSpan::CLOSE)
+            .finish();
+
+        let mut line = 0;
+        with_default(subscriber, || {
+            line = line!() + 1;
+            let span1 = tracing::info_span!("span1", x = 42);
+            let _e = span1.enter();
+        });
+        let actual = sanitize_timings(make_writer.get_string());
+        let expected = format!(
+            "fake time span1{{x=42}}: tracing_subscriber::fmt::fmt_subscriber::test: close timing timing at {}:{}\n",
+            file!(),
+            line
+        );
+        assert_eq!(expected, actual.as_str());
+    }
+
     #[test]
     fn synthesize_span_close_no_timing() {
         let make_writer = MockMakeWriter::default();
@@ -1027,6 +1139,51 @@ mod test {
         );
     }
 
+    fn span_fields_output(span_fields: format::SpanFields) -> String {
+        let make_writer = MockMakeWriter::default();
+        let subscriber = crate::fmt::Collector::builder()
+            .with_writer(make_writer.clone())
+            .with_level(false)
+            .with_target(false)
+            .with_ansi(false)
+            .with_timer(MockTime)
+            .with_span_fields(span_fields)
+            .finish();
+
+        with_default(subscriber, || {
+            let outer = tracing::info_span!("outer", x = 1);
+            let _outer = outer.enter();
+            let inner = tracing::info_span!("inner", y = 2);
+            let _inner = inner.enter();
+            tracing::info!("event on inner");
+        });
+        make_writer.get_string()
+    }
+
+    #[test]
+    fn span_fields_all() {
+        assert_eq!(
+            "fake time outer{x=1}:inner{y=2}: event on inner\n",
+            span_fields_output(format::SpanFields::All)
+        );
+    }
+
+    #[test]
+    fn span_fields_leaf() {
+        assert_eq!(
+            "fake time outer:inner{y=2}: event on inner\n",
+            span_fields_output(format::SpanFields::Leaf)
+        );
+    }
+
+    #[test]
+    fn span_fields_none() {
+        assert_eq!(
+            "fake time outer:inner: event on inner\n",
+            span_fields_output(format::SpanFields::None)
+        );
+    }
+
     #[test]
     fn make_writer_based_on_meta() {
         struct MakeByTarget {
@@ -1,4 +1,4 @@
-use super::{Format, FormatEvent, FormatFields, FormatTime};
+use super::{event_timestamp, Format, FormatEvent, FormatFields, FormatTime};
 use crate::{
     field::{RecordFields, VisitOutput},
     fmt::fmt_subscriber::{FmtContext, FormattedFields},
@@ -8,12 +8,12 @@ use serde::ser::{SerializeMap, Serializer as _};
 use serde_json::Serializer;
 use std::{
     collections::BTreeMap,
+    convert::TryFrom,
     fmt::{self, Write},
     io,
 };
 use tracing_core::{
     field::{self, Field},
-    span::Record,
     Collect, Event,
 };
 use tracing_serde::AsSerde;
@@ -190,7 +190,10 @@ where
         C: Collect + for<'a> LookupSpan<'a>,
     {
         let mut timestamp = String::new();
-        self.timer.format_time(&mut timestamp)?;
+        match event_timestamp(event) {
+            Some(time) => self.timer.format_at(&mut timestamp, time)?,
+            None => self.timer.format_time(&mut timestamp)?,
+        }
 
         #[cfg(feature = "tracing-log")]
         let normalized_meta = event.normalized_metadata();
@@ -199,6 +202,17 @@ where
         #[cfg(not(feature = "tracing-log"))]
         let meta = event.metadata();
 
+        // Format the event's fields with the configured field formatter, so
+        // that wrappers like `RedactingFields` apply to event fields just as
+        // they do to span fields. `JsonFields` serializes a JSON object; if a
+        // field formatter producing some other output is in use, fall back to
+        // collecting the fields directly.
+        let mut field_buf = String::new();
+        let formatted_fields: Option<BTreeMap<&str, serde_json::Value>> = ctx
+            .format_fields(&mut field_buf, event)
+            .ok()
+            .and_then(|()| serde_json::from_str(&field_buf).ok());
+
         let mut visit = || {
             let mut serializer = Serializer::new(WriteAdaptor::new(writer));
 
@@ -224,20 +238,43 @@ where
                 None
             };
 
+            // When the field formatter did not produce a JSON object, collect
+            // the event fields with a `JsonVisitor` so that values with a
+            // JSON-specific representation (such as byte slices, which are
+            // base64-encoded) are recorded consistently with span fields.
+            let mut scratch = String::new();
+            let mut visitor = JsonVisitor::new(&mut scratch);
+            let values = match formatted_fields.as_ref() {
+                Some(values) => values,
+                None => {
+                    event.record(&mut visitor);
+                    &visitor.values
+                }
+            };
             if self.format.flatten_event {
-                let mut visitor = tracing_serde::SerdeMapVisitor::new(serializer);
-                event.record(&mut visitor);
-
-                serializer = visitor.take_serializer()?;
+                for (key, value) in values {
+                    serializer.serialize_entry(key, value)?;
+                }
             } else {
-                use tracing_serde::fields::AsMap;
-                serializer.serialize_entry("fields", &event.field_map())?;
+                serializer.serialize_entry("fields", values)?;
             };
 
             if self.display_target {
                 serializer.serialize_entry("target", meta.target())?;
             }
 
+            if self.display_filename {
+                if let Some(file) = meta.file() {
+                    serializer.serialize_entry("file", file)?;
+                }
+            }
+
+            if self.display_line_number {
+                if let Some(line) = meta.line() {
+                    serializer.serialize_entry("line", &line)?;
+                }
+            }
+
             if self.format.display_current_span {
                 if let Some(ref span) = current_span {
                     serializer
@@ -331,7 +368,7 @@ impl<'a> FormatFields<'a> for JsonFields {
     /// By default, this appends a space to the current set of fields if it is
     /// non-empty, and then calls `self.format_fields`. If different behavior is
     /// required, the default implementation of this method can be overridden.
-    fn add_fields(&self, current: &'a mut String, fields: &Record<'_>) -> fmt::Result {
+    fn add_fields<R: RecordFields>(&self, current: &'a mut String, fields: R) -> fmt::Result {
         if !current.is_empty() {
             // If fields were previously recorded on this span, we need to parse
             // the current set of fields as JSON, add the new fields, and
@@ -433,10 +470,39 @@ impl<'a> field::Visit for JsonVisitor<'a> {
 
     /// Visit an unsigned 64-bit integer value.
     fn record_u64(&mut self, field: &Field, value: u64) {
+        // An explicit `event.timestamp` is consumed to produce the event's
+        // `"timestamp"` key; don't also record it as an ordinary field,
+        // matching the `tracing-opentelemetry` subscriber's handling of the
+        // field.
+        if field.name() == super::TIMESTAMP_FIELD_NAME {
+            return;
+        }
         self.values
             .insert(&field.name(), serde_json::Value::from(value));
     }
 
+    /// Visit a signed 128-bit integer value.
+    ///
+    /// Values that fit in an `i64` are recorded as JSON numbers; larger values
+    /// are recorded as strings, since JSON numbers are limited to 64 bits.
+    fn record_i128(&mut self, field: &Field, value: i128) {
+        match i64::try_from(value) {
+            Ok(value) => self.record_i64(field, value),
+            Err(_) => self.record_debug(field, &value),
+        }
+    }
+
+    /// Visit an unsigned 128-bit integer value.
+    ///
+    /// Values that fit in a `u64` are recorded as JSON numbers; larger values
+    /// are recorded as strings, since JSON numbers are limited to 64 bits.
+    fn record_u128(&mut self, field: &Field, value: u128) {
+        match u64::try_from(value) {
+            Ok(value) => self.record_u64(field, value),
+            Err(_) => self.record_debug(field, &value),
+        }
+    }
+
     /// Visit a boolean value.
     fn record_bool(&mut self, field: &Field, value: bool) {
         self.values
@@ -449,6 +515,15 @@ impl<'a> field::Visit for JsonVisitor<'a> {
             .insert(&field.name(), serde_json::Value::from(value));
     }
 
+    /// Visit a byte slice.
+    ///
+    /// Binary data cannot be represented directly in JSON, so it is recorded
+    /// as a base64-encoded string.
+    fn record_bytes(&mut self, field: &Field, value: &[u8]) {
+        self.values
+            .insert(&field.name(), serde_json::Value::from(base64_encode(value)));
+    }
+
     fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
         match field.name() {
             // Skip fields that are actually log metadata that have already been handled
@@ -466,6 +541,34 @@ impl<'a> field::Visit for JsonVisitor<'a> {
     }
 }
 
+/// Encodes `bytes` using the standard base64 alphabet, with padding.
+///
+/// This is implemented here rather than pulling in a base64 crate, as
+/// encoding is only a handful of lines and recorded byte slices are the only
+/// binary data this module handles.
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let group = (chunk[0] as u32) << 16
+            | (chunk.get(1).copied().unwrap_or(0) as u32) << 8
+            | chunk.get(2).copied().unwrap_or(0) as u32;
+        encoded.push(ALPHABET[(group >> 18 & 0x3f) as usize] as char);
+        encoded.push(ALPHABET[(group >> 12 & 0x3f) as usize] as char);
+        encoded.push(if chunk.len() > 1 {
+            ALPHABET[(group >> 6 & 0x3f) as usize] as char
+        } else {
+            '='
+        });
+        encoded.push(if chunk.len() > 2 {
+            ALPHABET[(group & 0x3f) as usize] as char
+        } else {
+            '='
+        });
+    }
+    encoded
+}
+
 /// A bridge between `fmt::Write` and `io::Write`.
 ///
 /// This is needed because tracing-subscriber's FormatEvent expects a fmt::Write
@@ -538,6 +641,54 @@ mod test {
         });
     }
 
+    #[test]
+    fn json_filename_and_line_number() {
+        let collector = collector()
+            .flatten_event(false)
+            .with_current_span(false)
+            .with_span_list(false)
+            .with_file(true)
+            .with_line_number(true);
+        let line = line!() + 7;
+        let expected = format!(
+            "{{\"timestamp\":\"fake time\",\"level\":\"INFO\",\"fields\":{{\"message\":\"some json test\"}},\"target\":\"tracing_subscriber::fmt::format::json::test\",\"file\":{},\"line\":{}}}\n",
+            serde_json::json!(file!()),
+            line
+        );
+        test_json(&expected, collector, || {
+            tracing::info!("some json test");
+        });
+    }
+
+    #[test]
+    fn json_bytes_field_is_base64() {
+        let expected =
+        "{\"timestamp\":\"fake time\",\"level\":\"INFO\",\"target\":\"tracing_subscriber::fmt::format::json::test\",\"fields\":{\"empty\":\"\",\"message\":\"binary\",\"payload\":\"3q2+7w==\"}}\n";
+        let collector = collector()
+            .flatten_event(false)
+            .with_current_span(false)
+            .with_span_list(false);
+        test_json(expected, collector, || {
+            tracing::info!(
+                payload = &b"\xde\xad\xbe\xef"[..],
+                empty = &b""[..],
+                "binary"
+            );
+        });
+    }
+
+    #[test]
+    fn base64_encoding() {
+        // RFC 4648 test vectors.
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foob"), "Zm9vYg==");
+        assert_eq!(base64_encode(b"fooba"), "Zm9vYmE=");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
     #[test]
     fn json_flattened_event() {
         let expected =
@@ -606,6 +757,21 @@ mod test {
         });
     }
 
+    #[test]
+    fn json_escapes_quotes_and_newlines() {
+        // Field values containing quotes, newlines, and backslashes must be
+        // escaped so that the output remains valid JSON.
+        let expected =
+        "{\"timestamp\":\"fake time\",\"level\":\"INFO\",\"target\":\"tracing_subscriber::fmt::format::json::test\",\"fields\":{\"message\":\"a \\\"quoted\\\" message\\nwith a newline\",\"path\":\"C:\\\\logs\"}}\n";
+        let collector = collector()
+            .flatten_event(false)
+            .with_current_span(true)
+            .with_span_list(true);
+        test_json(expected, collector, || {
+            tracing::info!(path = "C:\\logs", "a \"quoted\" message\nwith a newline");
+        });
+    }
+
     #[test]
     fn json_no_span() {
         let expected =
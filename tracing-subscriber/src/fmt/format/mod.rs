@@ -13,7 +13,7 @@ use std::{
 };
 use tracing_core::{
     field::{self, Field, Visit},
-    span, Collect, Event, Level,
+    span, Collect, Event, Level, Metadata,
 };
 
 #[cfg(feature = "tracing-log")]
@@ -34,8 +34,72 @@ mod pretty;
 #[cfg_attr(docsrs, doc(cfg(feature = "ansi")))]
 pub use pretty::*;
 
+mod redact;
+pub use redact::{RedactingFields, Redaction};
+
 use fmt::{Debug, Display};
 
+/// The name of the field used to attach an explicit timestamp to an event.
+///
+/// This matches the convention defined by the `tracing::time` module; see its
+/// documentation for details.
+pub(crate) const TIMESTAMP_FIELD_NAME: &str = "event.timestamp";
+
+/// Returns the explicit timestamp carried by the event's `event.timestamp`
+/// field, if one is present.
+pub(crate) fn event_timestamp(event: &Event<'_>) -> Option<std::time::SystemTime> {
+    struct TimestampVisitor(Option<std::time::SystemTime>);
+
+    impl Visit for TimestampVisitor {
+        fn record_u64(&mut self, field: &Field, value: u64) {
+            if field.name() == TIMESTAMP_FIELD_NAME {
+                self.0 = Some(std::time::UNIX_EPOCH + std::time::Duration::from_nanos(value));
+            }
+        }
+
+        fn record_debug(&mut self, _: &Field, _: &dyn fmt::Debug) {}
+    }
+
+    let mut visitor = TimestampVisitor(None);
+    event.record(&mut visitor);
+    visitor.0
+}
+
+/// The name of the field used to attach a runtime-determined target to an
+/// event, overriding its static target.
+///
+/// This is the same field the `tracing-log` bridge uses to carry a log
+/// record's target, so events from either source are displayed uniformly.
+/// [`EnvFilter`] also matches target directives against this field's value.
+///
+/// [`EnvFilter`]: crate::filter::EnvFilter#dynamic-targets
+pub(crate) const TARGET_FIELD_NAME: &str = "log.target";
+
+/// Returns the target override carried by the event's `log.target` field, if
+/// one is present.
+pub(crate) fn target_override(event: &Event<'_>) -> Option<String> {
+    struct TargetVisitor(Option<String>);
+
+    impl Visit for TargetVisitor {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == TARGET_FIELD_NAME {
+                self.0 = Some(value.to_owned());
+            }
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            if field.name() == TARGET_FIELD_NAME {
+                self.0 = Some(format!("{:?}", value));
+            }
+        }
+    }
+
+    event.metadata().fields().field(TARGET_FIELD_NAME)?;
+    let mut visitor = TargetVisitor(None);
+    event.record(&mut visitor);
+    visitor.0
+}
+
 /// A type that can format a tracing `Event` for a `fmt::Write`.
 ///
 /// `FormatEvent` is primarily used in the context of [`fmt::Collector`] or [`fmt::Subscriber`].
@@ -165,7 +229,7 @@ pub trait FormatFields<'writer> {
     /// By default, this appends a space to the current set of fields if it is
     /// non-empty, and then calls `self.format_fields`. If different behavior is
     /// required, the default implementation of this method can be overridden.
-    fn add_fields(&self, current: &'writer mut String, fields: &span::Record<'_>) -> fmt::Result {
+    fn add_fields<R: RecordFields>(&self, current: &'writer mut String, fields: R) -> fmt::Result {
         if !current.is_empty() {
             current.push(' ');
         }
@@ -240,6 +304,197 @@ pub struct Compact;
 #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
 pub struct Full;
 
+/// A `Format` marker that selects between the [`Full`], [`Compact`], and
+/// [`Pretty`] formats at runtime.
+///
+/// The [`compact`] and [`pretty`] builder methods select the event format at
+/// the type level, so the choice must be made at compile time. A `FormatMode`
+/// can instead be computed at runtime — for example, from a CLI flag — and
+/// passed to the [`with_format_mode`] builder methods:
+///
+/// ```
+/// use tracing_subscriber::fmt::format::FormatMode;
+///
+/// let mode = if std::env::var_os("LOG_COMPACT").is_some() {
+///     FormatMode::Compact
+/// } else {
+///     FormatMode::Full
+/// };
+///
+/// let collector = tracing_subscriber::fmt()
+///     .with_format_mode(mode)
+///     .finish();
+/// # drop(collector);
+/// ```
+///
+/// [`compact`]: Format::compact
+/// [`pretty`]: Format::pretty
+/// [`with_format_mode`]: Format::with_format_mode
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FormatMode {
+    /// The default, [`Full`] event format.
+    Full,
+    /// The single-line [`Compact`] event format.
+    ///
+    /// As with the [`compact`](Format::compact) builder method, this disables
+    /// the event's target.
+    Compact,
+    /// The multi-line [`Pretty`] event format.
+    #[cfg(feature = "ansi")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ansi")))]
+    Pretty,
+}
+
+impl Default for FormatMode {
+    fn default() -> Self {
+        FormatMode::Full
+    }
+}
+
+/// Configures which spans' fields are included when an event's span context is
+/// rendered.
+///
+/// The [`Full`] event format prefixes each event with the full chain of spans
+/// the event occurred in, including every span's fields. With deeply nested
+/// spans, repeating every ancestor's fields on every event can make log lines
+/// very wide; `SpanFields` allows limiting the fields to the innermost span,
+/// or omitting them entirely, while still showing the names of all spans in
+/// the chain. Pass the desired mode to the [`with_span_fields`] builder
+/// methods:
+///
+/// ```
+/// use tracing_subscriber::fmt::format::SpanFields;
+///
+/// let collector = tracing_subscriber::fmt()
+///     .with_span_fields(SpanFields::Leaf)
+///     .finish();
+/// # drop(collector);
+/// ```
+///
+/// [`with_span_fields`]: Format::with_span_fields
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SpanFields {
+    /// Include every span's fields (the default).
+    All,
+    /// Include only the innermost span's fields.
+    ///
+    /// Ancestor spans are rendered by name only.
+    Leaf,
+    /// Include no span fields, rendering just the chain of span names.
+    None,
+}
+
+impl Default for SpanFields {
+    fn default() -> Self {
+        SpanFields::All
+    }
+}
+
+/// A [`FormatFields`] implementation that matches the field format to a
+/// [`FormatMode`].
+///
+/// The [`Pretty`] event format places each field on its own indented line,
+/// which requires the [`Pretty`] *field* formatter; the other modes use the
+/// single-line [`DefaultFields`] format. This type keeps the field format in
+/// sync with the mode selected for the event format, and is installed by the
+/// `with_format_mode` builder methods.
+#[derive(Debug, Default)]
+pub struct ModalFields {
+    mode: FormatMode,
+    full: DefaultFields,
+    #[cfg(feature = "ansi")]
+    pretty: Pretty,
+}
+
+impl ModalFields {
+    /// Returns a new `ModalFields` field formatter for the given mode.
+    pub fn new(mode: FormatMode) -> Self {
+        Self {
+            mode,
+            ..Default::default()
+        }
+    }
+}
+
+impl<'writer> FormatFields<'writer> for ModalFields {
+    fn format_fields<R: RecordFields>(
+        &self,
+        writer: &'writer mut dyn fmt::Write,
+        fields: R,
+    ) -> fmt::Result {
+        match self.mode {
+            #[cfg(feature = "ansi")]
+            FormatMode::Pretty => self.pretty.format_fields(writer, fields),
+            _ => self.full.format_fields(writer, fields),
+        }
+    }
+
+    fn add_fields<R: RecordFields>(&self, current: &'writer mut String, fields: R) -> fmt::Result {
+        match self.mode {
+            #[cfg(feature = "ansi")]
+            FormatMode::Pretty => self.pretty.add_fields(current, fields),
+            _ => self.full.add_fields(current, fields),
+        }
+    }
+}
+
+/// Configures when ANSI terminal escape codes should be used in formatted
+/// output.
+///
+/// This is accepted by the `with_ansi` builder methods, which also accept a
+/// `bool` for backwards compatibility: `true` is equivalent to
+/// [`Ansi::Always`] and `false` to [`Ansi::Never`].
+#[cfg(feature = "ansi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ansi")))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Ansi {
+    /// Emit escape sequences only when the configured [`MakeWriter`] reports
+    /// that it writes to a terminal, and the `NO_COLOR` environment variable
+    /// is not set to a non-empty value.
+    ///
+    /// Whether the writer is a terminal is determined by
+    /// [`MakeWriter::is_terminal`], which defaults to `false` for writers that
+    /// do not implement it.
+    ///
+    /// [`MakeWriter`]: super::writer::MakeWriter
+    /// [`MakeWriter::is_terminal`]: super::writer::MakeWriter::is_terminal
+    Auto,
+    /// Always emit escape sequences.
+    Always,
+    /// Never emit escape sequences.
+    Never,
+}
+
+#[cfg(feature = "ansi")]
+impl From<bool> for Ansi {
+    fn from(ansi: bool) -> Self {
+        if ansi {
+            Ansi::Always
+        } else {
+            Ansi::Never
+        }
+    }
+}
+
+#[cfg(feature = "ansi")]
+impl Ansi {
+    /// Resolves this configuration to a per-writer decision, calling
+    /// `is_terminal` only in `Auto` mode.
+    pub(crate) fn is_enabled(self, is_terminal: impl FnOnce() -> bool) -> bool {
+        match self {
+            Ansi::Always => true,
+            Ansi::Never => false,
+            Ansi::Auto => !Self::no_color() && is_terminal(),
+        }
+    }
+
+    /// Returns whether the `NO_COLOR` environment variable
+    /// (<https://no-color.org/>) disables colored output.
+    fn no_color() -> bool {
+        std::env::var_os("NO_COLOR").map_or(false, |v| !v.is_empty())
+    }
+}
+
 /// A pre-configured event formatter.
 ///
 /// You will usually want to use this as the `FormatEvent` for a `FmtSubscriber`.
@@ -257,6 +512,9 @@ pub struct Format<F = Full, T = SystemTime> {
     pub(crate) display_level: bool,
     pub(crate) display_thread_id: bool,
     pub(crate) display_thread_name: bool,
+    pub(crate) display_filename: bool,
+    pub(crate) display_line_number: bool,
+    pub(crate) span_fields: SpanFields,
 }
 
 impl Default for Format<Full, SystemTime> {
@@ -270,6 +528,9 @@ impl Default for Format<Full, SystemTime> {
             display_level: true,
             display_thread_id: false,
             display_thread_name: false,
+            display_filename: false,
+            display_line_number: false,
+            span_fields: SpanFields::All,
         }
     }
 }
@@ -288,6 +549,9 @@ impl<F, T> Format<F, T> {
             display_level: self.display_level,
             display_thread_id: self.display_thread_id,
             display_thread_name: self.display_thread_name,
+            display_filename: self.display_filename,
+            display_line_number: self.display_line_number,
+            span_fields: self.span_fields,
         }
     }
 
@@ -325,6 +589,30 @@ impl<F, T> Format<F, T> {
             display_level: self.display_level,
             display_thread_id: self.display_thread_id,
             display_thread_name: self.display_thread_name,
+            display_filename: self.display_filename,
+            display_line_number: self.display_line_number,
+            span_fields: self.span_fields,
+        }
+    }
+
+    /// Use the output format selected at runtime by the given [`FormatMode`].
+    ///
+    /// See [`FormatMode`] for details. Unlike [`Format::compact`] and
+    /// [`Format::pretty`], this does not change any of the other display
+    /// options; they apply to whichever format is selected.
+    pub fn with_format_mode(self, mode: FormatMode) -> Format<FormatMode, T> {
+        Format {
+            format: mode,
+            timer: self.timer,
+            ansi: self.ansi,
+            display_target: self.display_target,
+            display_timestamp: self.display_timestamp,
+            display_level: self.display_level,
+            display_thread_id: self.display_thread_id,
+            display_thread_name: self.display_thread_name,
+            display_filename: self.display_filename,
+            display_line_number: self.display_line_number,
+            span_fields: self.span_fields,
         }
     }
 
@@ -355,6 +643,9 @@ impl<F, T> Format<F, T> {
             display_level: self.display_level,
             display_thread_id: self.display_thread_id,
             display_thread_name: self.display_thread_name,
+            display_filename: self.display_filename,
+            display_line_number: self.display_line_number,
+            span_fields: self.span_fields,
         }
     }
 
@@ -379,6 +670,9 @@ impl<F, T> Format<F, T> {
             display_level: self.display_level,
             display_thread_id: self.display_thread_id,
             display_thread_name: self.display_thread_name,
+            display_filename: self.display_filename,
+            display_line_number: self.display_line_number,
+            span_fields: self.span_fields,
         }
     }
 
@@ -393,6 +687,9 @@ impl<F, T> Format<F, T> {
             display_level: self.display_level,
             display_thread_id: self.display_thread_id,
             display_thread_name: self.display_thread_name,
+            display_filename: self.display_filename,
+            display_line_number: self.display_line_number,
+            span_fields: self.span_fields,
         }
     }
 
@@ -409,6 +706,17 @@ impl<F, T> Format<F, T> {
         }
     }
 
+    /// Sets which spans' fields are included when the event's span context is
+    /// rendered.
+    ///
+    /// See [`SpanFields`].
+    pub fn with_span_fields(self, span_fields: SpanFields) -> Format<F, T> {
+        Format {
+            span_fields,
+            ..self
+        }
+    }
+
     /// Sets whether or not an event's level is displayed.
     pub fn with_level(self, display_level: bool) -> Format<F, T> {
         Format {
@@ -439,6 +747,54 @@ impl<F, T> Format<F, T> {
         }
     }
 
+    /// Sets whether or not an event's source code file path is displayed.
+    ///
+    /// This defaults to `false`. Events whose metadata does not include a
+    /// file path — such as those forwarded from the `log` crate without one —
+    /// are unaffected.
+    pub fn with_file(self, display_filename: bool) -> Format<F, T> {
+        Format {
+            display_filename,
+            ..self
+        }
+    }
+
+    /// Sets whether or not an event's source code line number is displayed.
+    ///
+    /// This defaults to `false`. Events whose metadata does not include a
+    /// line number — such as those forwarded from the `log` crate without
+    /// one — are unaffected.
+    pub fn with_line_number(self, display_line_number: bool) -> Format<F, T> {
+        Format {
+            display_line_number,
+            ..self
+        }
+    }
+
+    /// Writes the event's source location, as configured by [`with_file`] and
+    /// [`with_line_number`], e.g. ` at src/server.rs:123`.
+    ///
+    /// [`with_file`]: Format::with_file
+    /// [`with_line_number`]: Format::with_line_number
+    fn format_location(&self, meta: &Metadata<'_>, writer: &mut dyn fmt::Write) -> fmt::Result {
+        let file = if self.display_filename {
+            meta.file()
+        } else {
+            None
+        };
+        let line = if self.display_line_number {
+            meta.line()
+        } else {
+            None
+        };
+        match (file, line) {
+            (Some(file), Some(line)) => write!(writer, " at {}:{}", file, line),
+            (Some(file), None) => write!(writer, " at {}", file),
+            (None, Some(line)) => write!(writer, " at line {}", line),
+            (None, None) => Ok(()),
+        }
+    }
+
     fn format_level(&self, level: Level, writer: &mut dyn fmt::Write) -> fmt::Result
     where
         F: LevelNames,
@@ -461,7 +817,11 @@ impl<F, T> Format<F, T> {
     }
 
     #[inline]
-    fn format_timestamp(&self, writer: &mut dyn fmt::Write) -> fmt::Result
+    fn format_timestamp(
+        &self,
+        writer: &mut dyn fmt::Write,
+        timestamp: Option<std::time::SystemTime>,
+    ) -> fmt::Result
     where
         T: FormatTime,
     {
@@ -470,6 +830,13 @@ impl<F, T> Format<F, T> {
             return Ok(());
         }
 
+        // If the event carries an explicit timestamp, format that; otherwise,
+        // format the current time.
+        let write_time = |writer: &mut dyn fmt::Write| match timestamp {
+            Some(time) => self.timer.format_at(writer, time),
+            None => self.timer.format_time(writer),
+        };
+
         // If ANSI color codes are enabled, format the timestamp with ANSI
         // colors.
         #[cfg(feature = "ansi")]
@@ -477,14 +844,14 @@ impl<F, T> Format<F, T> {
             if self.ansi {
                 let style = Style::new().dimmed();
                 write!(writer, "{}", style.prefix())?;
-                self.timer.format_time(writer)?;
+                write_time(writer)?;
                 write!(writer, "{} ", style.suffix())?;
                 return Ok(());
             }
         }
 
         // Otherwise, just format the timestamp without ANSI formatting.
-        self.timer.format_time(writer)?;
+        write_time(writer)?;
         writer.write_char(' ')
     }
 }
@@ -549,7 +916,7 @@ where
         #[cfg(not(feature = "tracing-log"))]
         let meta = event.metadata();
 
-        self.format_timestamp(writer)?;
+        self.format_timestamp(writer, event_timestamp(event))?;
         self.format_level(*meta.level(), writer)?;
 
         if self.display_thread_name {
@@ -573,19 +940,25 @@ where
         let full_ctx = {
             #[cfg(feature = "ansi")]
             {
-                FullCtx::new(ctx, event.parent(), self.ansi)
+                FullCtx::new(ctx, event.parent(), self.span_fields, self.ansi)
             }
             #[cfg(not(feature = "ansi"))]
             {
-                FullCtx::new(ctx, event.parent())
+                FullCtx::new(ctx, event.parent(), self.span_fields)
             }
         };
 
         write!(writer, "{}", full_ctx)?;
         if self.display_target {
-            write!(writer, "{}: ", meta.target())?;
+            let target = target_override(event);
+            write!(
+                writer,
+                "{}: ",
+                target.as_deref().unwrap_or_else(|| meta.target())
+            )?;
         }
         ctx.format_fields(writer, event)?;
+        self.format_location(meta, writer)?;
         writeln!(writer)
     }
 }
@@ -609,7 +982,7 @@ where
         #[cfg(not(feature = "tracing-log"))]
         let meta = event.metadata();
 
-        self.format_timestamp(writer)?;
+        self.format_timestamp(writer, event_timestamp(event))?;
         self.format_level(*meta.level(), writer)?;
 
         if self.display_thread_name {
@@ -631,7 +1004,8 @@ where
         }
 
         if self.display_target {
-            let target = meta.target();
+            let target_override = target_override(event);
+            let target = target_override.as_deref().unwrap_or_else(|| meta.target());
             #[cfg(feature = "ansi")]
             let target = if self.ansi {
                 Style::new().bold().paint(target)
@@ -643,6 +1017,7 @@ where
         }
 
         ctx.format_fields(writer, event)?;
+        self.format_location(meta, writer)?;
 
         let span = event
             .parent()
@@ -671,6 +1046,57 @@ where
     }
 }
 
+impl<F, T> Format<F, T> {
+    /// Returns a copy of this `Format` with the provided `format` marker,
+    /// borrowing the timer.
+    fn with_format_marker<F2>(&self, format: F2) -> Format<F2, &T> {
+        Format {
+            format,
+            timer: &self.timer,
+            ansi: self.ansi,
+            display_timestamp: self.display_timestamp,
+            display_target: self.display_target,
+            display_level: self.display_level,
+            display_thread_id: self.display_thread_id,
+            display_thread_name: self.display_thread_name,
+            display_filename: self.display_filename,
+            display_line_number: self.display_line_number,
+            span_fields: self.span_fields,
+        }
+    }
+}
+
+impl<C, N, T> FormatEvent<C, N> for Format<FormatMode, T>
+where
+    C: Collect + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+    T: FormatTime,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, C, N>,
+        writer: &mut dyn fmt::Write,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        match self.format {
+            FormatMode::Full => self
+                .with_format_marker(Full)
+                .format_event(ctx, writer, event),
+            FormatMode::Compact => {
+                let mut format = self.with_format_marker(Compact);
+                // Match the behavior of `Format::compact`, which disables the
+                // event's target.
+                format.display_target = false;
+                format.format_event(ctx, writer, event)
+            }
+            #[cfg(feature = "ansi")]
+            FormatMode::Pretty => self
+                .with_format_marker(Pretty::default())
+                .format_event(ctx, writer, event),
+        }
+    }
+}
+
 // === impl FormatFields ===
 
 impl<'writer, M> FormatFields<'writer> for M
@@ -730,6 +1156,28 @@ impl<'a> MakeVisitor<&'a mut dyn Write> for DefaultFields {
     }
 }
 
+/// The maximum number of bytes of a binary field value rendered as hex before
+/// the output is truncated.
+const MAX_HEX_BYTES: usize = 64;
+
+/// Formats a byte slice as a bounded-length lowercase hex string.
+///
+/// Slices longer than [`MAX_HEX_BYTES`] are truncated, with a trailing `…`
+/// marking the elided bytes.
+struct HexBytes<'a>(&'a [u8]);
+
+impl<'a> fmt::Debug for HexBytes<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0.iter().take(MAX_HEX_BYTES) {
+            write!(f, "{:02x}", byte)?;
+        }
+        if self.0.len() > MAX_HEX_BYTES {
+            f.write_str("…")?;
+        }
+        Ok(())
+    }
+}
+
 // === impl DefaultVisitor ===
 
 impl<'a> DefaultVisitor<'a> {
@@ -769,6 +1217,19 @@ impl<'a> field::Visit for DefaultVisitor<'a> {
         }
     }
 
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        // An explicit `event.timestamp` is consumed to override the displayed
+        // time; don't also render it as an ordinary field, matching the
+        // `tracing-opentelemetry` subscriber's handling of the field.
+        if field.name() != TIMESTAMP_FIELD_NAME {
+            self.record_debug(field, &value)
+        }
+    }
+
+    fn record_bytes(&mut self, field: &Field, value: &[u8]) {
+        self.record_debug(field, &HexBytes(value))
+    }
+
     fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
         if let Some(source) = value.source() {
             self.record_debug(field, &format_args!("{}, {}: {}", value, field, source))
@@ -782,12 +1243,16 @@ impl<'a> field::Visit for DefaultVisitor<'a> {
             return;
         }
 
+        // Skip fields that are actually log metadata that have already been handled,
+        // without padding the output as though a field were written.
+        #[cfg(feature = "tracing-log")]
+        if field.name().starts_with("log.") {
+            return;
+        }
+
         self.maybe_pad();
         self.result = match field.name() {
             "message" => write!(self.writer, "{:?}", value),
-            // Skip fields that are actually log metadata that have already been handled
-            #[cfg(feature = "tracing-log")]
-            name if name.starts_with("log.") => Ok(()),
             name if name.starts_with("r#") => write!(self.writer, "{}={:?}", &name[2..], value),
             name => write!(self.writer, "{}={:?}", name, value),
         };
@@ -823,6 +1288,7 @@ where
 {
     ctx: &'a FmtContext<'a, C, N>,
     span: Option<&'a span::Id>,
+    span_fields: SpanFields,
     #[cfg(feature = "ansi")]
     ansi: bool,
 }
@@ -836,14 +1302,28 @@ where
     pub(crate) fn new(
         ctx: &'a FmtContext<'a, C, N>,
         span: Option<&'a span::Id>,
+        span_fields: SpanFields,
         ansi: bool,
     ) -> Self {
-        Self { ctx, span, ansi }
+        Self {
+            ctx,
+            span,
+            span_fields,
+            ansi,
+        }
     }
 
     #[cfg(not(feature = "ansi"))]
-    pub(crate) fn new(ctx: &'a FmtContext<'a, C, N>, span: Option<&'a span::Id>) -> Self {
-        Self { ctx, span }
+    pub(crate) fn new(
+        ctx: &'a FmtContext<'a, C, N>,
+        span: Option<&'a span::Id>,
+        span_fields: SpanFields,
+    ) -> Self {
+        Self {
+            ctx,
+            span,
+            span_fields,
+        }
     }
 
     fn bold(&self) -> Style {
@@ -872,18 +1352,28 @@ where
             .and_then(|id| self.ctx.ctx.span(&id))
             .or_else(|| self.ctx.ctx.lookup_current());
 
+        // The scope is looked up from the innermost (leaf) span; remember its
+        // ID so that `SpanFields::Leaf` can tell it apart from its ancestors.
+        let leaf = span.as_ref().map(|span| span.id());
         let scope = span.into_iter().flat_map(|span| span.scope().from_root());
 
         for span in scope {
             write!(f, "{}", bold.paint(span.metadata().name()))?;
             seen = true;
 
-            let ext = span.extensions();
-            let fields = &ext
-                .get::<FormattedFields<N>>()
-                .expect("Unable to find FormattedFields in extensions; this is a bug");
-            if !fields.is_empty() {
-                write!(f, "{}{}{}", bold.paint("{"), fields, bold.paint("}"))?;
+            let display_fields = match self.span_fields {
+                SpanFields::All => true,
+                SpanFields::Leaf => Some(span.id()) == leaf,
+                SpanFields::None => false,
+            };
+            if display_fields {
+                let ext = span.extensions();
+                let fields = &ext
+                    .get::<FormattedFields<N>>()
+                    .expect("Unable to find FormattedFields in extensions; this is a bug");
+                if !fields.is_empty() {
+                    write!(f, "{}{}{}", bold.paint("{"), fields, bold.paint("}"))?;
+                }
             }
             f.write_char(':')?;
         }
@@ -1261,6 +1751,13 @@ pub(super) mod test {
         fn format_time(&self, w: &mut dyn fmt::Write) -> fmt::Result {
             write!(w, "fake time")
         }
+
+        fn format_at(&self, w: &mut dyn fmt::Write, time: std::time::SystemTime) -> fmt::Result {
+            let elapsed = time
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("time should be after the epoch");
+            write!(w, "fake time at {}", elapsed.as_secs())
+        }
     }
 
     #[test]
@@ -1317,6 +1814,57 @@ pub(super) mod test {
         run_test(subscriber, make_writer, expected);
     }
 
+    #[cfg(feature = "ansi")]
+    #[test]
+    fn with_ansi_auto() {
+        use super::Ansi;
+        use crate::fmt::writer::MakeWriter;
+
+        #[derive(Clone)]
+        struct Terminal {
+            inner: MockMakeWriter,
+            is_terminal: bool,
+        }
+
+        impl<'a> MakeWriter<'a> for Terminal {
+            type Writer = <MockMakeWriter as MakeWriter<'a>>::Writer;
+
+            fn make_writer(&'a self) -> Self::Writer {
+                self.inner.make_writer()
+            }
+
+            fn is_terminal(&self) -> bool {
+                self.is_terminal
+            }
+        }
+
+        let ansi = "\u{1b}[2mfake time\u{1b}[0m \u{1b}[32m INFO\u{1b}[0m tracing_subscriber::fmt::format::test: hello\n";
+        let plain = "fake time  INFO tracing_subscriber::fmt::format::test: hello\n";
+
+        let run = |is_terminal: bool, expected: &str| {
+            let make_writer = Terminal {
+                inner: MockMakeWriter::default(),
+                is_terminal,
+            };
+            let subscriber = crate::fmt::Collector::builder()
+                .with_writer(make_writer.clone())
+                .with_ansi(Ansi::Auto)
+                .with_timer(MockTime);
+            run_test(subscriber, make_writer.inner, expected);
+        };
+
+        // A terminal writer gets escape sequences; a non-terminal one doesn't.
+        run(true, ansi);
+        run(false, plain);
+
+        // `NO_COLOR` disables escape sequences even for a terminal. This is
+        // checked in the same test as the cases above so that the variable is
+        // never set while another test is resolving `Ansi::Auto` concurrently.
+        std::env::set_var("NO_COLOR", "1");
+        run(true, plain);
+        std::env::remove_var("NO_COLOR");
+    }
+
     #[cfg(feature = "ansi")]
     fn test_ansi(is_ansi: bool, expected: &str) {
         let make_writer = MockMakeWriter::default();
@@ -1333,6 +1881,142 @@ pub(super) mod test {
         assert_eq!(expected, buf.get_string())
     }
 
+    // The `log.target` field is only hidden from the formatted fields when
+    // the `tracing-log` feature is enabled.
+    #[cfg(feature = "tracing-log")]
+    #[test]
+    fn target_override_field_replaces_static_target() {
+        let make_writer = MockMakeWriter::default();
+        let subscriber = crate::fmt::Collector::builder()
+            .with_writer(make_writer.clone())
+            .with_timer(MockTime);
+        #[cfg(feature = "ansi")]
+        let subscriber = subscriber.with_ansi(false);
+        let _default = set_default(&subscriber.into());
+        tracing::info!(log.target = "plugin_foo", "hello");
+        assert_eq!(
+            "fake time  INFO plugin_foo: hello\n",
+            make_writer.get_string()
+        );
+    }
+
+    #[test]
+    fn with_file_and_line_number() {
+        let make_writer = MockMakeWriter::default();
+        let subscriber = crate::fmt::Collector::builder()
+            .with_writer(make_writer.clone())
+            .with_file(true)
+            .with_line_number(true)
+            .with_timer(MockTime);
+        #[cfg(feature = "ansi")]
+        let subscriber = subscriber.with_ansi(false);
+        let _default = set_default(&subscriber.into());
+        let line = line!() + 1;
+        tracing::info!("hello");
+        let expected = format!(
+            "fake time  INFO tracing_subscriber::fmt::format::test: hello at {}:{}\n",
+            file!(),
+            line
+        );
+        assert_eq!(expected, make_writer.get_string());
+    }
+
+    #[test]
+    fn with_file_only() {
+        let make_writer = MockMakeWriter::default();
+        let subscriber = crate::fmt::Collector::builder()
+            .with_writer(make_writer.clone())
+            .with_file(true)
+            .with_timer(MockTime);
+        #[cfg(feature = "ansi")]
+        let subscriber = subscriber.with_ansi(false);
+        let _default = set_default(&subscriber.into());
+        tracing::info!("hello");
+        let expected = format!(
+            "fake time  INFO tracing_subscriber::fmt::format::test: hello at {}\n",
+            file!()
+        );
+        assert_eq!(expected, make_writer.get_string());
+    }
+
+    #[test]
+    fn compact_with_line_number() {
+        let make_writer = MockMakeWriter::default();
+        let subscriber = crate::fmt::Collector::builder()
+            .with_writer(make_writer.clone())
+            .with_line_number(true)
+            .with_timer(MockTime)
+            .compact();
+        #[cfg(feature = "ansi")]
+        let subscriber = subscriber.with_ansi(false);
+        let _default = set_default(&subscriber.into());
+        let line = line!() + 1;
+        tracing::info!("hello");
+        let expected = format!("fake time I hello at line {}\n", line);
+        assert_eq!(expected, make_writer.get_string());
+    }
+
+    #[test]
+    fn hex_bytes_formatting() {
+        use super::{HexBytes, MAX_HEX_BYTES};
+
+        assert_eq!(format!("{:?}", HexBytes(&[])), "");
+        assert_eq!(
+            format!("{:?}", HexBytes(&[0xde, 0xad, 0xbe, 0xef])),
+            "deadbeef"
+        );
+
+        // A slice of exactly the maximum length is not truncated...
+        let max = vec![0xabu8; MAX_HEX_BYTES];
+        assert_eq!(format!("{:?}", HexBytes(&max)), "ab".repeat(MAX_HEX_BYTES));
+
+        // ...but one byte more is.
+        let over = vec![0xabu8; MAX_HEX_BYTES + 1];
+        assert_eq!(
+            format!("{:?}", HexBytes(&over)),
+            format!("{}…", "ab".repeat(MAX_HEX_BYTES))
+        );
+    }
+
+    #[test]
+    fn bytes_field_is_rendered_as_hex() {
+        let make_writer = MockMakeWriter::default();
+        let subscriber = crate::fmt::Collector::builder()
+            .with_writer(make_writer.clone())
+            .with_level(false)
+            .with_ansi(false)
+            .with_timer(MockTime);
+        let _default = set_default(&subscriber.into());
+
+        tracing::info!(id = &b"\xde\xad\xbe\xef"[..], "hello");
+        assert_eq!(
+            "fake time tracing_subscriber::fmt::format::test: hello id=deadbeef\n",
+            make_writer.get_string()
+        );
+    }
+
+    #[test]
+    fn explicit_event_timestamp_is_used() {
+        let make_writer = MockMakeWriter::default();
+        let collector = crate::fmt::Collector::builder()
+            .with_writer(make_writer.clone())
+            .with_level(false)
+            .with_ansi(false)
+            .with_timer(MockTime)
+            .finish();
+
+        with_default(collector, || {
+            tracing::info!(event.timestamp = 42_000_000_000u64, "hello");
+        });
+
+        // The `event.timestamp` field is consumed to produce the displayed
+        // time, and is not rendered as an ordinary field.
+        assert_eq!(
+            "fake time at 42 tracing_subscriber::fmt::format::test: hello\n",
+            make_writer.get_string()
+        );
+    }
+
     #[test]
     fn overridden_parents() {
         let make_writer = MockMakeWriter::default();
@@ -1431,4 +2115,58 @@ pub(super) mod test {
         assert!(!f.contains(FmtSpan::EXIT));
         assert!(f.contains(FmtSpan::CLOSE));
     }
+
+    #[cfg(feature = "ansi")]
+    fn format_mode_output(mode: super::FormatMode) -> (String, u32) {
+        let make_writer = MockMakeWriter::default();
+        let collector = crate::fmt::Collector::builder()
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .with_timer(MockTime)
+            .with_format_mode(mode)
+            .finish();
+        let _default = set_default(&Dispatch::new(collector));
+        let span = tracing::info_span!("span", answer = 42);
+        let _e = span.enter();
+        let event_line = line!() + 1;
+        tracing::info!(question = "life", "hello");
+        (make_writer.get_string(), event_line)
+    }
+
+    #[cfg(feature = "ansi")]
+    #[test]
+    fn format_mode_full() {
+        let (actual, _) = format_mode_output(super::FormatMode::Full);
+        assert_eq!(
+            "fake time  INFO span{answer=42}: tracing_subscriber::fmt::format::test: \
+             hello question=\"life\"\n",
+            actual
+        );
+    }
+
+    #[cfg(feature = "ansi")]
+    #[test]
+    fn format_mode_compact() {
+        let (actual, _) = format_mode_output(super::FormatMode::Compact);
+        assert_eq!("fake time I hello question=\"life\" answer=42\n", actual);
+    }
+
+    #[cfg(feature = "ansi")]
+    #[test]
+    fn format_mode_pretty() {
+        let (actual, event_line) = format_mode_output(super::FormatMode::Pretty);
+        // Note that the pretty *field* formatter manages its own ANSI styling,
+        // so the span's fields are bold even though `with_ansi(false)` was set
+        // on the collector; this matches the behavior of the `pretty()`
+        // builder with the default field formatter.
+        let expected = format!(
+            "  fake time  INFO tracing_subscriber::fmt::format::test: hello, \
+             question: \"life\"\n    at {}:{}\n    in \
+             tracing_subscriber::fmt::format::test::span with \
+             \u{1b}[1manswer\u{1b}[0m: 42\n\n",
+            file!(),
+            event_line,
+        );
+        assert_eq!(expected, actual);
+    }
 }
@@ -105,7 +105,7 @@ where
         let meta = event.metadata();
         write!(writer, "  ")?;
 
-        self.format_timestamp(writer)?;
+        self.format_timestamp(writer, super::event_timestamp(event))?;
 
         let style = if self.display_level && self.ansi {
             Pretty::style_for(meta.level())
@@ -229,7 +229,7 @@ impl<'writer> FormatFields<'writer> for Pretty {
         v.finish()
     }
 
-    fn add_fields(&self, current: &'writer mut String, fields: &span::Record<'_>) -> fmt::Result {
+    fn add_fields<R: RecordFields>(&self, current: &'writer mut String, fields: R) -> fmt::Result {
         let empty = current.is_empty();
         let mut v = PrettyVisitor::new(current, empty);
         fields.record(&mut v);
@@ -325,6 +325,19 @@ impl<'a> field::Visit for PrettyVisitor<'a> {
         }
     }
 
+    fn record_bytes(&mut self, field: &Field, value: &[u8]) {
+        self.record_debug(field, &HexBytes(value))
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        // An explicit `event.timestamp` is consumed to override the displayed
+        // time; don't also render it as an ordinary field, matching the
+        // `tracing-opentelemetry` subscriber's handling of the field.
+        if field.name() != super::TIMESTAMP_FIELD_NAME {
+            self.record_debug(field, &value)
+        }
+    }
+
     fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
         if let Some(source) = value.source() {
             let bold = self.bold();
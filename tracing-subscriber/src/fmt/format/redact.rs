@@ -0,0 +1,404 @@
+//! Redaction of sensitive field values before they are formatted.
+use super::{DefaultFields, FormatFields};
+use crate::field::{RecordFields, RecordFieldsMarker};
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use tracing_core::field::{Field, Visit};
+
+/// How a redacted field's value is rendered.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Redaction {
+    /// The field is omitted from the output entirely.
+    Drop,
+    /// The field's value is replaced with `[REDACTED]`.
+    ///
+    /// This is the default.
+    Replace,
+    /// The field's value is replaced with a hash of it, so that records with
+    /// equal values can still be correlated without revealing them.
+    ///
+    /// The hash is not cryptographic: a low-entropy value (such as a social
+    /// security number) can be recovered by hashing every candidate. Use
+    /// [`Redaction::Drop`] or [`Redaction::Replace`] when correlation is not
+    /// needed.
+    Hash,
+}
+
+impl Default for Redaction {
+    fn default() -> Self {
+        Redaction::Replace
+    }
+}
+
+/// A [`FormatFields`] wrapper that redacts the values of configured fields
+/// before the inner formatter sees them.
+///
+/// Fields are selected by exact name or by name prefix, and their values are
+/// dropped, replaced, or hashed according to the configured [`Redaction`].
+/// Because the wrapper interposes on *formatting*, it applies everywhere the
+/// wrapped formatter is used: event fields, span fields recorded at creation
+/// (which the [`fmt` subscriber] stores formatted in span extensions), and
+/// fields added to a span later with [`Span::record`]. Sensitive values thus
+/// never reach the stored representation, regardless of which crate emitted
+/// them.
+///
+/// # Examples
+///
+/// ```rust
+/// use tracing_subscriber::fmt::format::RedactingFields;
+///
+/// let collector = tracing_subscriber::fmt()
+///     .fmt_fields(
+///         RedactingFields::default()
+///             .redact("password")
+///             .redact("ssn")
+///             .redact_prefix("card_"),
+///     )
+///     .finish();
+/// ```
+///
+/// [`fmt` subscriber]: super::super::Subscriber
+/// [`Span::record`]: tracing::Span::record()
+#[derive(Debug)]
+pub struct RedactingFields<F = DefaultFields> {
+    inner: F,
+    config: RedactConfig,
+}
+
+impl Default for RedactingFields {
+    fn default() -> Self {
+        Self::new(DefaultFields::default())
+    }
+}
+
+/// The field-selection and replacement configuration, separated from the
+/// wrapped formatter so that the visitor need not be generic over it.
+#[derive(Debug, Default)]
+struct RedactConfig {
+    names: HashSet<String>,
+    prefixes: Vec<String>,
+    strategy: Redaction,
+}
+
+impl RedactConfig {
+    fn is_redacted(&self, name: &str) -> bool {
+        self.names.contains(name)
+            || self
+                .prefixes
+                .iter()
+                .any(|prefix| name.starts_with(prefix.as_str()))
+    }
+}
+
+impl<F> RedactingFields<F> {
+    /// Returns a new `RedactingFields` wrapping the given [`FormatFields`]
+    /// implementation.
+    ///
+    /// Until fields are configured with [`redact`] or [`redact_prefix`],
+    /// every field is passed through unchanged.
+    ///
+    /// [`redact`]: RedactingFields::redact()
+    /// [`redact_prefix`]: RedactingFields::redact_prefix()
+    pub fn new(inner: F) -> Self {
+        Self {
+            inner,
+            config: RedactConfig::default(),
+        }
+    }
+
+    /// Redacts the value of every field with exactly this name.
+    pub fn redact(mut self, name: impl Into<String>) -> Self {
+        self.config.names.insert(name.into());
+        self
+    }
+
+    /// Redacts the value of every field whose name starts with this prefix.
+    pub fn redact_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.config.prefixes.push(prefix.into());
+        self
+    }
+
+    /// Sets how redacted values are rendered.
+    ///
+    /// Defaults to [`Redaction::Replace`].
+    pub fn with_strategy(mut self, strategy: Redaction) -> Self {
+        self.config.strategy = strategy;
+        self
+    }
+}
+
+impl<'writer, F> FormatFields<'writer> for RedactingFields<F>
+where
+    F: FormatFields<'writer>,
+{
+    fn format_fields<R: RecordFields>(
+        &self,
+        writer: &'writer mut dyn fmt::Write,
+        fields: R,
+    ) -> fmt::Result {
+        self.inner.format_fields(
+            writer,
+            Redacted {
+                fields,
+                config: &self.config,
+            },
+        )
+    }
+
+    fn add_fields<R: RecordFields>(&self, current: &'writer mut String, fields: R) -> fmt::Result {
+        self.inner.add_fields(
+            current,
+            Redacted {
+                fields,
+                config: &self.config,
+            },
+        )
+    }
+}
+
+/// A [`RecordFields`] wrapper that records through a [`RedactingVisitor`].
+struct Redacted<'a, R> {
+    fields: R,
+    config: &'a RedactConfig,
+}
+
+impl<'a, R: RecordFields> crate::sealed::Sealed<RecordFieldsMarker> for Redacted<'a, R> {}
+
+impl<'a, R: RecordFields> RecordFields for Redacted<'a, R> {
+    fn record(&self, visitor: &mut dyn Visit) {
+        self.fields.record(&mut RedactingVisitor {
+            inner: visitor,
+            config: self.config,
+        });
+    }
+}
+
+/// Forwards non-redacted fields to the inner visitor unchanged, and redacted
+/// fields according to the configured strategy.
+struct RedactingVisitor<'a> {
+    inner: &'a mut dyn Visit,
+    config: &'a RedactConfig,
+}
+
+impl<'a> RedactingVisitor<'a> {
+    /// Records the redacted form of a field whose value renders as `repr`.
+    ///
+    /// `repr` is only evaluated for the [`Redaction::Hash`] strategy.
+    fn redact(&mut self, field: &Field, repr: impl FnOnce() -> String) {
+        match self.config.strategy {
+            Redaction::Drop => {}
+            Redaction::Replace => self.inner.record_str(field, "[REDACTED]"),
+            Redaction::Hash => {
+                let mut hasher = DefaultHasher::new();
+                repr().hash(&mut hasher);
+                self.inner
+                    .record_str(field, &format!("{:016x}", hasher.finish()));
+            }
+        }
+    }
+}
+
+macro_rules! forward_record {
+    ($($record:ident: $ty:ty),+ $(,)?) => {
+        $(
+            fn $record(&mut self, field: &Field, value: $ty) {
+                if self.config.is_redacted(field.name()) {
+                    self.redact(field, || format!("{:?}", value));
+                } else {
+                    self.inner.$record(field, value);
+                }
+            }
+        )+
+    };
+}
+
+impl<'a> Visit for RedactingVisitor<'a> {
+    forward_record! {
+        record_f64: f64,
+        record_i64: i64,
+        record_u64: u64,
+        record_i128: i128,
+        record_u128: u128,
+        record_bool: bool,
+        record_bytes: &[u8],
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if self.config.is_redacted(field.name()) {
+            // Hash the string itself, not its `Debug` representation, so the
+            // hash does not depend on how the value was recorded.
+            self.redact(field, || value.to_string());
+        } else {
+            self.inner.record_str(field, value);
+        }
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        if self.config.is_redacted(field.name()) {
+            self.redact(field, || value.to_string());
+        } else {
+            self.inner.record_error(field, value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if self.config.is_redacted(field.name()) {
+            self.redact(field, || format!("{:?}", value));
+        } else {
+            self.inner.record_debug(field, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fmt::{format::test::MockTime, test::MockMakeWriter};
+    use tracing::collect::with_default;
+
+    fn run(fields: RedactingFields, producer: impl FnOnce()) -> String {
+        let make_writer = MockMakeWriter::default();
+        let collector = crate::fmt::Collector::builder()
+            .fmt_fields(fields)
+            .with_writer(make_writer.clone())
+            .with_ansi(false)
+            .with_timer(MockTime)
+            .finish();
+
+        with_default(collector, producer);
+
+        let buf = make_writer.buf();
+        String::from_utf8(buf.to_vec()).expect("output should be UTF-8")
+    }
+
+    #[test]
+    fn redacted_event_fields_are_replaced() {
+        let output = run(RedactingFields::default().redact("password"), || {
+            tracing::info!(username = "alice", password = "hunter2", "login");
+        });
+        assert!(output.contains("username=\"alice\""), "{:?}", output);
+        assert!(output.contains("password=\"[REDACTED]\""), "{:?}", output);
+        assert!(!output.contains("hunter2"), "{:?}", output);
+    }
+
+    #[test]
+    fn drop_strategy_omits_the_field_entirely() {
+        let output = run(
+            RedactingFields::default()
+                .redact("password")
+                .with_strategy(Redaction::Drop),
+            || {
+                tracing::info!(username = "alice", password = "hunter2", "login");
+            },
+        );
+        assert!(output.contains("username=\"alice\""), "{:?}", output);
+        assert!(!output.contains("password"), "{:?}", output);
+        assert!(!output.contains("hunter2"), "{:?}", output);
+    }
+
+    #[test]
+    fn hash_strategy_is_deterministic_and_hides_the_value() {
+        let output = run(
+            RedactingFields::default()
+                .redact("password")
+                .with_strategy(Redaction::Hash),
+            || {
+                tracing::info!(password = "hunter2", "first");
+                tracing::info!(password = "hunter2", "second");
+            },
+        );
+        assert!(!output.contains("hunter2"), "{:?}", output);
+        let hashes: Vec<_> = output
+            .lines()
+            .map(|line| {
+                line.split("password=")
+                    .nth(1)
+                    .expect("every line should have a password field")
+                    .split_whitespace()
+                    .next()
+                    .expect("the field should have a value")
+            })
+            .collect();
+        assert_eq!(hashes.len(), 2);
+        assert_eq!(
+            hashes[0], hashes[1],
+            "equal values should hash identically so records can be correlated"
+        );
+    }
+
+    #[test]
+    fn prefixes_match_any_field_they_start() {
+        let output = run(RedactingFields::default().redact_prefix("card_"), || {
+            tracing::info!(card_number = "4111", card_cvv = 123, attempt = 1, "charge");
+        });
+        assert!(
+            output.contains("card_number=\"[REDACTED]\""),
+            "{:?}",
+            output
+        );
+        assert!(output.contains("card_cvv=\"[REDACTED]\""), "{:?}", output);
+        assert!(output.contains("attempt=1"), "{:?}", output);
+        assert!(!output.contains("4111"), "{:?}", output);
+        assert!(!output.contains("123"), "{:?}", output);
+    }
+
+    #[test]
+    fn span_fields_are_redacted_before_storage() {
+        let output = run(RedactingFields::default().redact("ssn"), || {
+            let span = tracing::info_span!("user", ssn = "078-05-1120", id = 42);
+            let _guard = span.enter();
+            tracing::info!("lookup");
+        });
+        assert!(output.contains("ssn=\"[REDACTED]\""), "{:?}", output);
+        assert!(output.contains("id=42"), "{:?}", output);
+        assert!(!output.contains("078-05-1120"), "{:?}", output);
+    }
+
+    #[test]
+    fn fields_recorded_after_span_creation_are_redacted() {
+        let output = run(RedactingFields::default().redact("password"), || {
+            let span =
+                tracing::info_span!("login", user = "alice", password = tracing::field::Empty);
+            span.record("password", &"hunter2");
+            let _guard = span.enter();
+            tracing::info!("authenticated");
+        });
+        assert!(output.contains("password=\"[REDACTED]\""), "{:?}", output);
+        assert!(output.contains("user=\"alice\""), "{:?}", output);
+        assert!(!output.contains("hunter2"), "{:?}", output);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_output_is_redacted() {
+        use crate::fmt::format::JsonFields;
+
+        let make_writer = MockMakeWriter::default();
+        let collector = crate::fmt::Collector::builder()
+            .json()
+            .fmt_fields(RedactingFields::new(JsonFields::new()).redact("password"))
+            .with_writer(make_writer.clone())
+            .with_timer(MockTime)
+            .finish();
+
+        with_default(collector, || {
+            let span = tracing::info_span!("login", password = "hunter2");
+            let _guard = span.enter();
+            tracing::info!(password = "hunter2", username = "alice", "authenticated");
+        });
+
+        let buf = make_writer.buf();
+        let output = std::str::from_utf8(&buf[..]).expect("output should be UTF-8");
+        assert!(!output.contains("hunter2"), "{:?}", output);
+
+        let json: serde_json::Value =
+            serde_json::from_str(output.lines().next().expect("a line should be written"))
+                .expect("output should be valid JSON");
+        assert_eq!(json["fields"]["password"], "[REDACTED]");
+        assert_eq!(json["fields"]["username"], "alice");
+        assert_eq!(json["span"]["password"], "[REDACTED]");
+    }
+}
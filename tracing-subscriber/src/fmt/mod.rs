@@ -284,7 +284,7 @@
 //! [`Collect`]:
 //!     https://docs.rs/tracing/latest/tracing/trait.Collect.html
 //! [`tracing`]: https://crates.io/crates/tracing
-use std::{any::TypeId, error::Error, io, ptr::NonNull};
+use std::{any::TypeId, io, ptr::NonNull};
 use tracing_core::{collect::Interest, span, Event, Metadata};
 
 mod fmt_subscriber;
@@ -307,6 +307,11 @@ pub use self::{
     writer::{MakeWriter, TestWriter},
 };
 
+#[cfg(feature = "ansi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ansi")))]
+#[doc(inline)]
+pub use self::format::Ansi;
+
 /// A `Collector` that logs formatted representations of `tracing` events.
 ///
 /// This consists of an inner `Formatter` wrapped in a subscriber that performs filtering.
@@ -477,6 +482,11 @@ where
         self.inner.record_follows_from(span, follows)
     }
 
+    #[inline]
+    fn event_enabled(&self, event: &Event<'_>) -> bool {
+        self.inner.event_enabled(event)
+    }
+
     #[inline]
     fn event(&self, event: &Event<'_>) {
         self.inner.event(event);
@@ -570,12 +580,15 @@ where
     /// # Errors
     /// Returns an Error if the initialization was unsuccessful, likely
     /// because a global collector was already installed by another
-    /// call to `try_init`.
-    pub fn try_init(self) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+    /// call to `try_init`. Use [`TryInitError::is_global_dispatcher_set`]
+    /// and [`TryInitError::is_logger_set`] to distinguish which global was
+    /// already in place.
+    ///
+    /// [`TryInitError::is_global_dispatcher_set`]: crate::util::TryInitError::is_global_dispatcher_set()
+    /// [`TryInitError::is_logger_set`]: crate::util::TryInitError::is_logger_set()
+    pub fn try_init(self) -> Result<(), crate::util::TryInitError> {
         use crate::util::SubscriberInitExt;
-        self.finish().try_init()?;
-
-        Ok(())
+        self.finish().try_init()
     }
 
     /// Install this collector as the global default.
@@ -683,10 +696,31 @@ where
         }
     }
 
-    /// Enable ANSI terminal colors for formatted output.
+    /// Configure ANSI terminal colors for formatted output.
+    ///
+    /// This accepts either a `bool`, unconditionally enabling or disabling
+    /// escape sequences, or an [`Ansi`] mode. In [`Ansi::Auto`] mode, escape
+    /// sequences are emitted only if the configured [`MakeWriter`] [reports
+    /// that it writes to a terminal][is_terminal] and the `NO_COLOR`
+    /// environment variable is not set.
+    ///
+    /// Note that in `Auto` mode, the writer is consulted when this method is
+    /// called: configure the writer with [`with_writer`] *before* calling
+    /// `with_ansi`.
+    ///
+    /// [`Ansi`]: format::Ansi
+    /// [`Ansi::Auto`]: format::Ansi::Auto
+    /// [is_terminal]: writer::MakeWriter::is_terminal
+    /// [`with_writer`]: CollectorBuilder::with_writer
     #[cfg(feature = "ansi")]
     #[cfg_attr(docsrs, doc(cfg(feature = "ansi")))]
-    pub fn with_ansi(self, ansi: bool) -> CollectorBuilder<N, format::Format<L, T>, F, W> {
+    pub fn with_ansi(
+        self,
+        ansi: impl Into<format::Ansi>,
+    ) -> CollectorBuilder<N, format::Format<L, T>, F, W>
+    where
+        W: for<'writer> MakeWriter<'writer>,
+    {
         CollectorBuilder {
             inner: self.inner.with_ansi(ansi),
             ..self
@@ -704,6 +738,20 @@ where
         }
     }
 
+    /// Sets which spans' fields are included when an event's span context is
+    /// displayed.
+    ///
+    /// See [`format::SpanFields`].
+    pub fn with_span_fields(
+        self,
+        span_fields: format::SpanFields,
+    ) -> CollectorBuilder<N, format::Format<L, T>, F, W> {
+        CollectorBuilder {
+            inner: self.inner.with_span_fields(span_fields),
+            ..self
+        }
+    }
+
     /// Sets whether or not an event's level is displayed.
     pub fn with_level(
         self,
@@ -715,6 +763,28 @@ where
         }
     }
 
+    /// Sets whether or not an event's source code file path is displayed.
+    pub fn with_file(
+        self,
+        display_filename: bool,
+    ) -> CollectorBuilder<N, format::Format<L, T>, F, W> {
+        CollectorBuilder {
+            inner: self.inner.with_file(display_filename),
+            ..self
+        }
+    }
+
+    /// Sets whether or not an event's source code line number is displayed.
+    pub fn with_line_number(
+        self,
+        display_line_number: bool,
+    ) -> CollectorBuilder<N, format::Format<L, T>, F, W> {
+        CollectorBuilder {
+            inner: self.inner.with_line_number(display_line_number),
+            ..self
+        }
+    }
+
     /// Sets whether or not the [name] of the current thread is displayed
     /// when formatting events
     ///
@@ -756,6 +826,22 @@ where
         }
     }
 
+    /// Sets the collector being built to use a [format selected at
+    /// runtime](format::FormatMode).
+    ///
+    /// Unlike [`compact`](Self::compact) and [`pretty`](Self::pretty), which
+    /// select the formatter at the type level, the mode may be computed at
+    /// runtime — for example, from a CLI flag.
+    pub fn with_format_mode(
+        self,
+        mode: format::FormatMode,
+    ) -> CollectorBuilder<format::ModalFields, format::Format<format::FormatMode, T>, F, W> {
+        CollectorBuilder {
+            filter: self.filter,
+            inner: self.inner.with_format_mode(mode),
+        }
+    }
+
     /// Sets the collector being built to use an [excessively pretty, human-readable formatter](crate::fmt::format::Pretty).
     #[cfg(feature = "ansi")]
     #[cfg_attr(docsrs, doc(cfg(feature = "ansi")))]
@@ -1099,7 +1185,7 @@ impl<N, E, F, W> CollectorBuilder<N, E, F, W> {
 /// This is shorthand for
 ///
 /// ```rust
-/// # fn doc() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+/// # fn doc() -> Result<(), tracing_subscriber::util::TryInitError> {
 /// tracing_subscriber::fmt().try_init()
 /// # }
 /// ```
@@ -1113,9 +1199,27 @@ impl<N, E, F, W> CollectorBuilder<N, E, F, W> {
 ///
 /// [`LogTracer`]:
 ///     https://docs.rs/tracing-log/0.1.0/tracing_log/struct.LogTracer.html
+/// For `env_logger`-style setup with a custom environment variable, default
+/// directives, or writer, configure the collector explicitly. `try_init`
+/// installs the `LogTracer` as the `log` logger as well, and returns an error
+/// — rather than panicking — if a global logger or dispatcher has already
+/// been set. (The reverse bridge, emitting `tracing` events as `log` records,
+/// is controlled by the `tracing` crate's "log" feature instead.)
+///
+/// ```rust,no_run
+/// use tracing_subscriber::filter::EnvFilter;
+///
+/// # fn doc() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+/// tracing_subscriber::fmt()
+///     .with_env_filter(EnvFilter::from_env_or("MY_APP_LOG", "info"))
+///     .with_writer(std::io::stderr)
+///     .try_init()?;
+/// # Ok(()) }
+/// ```
+///
 /// [`RUST_LOG` environment variable]:
 ///     ../filter/struct.EnvFilter.html#associatedconstant.DEFAULT_ENV
-pub fn try_init() -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+pub fn try_init() -> Result<(), crate::util::TryInitError> {
     let builder = Collector::builder();
 
     #[cfg(feature = "env-filter")]
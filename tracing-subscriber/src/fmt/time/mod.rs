@@ -16,7 +16,35 @@ mod datetime;
 ///
 /// The full list of provided implementations can be found in [`time`].
 ///
+/// # Examples
+///
+/// A custom timer can be provided to the [`fmt` collector][fmt] by
+/// implementing this trait and passing the timer to
+/// [`CollectorBuilder::with_timer`]:
+///
+/// ```
+/// use std::fmt;
+/// use tracing_subscriber::fmt::time::FormatTime;
+///
+/// struct EpochSeconds;
+///
+/// impl FormatTime for EpochSeconds {
+///     fn format_time(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+///         let epoch = std::time::SystemTime::now()
+///             .duration_since(std::time::UNIX_EPOCH)
+///             .unwrap_or_default();
+///         write!(w, "{}", epoch.as_secs())
+///     }
+/// }
+///
+/// let collector = tracing_subscriber::fmt()
+///     .with_timer(EpochSeconds)
+///     .finish();
+/// ```
+///
 /// [`time`]: self
+/// [fmt]: super
+/// [`CollectorBuilder::with_timer`]: super::CollectorBuilder::with_timer
 pub trait FormatTime {
     /// Measure and write out the current time.
     ///
@@ -24,6 +52,21 @@ pub trait FormatTime {
     /// mechanism, and write it out to the given `fmt::Write`. Implementors must insert a trailing
     /// space themselves if they wish to separate the time from subsequent log message text.
     fn format_time(&self, w: &mut dyn fmt::Write) -> fmt::Result;
+
+    /// Write out the provided time, rather than the current one.
+    ///
+    /// This is called when an event carries an explicit timestamp in its
+    /// `event.timestamp` field, as described in [`tracing::time`]. The default
+    /// implementation ignores the provided time and falls back to
+    /// [`format_time`], so existing implementations are unaffected; timers
+    /// that format wall-clock times should override it.
+    ///
+    /// [`tracing::time`]: https://docs.rs/tracing/latest/tracing/time/index.html
+    /// [`format_time`]: FormatTime::format_time
+    fn format_at(&self, w: &mut dyn fmt::Write, time: std::time::SystemTime) -> fmt::Result {
+        let _ = time;
+        self.format_time(w)
+    }
 }
 
 /// Returns a new `SystemTime` timestamp provider.
@@ -66,6 +109,10 @@ where
     fn format_time(&self, w: &mut dyn fmt::Write) -> fmt::Result {
         (*self).format_time(w)
     }
+
+    fn format_at(&self, w: &mut dyn fmt::Write, time: std::time::SystemTime) -> fmt::Result {
+        (*self).format_at(w, time)
+    }
 }
 
 impl FormatTime for () {
@@ -114,6 +161,11 @@ impl FormatTime for SystemTime {
     fn format_time(&self, w: &mut dyn fmt::Write) -> fmt::Result {
         write!(w, "{}", chrono::Local::now().format("%b %d %H:%M:%S%.3f"))
     }
+
+    fn format_at(&self, w: &mut dyn fmt::Write, time: std::time::SystemTime) -> fmt::Result {
+        let time = chrono::DateTime::<chrono::Local>::from(time);
+        write!(w, "{}", time.format("%b %d %H:%M:%S%.3f"))
+    }
 }
 
 #[cfg(not(feature = "chrono"))]
@@ -125,6 +177,10 @@ impl FormatTime for SystemTime {
             datetime::DateTime::from(std::time::SystemTime::now())
         )
     }
+
+    fn format_at(&self, w: &mut dyn fmt::Write, time: std::time::SystemTime) -> fmt::Result {
+        write!(w, "{}", datetime::DateTime::from(time))
+    }
 }
 
 impl FormatTime for Uptime {
@@ -189,7 +245,11 @@ impl ChronoUtc {
 #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
 impl FormatTime for ChronoUtc {
     fn format_time(&self, w: &mut dyn fmt::Write) -> fmt::Result {
-        let time = chrono::Utc::now();
+        self.format_at(w, std::time::SystemTime::now())
+    }
+
+    fn format_at(&self, w: &mut dyn fmt::Write, time: std::time::SystemTime) -> fmt::Result {
+        let time = chrono::DateTime::<chrono::Utc>::from(time);
         match self.format {
             ChronoFmtType::Rfc3339 => write!(w, "{}", time.to_rfc3339()),
             ChronoFmtType::Custom(ref format_str) => write!(w, "{}", time.format(format_str)),
@@ -235,7 +295,11 @@ impl ChronoLocal {
 #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
 impl FormatTime for ChronoLocal {
     fn format_time(&self, w: &mut dyn fmt::Write) -> fmt::Result {
-        let time = chrono::Local::now();
+        self.format_at(w, std::time::SystemTime::now())
+    }
+
+    fn format_at(&self, w: &mut dyn fmt::Write, time: std::time::SystemTime) -> fmt::Result {
+        let time = chrono::DateTime::<chrono::Local>::from(time);
         match self.format {
             ChronoFmtType::Rfc3339 => write!(w, "{}", time.to_rfc3339()),
             ChronoFmtType::Custom(ref format_str) => write!(w, "{}", time.format(format_str)),
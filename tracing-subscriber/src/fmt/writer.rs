@@ -123,6 +123,14 @@ pub trait MakeWriter<'a> {
     /// a [`io::Write`] instance is expensive, be sure to cache it when
     /// implementing [`MakeWriter`] to improve performance.
     ///
+    /// [`fmt::Subscriber`] and [`fmt::Collector`] format each event (including
+    /// the events synthesized for span lifecycles) into a buffer before
+    /// writing it to the returned [`Writer`] in a single `write_all` call.
+    /// Therefore, as long as writing the buffer to the underlying output is
+    /// atomic — as it is for the standard output streams and for writers that
+    /// lock, such as [`std::sync::Mutex`] — events emitted concurrently from
+    /// multiple threads will not be interleaved mid-line.
+    ///
     /// [`Writer`]: MakeWriter::Writer
     /// [`fmt::Subscriber`]: super::super::fmt::Subscriber
     /// [`fmt::Collector`]: super::super::fmt::Collector
@@ -221,6 +229,21 @@ pub trait MakeWriter<'a> {
         let _ = meta;
         self.make_writer()
     }
+
+    /// Returns whether the writers produced by this `MakeWriter` write to a
+    /// terminal.
+    ///
+    /// This is consulted when ANSI color output is configured as
+    /// [`Ansi::Auto`], so that escape sequences are only emitted when the
+    /// output will actually be displayed in a terminal. The default
+    /// implementation conservatively returns `false`; implementations that
+    /// know their output is a terminal (or can check, e.g. with
+    /// [`std::io::IsTerminal`]) should override this.
+    ///
+    /// [`Ansi::Auto`]: super::format::Ansi::Auto
+    fn is_terminal(&self) -> bool {
+        false
+    }
 }
 
 /// Extension trait adding combinators for working with types implementing
@@ -443,6 +466,36 @@ pub trait MakeWriterExt<'a>: MakeWriter<'a> {
         Tee::new(self, other)
     }
 
+    /// Combines `self` with another type implementing [`MakeWriter`], returning
+    /// a new [`MakeWriter`] that produces [writers] that write to *both*
+    /// outputs.
+    ///
+    /// This is an alias for [`and`](Self::and), named for the `tee(1)`
+    /// utility. Note that the event is formatted only once; the formatted
+    /// bytes are then written to each output. Combined with the level
+    /// filtering combinators, this splits a single formatting subscriber's
+    /// output across multiple differently-filtered sinks:
+    ///
+    /// ```
+    /// use tracing::Level;
+    /// use tracing_subscriber::fmt::writer::MakeWriterExt;
+    ///
+    /// // Everything goes to stdout, and warnings and errors are *also*
+    /// // duplicated to stderr.
+    /// let mk_writer = std::io::stdout.tee(std::io::stderr.with_max_level(Level::WARN));
+    ///
+    /// tracing_subscriber::fmt().with_writer(mk_writer).init();
+    /// ```
+    ///
+    /// [writers]: std::io::Write
+    fn tee<B>(self, other: B) -> Tee<Self, B>
+    where
+        Self: Sized,
+        B: MakeWriter<'a> + Sized,
+    {
+        self.and(other)
+    }
+
     /// Combines `self` with another type implementing [`MakeWriter`], returning
     /// a new [`MakeWriter`] that calls `other`'s [`make_writer`] if `self`'s
     /// `make_writer` returns [`OptionalWriter::none`].
@@ -538,7 +591,7 @@ pub struct TestWriter {
 /// [`Collect`]: tracing::Collect
 /// [`io::Write`]: std::io::Write
 pub struct BoxMakeWriter {
-    inner: Box<dyn for<'a> MakeWriter<'a, Writer = Box<dyn Write + 'a>> + Send + Sync>,
+    inner: Box<dyn for<'a> MakeWriter<'a, Writer = Box<dyn Write + Send + 'a>> + Send + Sync>,
     name: &'static str,
 }
 
@@ -684,9 +737,13 @@ impl<'a> MakeWriter<'a> for TestWriter {
 impl BoxMakeWriter {
     /// Constructs a `BoxMakeWriter` wrapping a type implementing [`MakeWriter`].
     ///
+    /// The wrapped [`MakeWriter`]'s writers must be [`Send`], so that the
+    /// erased writer can still be moved across threads (such as when it is
+    /// handed off to a non-blocking writer's worker thread).
     pub fn new<M>(make_writer: M) -> Self
     where
         M: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+        for<'a> <M as MakeWriter<'a>>::Writer: Send,
     {
         Self {
             inner: Box::new(Boxed(make_writer)),
@@ -704,7 +761,7 @@ impl Debug for BoxMakeWriter {
 }
 
 impl<'a> MakeWriter<'a> for BoxMakeWriter {
-    type Writer = Box<dyn Write + 'a>;
+    type Writer = Box<dyn Write + Send + 'a>;
 
     fn make_writer(&'a self) -> Self::Writer {
         self.inner.make_writer()
@@ -713,6 +770,10 @@ impl<'a> MakeWriter<'a> for BoxMakeWriter {
     fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
         self.inner.make_writer_for(meta)
     }
+
+    fn is_terminal(&self) -> bool {
+        self.inner.is_terminal()
+    }
 }
 
 struct Boxed<M>(M);
@@ -720,8 +781,9 @@ struct Boxed<M>(M);
 impl<'a, M> MakeWriter<'a> for Boxed<M>
 where
     M: MakeWriter<'a>,
+    M::Writer: Send,
 {
-    type Writer = Box<dyn Write + 'a>;
+    type Writer = Box<dyn Write + Send + 'a>;
 
     fn make_writer(&'a self) -> Self::Writer {
         let w = self.0.make_writer();
@@ -732,6 +794,10 @@ where
         let w = self.0.make_writer_for(meta);
         Box::new(w)
     }
+
+    fn is_terminal(&self) -> bool {
+        self.0.is_terminal()
+    }
 }
 
 // === impl Mutex/MutexGuardWriter ===
@@ -888,6 +954,10 @@ impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for WithMaxLevel<M> {
         }
         OptionalWriter::none()
     }
+
+    fn is_terminal(&self) -> bool {
+        self.make.is_terminal()
+    }
 }
 
 // === impl WithMinLevel ===
@@ -921,6 +991,10 @@ impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for WithMinLevel<M> {
         }
         OptionalWriter::none()
     }
+
+    fn is_terminal(&self) -> bool {
+        self.make.is_terminal()
+    }
 }
 
 // ==== impl WithFilter ===
@@ -962,6 +1036,10 @@ where
             OptionalWriter::none()
         }
     }
+
+    fn is_terminal(&self) -> bool {
+        self.make.is_terminal()
+    }
 }
 
 // === impl Tee ===
@@ -993,6 +1071,12 @@ where
     fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
         Tee::new(self.a.make_writer_for(meta), self.b.make_writer_for(meta))
     }
+
+    // Only report a terminal if escape sequences will be displayed correctly
+    // on *both* outputs.
+    fn is_terminal(&self) -> bool {
+        self.a.is_terminal() && self.b.is_terminal()
+    }
 }
 
 macro_rules! impl_tee {
@@ -1078,6 +1162,12 @@ where
             EitherWriter::B(_) => EitherWriter::B(self.or_else.make_writer_for(meta)),
         }
     }
+
+    // Only report a terminal if escape sequences will be displayed correctly
+    // on *both* outputs.
+    fn is_terminal(&self) -> bool {
+        self.inner.is_terminal() && self.or_else.is_terminal()
+    }
 }
 
 // === blanket impls ===
@@ -1133,6 +1223,18 @@ mod test {
         }
     }
 
+    #[test]
+    fn boxed_writer_is_send() {
+        // A `BoxMakeWriter`'s erased writers can be moved to another thread,
+        // such as a non-blocking writer's worker thread.
+        let make_writer: &'static BoxMakeWriter =
+            Box::leak(Box::new(BoxMakeWriter::new(std::io::stdout)));
+        let writer = make_writer.make_writer();
+        std::thread::spawn(move || drop(writer))
+            .join()
+            .expect("thread should not panic");
+    }
+
     #[test]
     fn custom_writer_closure() {
         let buf = Arc::new(Mutex::new(Vec::new()));
@@ -1345,4 +1447,77 @@ mod test {
         has_lines(&a_buf, &lines[..]);
         has_lines(&b_buf, &lines[..]);
     }
+
+    #[test]
+    fn tee_formats_once_and_filters_per_sink() {
+        use crate::fmt::{format::FormatEvent, FmtContext};
+        use crate::registry::LookupSpan;
+        use std::sync::atomic::AtomicUsize;
+        use tracing_core::{Collect, Event};
+
+        /// Counts how many times an event is formatted, delegating the actual
+        /// formatting to the wrapped formatter.
+        struct CountingFormat<F> {
+            inner: F,
+            count: Arc<AtomicUsize>,
+        }
+
+        impl<C, N, F> FormatEvent<C, N> for CountingFormat<F>
+        where
+            C: Collect + for<'a> LookupSpan<'a>,
+            N: for<'a> crate::fmt::FormatFields<'a> + 'static,
+            F: FormatEvent<C, N>,
+        {
+            fn format_event(
+                &self,
+                ctx: &FmtContext<'_, C, N>,
+                writer: &mut dyn std::fmt::Write,
+                event: &Event<'_>,
+            ) -> std::fmt::Result {
+                self.count.fetch_add(1, Ordering::Relaxed);
+                self.inner.format_event(ctx, writer, event)
+            }
+        }
+
+        let file_buf = Arc::new(Mutex::new(Vec::new()));
+        let file = MockMakeWriter::new(file_buf.clone());
+
+        let stderr_buf = Arc::new(Mutex::new(Vec::new()));
+        let stderr = MockMakeWriter::new(stderr_buf.clone());
+
+        // Everything goes to the "file"; WARN and above are duplicated to
+        // the "stderr" sink.
+        let make_writer = file.tee(stderr.with_max_level(Level::WARN));
+
+        let format_count = Arc::new(AtomicUsize::new(0));
+        let c = {
+            #[cfg(feature = "ansi")]
+            let f = Format::default().without_time().with_ansi(false);
+            #[cfg(not(feature = "ansi"))]
+            let f = Format::default().without_time();
+            Collector::builder()
+                .event_format(CountingFormat {
+                    inner: f,
+                    count: format_count.clone(),
+                })
+                .with_writer(make_writer)
+                .with_max_level(Level::TRACE)
+                .finish()
+        };
+
+        let _s = tracing::collect::set_default(c);
+
+        info!("just the file");
+        error!("both sinks");
+
+        has_lines(
+            &file_buf,
+            &[(Level::INFO, "just the file"), (Level::ERROR, "both sinks")],
+        );
+        has_lines(&stderr_buf, &[(Level::ERROR, "both sinks")]);
+
+        // Each event must be formatted exactly once, even though the ERROR
+        // event was written to two sinks.
+        assert_eq!(format_count.load(Ordering::Relaxed), 2);
+    }
 }
@@ -0,0 +1,445 @@
+//! A subscriber that records span durations into per-callsite histograms.
+//!
+//! The [`DurationHistogramSubscriber`] in this module measures how long each
+//! span took — its busy time by default, or its total lifetime — and records
+//! the duration into a histogram keyed by the span's `(target, name)`. The
+//! recorded distributions can be inspected at any time with
+//! [`snapshot`], which summarizes each histogram as a count, several
+//! percentiles, and a maximum, without exporting to an external system.
+//!
+//! Histograms use log-linear bucketing: durations are grouped into buckets
+//! whose width grows with the magnitude of the value, so percentile
+//! estimates have a small *relative* error (under 2%) across the whole range
+//! of representable durations, at a fixed memory cost per callsite.
+//!
+//! Timing data is read from the shared [`Timings`] extension. The subscriber
+//! keeps that extension updated itself, so it does not require a
+//! [`TimingsSubscriber`] in the stack — but if one is present (or another
+//! subscriber already maintains the extension), the updates are shared rather
+//! than duplicated.
+//!
+//! # Examples
+//!
+//! ```
+//! use tracing_subscriber::{prelude::*, histogram::DurationHistogramSubscriber};
+//!
+//! let histograms = DurationHistogramSubscriber::new();
+//! // Keep a handle for reading snapshots; clones share the same histograms.
+//! let handle = histograms.clone();
+//! let collector = tracing_subscriber::registry().with(histograms);
+//! # let _ = collector;
+//!
+//! // ... later, report the recorded distributions:
+//! for ((target, name), summary) in handle.snapshot() {
+//!     println!(
+//!         "{}::{}: n={} p50={:?} p99={:?} max={:?}",
+//!         target, name, summary.count, summary.p50, summary.p99, summary.max,
+//!     );
+//! }
+//! ```
+//!
+//! [`snapshot`]: DurationHistogramSubscriber::snapshot
+//! [`Timings`]: crate::registry::Timings
+//! [`TimingsSubscriber`]: crate::registry::TimingsSubscriber
+use crate::{
+    registry::{LookupSpan, Timings},
+    subscribe::Context,
+    Subscribe,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tracing_core::{span, Collect};
+
+/// A [`Subscribe`] implementation that records span durations into
+/// histograms keyed by the span's `(target, name)`.
+///
+/// See the [module-level documentation][self] for details.
+#[derive(Clone, Debug)]
+pub struct DurationHistogramSubscriber {
+    histograms: Arc<Mutex<HashMap<(&'static str, &'static str), Histogram>>>,
+    kind: DurationKind,
+}
+
+/// A summary of the durations recorded for one span callsite.
+///
+/// Percentiles are estimates: each is accurate to within the width of the
+/// histogram bucket it falls in (a relative error of less than 2%). The
+/// [`max`] is exact.
+///
+/// [`max`]: HistogramSummary::max
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct HistogramSummary {
+    /// The number of span closures recorded.
+    pub count: u64,
+    /// The estimated 50th-percentile (median) duration.
+    pub p50: Duration,
+    /// The estimated 90th-percentile duration.
+    pub p90: Duration,
+    /// The estimated 99th-percentile duration.
+    pub p99: Duration,
+    /// The longest duration recorded.
+    pub max: Duration,
+}
+
+/// Which duration of a span is recorded when it closes.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum DurationKind {
+    /// The time the span spent entered on some thread.
+    Busy,
+    /// The time from the span's creation to its closure.
+    Total,
+}
+
+/// The number of bits of a duration's magnitude preserved by a bucket.
+///
+/// Each power-of-two range of durations is divided into `2^GROUP_BITS`
+/// linear buckets, bounding the relative error of a percentile estimate at
+/// `2^-GROUP_BITS` (with 6 bits, under 2%). Durations are recorded in
+/// nanoseconds, so the largest group covers durations of hundreds of years;
+/// a fully populated histogram holds fewer than 4096 buckets.
+const GROUP_BITS: u32 = 6;
+const SUB_BUCKETS: u64 = 1 << GROUP_BITS;
+
+/// A log-linear histogram of durations, in nanoseconds.
+#[derive(Debug, Default)]
+struct Histogram {
+    /// Bucket occupancy counts, indexed by [`bucket_index`]. Grown on demand
+    /// to the highest occupied bucket.
+    buckets: Vec<u64>,
+    count: u64,
+    max: u64,
+}
+
+/// Returns the index of the bucket covering `nanos`.
+///
+/// Values below `SUB_BUCKETS` map to a bucket apiece; each subsequent
+/// power-of-two range is divided into `SUB_BUCKETS` equal buckets. Indices
+/// are contiguous and ordered by magnitude.
+fn bucket_index(nanos: u64) -> usize {
+    if nanos < SUB_BUCKETS {
+        return nanos as usize;
+    }
+    let msb = 63 - u64::from(nanos.leading_zeros());
+    let group = msb - u64::from(GROUP_BITS) + 1;
+    let sub = (nanos >> (msb - u64::from(GROUP_BITS))) & (SUB_BUCKETS - 1);
+    (group << GROUP_BITS | sub) as usize
+}
+
+/// Returns the midpoint of the bucket at `index`, the value reported for
+/// percentiles that fall in it.
+fn bucket_midpoint(index: usize) -> u64 {
+    let group = (index >> GROUP_BITS) as u64;
+    let sub = index as u64 & (SUB_BUCKETS - 1);
+    if group == 0 {
+        // The first group's buckets each hold a single value.
+        return sub;
+    }
+    let lower = (SUB_BUCKETS + sub) << (group - 1);
+    let width = 1 << (group - 1);
+    lower + width / 2
+}
+
+// === impl Histogram ===
+
+impl Histogram {
+    fn record(&mut self, nanos: u64) {
+        let index = bucket_index(nanos);
+        if self.buckets.len() <= index {
+            self.buckets.resize(index + 1, 0);
+        }
+        self.buckets[index] += 1;
+        self.count += 1;
+        self.max = self.max.max(nanos);
+    }
+
+    /// Returns the estimated duration at quantile `q` (in `0.0..=1.0`).
+    fn percentile(&self, q: f64) -> u64 {
+        debug_assert!(self.count > 0);
+        let target = ((q * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, &bucket) in self.buckets.iter().enumerate() {
+            seen += bucket;
+            if seen >= target {
+                // An estimate can't exceed the largest recorded value.
+                return bucket_midpoint(index).min(self.max);
+            }
+        }
+        self.max
+    }
+
+    fn summary(&self) -> HistogramSummary {
+        HistogramSummary {
+            count: self.count,
+            p50: Duration::from_nanos(self.percentile(0.50)),
+            p90: Duration::from_nanos(self.percentile(0.90)),
+            p99: Duration::from_nanos(self.percentile(0.99)),
+            max: Duration::from_nanos(self.max),
+        }
+    }
+}
+
+// === impl DurationHistogramSubscriber ===
+
+impl DurationHistogramSubscriber {
+    /// Returns a new `DurationHistogramSubscriber` that records each span's
+    /// *busy* duration — the time the span was entered on some thread.
+    ///
+    /// To record total lifetimes instead, use [`with_total_duration`].
+    ///
+    /// [`with_total_duration`]: DurationHistogramSubscriber::with_total_duration
+    pub fn new() -> Self {
+        Self {
+            histograms: Arc::new(Mutex::new(HashMap::new())),
+            kind: DurationKind::Busy,
+        }
+    }
+
+    /// Records each span's *total* duration — the time from its creation to
+    /// its closure — rather than only the time it was entered.
+    pub fn with_total_duration(self) -> Self {
+        Self {
+            kind: DurationKind::Total,
+            ..self
+        }
+    }
+
+    /// Returns a summary of every histogram recorded so far.
+    ///
+    /// Keys are the `(target, name)` of the span callsites whose durations
+    /// were recorded. Any clone of this subscriber — including the one
+    /// installed in a collector — reads the same histograms.
+    pub fn snapshot(&self) -> HashMap<(&'static str, &'static str), HistogramSummary> {
+        self.histograms
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(key, histogram)| (*key, histogram.summary()))
+            .collect()
+    }
+
+    /// Discards all recorded durations.
+    pub fn reset(&self) {
+        self.histograms.lock().unwrap().clear();
+    }
+
+    /// Discards the histograms of every callsite for which `f` returns
+    /// `false`.
+    ///
+    /// This can be used to drop high-cardinality or uninteresting callsites
+    /// while retaining the rest, e.g. after a [`snapshot`].
+    ///
+    /// [`snapshot`]: DurationHistogramSubscriber::snapshot
+    pub fn retain(&self, mut f: impl FnMut(&'static str, &'static str) -> bool) {
+        self.histograms
+            .lock()
+            .unwrap()
+            .retain(|(target, name), _| f(target, name));
+    }
+}
+
+impl Default for DurationHistogramSubscriber {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> Subscribe<C> for DurationHistogramSubscriber
+where
+    C: Collect + for<'lookup> LookupSpan<'lookup>,
+{
+    fn new_span(&self, _attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, C>) {
+        let span = ctx.span(id).expect("Span not found, this is a bug");
+        let mut extensions = span.extensions_mut();
+        if extensions.get_mut::<Timings>().is_none() {
+            extensions.insert(Timings::new());
+        }
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, C>) {
+        let span = ctx.span(id).expect("Span not found, this is a bug");
+        let mut extensions = span.extensions_mut();
+        if let Some(timings) = extensions.get_mut::<Timings>() {
+            timings.enter();
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, C>) {
+        let span = ctx.span(id).expect("Span not found, this is a bug");
+        let mut extensions = span.extensions_mut();
+        if let Some(timings) = extensions.get_mut::<Timings>() {
+            timings.exit();
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, C>) {
+        let span = ctx.span(&id).expect("Span not found, this is a bug");
+        let duration = {
+            let extensions = span.extensions();
+            let timings = match extensions.get::<Timings>() {
+                Some(timings) => timings,
+                None => return,
+            };
+            match self.kind {
+                DurationKind::Busy => timings.busy(),
+                // The period since the last enter or exit has not yet been
+                // added to either total, so include it in the lifetime.
+                DurationKind::Total => timings.busy() + timings.idle() + timings.last_changed(),
+            }
+        };
+
+        let metadata = span.metadata();
+        self.histograms
+            .lock()
+            .unwrap()
+            .entry((metadata.target(), metadata.name()))
+            .or_default()
+            .record(duration.as_nanos() as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, registry};
+    use tracing::collect::with_default;
+
+    #[test]
+    fn bucket_indices_are_contiguous_and_ordered() {
+        let mut last = bucket_index(0);
+        assert_eq!(last, 0);
+        for nanos in 1..=1_000_000u64 {
+            let index = bucket_index(nanos);
+            assert!(
+                index == last || index == last + 1,
+                "index for {} jumped from {} to {}",
+                nanos,
+                last,
+                index,
+            );
+            last = index;
+        }
+    }
+
+    #[test]
+    fn percentiles_of_a_uniform_distribution() {
+        let mut histogram = Histogram::default();
+        for nanos in 1..=100_000u64 {
+            histogram.record(nanos);
+        }
+        let summary = histogram.summary();
+
+        // Estimates are accurate to within one bucket, i.e. a relative
+        // error of 2^-GROUP_BITS.
+        let assert_close = |estimate: Duration, expected: u64| {
+            let estimate = estimate.as_nanos() as u64;
+            let tolerance = expected >> GROUP_BITS;
+            assert!(
+                estimate.abs_diff(expected) <= tolerance,
+                "estimate {} should be within {} of {}",
+                estimate,
+                tolerance,
+                expected,
+            );
+        };
+
+        assert_eq!(summary.count, 100_000);
+        assert_close(summary.p50, 50_000);
+        assert_close(summary.p90, 90_000);
+        assert_close(summary.p99, 99_000);
+        assert_eq!(summary.max, Duration::from_nanos(100_000));
+    }
+
+    #[test]
+    fn records_spans_closed_concurrently() {
+        let histograms = DurationHistogramSubscriber::new();
+        let handle = histograms.clone();
+        let dispatch = tracing::Dispatch::new(registry().with(histograms));
+
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let dispatch = dispatch.clone();
+                std::thread::spawn(move || {
+                    tracing::dispatch::with_default(&dispatch, || {
+                        for _ in 0..100 {
+                            tracing::info_span!("concurrent").in_scope(|| {});
+                        }
+                    })
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let snapshot = handle.snapshot();
+        let summary = snapshot
+            .values()
+            .next()
+            .expect("spans should have been recorded");
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(summary.count, 800);
+    }
+
+    #[test]
+    fn busy_duration_excludes_unentered_time() {
+        let histograms = DurationHistogramSubscriber::new();
+        let handle = histograms.clone();
+
+        with_default(registry().with(histograms), || {
+            let span = tracing::info_span!("busy");
+            span.in_scope(|| std::thread::sleep(Duration::from_millis(5)));
+            std::thread::sleep(Duration::from_millis(20));
+            drop(span);
+        });
+
+        let snapshot = handle.snapshot();
+        let summary = snapshot.values().next().expect("span should have closed");
+        assert!(summary.max >= Duration::from_millis(5), "{:?}", summary.max);
+        assert!(summary.max < Duration::from_millis(20), "{:?}", summary.max);
+    }
+
+    #[test]
+    fn total_duration_includes_unentered_time() {
+        let histograms = DurationHistogramSubscriber::new().with_total_duration();
+        let handle = histograms.clone();
+
+        with_default(registry().with(histograms), || {
+            let span = tracing::info_span!("total");
+            span.in_scope(|| std::thread::sleep(Duration::from_millis(5)));
+            std::thread::sleep(Duration::from_millis(5));
+            drop(span);
+        });
+
+        let snapshot = handle.snapshot();
+        let summary = snapshot.values().next().expect("span should have closed");
+        assert!(
+            summary.max >= Duration::from_millis(10),
+            "{:?}",
+            summary.max
+        );
+    }
+
+    #[test]
+    fn reset_and_retain_discard_histograms() {
+        let histograms = DurationHistogramSubscriber::new();
+        let handle = histograms.clone();
+
+        with_default(registry().with(histograms), || {
+            tracing::info_span!("first").in_scope(|| {});
+            tracing::info_span!("second").in_scope(|| {});
+        });
+        assert_eq!(handle.snapshot().len(), 2);
+
+        handle.retain(|_, name| name == "first");
+        let snapshot = handle.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert!(snapshot.keys().all(|(_, name)| *name == "first"));
+
+        handle.reset();
+        assert!(handle.snapshot().is_empty());
+    }
+}
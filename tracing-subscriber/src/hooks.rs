@@ -0,0 +1,195 @@
+//! A subscriber that invokes user-provided callbacks on span and event
+//! lifecycle notifications.
+//!
+//! Small applications frequently want to run a closure whenever a span is
+//! entered or closed — to bump a gauge, or to set an MDC-style thread-local
+//! for a legacy logging system — and implementing the full [`Subscribe`]
+//! trait for this is a big hammer. [`CallbackSubscriber`] is built from
+//! optional closures, each defaulting to a no-op, and can be composed with a
+//! collector (and filtered) like any other subscriber.
+//!
+//! The enter, exit, and close callbacks are passed the span's [`Metadata`],
+//! so they can match on the span's name or target cheaply, without requiring
+//! the underlying collector to implement [`LookupSpan`]. They are *not*
+//! given access to the span's extensions, field values, or parent context;
+//! callbacks that need those should be written as a full [`Subscribe`]
+//! implementation against a [`Registry`] instead.
+//!
+//! # Examples
+//!
+//! Counting the spans currently entered on any thread:
+//!
+//! ```rust
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use std::sync::Arc;
+//! use tracing_subscriber::{hooks::CallbackSubscriber, prelude::*};
+//!
+//! let in_flight = Arc::new(AtomicUsize::new(0));
+//! let subscriber = CallbackSubscriber::new()
+//!     .on_enter({
+//!         let in_flight = in_flight.clone();
+//!         move |_id, _meta| {
+//!             in_flight.fetch_add(1, Ordering::Relaxed);
+//!         }
+//!     })
+//!     .on_exit({
+//!         let in_flight = in_flight.clone();
+//!         move |_id, _meta| {
+//!             in_flight.fetch_sub(1, Ordering::Relaxed);
+//!         }
+//!     });
+//!
+//! let collector = tracing_subscriber::registry().with(subscriber);
+//! ```
+//!
+//! [`LookupSpan`]: crate::registry::LookupSpan
+//! [`Registry`]: crate::registry::Registry
+//! [`Subscribe`]: crate::subscribe::Subscribe
+use crate::subscribe::{Context, Subscribe};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use tracing_core::{
+    metadata::Metadata,
+    span::{Attributes, Id},
+    Collect, Event,
+};
+
+/// A callback invoked when a span is constructed.
+type NewSpanHook = Box<dyn Fn(&Attributes<'_>, &Id) + Send + Sync + 'static>;
+
+/// A callback invoked when a span is entered, exited, or closed.
+type SpanHook = Box<dyn Fn(&Id, &'static Metadata<'static>) + Send + Sync + 'static>;
+
+/// A callback invoked when an event occurs.
+type EventHook = Box<dyn Fn(&Event<'_>) + Send + Sync + 'static>;
+
+/// A [`Subscribe`] implementation built from optional callbacks, each
+/// defaulting to a no-op.
+///
+/// See the [module-level documentation][self] for details and an example.
+#[derive(Default)]
+pub struct CallbackSubscriber {
+    on_new_span: Option<NewSpanHook>,
+    on_enter: Option<SpanHook>,
+    on_exit: Option<SpanHook>,
+    on_close: Option<SpanHook>,
+    on_event: Option<EventHook>,
+    /// Metadata for the spans this subscriber has seen, tracked so that the
+    /// enter, exit, and close callbacks can be passed metadata without
+    /// requiring the collector to implement `LookupSpan`.
+    spans: Mutex<HashMap<Id, &'static Metadata<'static>>>,
+}
+
+impl CallbackSubscriber {
+    /// Returns a new `CallbackSubscriber` with no callbacks set.
+    ///
+    /// Until callbacks are added, every notification is a no-op.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a callback invoked when a new span is constructed, with the
+    /// span's [`Attributes`] and `Id`.
+    pub fn on_new_span(mut self, f: impl Fn(&Attributes<'_>, &Id) + Send + Sync + 'static) -> Self {
+        self.on_new_span = Some(Box::new(f));
+        self
+    }
+
+    /// Sets a callback invoked when a span is entered, with the span's `Id`
+    /// and [`Metadata`].
+    pub fn on_enter(
+        mut self,
+        f: impl Fn(&Id, &'static Metadata<'static>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_enter = Some(Box::new(f));
+        self
+    }
+
+    /// Sets a callback invoked when a span is exited, with the span's `Id`
+    /// and [`Metadata`].
+    pub fn on_exit(
+        mut self,
+        f: impl Fn(&Id, &'static Metadata<'static>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_exit = Some(Box::new(f));
+        self
+    }
+
+    /// Sets a callback invoked when a span is closed, with the span's `Id`
+    /// and [`Metadata`].
+    pub fn on_close(
+        mut self,
+        f: impl Fn(&Id, &'static Metadata<'static>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_close = Some(Box::new(f));
+        self
+    }
+
+    /// Sets a callback invoked when an event occurs, with the [`Event`]
+    /// itself.
+    pub fn on_event(mut self, f: impl Fn(&Event<'_>) + Send + Sync + 'static) -> Self {
+        self.on_event = Some(Box::new(f));
+        self
+    }
+
+    /// Returns `true` if any callback needs span metadata to be tracked.
+    fn tracks_spans(&self) -> bool {
+        self.on_enter.is_some() || self.on_exit.is_some() || self.on_close.is_some()
+    }
+}
+
+impl<C: Collect> Subscribe<C> for CallbackSubscriber {
+    fn new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, C>) {
+        if self.tracks_spans() {
+            self.spans
+                .lock()
+                .unwrap()
+                .insert(id.clone(), attrs.metadata());
+        }
+        if let Some(ref hook) = self.on_new_span {
+            hook(attrs, id);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, C>) {
+        if let Some(ref hook) = self.on_event {
+            hook(event);
+        }
+    }
+
+    fn on_enter(&self, id: &Id, _ctx: Context<'_, C>) {
+        if let Some(ref hook) = self.on_enter {
+            if let Some(meta) = self.spans.lock().unwrap().get(id) {
+                hook(id, meta);
+            }
+        }
+    }
+
+    fn on_exit(&self, id: &Id, _ctx: Context<'_, C>) {
+        if let Some(ref hook) = self.on_exit {
+            if let Some(meta) = self.spans.lock().unwrap().get(id) {
+                hook(id, meta);
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, _ctx: Context<'_, C>) {
+        let meta = self.spans.lock().unwrap().remove(&id);
+        if let (Some(ref hook), Some(meta)) = (&self.on_close, meta) {
+            hook(&id, meta);
+        }
+    }
+}
+
+impl fmt::Debug for CallbackSubscriber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallbackSubscriber")
+            .field("on_new_span", &self.on_new_span.is_some())
+            .field("on_enter", &self.on_enter.is_some())
+            .field("on_exit", &self.on_exit.is_some())
+            .field("on_close", &self.on_close.is_some())
+            .field("on_event", &self.on_event.is_some())
+            .finish()
+    }
+}
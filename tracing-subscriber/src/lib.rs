@@ -109,9 +109,16 @@ pub mod filter;
 #[cfg(feature = "fmt")]
 #[cfg_attr(docsrs, doc(cfg(feature = "fmt")))]
 pub mod fmt;
+#[cfg(feature = "registry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "registry")))]
+pub mod histogram;
+pub mod hooks;
 pub mod prelude;
 pub mod registry;
 pub mod reload;
+#[cfg(feature = "registry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "registry")))]
+pub mod sampling;
 pub mod subscribe;
 pub(crate) mod sync;
 pub mod util;
@@ -6,6 +6,7 @@ use std::{
     collections::HashMap,
     fmt,
     hash::{BuildHasherDefault, Hasher},
+    marker::PhantomData,
 };
 
 #[allow(warnings)]
@@ -48,6 +49,16 @@ impl<'a> Extensions<'a> {
     pub fn get<T: 'static>(&self) -> Option<&T> {
         self.inner.get::<T>()
     }
+
+    /// Returns the number of types stored in this `Extensions`.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if this `Extensions` stores no types.
+    pub fn is_empty(&self) -> bool {
+        self.inner.len() == 0
+    }
 }
 
 /// An mutable reference to a Span's extensions.
@@ -104,6 +115,86 @@ impl<'a> ExtensionsMut<'a> {
     pub fn remove<T: Send + Sync + 'static>(&mut self) -> Option<T> {
         self.inner.remove::<T>()
     }
+
+    /// Returns a mutable reference to the `T` stored in this `Extensions`,
+    /// inserting the value returned by `f` if no `T` is present.
+    ///
+    /// Unlike checking [`get_mut`] and then calling [`insert`], this performs
+    /// a single lookup, and does not re-box a value that is already present.
+    ///
+    /// [`get_mut`]: Self::get_mut
+    /// [`insert`]: Self::insert
+    pub fn get_or_insert_with<T: Send + Sync + 'static>(
+        &mut self,
+        f: impl FnOnce() -> T,
+    ) -> &mut T {
+        self.inner.get_or_insert_with(f)
+    }
+
+    /// Returns a mutable reference to the `T` stored in this `Extensions`,
+    /// inserting `T::default()` if no `T` is present.
+    pub fn get_or_insert_default<T: Default + Send + Sync + 'static>(&mut self) -> &mut T {
+        self.get_or_insert_with(T::default)
+    }
+
+    /// Returns an [`Entry`] for the `T` stored in this `Extensions`, mirroring
+    /// the [`HashMap` entry API](std::collections::HashMap::entry).
+    pub fn entry<T: Send + Sync + 'static>(&mut self) -> Entry<'_, 'a, T> {
+        Entry {
+            extensions: self,
+            _ty: PhantomData,
+        }
+    }
+
+    /// Returns the number of types stored in this `Extensions`.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if this `Extensions` stores no types.
+    pub fn is_empty(&self) -> bool {
+        self.inner.len() == 0
+    }
+}
+
+/// A view into the slot for a single type in a span's [`ExtensionsMut`],
+/// returned by [`ExtensionsMut::entry`].
+#[derive(Debug)]
+pub struct Entry<'a, 'ext, T> {
+    extensions: &'a mut ExtensionsMut<'ext>,
+    _ty: PhantomData<fn() -> T>,
+}
+
+impl<'a, 'ext, T: Send + Sync + 'static> Entry<'a, 'ext, T> {
+    /// Inserts `val` if no `T` is present, and returns a mutable reference to
+    /// the `T` in the entry.
+    pub fn or_insert(self, val: T) -> &'a mut T {
+        self.or_insert_with(|| val)
+    }
+
+    /// Inserts the value returned by `f` if no `T` is present, and returns a
+    /// mutable reference to the `T` in the entry.
+    pub fn or_insert_with(self, f: impl FnOnce() -> T) -> &'a mut T {
+        self.extensions.inner.get_or_insert_with(f)
+    }
+
+    /// Inserts `T::default()` if no `T` is present, and returns a mutable
+    /// reference to the `T` in the entry.
+    pub fn or_default(self) -> &'a mut T
+    where
+        T: Default,
+    {
+        self.or_insert_with(T::default)
+    }
+
+    /// Provides in-place mutable access to the `T` in the entry, if one is
+    /// present, before any potential insertion.
+    pub fn and_modify(self, f: impl FnOnce(&mut T)) -> Self {
+        if let Some(val) = self.extensions.inner.get_mut::<T>() {
+            f(val);
+        }
+        self
+    }
 }
 
 /// A type map of span extensions.
@@ -157,6 +248,29 @@ impl ExtensionsInner {
             .and_then(|boxed| (&mut **boxed as &mut (dyn Any + 'static)).downcast_mut())
     }
 
+    /// Get a mutable reference to the `T` in this `Extensions`, inserting the
+    /// value returned by `f` if no `T` is present.
+    ///
+    /// This performs a single map lookup, and only boxes the value if it was
+    /// not already present.
+    pub(crate) fn get_or_insert_with<T: Send + Sync + 'static>(
+        &mut self,
+        f: impl FnOnce() -> T,
+    ) -> &mut T {
+        let boxed = self
+            .map
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(f()));
+        (&mut **boxed as &mut (dyn Any + 'static))
+            .downcast_mut()
+            .expect("extension keyed by `T`'s `TypeId` should downcast to `T`")
+    }
+
+    /// Returns the number of types stored in this `Extensions`.
+    pub(crate) fn len(&self) -> usize {
+        self.map.len()
+    }
+
     /// Remove a type from this `Extensions`.
     ///
     /// If a extension of this type existed, it will be returned.
@@ -215,6 +329,83 @@ mod tests {
         assert_eq!(extensions.get(), Some(&MyType(10)));
     }
 
+    #[test]
+    fn get_or_insert_with_only_inserts_when_missing() {
+        let mut extensions = ExtensionsInner::new();
+
+        assert_eq!(*extensions.get_or_insert_with(|| MyType(10)), MyType(10));
+
+        // The existing value is returned; the closure must not run again.
+        let val = extensions.get_or_insert_with::<MyType>(|| panic!("should not be called"));
+        assert_eq!(*val, MyType(10));
+        val.0 += 1;
+        assert_eq!(extensions.get(), Some(&MyType(11)));
+        assert_eq!(extensions.len(), 1);
+    }
+
+    #[test]
+    fn entry_mirrors_hash_map_entry() {
+        let inner = crate::sync::RwLock::new(ExtensionsInner::new());
+        let mut extensions = ExtensionsMut::new(inner.write().unwrap());
+
+        assert!(extensions.is_empty());
+        assert_eq!(*extensions.entry().or_insert(MyType(10)), MyType(10));
+        assert_eq!(
+            *extensions
+                .entry::<MyType>()
+                .and_modify(|v| v.0 += 1)
+                .or_insert(MyType(0)),
+            MyType(11)
+        );
+        assert_eq!(*extensions.entry::<i32>().or_default(), 0);
+        assert_eq!(extensions.len(), 2);
+        assert!(!extensions.is_empty());
+    }
+
+    #[test]
+    fn get_or_insert_is_shared_between_subscribers() {
+        use crate::{registry::LookupSpan, subscribe::Context, Subscribe};
+        use tracing_core::{
+            span::{Attributes, Id},
+            Collect,
+        };
+
+        // A per-span value shared by every subscriber in the stack: whichever
+        // subscriber touches the span first inserts it, and the others reuse
+        // (and increment) the same value rather than clobbering it.
+        #[derive(Default)]
+        struct TouchCount(usize);
+
+        struct TouchSubscriber;
+
+        impl<C> Subscribe<C> for TouchSubscriber
+        where
+            C: Collect + for<'a> LookupSpan<'a>,
+        {
+            fn new_span(&self, _: &Attributes<'_>, id: &Id, ctx: Context<'_, C>) {
+                let span = ctx.span(id).expect("Missing span; this is a bug");
+                let mut extensions = span.extensions_mut();
+                extensions.get_or_insert_default::<TouchCount>().0 += 1;
+            }
+
+            fn on_close(&self, id: Id, ctx: Context<'_, C>) {
+                let span = ctx.span(&id).expect("Missing span; this is a bug");
+                let extensions = span.extensions();
+                let touches = extensions.get::<TouchCount>().expect("Missing TouchCount");
+                assert_eq!(touches.0, 2, "both subscribers should share one value");
+            }
+        }
+
+        let subscriber = TouchSubscriber
+            .and_then(TouchSubscriber)
+            .with_collector(crate::registry::Registry::default());
+
+        tracing::collect::with_default(subscriber, || {
+            let span = tracing::debug_span!("span");
+            drop(span);
+        });
+    }
+
     #[test]
     fn clear_retains_capacity() {
         let mut extensions = ExtensionsInner::new();
@@ -64,6 +64,7 @@ use tracing_core::{field::FieldSet, span::Id, Metadata};
 
 /// A module containing a type map of span extensions.
 mod extensions;
+mod timings;
 
 cfg_feature!("registry", {
     mod sharded;
@@ -73,7 +74,8 @@ cfg_feature!("registry", {
     pub use sharded::Registry;
 });
 
-pub use extensions::{Extensions, ExtensionsMut};
+pub use extensions::{Entry, Extensions, ExtensionsMut};
+pub use timings::{Timings, TimingsSubscriber};
 
 /// Provides access to stored span data.
 ///
@@ -125,6 +127,27 @@ pub trait LookupSpan<'a> {
     }
 }
 
+/// How a span's parent was determined when the span was created.
+///
+/// This is recorded at creation time from the span's [`Attributes`], which
+/// are otherwise only available in `new_span`; [`SpanData::parent_kind`]
+/// allows it to be recovered after the fact, when [`SpanData::parent`] can no
+/// longer distinguish an explicit parent from a contextual one resolved by
+/// the registry.
+///
+/// [`Attributes`]: tracing_core::span::Attributes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParentKind {
+    /// The span was explicitly created as the root of a new trace, with
+    /// `parent: None`.
+    Root,
+    /// The span's parent was determined contextually, from the span that was
+    /// current when it was created (if any).
+    Contextual,
+    /// The span was created with an explicit `parent:` span.
+    Explicit,
+}
+
 /// A stored representation of data associated with a span.
 pub trait SpanData<'a> {
     /// Returns this span's ID.
@@ -136,6 +159,22 @@ pub trait SpanData<'a> {
     /// Returns a reference to the ID
     fn parent(&self) -> Option<&Id>;
 
+    /// Returns how this span's parent was determined when it was created, if
+    /// that is known.
+    ///
+    /// Unlike [`parent`], which returns whichever parent the registry
+    /// resolved, this distinguishes spans created with `parent: None` (an
+    /// explicit new trace root), spans whose parent was the current span, and
+    /// spans created with an explicit `parent:`.
+    ///
+    /// The default implementation returns `None`, for `SpanData`
+    /// implementations that do not record the parenting kind.
+    ///
+    /// [`parent`]: SpanData::parent
+    fn parent_kind(&self) -> Option<ParentKind> {
+        None
+    }
+
     /// Returns a reference to this span's `Extensions`.
     ///
     /// The extensions may be used by `Subscriber`s to store additional data
@@ -241,6 +280,16 @@ where
     }
 }
 
+impl<'a, R> DoubleEndedIterator for ScopeFromRoot<'a, R>
+where
+    R: LookupSpan<'a>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.spans.next_back()
+    }
+}
+
 impl<'a, R> Debug for ScopeFromRoot<'a, R>
 where
     R: LookupSpan<'a>,
@@ -285,6 +334,22 @@ where
         self.data.parent()
     }
 
+    /// Returns how this span's parent was determined when it was created, if
+    /// the underlying [`SpanData`] recorded it.
+    ///
+    /// See [`SpanData::parent_kind`] for details.
+    pub fn parent_kind(&self) -> Option<ParentKind> {
+        self.data.parent_kind()
+    }
+
+    /// Returns `true` if `id` identifies one of this span's parents, whether
+    /// direct or transitive.
+    ///
+    /// This returns `false` if `id` is this span's own ID.
+    pub fn has_parent(&self, id: &Id) -> bool {
+        self.scope().skip(1).any(|span| span.id() == *id)
+    }
+
     /// Returns a `SpanRef` describing this span's parent, or `None` if this
     /// span is the root of its trace tree.
     pub fn parent(&self) -> Option<Self> {
@@ -432,6 +497,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn spanref_scope_invariants_hold_at_any_depth() {
+        let last_depth = Arc::new(Mutex::new(0));
+
+        #[derive(Default)]
+        struct AssertingSubscriber {
+            last_depth: Arc<Mutex<usize>>,
+        }
+
+        impl<S> Subscribe<S> for AssertingSubscriber
+        where
+            S: Collect + for<'lookup> LookupSpan<'lookup>,
+        {
+            fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+                let span = ctx.span(id).unwrap();
+                let leaf_to_root = span.scope().map(|span| span.id()).collect::<Vec<_>>();
+
+                // `from_root` must yield exactly the same spans, reversed.
+                let mut root_to_leaf = span
+                    .scope()
+                    .from_root()
+                    .map(|span| span.id())
+                    .collect::<Vec<_>>();
+                root_to_leaf.reverse();
+                assert_eq!(leaf_to_root, root_to_leaf);
+
+                // `ScopeFromRoot` is double-ended, so reversing it directly
+                // must also recover the leaf-to-root order.
+                let reversed = span
+                    .scope()
+                    .from_root()
+                    .rev()
+                    .map(|span| span.id())
+                    .collect::<Vec<_>>();
+                assert_eq!(leaf_to_root, reversed);
+
+                // Every span in the scope except the leaf is a parent of the
+                // leaf, but the leaf is not its own parent.
+                for parent in &leaf_to_root[1..] {
+                    assert!(span.has_parent(parent));
+                }
+                assert!(!span.has_parent(id));
+
+                *self.last_depth.lock().unwrap() = leaf_to_root.len();
+            }
+        }
+
+        let _guard = tracing::collect::set_default(crate::registry().with(AssertingSubscriber {
+            last_depth: last_depth.clone(),
+        }));
+
+        // A 20-deep chain also exercises the `smallvec` spill path in
+        // `Scope::from_root`, which buffers 16 spans inline.
+        let mut spans = Vec::new();
+        for depth in 1..=20 {
+            spans.push(tracing::info_span!("span").entered());
+            assert_eq!(*last_depth.lock().unwrap(), depth);
+        }
+    }
+
     #[test]
     fn spanref_scope_fromroot_iteration_order() {
         let last_entered_scope = Arc::new(Mutex::new(Vec::new()));
@@ -5,13 +5,15 @@ use super::stack::SpanStack;
 use crate::{
     registry::{
         extensions::{Extensions, ExtensionsInner, ExtensionsMut},
-        LookupSpan, SpanData,
+        LookupSpan, ParentKind, SpanData, SpanRef,
     },
     sync::RwLock,
 };
 use std::{
     cell::{Cell, RefCell},
-    sync::atomic::{fence, AtomicUsize, Ordering},
+    collections::HashSet,
+    sync::atomic::{fence, AtomicU64, AtomicUsize, Ordering},
+    time::Instant,
 };
 use tracing_core::{
     dispatch::{self, Dispatch},
@@ -43,8 +45,24 @@ cfg_feature!("registry", {
     /// [extensions]: super::Extensions
     #[derive(Debug)]
     pub struct Registry {
-        spans: Pool<DataInner>,
+        spans: Pool<DataInner, RegistryConfig>,
         current_spans: ThreadLocal<RefCell<SpanStack>>,
+        span_count: AtomicUsize,
+        // The identity stamped into the high bits of every span `Id` this
+        // registry creates, so that an `Id` produced by a *different*
+        // registry can be recognized and rejected rather than silently
+        // resolving to an unrelated span's data. See [`IDENTITY_BITS`].
+        identity: u64,
+        // The slab indices of all currently-open spans. The slab itself cannot
+        // be iterated, so this set is maintained alongside it to allow
+        // [`Registry::spans`] to enumerate the live spans.
+        //
+        // Maintaining this set takes a write lock on every span creation and
+        // close, serializing threads that would otherwise proceed through the
+        // lock-free slab independently. It is therefore only populated when
+        // requested via [`Registry::with_span_tracking`], and is `None` for
+        // registries constructed with `Registry::default()`.
+        live: Option<RwLock<HashSet<usize>>>,
     }
 
     /// Span data stored in a [`Registry`].
@@ -59,7 +77,10 @@ cfg_feature!("registry", {
     #[derive(Debug)]
     pub struct Data<'a> {
         /// Immutable reference to the pooled `DataInner` entry.
-        inner: Ref<'a, DataInner>,
+        inner: Ref<'a, DataInner, RegistryConfig>,
+        /// The identity of the [`Registry`] this entry belongs to, needed to
+        /// reconstruct the span's stamped `Id` from its slab index.
+        identity: u64,
     }
 });
 
@@ -73,6 +94,8 @@ cfg_feature!("registry", {
 struct DataInner {
     metadata: &'static Metadata<'static>,
     parent: Option<Id>,
+    parent_kind: ParentKind,
+    created: Instant,
     ref_count: AtomicUsize,
     // The span's `Extensions` typemap. Allocations for the `HashMap` backing
     // this are pooled and reused in place.
@@ -84,20 +107,67 @@ struct DataInner {
 impl Default for Registry {
     fn default() -> Self {
         Self {
-            spans: Pool::new(),
+            spans: Pool::new_with_config(),
             current_spans: ThreadLocal::new(),
+            span_count: AtomicUsize::new(0),
+            identity: next_identity(),
+            live: None,
         }
     }
 }
 
+/// The number of high bits of a span `Id` used to carry the identity of the
+/// `Registry` that created it.
+///
+/// When span `Id`s from two registries are mixed up — typically by an
+/// application combining a scoped dispatcher with a global one — an `Id`
+/// created by one registry may happen to name a live slab index in the
+/// other, and would silently resolve to an unrelated span's data and
+/// extensions. Stamping each `Id` with its registry's identity lets lookups
+/// reject such foreign `Id`s instead.
+///
+/// Eight bits keeps the stamp cheap on 32-bit targets (the slab reserves
+/// the same number of index bits); identities are reused after 256
+/// registries have been constructed, so the protection is probabilistic in
+/// processes that create very many registries, and merely falls back to the
+/// previous behavior when two registries share an identity.
+const IDENTITY_BITS: usize = 8;
+
+/// Shift from the bottom of a span `Id` to its identity stamp.
+const IDENTITY_SHIFT: usize = 64 - IDENTITY_BITS;
+
+/// Mask covering the slab-index portion of a span `Id`.
+const IDX_MASK: u64 = (1 << IDENTITY_SHIFT) - 1;
+
+/// [`Pool`] configuration reserving the high [`IDENTITY_BITS`] of each slab
+/// index for the registry identity stamp.
+#[derive(Debug)]
+struct RegistryConfig;
+
+impl sharded_slab::Config for RegistryConfig {
+    const RESERVED_BITS: usize = IDENTITY_BITS;
+}
+
+/// Returns the identity stamp for the next `Registry`.
+fn next_identity() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    NEXT.fetch_add(1, Ordering::Relaxed) & ((1 << IDENTITY_BITS) - 1)
+}
+
 #[inline]
-fn idx_to_id(idx: usize) -> Id {
-    Id::from_u64(idx as u64 + 1)
+fn idx_to_id(identity: u64, idx: usize) -> Id {
+    Id::from_u64(identity << IDENTITY_SHIFT | (idx as u64 + 1))
 }
 
 #[inline]
 fn id_to_idx(id: &Id) -> usize {
-    id.into_u64() as usize - 1
+    (id.into_u64() & IDX_MASK) as usize - 1
+}
+
+/// Returns the identity stamp carried by the given span `Id`.
+#[inline]
+fn id_identity(id: &Id) -> u64 {
+    id.into_u64() >> IDENTITY_SHIFT
 }
 
 /// A guard that tracks how many [`Registry`]-backed `Subscriber`s have
@@ -128,10 +198,107 @@ pub(crate) struct CloseGuard<'a> {
 }
 
 impl Registry {
-    fn get(&self, id: &Id) -> Option<Ref<'_, DataInner>> {
+    /// Returns a new registry that tracks the set of currently-open spans,
+    /// enabling [`Registry::spans`].
+    ///
+    /// Tracking requires taking a write lock (and inserting into a `HashSet`)
+    /// every time a span is created, and again when it closes. This
+    /// serializes threads that would otherwise create spans concurrently
+    /// through the lock-free slab, so it is not enabled by default;
+    /// registries constructed with `Registry::default()` pay no such cost,
+    /// and their [`Registry::spans`] yields nothing.
+    pub fn with_span_tracking() -> Self {
+        Self {
+            live: Some(RwLock::new(HashSet::new())),
+            ..Default::default()
+        }
+    }
+
+    fn get(&self, id: &Id) -> Option<Ref<'_, DataInner, RegistryConfig>> {
+        // An `Id` stamped with a different identity was created by another
+        // registry; even if its index happens to name a live slot in this
+        // one, it does not refer to any span *this* registry created.
+        if id_identity(id) != self.identity {
+            return None;
+        }
         self.spans.get(id_to_idx(id))
     }
 
+    /// Returns the number of spans currently stored in this registry.
+    ///
+    /// This is primarily intended for monitoring: in a long-running
+    /// application, a steadily growing span count usually indicates that span
+    /// handles (or cloned span IDs held by a subscriber) are being leaked.
+    pub fn span_count(&self) -> usize {
+        self.span_count.load(Ordering::Relaxed)
+    }
+
+    /// Returns an iterator over all spans currently stored in this registry,
+    /// in no particular order.
+    ///
+    /// The bookkeeping this requires adds locking to span creation and close,
+    /// so it is only performed for registries constructed with
+    /// [`Registry::with_span_tracking`]; for a registry constructed with
+    /// `Registry::default()`, this iterator is always empty.
+    ///
+    /// This is intended for diagnostic dumps — printing every open span
+    /// (with its fields, parent, and age) when a service appears stuck, much
+    /// like a thread dump. Each item is a [`SpanRef`], so callers can read a
+    /// span's [extensions], walk to its [parent], and reconstruct the span
+    /// tree from the parent IDs. The time at which each span was created is
+    /// available from [`Data::created`] via [`LookupSpan::span_data`].
+    ///
+    /// # Consistency
+    ///
+    /// The set of span IDs is snapshotted when this method is called, and no
+    /// registry-wide lock is held while the iterator is consumed. This means
+    /// the view is only snapshot-*ish*: spans created after the call will not
+    /// be yielded, and spans that close while iterating are silently skipped.
+    /// It is safe to create spans, record events, and look up span data from
+    /// within the iteration.
+    ///
+    /// # Examples
+    ///
+    /// Dumping a tree of the currently-open spans on demand (a signal
+    /// handling crate can be used to trigger this from `SIGUSR1`):
+    ///
+    /// ```no_run
+    /// use tracing_subscriber::registry::{LookupSpan, Registry, SpanRef};
+    /// use std::sync::Arc;
+    ///
+    /// fn dump(span: &SpanRef<'_, Registry>, registry: &Registry, depth: usize) {
+    ///     let age = registry
+    ///         .span_data(&span.id())
+    ///         .map(|data| data.created().elapsed());
+    ///     println!("{:indent$}{}: {:?}", "", span.name(), age, indent = depth * 2);
+    ///     for child in registry.spans().filter(|s| s.parent_id() == Some(&span.id())) {
+    ///         dump(&child, registry, depth + 1);
+    ///     }
+    /// }
+    ///
+    /// let registry = Arc::new(Registry::with_span_tracking());
+    /// // ... install `registry` as the collector, then from a signal
+    /// // handler or admin endpoint:
+    /// for root in registry.spans().filter(|span| span.parent_id().is_none()) {
+    ///     dump(&root, &registry, 0);
+    /// }
+    /// ```
+    ///
+    /// [extensions]: SpanRef::extensions
+    /// [parent]: SpanRef::parent
+    pub fn spans(&self) -> impl Iterator<Item = SpanRef<'_, Registry>> + '_ {
+        let ids: Vec<Id> = match self.live.as_ref() {
+            Some(live) => live
+                .read()
+                .expect("Mutex poisoned")
+                .iter()
+                .map(|&idx| idx_to_id(self.identity, idx))
+                .collect(),
+            None => Vec::new(),
+        };
+        ids.into_iter().filter_map(move |id| self.span(&id))
+    }
+
     /// Returns a guard which tracks how many `Subscriber`s have
     /// processed an `on_close` notification via the `CLOSE_COUNT` thread-local.
     /// For additional details, see [`CloseGuard`].
@@ -168,12 +335,18 @@ impl Collect for Registry {
 
     #[inline]
     fn new_span(&self, attrs: &span::Attributes<'_>) -> span::Id {
-        let parent = if attrs.is_root() {
-            None
+        let (parent, parent_kind) = if attrs.is_root() {
+            (None, ParentKind::Root)
         } else if attrs.is_contextual() {
-            self.current_span().id().map(|id| self.clone_span(id))
+            (
+                self.current_span().id().map(|id| self.clone_span(id)),
+                ParentKind::Contextual,
+            )
         } else {
-            attrs.parent().map(|id| self.clone_span(id))
+            (
+                attrs.parent().map(|id| self.clone_span(id)),
+                ParentKind::Explicit,
+            )
         };
 
         let id = self
@@ -185,12 +358,18 @@ impl Collect for Registry {
             .create_with(|data| {
                 data.metadata = attrs.metadata();
                 data.parent = parent;
+                data.parent_kind = parent_kind;
+                data.created = Instant::now();
                 let refs = data.ref_count.get_mut();
                 debug_assert_eq!(*refs, 0);
                 *refs = 1;
             })
             .expect("Unable to allocate another span");
-        idx_to_id(id)
+        self.span_count.fetch_add(1, Ordering::Relaxed);
+        if let Some(live) = self.live.as_ref() {
+            live.write().expect("Mutex poisoned").insert(id);
+        }
+        idx_to_id(self.identity, id)
     }
 
     /// This is intentionally not implemented, as recording fields
@@ -205,6 +384,12 @@ impl Collect for Registry {
     fn event(&self, _: &Event<'_>) {}
 
     fn enter(&self, id: &span::Id) {
+        // A span entered on a dispatcher other than the one that created it
+        // carries another registry's identity (or no longer exists); ignore
+        // it rather than pushing a bogus entry onto the current-span stack.
+        if self.get(id).is_none() {
+            return;
+        }
         if self
             .current_spans
             .get_or_default()
@@ -233,7 +418,11 @@ impl Collect for Registry {
         // calls to `try_close`: we have to ensure that all threads have
         // dropped their refs to the span before the span is closed.
         let refs = span.ref_count.fetch_add(1, Ordering::Relaxed);
-        assert_ne!(refs, 0, "tried to clone a span ({:?}) that already closed", id);
+        assert_ne!(
+            refs, 0,
+            "tried to clone a span ({:?}) that already closed",
+            id
+        );
         id.clone()
     }
 
@@ -281,7 +470,10 @@ impl<'a> LookupSpan<'a> for Registry {
 
     fn span_data(&'a self, id: &Id) -> Option<Self::Data> {
         let inner = self.get(id)?;
-        Some(Data { inner })
+        Some(Data {
+            inner,
+            identity: self.identity,
+        })
     }
 }
 
@@ -311,8 +503,16 @@ impl<'a> Drop for CloseGuard<'a> {
             // If the current close count is 1, this stack frame is the last
             // `on_close` call. If the span is closing, it's okay to remove the
             // span.
-            if c == 1 && self.is_closing {
-                self.registry.spans.clear(id_to_idx(&self.id));
+            if c == 1 && self.is_closing && self.registry.spans.clear(id_to_idx(&self.id)) {
+                self.registry.span_count.fetch_sub(1, Ordering::Relaxed);
+                // `spans.clear` has already returned, so this cannot deadlock
+                // with the recursive parent close triggered by
+                // `DataInner::clear`.
+                if let Some(live) = self.registry.live.as_ref() {
+                    live.write()
+                        .expect("Mutex poisoned")
+                        .remove(&id_to_idx(&self.id));
+                }
             }
         });
     }
@@ -320,9 +520,19 @@ impl<'a> Drop for CloseGuard<'a> {
 
 // === impl Data ===
 
+impl<'a> Data<'a> {
+    /// Returns the `Instant` at which this span was created.
+    ///
+    /// Together with [`Registry::spans`], this allows diagnostic dumps to
+    /// report how long each open span has been running.
+    pub fn created(&self) -> Instant {
+        self.inner.created
+    }
+}
+
 impl<'a> SpanData<'a> for Data<'a> {
     fn id(&self) -> Id {
-        idx_to_id(self.inner.key())
+        idx_to_id(self.identity, self.inner.key())
     }
 
     fn metadata(&self) -> &'static Metadata<'static> {
@@ -333,6 +543,10 @@ impl<'a> SpanData<'a> for Data<'a> {
         self.inner.parent.as_ref()
     }
 
+    fn parent_kind(&self) -> Option<ParentKind> {
+        Some(self.inner.parent_kind)
+    }
+
     fn extensions(&self) -> Extensions<'_> {
         Extensions::new(self.inner.extensions.read().expect("Mutex poisoned"))
     }
@@ -382,6 +596,10 @@ impl Default for DataInner {
         Self {
             metadata: &NULL_METADATA,
             parent: None,
+            // Like the null metadata above, these placeholders are
+            // overwritten when actual span data is inserted into the entry.
+            parent_kind: ParentKind::Contextual,
+            created: Instant::now(),
             ref_count: AtomicUsize::new(0),
             extensions: RwLock::new(ExtensionsInner::new()),
         }
@@ -462,6 +680,96 @@ mod tests {
         });
     }
 
+    #[test]
+    fn spans_iterates_over_open_spans() {
+        let subscriber = AssertionSubscriber.with_collector(Registry::with_span_tracking());
+        let dispatch = dispatch::Dispatch::new(subscriber);
+        dispatch::with_default(&dispatch, || {
+            let registry = dispatch
+                .downcast_ref::<Registry>()
+                .expect("dispatch should downcast to the registry");
+            assert_eq!(registry.spans().count(), 0);
+
+            let outer = tracing::debug_span!("outer");
+            let _enter = outer.enter();
+            let inner = tracing::debug_span!("inner");
+
+            let spans: Vec<_> = registry.spans().collect();
+            assert_eq!(spans.len(), 2);
+            let outer_ref = spans
+                .iter()
+                .find(|span| span.name() == "outer")
+                .expect("outer span should be yielded");
+            let inner_ref = spans
+                .iter()
+                .find(|span| span.name() == "inner")
+                .expect("inner span should be yielded");
+            assert_eq!(inner_ref.parent_id(), Some(&outer_ref.id()));
+
+            // The creation timestamp must not be in the future.
+            let created = registry
+                .span_data(&outer_ref.id())
+                .expect("outer span should have data")
+                .created();
+            assert!(created.elapsed() >= std::time::Duration::from_secs(0));
+            drop(spans);
+
+            drop(inner);
+            let names: Vec<_> = registry.spans().map(|span| span.name()).collect();
+            assert_eq!(names, vec!["outer"]);
+        });
+    }
+
+    #[test]
+    fn spans_is_empty_without_span_tracking() {
+        let subscriber = AssertionSubscriber.with_collector(Registry::default());
+        let dispatch = dispatch::Dispatch::new(subscriber);
+        dispatch::with_default(&dispatch, || {
+            let registry = dispatch
+                .downcast_ref::<Registry>()
+                .expect("dispatch should downcast to the registry");
+
+            let span = tracing::debug_span!("span");
+            let _enter = span.enter();
+
+            // A default registry does not pay for the live-span bookkeeping,
+            // so there is nothing to iterate.
+            assert_eq!(registry.spans().count(), 0);
+        });
+    }
+
+    #[test]
+    fn parent_kind_records_how_the_parent_was_determined() {
+        use crate::registry::ParentKind;
+
+        let subscriber = AssertionSubscriber.with_collector(Registry::default());
+        let dispatch = dispatch::Dispatch::new(subscriber);
+        dispatch::with_default(&dispatch, || {
+            let registry = dispatch
+                .downcast_ref::<Registry>()
+                .expect("dispatch should downcast to the registry");
+            let kind = |span: &tracing::Span| {
+                registry
+                    .span(&span.id().expect("span should be enabled"))
+                    .expect("span should be in the registry")
+                    .parent_kind()
+            };
+
+            let outer = tracing::debug_span!("outer");
+            let _enter = outer.enter();
+            let contextual = tracing::debug_span!("contextual");
+            let root = tracing::debug_span!(parent: None, "root");
+            let explicit = tracing::debug_span!(parent: &outer, "explicit");
+
+            assert_eq!(kind(&contextual), Some(ParentKind::Contextual));
+            assert_eq!(kind(&root), Some(ParentKind::Root));
+            assert_eq!(kind(&explicit), Some(ParentKind::Explicit));
+            // A contextual span created while no span was current is still
+            // `Contextual` — only an explicit `parent: None` is a `Root`.
+            assert_eq!(kind(&outer), Some(ParentKind::Contextual));
+        });
+    }
+
     #[test]
     fn multiple_subscribers_can_access_closed_span() {
         let subscriber = AssertionSubscriber
@@ -642,6 +950,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn span_count_tracks_live_spans() {
+        let subscriber = AssertionSubscriber.with_collector(Registry::default());
+        let dispatch = dispatch::Dispatch::new(subscriber);
+        let registry = dispatch
+            .downcast_ref::<Registry>()
+            .expect("dispatch should contain a registry");
+
+        dispatch::with_default(&dispatch, || {
+            assert_eq!(registry.span_count(), 0);
+
+            let span1 = tracing::info_span!("span1");
+            let span2 = tracing::info_span!("span2");
+            assert_eq!(registry.span_count(), 2);
+
+            // A cloned handle keeps the span's slot live...
+            let span1_clone = span1.clone();
+            drop(span1);
+            assert_eq!(registry.span_count(), 2);
+
+            // ...until the last reference is dropped.
+            drop(span1_clone);
+            assert_eq!(registry.span_count(), 1);
+
+            drop(span2);
+            assert_eq!(registry.span_count(), 0);
+        });
+    }
+
+    #[test]
+    fn dropped_spans_do_not_accumulate() {
+        let subscriber = AssertionSubscriber.with_collector(Registry::default());
+        let dispatch = dispatch::Dispatch::new(subscriber);
+        let registry = dispatch
+            .downcast_ref::<Registry>()
+            .expect("dispatch should contain a registry");
+
+        // Each dropped span must release its slot back to the pool for reuse;
+        // if closed spans accumulated instead, this would grow the registry
+        // linearly.
+        dispatch::with_default(&dispatch, || {
+            for _ in 0..10_000 {
+                let span = tracing::info_span!("span");
+                drop(span);
+                assert_eq!(registry.span_count(), 0);
+            }
+        });
+    }
+
     #[test]
     fn spans_are_removed_from_registry() {
         let (close_subscriber, state) = CloseSubscriber::new();
@@ -797,4 +1154,41 @@ mod tests {
             state.assert_closed_in_order(&["child", "parent", "grandparent"]);
         });
     }
+
+    #[test]
+    fn foreign_span_ids_do_not_resolve_to_local_spans() {
+        // This test asserts that a span `Id` created by one registry cannot
+        // be mistaken for a span in another, even when both registries have
+        // live spans at the same slab index. An application mixing a scoped
+        // dispatcher with a global one can misdirect an `enter` in exactly
+        // this way; `lookup_current` must then see no current span rather
+        // than an unrelated local span's data.
+        struct RecordCurrent(Arc<Mutex<Option<Option<&'static str>>>>);
+        impl<C> Subscribe<C> for RecordCurrent
+        where
+            C: Collect + for<'a> LookupSpan<'a>,
+        {
+            fn on_event(&self, _: &tracing_core::Event<'_>, ctx: Context<'_, C>) {
+                *self.0.lock().unwrap() = Some(ctx.lookup_current().map(|span| span.name()));
+            }
+        }
+
+        let current = Arc::new(Mutex::new(None));
+        let subscriber = RecordCurrent(current.clone()).with_collector(Registry::default());
+        let local_dispatch = dispatch::Dispatch::new(subscriber);
+        let foreign_dispatch = dispatch::Dispatch::new(Registry::default());
+
+        // Give both registries a live span at the same slab index.
+        let local = dispatch::with_default(&local_dispatch, || tracing::info_span!("local"));
+        let foreign = dispatch::with_default(&foreign_dispatch, || tracing::info_span!("foreign"));
+
+        // Simulate a misdirected dispatcher by entering the foreign span's
+        // `Id` on the local registry.
+        local_dispatch.enter(&foreign.id().expect("span must be enabled"));
+
+        dispatch::with_default(&local_dispatch, || tracing::info!("check"));
+
+        assert_eq!(*current.lock().unwrap(), Some(None));
+        drop((local, foreign));
+    }
 }
@@ -0,0 +1,253 @@
+//! A shared source of span busy/idle timing data.
+use crate::{registry::LookupSpan, subscribe::Context, Subscribe};
+use std::time::{Duration, Instant};
+use tracing_core::{span::Attributes, span::Id, Collect};
+
+/// Span timing data stored in a span's [`Extensions`].
+///
+/// A span is considered _busy_ while it is entered on some thread, and _idle_
+/// while it exists but is not entered. `Timings` accumulates both durations as
+/// the span is entered and exited, so that subscribers interested in timing
+/// data — such as metrics or tracing-system subscribers — can read a single
+/// shared source from the span's extensions rather than each maintaining its
+/// own bookkeeping (and each calling [`Instant::now`] on every enter and
+/// exit).
+///
+/// The easiest way to keep a `Timings` extension updated is to add a
+/// [`TimingsSubscriber`] to the subscriber stack *below* any subscribers that
+/// read the timings. Subscribers that manage their own extension may instead
+/// call [`enter`] and [`exit`] directly.
+///
+/// Updates are well-defined when several subscribers update the same
+/// `Timings`: the busy/idle state only changes when the span is entered for
+/// the first time or exited for the last time, so redundant [`enter`] and
+/// [`exit`] calls — one per updating subscriber — are no-ops rather than
+/// double-counting.
+///
+/// [`Extensions`]: super::Extensions
+/// [`enter`]: Timings::enter
+/// [`exit`]: Timings::exit
+#[derive(Debug)]
+pub struct Timings {
+    idle: u64,
+    busy: u64,
+    last: Instant,
+    entered: usize,
+}
+
+impl Timings {
+    /// Returns a new `Timings`, considering the span idle as of now.
+    pub fn new() -> Self {
+        Self {
+            idle: 0,
+            busy: 0,
+            last: Instant::now(),
+            entered: 0,
+        }
+    }
+
+    /// Records that the span has been entered.
+    ///
+    /// The first `enter` after construction or after a matching number of
+    /// [`exit`]s ends the current idle period and begins a busy period;
+    /// further nested or redundant `enter`s are no-ops.
+    ///
+    /// [`exit`]: Timings::exit
+    pub fn enter(&mut self) {
+        if self.entered == 0 {
+            let now = Instant::now();
+            self.idle += (now - self.last).as_nanos() as u64;
+            self.last = now;
+        }
+        self.entered += 1;
+    }
+
+    /// Records that the span has been exited.
+    ///
+    /// The `exit` matching the first [`enter`] ends the current busy period
+    /// and begins an idle period; unbalanced `exit`s are no-ops.
+    ///
+    /// [`enter`]: Timings::enter
+    pub fn exit(&mut self) {
+        match self.entered {
+            0 => {}
+            1 => {
+                let now = Instant::now();
+                self.busy += (now - self.last).as_nanos() as u64;
+                self.last = now;
+                self.entered = 0;
+            }
+            _ => self.entered -= 1,
+        }
+    }
+
+    /// Returns the total time the span has spent entered.
+    ///
+    /// This does not include any busy period that is still in progress; use
+    /// [`last_changed`] to account for it if needed.
+    ///
+    /// [`last_changed`]: Timings::last_changed
+    pub fn busy(&self) -> Duration {
+        Duration::from_nanos(self.busy)
+    }
+
+    /// Returns the total time the span has existed without being entered.
+    ///
+    /// This does not include any idle period that is still in progress; use
+    /// [`last_changed`] to account for it if needed.
+    ///
+    /// [`last_changed`]: Timings::last_changed
+    pub fn idle(&self) -> Duration {
+        Duration::from_nanos(self.idle)
+    }
+
+    /// Returns the time elapsed since the span last became busy or idle.
+    ///
+    /// This is the duration of the period currently in progress, which has not
+    /// yet been added to [`busy`] or [`idle`].
+    ///
+    /// [`busy`]: Timings::busy
+    /// [`idle`]: Timings::idle
+    pub fn last_changed(&self) -> Duration {
+        self.last.elapsed()
+    }
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A subscriber that records a [`Timings`] extension for every span.
+///
+/// This subscriber does nothing except keep the extension updated as spans are
+/// entered and exited. Add it to the stack *below* (i.e., before) any
+/// subscribers that read the timing data, so that the extension exists by the
+/// time their `new_span` callbacks run:
+///
+/// ```
+/// use tracing_subscriber::{registry::TimingsSubscriber, prelude::*};
+///
+/// let subscriber = tracing_subscriber::registry()
+///     .with(TimingsSubscriber::new());
+///     // ... subscribers that read `registry::Timings` ...
+/// ```
+#[derive(Debug, Default)]
+pub struct TimingsSubscriber {
+    _p: (),
+}
+
+impl TimingsSubscriber {
+    /// Returns a new `TimingsSubscriber`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<C> Subscribe<C> for TimingsSubscriber
+where
+    C: Collect + for<'a> LookupSpan<'a>,
+{
+    fn new_span(&self, _attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, C>) {
+        let span = ctx.span(id).expect("Span not found, this is a bug");
+        let mut extensions = span.extensions_mut();
+        if extensions.get_mut::<Timings>().is_none() {
+            extensions.insert(Timings::new());
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, C>) {
+        let span = ctx.span(id).expect("Span not found, this is a bug");
+        let mut extensions = span.extensions_mut();
+        if let Some(timings) = extensions.get_mut::<Timings>() {
+            timings.enter();
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, C>) {
+        let span = ctx.span(id).expect("Span not found, this is a bug");
+        let mut extensions = span.extensions_mut();
+        if let Some(timings) = extensions.get_mut::<Timings>() {
+            timings.exit();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct CloseTimings {
+        on_close: Arc<Mutex<Option<(Duration, Duration)>>>,
+    }
+
+    impl<C> Subscribe<C> for CloseTimings
+    where
+        C: Collect + for<'a> LookupSpan<'a>,
+    {
+        fn on_close(&self, id: Id, ctx: Context<'_, C>) {
+            let span = ctx.span(&id).expect("Span not found, this is a bug");
+            let extensions = span.extensions();
+            let timings = extensions
+                .get::<Timings>()
+                .expect("timings extension should exist");
+            *self.on_close.lock().unwrap() = Some((timings.busy(), timings.idle()));
+        }
+    }
+
+    #[test]
+    fn records_busy_and_idle() {
+        let closed = Arc::new(Mutex::new(None));
+        let _guard =
+            tracing::collect::set_default(crate::registry().with(TimingsSubscriber::new()).with(
+                CloseTimings {
+                    on_close: closed.clone(),
+                },
+            ));
+
+        // Idle after the final exit is still in progress when the span closes
+        // (see `Timings::last_changed`), so idle before the first enter.
+        let span = tracing::info_span!("timed");
+        std::thread::sleep(Duration::from_millis(5));
+        span.in_scope(|| std::thread::sleep(Duration::from_millis(5)));
+        drop(span);
+
+        let (busy, idle) = closed.lock().unwrap().expect("span should have closed");
+        assert!(busy >= Duration::from_millis(5), "busy: {:?}", busy);
+        assert!(idle >= Duration::from_millis(5), "idle: {:?}", idle);
+    }
+
+    #[test]
+    fn redundant_updates_do_not_double_count() {
+        // Two `TimingsSubscriber`s both update the same extension; enter/exit
+        // must only be counted once.
+        let closed = Arc::new(Mutex::new(None));
+        let _guard = tracing::collect::set_default(
+            crate::registry()
+                .with(TimingsSubscriber::new())
+                .with(TimingsSubscriber::new())
+                .with(CloseTimings {
+                    on_close: closed.clone(),
+                }),
+        );
+
+        let start = Instant::now();
+        let span = tracing::info_span!("timed");
+        span.in_scope(|| std::thread::sleep(Duration::from_millis(5)));
+        drop(span);
+        let elapsed = start.elapsed();
+
+        let (busy, idle) = closed.lock().unwrap().expect("span should have closed");
+        assert!(
+            busy + idle <= elapsed,
+            "busy ({:?}) + idle ({:?}) should not exceed the span's lifetime ({:?})",
+            busy,
+            idle,
+            elapsed
+        );
+    }
+}
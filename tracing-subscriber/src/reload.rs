@@ -9,11 +9,28 @@
 //! change at runtime. Note that this subscriber introduces a (relatively small)
 //! amount of overhead, and should thus only be used as needed.
 //!
+//! # Poisoning and re-entrancy
+//!
+//! The wrapped subscriber is stored behind a reader-writer lock. If a panic
+//! poisons that lock — for example, because a closure passed to
+//! [`Handle::modify`] panicked mid-swap — the wrapper degrades to
+//! *pass-through* behavior rather than panicking inside instrumentation:
+//! `enabled` returns `true`, and all other callbacks become no-ops. The
+//! degraded state can be observed with [`Handle::is_poisoned`].
+//!
+//! Because the subscriber's callbacks hold a read lock, calling
+//! [`Handle::reload`] or [`Handle::modify`] from *within* one of the wrapped
+//! subscriber's callbacks (e.g. from inside `on_event`) would deadlock
+//! waiting for the write lock. Instead of deadlocking, these methods detect
+//! this case and return an [`Error`] for which [`Error::is_reentrant`]
+//! returns `true`.
+//!
 //! [`Subscribe`]: crate::Subscribe
 use crate::subscribe;
-use crate::sync::RwLock;
+use crate::sync::{RwLock, RwLockReadGuard};
 
 use std::{
+    cell::Cell,
     error, fmt,
     sync::{Arc, Weak},
 };
@@ -49,6 +66,36 @@ pub struct Error {
 enum ErrorKind {
     CollectorGone,
     Poisoned,
+    Reentrant,
+}
+
+thread_local! {
+    /// The number of `reload::Subscriber` callbacks currently executing on
+    /// this thread. While a callback runs, the subscriber holds a read lock
+    /// on the wrapped value, so reloading from inside one would deadlock
+    /// waiting for the write lock.
+    static CALLBACK_DEPTH: Cell<usize> = Cell::new(0);
+}
+
+/// RAII guard tracking that the current thread is inside one of the wrapped
+/// subscriber's callbacks.
+struct CallbackGuard(());
+
+impl CallbackGuard {
+    fn enter() -> Self {
+        CALLBACK_DEPTH.with(|depth| depth.set(depth.get() + 1));
+        Self(())
+    }
+
+    fn in_callback() -> bool {
+        CALLBACK_DEPTH.with(|depth| depth.get() > 0)
+    }
+}
+
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        CALLBACK_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
 }
 
 // ===== impl Collect =====
@@ -58,14 +105,30 @@ where
     S: crate::Subscribe<C> + 'static,
     C: Collect,
 {
+    #[inline]
+    fn on_register_dispatch(&self, collector: &tracing_core::Dispatch) {
+        let _guard = CallbackGuard::enter();
+        if let Some(inner) = self.read() {
+            inner.on_register_dispatch(collector)
+        }
+    }
+
     #[inline]
     fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
-        try_lock!(self.inner.read(), else return Interest::sometimes()).register_callsite(metadata)
+        let _guard = CallbackGuard::enter();
+        self.read()
+            .map(|inner| inner.register_callsite(metadata))
+            .unwrap_or_else(Interest::sometimes)
     }
 
     #[inline]
     fn enabled(&self, metadata: &Metadata<'_>, ctx: subscribe::Context<'_, C>) -> bool {
-        try_lock!(self.inner.read(), else return false).enabled(metadata, ctx)
+        let _guard = CallbackGuard::enter();
+        // If the lock was poisoned, pass the event through rather than
+        // silently discarding it.
+        self.read()
+            .map(|inner| inner.enabled(metadata, ctx))
+            .unwrap_or(true)
     }
 
     #[inline]
@@ -75,7 +138,10 @@ where
         id: &span::Id,
         ctx: subscribe::Context<'_, C>,
     ) {
-        try_lock!(self.inner.read()).new_span(attrs, id, ctx)
+        let _guard = CallbackGuard::enter();
+        if let Some(inner) = self.read() {
+            inner.new_span(attrs, id, ctx)
+        }
     }
 
     #[inline]
@@ -85,37 +151,67 @@ where
         values: &span::Record<'_>,
         ctx: subscribe::Context<'_, C>,
     ) {
-        try_lock!(self.inner.read()).on_record(span, values, ctx)
+        let _guard = CallbackGuard::enter();
+        if let Some(inner) = self.read() {
+            inner.on_record(span, values, ctx)
+        }
     }
 
     #[inline]
     fn on_follows_from(&self, span: &span::Id, follows: &span::Id, ctx: subscribe::Context<'_, C>) {
-        try_lock!(self.inner.read()).on_follows_from(span, follows, ctx)
+        let _guard = CallbackGuard::enter();
+        if let Some(inner) = self.read() {
+            inner.on_follows_from(span, follows, ctx)
+        }
+    }
+
+    #[inline]
+    fn event_enabled(&self, event: &Event<'_>, ctx: subscribe::Context<'_, C>) -> bool {
+        let _guard = CallbackGuard::enter();
+        match self.read() {
+            Some(inner) => inner.event_enabled(event, ctx),
+            None => true,
+        }
     }
 
     #[inline]
     fn on_event(&self, event: &Event<'_>, ctx: subscribe::Context<'_, C>) {
-        try_lock!(self.inner.read()).on_event(event, ctx)
+        let _guard = CallbackGuard::enter();
+        if let Some(inner) = self.read() {
+            inner.on_event(event, ctx)
+        }
     }
 
     #[inline]
     fn on_enter(&self, id: &span::Id, ctx: subscribe::Context<'_, C>) {
-        try_lock!(self.inner.read()).on_enter(id, ctx)
+        let _guard = CallbackGuard::enter();
+        if let Some(inner) = self.read() {
+            inner.on_enter(id, ctx)
+        }
     }
 
     #[inline]
     fn on_exit(&self, id: &span::Id, ctx: subscribe::Context<'_, C>) {
-        try_lock!(self.inner.read()).on_exit(id, ctx)
+        let _guard = CallbackGuard::enter();
+        if let Some(inner) = self.read() {
+            inner.on_exit(id, ctx)
+        }
     }
 
     #[inline]
     fn on_close(&self, id: span::Id, ctx: subscribe::Context<'_, C>) {
-        try_lock!(self.inner.read()).on_close(id, ctx)
+        let _guard = CallbackGuard::enter();
+        if let Some(inner) = self.read() {
+            inner.on_close(id, ctx)
+        }
     }
 
     #[inline]
     fn on_id_change(&self, old: &span::Id, new: &span::Id, ctx: subscribe::Context<'_, C>) {
-        try_lock!(self.inner.read()).on_id_change(old, new, ctx)
+        let _guard = CallbackGuard::enter();
+        if let Some(inner) = self.read() {
+            inner.on_id_change(old, new, ctx)
+        }
     }
 }
 
@@ -136,6 +232,13 @@ impl<S> Subscriber<S> {
             inner: Arc::downgrade(&self.inner),
         }
     }
+
+    /// Acquires a read lock on the wrapped subscriber, or `None` if the lock
+    /// was poisoned by a panic on another thread.
+    #[inline]
+    fn read(&self) -> Option<RwLockReadGuard<'_, S>> {
+        self.inner.read().ok()
+    }
 }
 
 // ===== impl Handle =====
@@ -151,11 +254,17 @@ impl<S> Handle<S> {
     /// Invokes a closure with a mutable reference to the current subscriber,
     /// allowing it to be modified in place.
     pub fn modify(&self, f: impl FnOnce(&mut S)) -> Result<(), Error> {
+        // The subscriber's callbacks hold a read lock for their entire
+        // duration; taking the write lock from inside one would deadlock.
+        if CallbackGuard::in_callback() {
+            return Err(Error::reentrant());
+        }
+
         let inner = self.inner.upgrade().ok_or(Error {
             kind: ErrorKind::CollectorGone,
         })?;
 
-        let mut lock = try_lock!(inner.write(), else return Err(Error::poisoned()));
+        let mut lock = inner.write().map_err(|_| Error::poisoned())?;
         f(&mut *lock);
         // Release the lock before rebuilding the interest cache, as that
         // function will lock the new subscriber.
@@ -190,9 +299,27 @@ impl<S> Handle<S> {
         let inner = self.inner.upgrade().ok_or(Error {
             kind: ErrorKind::CollectorGone,
         })?;
-        let inner = try_lock!(inner.read(), else return Err(Error::poisoned()));
+        let inner = inner.read().map_err(|_| Error::poisoned())?;
         Ok(f(&*inner))
     }
+
+    /// Returns `true` if the lock protecting the wrapped subscriber was
+    /// poisoned by a panic, such as a panicking closure passed to
+    /// [`modify`](Self::modify).
+    ///
+    /// While poisoned, the `reload::Subscriber` passes events and spans
+    /// through without calling the wrapped subscriber, and further calls to
+    /// [`reload`](Self::reload) or [`modify`](Self::modify) will return an
+    /// [`Error`] for which [`Error::is_poisoned`] returns `true`.
+    ///
+    /// Note that when the `parking_lot` feature is enabled, locks are never
+    /// poisoned, and this method always returns `false`.
+    pub fn is_poisoned(&self) -> bool {
+        self.inner
+            .upgrade()
+            .map(|lock| lock.is_poisoned())
+            .unwrap_or(false)
+    }
 }
 
 impl<S> Clone for Handle<S> {
@@ -212,6 +339,12 @@ impl Error {
         }
     }
 
+    fn reentrant() -> Self {
+        Self {
+            kind: ErrorKind::Reentrant,
+        }
+    }
+
     /// Returns `true` if this error occurred because the subscriber was poisoned by
     /// a panic on another thread.
     pub fn is_poisoned(&self) -> bool {
@@ -223,6 +356,13 @@ impl Error {
     pub fn is_dropped(&self) -> bool {
         matches!(self.kind, ErrorKind::CollectorGone)
     }
+
+    /// Returns `true` if this error occurred because a reload was attempted
+    /// from within one of the wrapped subscriber's own callbacks, which would
+    /// have deadlocked.
+    pub fn is_reentrant(&self) -> bool {
+        matches!(self.kind, ErrorKind::Reentrant)
+    }
 }
 
 impl fmt::Display for Error {
@@ -230,6 +370,9 @@ impl fmt::Display for Error {
         let msg = match self.kind {
             ErrorKind::CollectorGone => "subscriber no longer exists",
             ErrorKind::Poisoned => "lock poisoned",
+            ErrorKind::Reentrant => {
+                "cannot reload from within a subscriber callback; this would deadlock"
+            }
         };
         f.pad(msg)
     }
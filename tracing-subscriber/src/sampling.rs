@@ -0,0 +1,365 @@
+//! A subscriber for opt-in sampling of span trees.
+//!
+//! In high-throughput services, recording every trace is often too expensive.
+//! The [`SampleSubscriber`] in this module makes a sampling decision once per
+//! *root* span — always, never, one in every _n_ roots, or via a custom
+//! callback — and propagates that decision to every descendant of the root,
+//! whether the parent is contextual or was set explicitly.
+//!
+//! The decision is stored in the root span's [extensions] as a
+//! [`SamplingDecision`], so other subscribers in the stack can consult it with
+//! [`SamplingDecision::lookup`] and skip their own processing of unsampled
+//! spans. In addition, the `SampleSubscriber` disables *events* that occur
+//! inside unsampled spans for the entire subscriber stack, so downstream
+//! subscribers do not need to filter those themselves. Span notifications are
+//! still forwarded for unsampled spans, since the span tree must be tracked in
+//! order to propagate decisions to new children.
+//!
+//! # Examples
+//!
+//! ```
+//! use tracing_subscriber::{prelude::*, sampling::SampleSubscriber};
+//!
+//! // Record one in every 100 traces.
+//! let collector = tracing_subscriber::registry()
+//!     .with(SampleSubscriber::ratio(100))
+//!     .with(tracing_subscriber::fmt::subscriber());
+//! # let _ = collector;
+//! ```
+//!
+//! [extensions]: crate::registry::SpanData::extensions
+use crate::{registry::LookupSpan, subscribe::Context, Subscribe};
+use std::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use tracing_core::{span, Collect, Interest, Metadata};
+
+/// A [`Subscribe`] implementation that decides, once per root span, whether a
+/// span tree should be recorded.
+///
+/// See the [module-level documentation][self] for details.
+pub struct SampleSubscriber {
+    policy: Policy,
+}
+
+/// The sampling decision made for a span, stored in its [extensions].
+///
+/// Other subscribers can look this up with [`SamplingDecision::lookup`] to
+/// skip work for unsampled spans.
+///
+/// [extensions]: crate::registry::SpanData::extensions
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SamplingDecision(bool);
+
+enum Policy {
+    Always,
+    Never,
+    Ratio { rate: u64, counter: AtomicU64 },
+    Custom(Box<dyn Fn(&span::Attributes<'_>) -> bool + Send + Sync + 'static>),
+}
+
+// === impl SampleSubscriber ===
+
+impl SampleSubscriber {
+    /// Returns a `SampleSubscriber` that samples every root span.
+    pub fn always() -> Self {
+        Self {
+            policy: Policy::Always,
+        }
+    }
+
+    /// Returns a `SampleSubscriber` that samples no root spans.
+    pub fn never() -> Self {
+        Self {
+            policy: Policy::Never,
+        }
+    }
+
+    /// Returns a `SampleSubscriber` that samples one in every `rate` root
+    /// spans, starting with the first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is 0.
+    pub fn ratio(rate: u64) -> Self {
+        assert!(rate > 0, "sample rate must be greater than 0");
+        Self {
+            policy: Policy::Ratio {
+                rate,
+                counter: AtomicU64::new(0),
+            },
+        }
+    }
+
+    /// Returns a `SampleSubscriber` that calls `policy` with each root span's
+    /// [`Attributes`] to decide whether to sample its tree.
+    ///
+    /// This can be used to sample based on a span's fields — for example, to
+    /// record only spans created with a `sampled = true` field.
+    ///
+    /// [`Attributes`]: tracing_core::span::Attributes
+    pub fn with_policy<F>(policy: F) -> Self
+    where
+        F: Fn(&span::Attributes<'_>) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            policy: Policy::Custom(Box::new(policy)),
+        }
+    }
+
+    fn decide(&self, attrs: &span::Attributes<'_>) -> SamplingDecision {
+        let sampled = match &self.policy {
+            Policy::Always => true,
+            Policy::Never => false,
+            Policy::Ratio { rate, counter } => counter.fetch_add(1, Ordering::Relaxed) % rate == 0,
+            Policy::Custom(policy) => policy(attrs),
+        };
+        SamplingDecision(sampled)
+    }
+}
+
+impl<C> Subscribe<C> for SampleSubscriber
+where
+    C: Collect + for<'lookup> LookupSpan<'lookup>,
+{
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        if metadata.is_event() {
+            // Whether an event is recorded depends on the sampling decision
+            // for the span it occurs in, so it can't be cached per-callsite.
+            Interest::sometimes()
+        } else {
+            Interest::always()
+        }
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, ctx: Context<'_, C>) -> bool {
+        if !metadata.is_event() {
+            return true;
+        }
+
+        // Suppress events that occur inside an unsampled span. Events outside
+        // of any span are always enabled.
+        ctx.lookup_current()
+            .and_then(|span| {
+                let ext = span.extensions();
+                ext.get::<SamplingDecision>().copied()
+            })
+            .map(SamplingDecision::is_sampled)
+            .unwrap_or(true)
+    }
+
+    fn new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, C>) {
+        let inherited = if let Some(parent) = attrs.parent() {
+            SamplingDecision::lookup(&ctx, parent)
+        } else if attrs.is_contextual() {
+            ctx.lookup_current().and_then(|span| {
+                let ext = span.extensions();
+                ext.get::<SamplingDecision>().copied()
+            })
+        } else {
+            None
+        };
+
+        // A span without a sampled parent is the root of a new trace, so make
+        // a fresh decision for it.
+        let decision = inherited.unwrap_or_else(|| self.decide(attrs));
+        ctx.span(id)
+            .expect("span should exist in the registry; this is a bug")
+            .extensions_mut()
+            .insert(decision);
+    }
+}
+
+impl fmt::Debug for SampleSubscriber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let policy: &dyn fmt::Debug = match &self.policy {
+            Policy::Always => &"Always",
+            Policy::Never => &"Never",
+            Policy::Ratio { rate, .. } => return write!(f, "SampleSubscriber(Ratio(1/{}))", rate),
+            Policy::Custom(_) => &"Custom",
+        };
+        f.debug_tuple("SampleSubscriber").field(policy).finish()
+    }
+}
+
+// === impl SamplingDecision ===
+
+impl SamplingDecision {
+    /// Returns `true` if the span's tree was sampled.
+    pub fn is_sampled(self) -> bool {
+        self.0
+    }
+
+    /// Returns the sampling decision for the span with the given `id`, if a
+    /// [`SampleSubscriber`] in the stack has made one.
+    ///
+    /// This returns `None` if no span exists for the ID, or if no
+    /// `SampleSubscriber` is layered below the calling subscriber.
+    pub fn lookup<C>(ctx: &Context<'_, C>, id: &span::Id) -> Option<Self>
+    where
+        C: Collect + for<'lookup> LookupSpan<'lookup>,
+    {
+        let span = ctx.span(id)?;
+        let ext = span.extensions();
+        ext.get::<SamplingDecision>().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, registry};
+    use std::sync::{Arc, Mutex};
+    use tracing::collect::with_default;
+
+    /// Records the sampling decision observed for each new span, and the
+    /// number of events seen.
+    #[derive(Clone, Default)]
+    struct Probe {
+        spans: Arc<Mutex<Vec<(&'static str, Option<bool>)>>>,
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Probe {
+        fn spans(&self) -> Vec<(&'static str, Option<bool>)> {
+            self.spans.lock().unwrap().clone()
+        }
+
+        fn events(&self) -> Vec<String> {
+            self.events.lock().unwrap().clone()
+        }
+    }
+
+    impl<C> Subscribe<C> for Probe
+    where
+        C: Collect + for<'lookup> LookupSpan<'lookup>,
+    {
+        fn new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, C>) {
+            let sampled = SamplingDecision::lookup(&ctx, id).map(SamplingDecision::is_sampled);
+            self.spans
+                .lock()
+                .unwrap()
+                .push((attrs.metadata().name(), sampled));
+        }
+
+        fn on_event(&self, event: &tracing_core::Event<'_>, _: Context<'_, C>) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(event.metadata().name().to_string());
+        }
+    }
+
+    #[test]
+    fn events_in_unsampled_spans_are_suppressed() {
+        let probe = Probe::default();
+        let collector = registry()
+            .with(SampleSubscriber::never())
+            .with(probe.clone());
+
+        with_default(collector, || {
+            tracing::info_span!("root").in_scope(|| {
+                tracing::info!("in span");
+            });
+            tracing::info!("outside any span");
+        });
+
+        assert_eq!(probe.spans(), vec![("root", Some(false))]);
+        // Only the event outside of the unsampled span is recorded.
+        assert_eq!(probe.events().len(), 1);
+    }
+
+    #[test]
+    fn always_samples_everything() {
+        let probe = Probe::default();
+        let collector = registry()
+            .with(SampleSubscriber::always())
+            .with(probe.clone());
+
+        with_default(collector, || {
+            tracing::info_span!("root").in_scope(|| {
+                tracing::info!("in span");
+            });
+        });
+
+        assert_eq!(probe.spans(), vec![("root", Some(true))]);
+        assert_eq!(probe.events().len(), 1);
+    }
+
+    #[test]
+    fn contextual_children_inherit_the_decision() {
+        let probe = Probe::default();
+        let sampler = SampleSubscriber::with_policy(|attrs| attrs.metadata().name() == "keep");
+        let collector = registry().with(sampler).with(probe.clone());
+
+        with_default(collector, || {
+            tracing::info_span!("keep").in_scope(|| {
+                tracing::info_span!("child").in_scope(|| {
+                    tracing::info!("sampled event");
+                });
+            });
+            tracing::info_span!("drop").in_scope(|| {
+                tracing::info_span!("child").in_scope(|| {
+                    tracing::info!("unsampled event");
+                });
+            });
+        });
+
+        assert_eq!(
+            probe.spans(),
+            vec![
+                ("keep", Some(true)),
+                ("child", Some(true)),
+                ("drop", Some(false)),
+                ("child", Some(false)),
+            ]
+        );
+        assert_eq!(probe.events().len(), 1);
+    }
+
+    #[test]
+    fn explicit_parents_inherit_the_decision() {
+        let probe = Probe::default();
+        let sampler = SampleSubscriber::with_policy(|attrs| attrs.metadata().name() == "keep");
+        let collector = registry().with(sampler).with(probe.clone());
+
+        with_default(collector, || {
+            let keep = tracing::info_span!("keep");
+            let drop = tracing::info_span!("drop");
+            // Neither parent is entered; the children name them explicitly.
+            tracing::info_span!(parent: &keep, "kept child");
+            tracing::info_span!(parent: &drop, "dropped child");
+        });
+
+        assert_eq!(
+            probe.spans(),
+            vec![
+                ("keep", Some(true)),
+                ("drop", Some(false)),
+                ("kept child", Some(true)),
+                ("dropped child", Some(false)),
+            ]
+        );
+    }
+
+    #[test]
+    fn ratio_samples_one_in_n_roots() {
+        let probe = Probe::default();
+        let collector = registry()
+            .with(SampleSubscriber::ratio(2))
+            .with(probe.clone());
+
+        with_default(collector, || {
+            for _ in 0..4 {
+                tracing::info_span!("root");
+            }
+        });
+
+        let sampled: Vec<Option<bool>> = probe.spans().iter().map(|(_, s)| *s).collect();
+        assert_eq!(
+            sampled,
+            vec![Some(true), Some(false), Some(true), Some(false)]
+        );
+    }
+}
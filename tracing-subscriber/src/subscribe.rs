@@ -2,14 +2,24 @@
 //!
 //! [collector]: tracing_core::Collect
 use tracing_core::{
+    callsite,
     collect::{Collect, Interest},
     metadata::Metadata,
-    span, Event, LevelFilter,
+    span, Dispatch, Event, LevelFilter,
 };
 
 #[cfg(feature = "registry")]
 use crate::registry::{self, LookupSpan, Registry, SpanRef};
-use std::{any::TypeId, marker::PhantomData, ptr::NonNull};
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    marker::PhantomData,
+    ptr::NonNull,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+};
 
 /// A composable handler for `tracing` events.
 ///
@@ -206,6 +216,26 @@ where
     C: Collect,
     Self: 'static,
 {
+    /// Invoked when this subscriber is installed into a [`Dispatch`] as part
+    /// of a collector.
+    ///
+    /// This is called exactly once per `Dispatch` creation, when the layered
+    /// collector containing this subscriber is used to create a new
+    /// [`Dispatch`] (by [`Dispatch::new`] or when setting it as the global
+    /// default). It allows the subscriber to capture a handle to the
+    /// dispatcher it is part of — for example, to [downgrade] it to a
+    /// [`WeakDispatch`] that background threads can later use to re-enter the
+    /// collector. Storing a full `Dispatch` would create a reference cycle
+    /// that keeps the collector alive forever.
+    ///
+    /// [`Dispatch`]: tracing_core::Dispatch
+    /// [`Dispatch::new`]: tracing_core::Dispatch::new
+    /// [`WeakDispatch`]: tracing_core::dispatch::WeakDispatch
+    /// [downgrade]: tracing_core::Dispatch::downgrade
+    fn on_register_dispatch(&self, collector: &Dispatch) {
+        let _ = collector;
+    }
+
     /// Registers a new callsite with this subscriber, returning whether or not
     /// the subscriber is interested in being notified about the callsite, similarly
     /// to [`Collect::register_callsite`].
@@ -308,6 +338,21 @@ where
     // seems like a good future-proofing measure as it may grow other methods later...
     fn on_follows_from(&self, _span: &span::Id, _follows: &span::Id, _ctx: Context<'_, C>) {}
 
+    /// Called before [`on_event`], to determine if `on_event` should be called.
+    ///
+    /// Unlike [`enabled`], which sees only the event's static [`Metadata`],
+    /// this method is passed the [`Event`] itself, so the decision may depend
+    /// on the event's field *values*. Returning `false` prevents `on_event`
+    /// from being called for this subscriber *and* for every subscriber below
+    /// it in the stack; it cannot enable an event that filtering already
+    /// rejected.
+    ///
+    /// [`on_event`]: Subscribe::on_event
+    /// [`enabled`]: Subscribe::enabled
+    fn event_enabled(&self, _event: &Event<'_>, _ctx: Context<'_, C>) -> bool {
+        true
+    }
+
     /// Notifies this subscriber that an event has occurred.
     fn on_event(&self, _event: &Event<'_>, _ctx: Context<'_, C>) {}
 
@@ -432,6 +477,7 @@ where
         Layered {
             subscriber,
             inner: self,
+            callsite_interest: Arc::new(InterestCache::default()),
             _s: PhantomData,
         }
     }
@@ -488,6 +534,7 @@ where
         Layered {
             subscriber: self,
             inner,
+            callsite_interest: Arc::new(InterestCache::default()),
             _s: PhantomData,
         }
     }
@@ -552,6 +599,17 @@ pub struct Context<'a, C> {
 pub struct Layered<S, I, C = I> {
     subscriber: S,
     inner: I,
+
+    /// The composed `Interest` for each callsite registered with this stack
+    /// whose interest resolved definitively (`always` or `never`).
+    ///
+    /// When the composed interest is definitive, `enabled` answers from this
+    /// cache rather than walking the whole chain of subscribers again. Only
+    /// the `Layered` acting as the `Collect` implementation at the top of a
+    /// stack populates this; a `Layered` composing two subscribers leaves it
+    /// empty.
+    callsite_interest: Arc<InterestCache>,
+
     _s: PhantomData<fn(C)>,
 }
 
@@ -561,6 +619,28 @@ pub struct Identity {
     _p: (),
 }
 
+/// Cached composed `Interest`s for the callsites a [`Layered`] stack has
+/// registered, keyed by callsite identifier.
+///
+/// Only definitive (`always`/`never`) interests are stored: a `sometimes`
+/// interest means the stack's filters must be re-evaluated for every span and
+/// event, so a cache entry could never answer for it. Skipping those entries
+/// also means that a stack whose composed interest is always `sometimes` —
+/// most commonly, one containing an `EnvFilter` with dynamic directives —
+/// leaves the cache empty, and `enabled` can skip it entirely by checking
+/// `nonempty` first, without acquiring the lock.
+#[derive(Debug, Default)]
+struct InterestCache {
+    /// Whether `interests` contains any entries.
+    ///
+    /// `enabled` loads this before touching the lock, so that stacks with no
+    /// cached interests pay a single atomic load (of a value that is never
+    /// written after callsite registration) rather than a lock acquisition
+    /// and a hash lookup that would always fall through.
+    nonempty: AtomicBool,
+    interests: RwLock<HashMap<callsite::Identifier, Interest>>,
+}
+
 // === impl Layered ===
 
 impl<S, C> Collect for Layered<S, C>
@@ -570,27 +650,64 @@ where
 {
     fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
         let outer = self.subscriber.register_callsite(metadata);
-        if outer.is_never() {
+        let interest = if outer.is_never() {
             // if the outer subscriber has disabled the callsite, return now so that
-            // the collector doesn't get its hopes up.
-            return outer;
-        }
-
-        // The intention behind calling `inner.register_callsite()` before the if statement
-        // is to ensure that the inner subscriber is informed that the callsite exists
-        // regardless of the outer subscriber's filtering decision.
-        let inner = self.inner.register_callsite(metadata);
-        if outer.is_sometimes() {
-            // if this interest is "sometimes", return "sometimes" to ensure that
-            // filters are reevaluated.
+            // neither the inner subscribers nor the collector get their hopes up.
             outer
         } else {
-            // otherwise, allow the inner subscriber or collector to weigh in.
-            inner
+            // The intention behind calling `inner.register_callsite()` before the if statement
+            // is to ensure that the inner subscriber is informed that the callsite exists
+            // regardless of the outer subscriber's filtering decision.
+            let inner = self.inner.register_callsite(metadata);
+            if outer.is_sometimes() {
+                // if this interest is "sometimes", return "sometimes" to ensure that
+                // filters are reevaluated.
+                outer
+            } else {
+                // otherwise, allow the inner subscriber or collector to weigh in.
+                inner
+            }
+        };
+
+        // Cache the composed interest if it is definitive, so that `enabled`
+        // can answer those callsites without walking the chain of subscribers
+        // again. A `sometimes` interest is never cached — it must be
+        // re-evaluated per span or event regardless — but a callsite that is
+        // *re*-registered (after a filter reload, say) may have gone from
+        // definitive to `sometimes`, so any stale entry is removed. If the
+        // lock is poisoned, simply skip caching; `enabled` falls back to
+        // asking every subscriber.
+        if let Ok(mut cache) = self.callsite_interest.interests.write() {
+            if interest.is_sometimes() {
+                cache.remove(&metadata.callsite());
+            } else {
+                cache.insert(metadata.callsite(), interest.clone());
+            }
+            self.callsite_interest
+                .nonempty
+                .store(!cache.is_empty(), Ordering::Release);
         }
+        interest
     }
 
     fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        // If registering this callsite produced a definitive composed
+        // interest, the decision cannot change per-event, so there is no
+        // need to ask every subscriber in the chain again. This mirrors the
+        // macros' own handling of cached `Interest`s, and makes `enabled`
+        // near-free for callsites that a filtering subscriber has already
+        // disabled outright. A stack whose interests all composed to
+        // `sometimes` has an empty cache, and skips even the lock here.
+        if self.callsite_interest.nonempty.load(Ordering::Acquire) {
+            if let Ok(cache) = self.callsite_interest.interests.read() {
+                match cache.get(&metadata.callsite()) {
+                    Some(interest) if interest.is_always() => return true,
+                    Some(interest) if interest.is_never() => return false,
+                    _ => {}
+                }
+            }
+        }
+
         if self.subscriber.enabled(metadata, self.ctx()) {
             // if the outer subscriber enables the callsite metadata, ask the collector.
             self.inner.enabled(metadata)
@@ -607,6 +724,11 @@ where
         )
     }
 
+    fn on_register_dispatch(&self, collector: &Dispatch) {
+        self.inner.on_register_dispatch(collector);
+        self.subscriber.on_register_dispatch(collector);
+    }
+
     fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
         let id = self.inner.new_span(span);
         self.subscriber.new_span(span, &id, self.ctx());
@@ -623,6 +745,10 @@ where
         self.subscriber.on_follows_from(span, follows, self.ctx());
     }
 
+    fn event_enabled(&self, event: &Event<'_>) -> bool {
+        self.subscriber.event_enabled(event, self.ctx()) && self.inner.event_enabled(event)
+    }
+
     fn event(&self, event: &Event<'_>) {
         self.inner.event(event);
         self.subscriber.on_event(event, self.ctx());
@@ -722,13 +848,19 @@ where
     fn enabled(&self, metadata: &Metadata<'_>, ctx: Context<'_, C>) -> bool {
         if self.subscriber.enabled(metadata, ctx.clone()) {
             // if the outer subscriber enables the callsite metadata, ask the inner subscriber.
-            self.subscriber.enabled(metadata, ctx)
+            self.inner.enabled(metadata, ctx)
         } else {
             // otherwise, the callsite is disabled by this subscriber
             false
         }
     }
 
+    #[inline]
+    fn on_register_dispatch(&self, collector: &Dispatch) {
+        self.inner.on_register_dispatch(collector);
+        self.subscriber.on_register_dispatch(collector);
+    }
+
     #[inline]
     fn new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, C>) {
         self.inner.new_span(attrs, id, ctx.clone());
@@ -747,6 +879,11 @@ where
         self.subscriber.on_follows_from(span, follows, ctx);
     }
 
+    #[inline]
+    fn event_enabled(&self, event: &Event<'_>, ctx: Context<'_, C>) -> bool {
+        self.subscriber.event_enabled(event, ctx.clone()) && self.inner.event_enabled(event, ctx)
+    }
+
     #[inline]
     fn on_event(&self, event: &Event<'_>, ctx: Context<'_, C>) {
         self.inner.on_event(event, ctx.clone());
@@ -793,6 +930,13 @@ where
     S: Subscribe<C>,
     C: Collect,
 {
+    #[inline]
+    fn on_register_dispatch(&self, collector: &Dispatch) {
+        if let Some(ref inner) = self {
+            inner.on_register_dispatch(collector)
+        }
+    }
+
     #[inline]
     fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
         match self {
@@ -838,6 +982,14 @@ where
         }
     }
 
+    #[inline]
+    fn event_enabled(&self, event: &Event<'_>, ctx: Context<'_, C>) -> bool {
+        match self {
+            Some(ref inner) => inner.event_enabled(event, ctx),
+            None => true,
+        }
+    }
+
     #[inline]
     fn on_event(&self, event: &Event<'_>, ctx: Context<'_, C>) {
         if let Some(ref inner) = self {
@@ -1085,6 +1237,41 @@ where
         self.collector.as_ref()?.span(id)
     }
 
+    /// Returns [stored data] for the span with the given `id`, reporting a
+    /// diagnostic if no such span exists.
+    ///
+    /// This behaves like [`Context::span`], except that if the registry has no
+    /// data for `id`, a warning event is emitted (once per process) before
+    /// `None` is returned. A missing span in a notification path usually
+    /// indicates a bug in a subscriber or collector composition — for example,
+    /// a subscriber forwarding span IDs generated by a *different* collector.
+    /// Subscribers should prefer this method over unwrapping the result of
+    /// [`Context::span`] in methods like [`on_enter`], so that such bugs
+    /// degrade into missing data rather than panicking inside instrumentation.
+    ///
+    /// <div class="example-wrap" style="display:inline-block">
+    /// <pre class="ignore" style="white-space:normal;font:inherit;">
+    ///
+    /// **Note**: This requires the wrapped collector to implement the [`LookupSpan`] trait.
+    /// See the documentation on [`Context`]'s declaration for details.
+    ///
+    /// </pre></div>
+    ///
+    /// [stored data]: super::registry::SpanRef
+    /// [`on_enter`]: Subscribe::on_enter
+    #[cfg(feature = "registry")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "registry")))]
+    pub fn span_or_report(&self, id: &span::Id) -> Option<registry::SpanRef<'_, C>>
+    where
+        C: for<'lookup> LookupSpan<'lookup>,
+    {
+        let span = self.span(id);
+        if span.is_none() {
+            report_unknown_span(id);
+        }
+        span
+    }
+
     /// Returns `true` if an active span exists for the given `Id`.
     ///
     /// <div class="example-wrap" style="display:inline-block">
@@ -1207,6 +1394,63 @@ impl<'a, C> Context<'a, C> {
     }
 }
 
+/// Emits a warning event recording that a subscriber was passed a span ID the
+/// registry has no data for.
+///
+/// The warning is only emitted once per process: a subscriber that repeatedly
+/// forwards bogus IDs would otherwise flood the output with diagnostics from
+/// inside instrumentation paths.
+#[cfg(feature = "registry")]
+fn report_unknown_span(id: &span::Id) {
+    use std::sync::Once;
+    use tracing_core::{callsite::Callsite, field::FieldSet, identify_callsite, metadata::Kind};
+
+    struct UnknownSpanCallsite;
+    static CALLSITE: UnknownSpanCallsite = UnknownSpanCallsite;
+    static META: Metadata<'static> = Metadata::new(
+        "unknown_span_id",
+        "tracing_subscriber::subscribe",
+        tracing_core::Level::WARN,
+        None,
+        None,
+        Some("tracing_subscriber::subscribe"),
+        FieldSet::new(&["message"], identify_callsite!(&CALLSITE)),
+        Kind::EVENT,
+    );
+    impl Callsite for UnknownSpanCallsite {
+        fn set_interest(&self, _: Interest) {}
+        fn metadata(&self) -> &Metadata<'_> {
+            &META
+        }
+    }
+
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        tracing_core::dispatch::get_default(|dispatch| {
+            if !dispatch.enabled(&META) {
+                return;
+            }
+            let message = META
+                .fields()
+                .field("message")
+                .expect("metadata should have a message field");
+            // `Event::dispatch` can't be used here, as it would re-enter
+            // `get_default` while the dispatch context is already entered.
+            dispatch.event(&Event::new(
+                &META,
+                &META.fields().value_set(&[(
+                    &message,
+                    Some(&format_args!(
+                        "a subscriber was passed span ID {:?}, but the registry has no span \
+                         for that ID; this is a bug in a subscriber or collector composition",
+                        id
+                    ) as &dyn tracing_core::field::Value),
+                )]),
+            ));
+        });
+    });
+}
+
 impl<'a, C> Clone for Context<'a, C> {
     #[inline]
     fn clone(&self) -> Self {
@@ -1386,6 +1630,12 @@ pub(crate) mod tests {
                 let _guard = tracing::info_span!("contextual").entered();
                 tracing::info!("contextual span");
                 assert_eq!(*last_event_span.lock().unwrap(), Some("contextual"));
+
+                tracing::info!(parent: &parent, "explicit span inside contextual");
+                assert_eq!(*last_event_span.lock().unwrap(), Some("explicit"));
+
+                tracing::info!(parent: None, "explicit root");
+                assert_eq!(*last_event_span.lock().unwrap(), None);
             },
         );
     }
@@ -53,5 +53,12 @@ mod parking_lot_impl {
         pub(crate) fn write<'a>(&'a self) -> LockResult<RwLockWriteGuard<'a, T>> {
             Ok(self.inner.write())
         }
+
+        #[inline]
+        #[allow(dead_code)] // mirrors `std::sync::RwLock::is_poisoned`;
+        pub(crate) fn is_poisoned(&self) -> bool {
+            // `parking_lot` locks are never poisoned by panics.
+            false
+        }
     }
 }
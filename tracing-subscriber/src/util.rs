@@ -52,7 +52,7 @@ where
     /// [global default subscriber]: tracing::dispatch#setting-the-default-collector
     /// [`log`]: https://crates.io/log
     fn try_init(self) -> Result<(), TryInitError> {
-        dispatch::set_global_default(self.into()).map_err(TryInitError::new)?;
+        dispatch::set_global_default(self.into()).map_err(TryInitError::dispatcher)?;
 
         // Since we are setting the global default subscriber, we can
         // opportunistically go ahead and set its global max level hint as
@@ -64,7 +64,7 @@ where
             // subscriber, so that we get its max level hint.
             .with_max_level(tracing_core::LevelFilter::current().as_log())
             .init()
-            .map_err(TryInitError::new)?;
+            .map_err(TryInitError::logger)?;
 
         Ok(())
     }
@@ -93,13 +93,53 @@ impl<T> SubscriberInitExt for T where T: Into<Dispatch> {}
 /// Error returned by [`try_init`](SubscriberInitExt::try_init) if a global default subscriber could not be initialized.
 pub struct TryInitError {
     inner: Box<dyn Error + Send + Sync + 'static>,
+    kind: ErrorKind,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    DispatcherSet,
+    #[cfg(feature = "tracing-log")]
+    LoggerSet,
 }
 
 // ==== impl TryInitError ====
 
 impl TryInitError {
-    fn new(e: impl Into<Box<dyn Error + Send + Sync + 'static>>) -> Self {
-        Self { inner: e.into() }
+    fn dispatcher(e: impl Into<Box<dyn Error + Send + Sync + 'static>>) -> Self {
+        Self {
+            inner: e.into(),
+            kind: ErrorKind::DispatcherSet,
+        }
+    }
+
+    #[cfg(feature = "tracing-log")]
+    fn logger(e: impl Into<Box<dyn Error + Send + Sync + 'static>>) -> Self {
+        Self {
+            inner: e.into(),
+            kind: ErrorKind::LoggerSet,
+        }
+    }
+
+    /// Returns `true` if initialization failed because a global default
+    /// trace dispatcher had already been set.
+    pub fn is_global_dispatcher_set(&self) -> bool {
+        matches!(self.kind, ErrorKind::DispatcherSet)
+    }
+
+    /// Returns `true` if initialization failed because a global `log` logger
+    /// had already been set.
+    ///
+    /// This can only occur when the "tracing-log" feature is enabled. Note
+    /// that in this case the global trace dispatcher *was* installed
+    /// successfully; only the `log` compatibility bridge could not be.
+    pub fn is_logger_set(&self) -> bool {
+        #[cfg(feature = "tracing-log")]
+        {
+            matches!(self.kind, ErrorKind::LoggerSet)
+        }
+        #[cfg(not(feature = "tracing-log"))]
+        false
     }
 }
 
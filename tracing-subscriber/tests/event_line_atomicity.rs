@@ -0,0 +1,83 @@
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tracing::Level;
+use tracing_subscriber::fmt::Collector;
+
+/// A writer that appends to a shared buffer, locking it once per `write` call.
+///
+/// If the fmt collector issued more than one write per event, writes from
+/// concurrent threads could interleave mid-line.
+#[derive(Clone)]
+struct SharedWriter(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for SharedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn each_event_writes_one_complete_line() {
+    const THREADS: usize = 8;
+    const EVENTS_PER_THREAD: usize = 100;
+
+    let buf = Arc::new(Mutex::new(Vec::new()));
+    let writer = SharedWriter(buf.clone());
+
+    let collector = Collector::builder()
+        .with_writer(move || writer.clone())
+        .without_time()
+        .with_ansi(false)
+        .with_max_level(Level::INFO)
+        .finish();
+    // The global default is used so that the spawned threads inherit it.
+    tracing::collect::set_global_default(collector).expect("failed to set collector");
+
+    let handles: Vec<_> = (0..THREADS)
+        .map(|thread| {
+            thread::spawn(move || {
+                for seq in 0..EVENTS_PER_THREAD {
+                    tracing::info!(thread, seq, "hello");
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().expect("thread should not panic");
+    }
+
+    let buf = buf.lock().unwrap();
+    let output = std::str::from_utf8(&buf[..]).expect("output should be valid UTF-8");
+
+    let mut lines = 0;
+    for line in output.lines() {
+        // Every line must be exactly one complete event; a partial or
+        // interleaved line would not parse this way.
+        assert!(
+            line.starts_with(" INFO"),
+            "line did not start with the level: {:?}",
+            line
+        );
+        assert_eq!(
+            line.matches("hello").count(),
+            1,
+            "line did not contain exactly one event: {:?}",
+            line
+        );
+        assert!(
+            line.contains("thread=") && line.contains("seq="),
+            "line was missing fields: {:?}",
+            line
+        );
+        lines += 1;
+    }
+
+    assert_eq!(lines, THREADS * EVENTS_PER_THREAD);
+}
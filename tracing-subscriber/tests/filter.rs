@@ -138,6 +138,63 @@ fn add_directive_enables_event() {
     finished.assert_finished();
 }
 
+#[test]
+fn add_directive_mut_enables_events_immediately() {
+    let filter = EnvFilter::new(LevelFilter::INFO.to_string());
+    let (filter, handle) = tracing_subscriber::reload::Subscriber::new(filter);
+
+    let (subscriber, finished) = collector::mock()
+        .event(event::mock().at_level(Level::INFO).with_target("hello"))
+        .event(event::mock().at_level(Level::TRACE).with_target("hello"))
+        .done()
+        .run_with_handle();
+    let subscriber = subscriber.with(filter);
+
+    with_default(subscriber, || {
+        tracing::trace!(target: "hello", "this should be disabled");
+        tracing::info!(target: "hello", "this shouldn't be");
+
+        handle
+            .modify(|filter| {
+                filter.add_directive_mut("hello=trace".parse().expect("directive should parse"));
+            })
+            .expect("modify should succeed");
+
+        tracing::trace!(target: "hello", "this should be enabled now");
+    });
+
+    finished.assert_finished();
+}
+
+#[test]
+fn remove_directives_for_target_disables_events_immediately() {
+    let filter = EnvFilter::new("info,hello=trace");
+    let (filter, handle) = tracing_subscriber::reload::Subscriber::new(filter);
+
+    let (subscriber, finished) = collector::mock()
+        .event(event::mock().at_level(Level::TRACE).with_target("hello"))
+        .event(event::mock().at_level(Level::INFO).with_target("hello"))
+        .done()
+        .run_with_handle();
+    let subscriber = subscriber.with(filter);
+
+    with_default(subscriber, || {
+        tracing::trace!(target: "hello", "this should be enabled");
+
+        handle
+            .modify(|filter| {
+                assert_eq!(filter.remove_directives_for_target("hello"), 1);
+            })
+            .expect("modify should succeed");
+
+        tracing::trace!(target: "hello", "this should be disabled now");
+        // the global `info` directive still applies.
+        tracing::info!(target: "hello", "this should still be enabled");
+    });
+
+    finished.assert_finished();
+}
+
 #[test]
 fn span_name_filter_is_dynamic() {
     let filter: EnvFilter = "info,[cool_span]=debug"
@@ -183,3 +240,144 @@ fn span_name_filter_is_dynamic() {
 
     finished.assert_finished();
 }
+
+#[test]
+fn cached_scope_decisions_follow_enter_and_exit() {
+    // Emitting the same events repeatedly inside and outside a span exercises
+    // the per-thread cache of scope decisions; entering or exiting the span
+    // must invalidate it, so the same callsites toggle between enabled and
+    // disabled as the scope changes.
+    let filter: EnvFilter = "info,[cool_span]=debug"
+        .parse()
+        .expect("filter should parse");
+    let mut collector = collector::mock();
+    for _ in 0..2 {
+        collector = collector
+            .enter(span::mock().named("cool_span"))
+            .event(event::mock().at_level(Level::DEBUG))
+            .event(event::mock().at_level(Level::DEBUG))
+            .exit(span::mock().named("cool_span"))
+            .event(event::mock().at_level(Level::INFO));
+    }
+    let (subscriber, finished) = collector.done().run_with_handle();
+    let subscriber = subscriber.with(filter);
+
+    with_default(subscriber, || {
+        let cool_span = tracing::info_span!("cool_span");
+        for _ in 0..2 {
+            {
+                let _enter = cool_span.enter();
+                tracing::debug!("i'm enabled by the scope");
+                tracing::debug!("and so am i");
+            }
+            tracing::debug!("i should be disabled outside the span");
+            tracing::info!("i'm enabled everywhere");
+        }
+    });
+
+    finished.assert_finished();
+}
+
+#[test]
+fn dynamic_target_field_overrides_static_target() {
+    let filter: EnvFilter = "plugin_foo=debug".parse().expect("filter should parse");
+    let (subscriber, finished) = collector::mock()
+        .event(event::mock().at_level(Level::DEBUG))
+        .event(event::mock().at_level(Level::INFO))
+        .done()
+        .run_with_handle();
+    let subscriber = subscriber.with(filter);
+
+    with_default(subscriber, || {
+        // These events' static target is this test crate; the value of the
+        // `log.target` field determines whether they are enabled.
+        tracing::debug!(log.target = "plugin_foo", "enabled by the override");
+        tracing::info!(log.target = "plugin_foo::module", "enabled by prefix match");
+        tracing::debug!(log.target = "plugin_bar", "not enabled for other plugins");
+        tracing::debug!("and not for the static target");
+    });
+
+    finished.assert_finished();
+}
+
+#[test]
+fn dynamic_targets_fall_back_to_default_directive() {
+    let filter: EnvFilter = "info,plugin_foo=debug"
+        .parse()
+        .expect("filter should parse");
+    let (subscriber, finished) = collector::mock()
+        .event(event::mock().at_level(Level::DEBUG))
+        .event(event::mock().at_level(Level::INFO))
+        .done()
+        .run_with_handle();
+    let subscriber = subscriber.with(filter);
+
+    with_default(subscriber, || {
+        tracing::debug!(log.target = "plugin_foo", "enabled by the plugin directive");
+        tracing::debug!(log.target = "plugin_bar", "the default level is info");
+        tracing::info!(
+            log.target = "plugin_bar",
+            "enabled by the default directive"
+        );
+    });
+
+    finished.assert_finished();
+}
+
+#[test]
+fn unmatched_directives_warn_exactly_once() {
+    let filter: EnvFilter = "info,this_target_is_a_typo=debug"
+        .parse()
+        .expect("filter should parse");
+    let (subscriber, finished) = collector::mock()
+        .event(event::mock().at_level(Level::INFO))
+        .event(event::mock().at_level(Level::WARN))
+        .done()
+        .run_with_handle();
+    let dispatch = tracing::dispatch::Dispatch::new(subscriber.with(filter));
+
+    tracing::dispatch::with_default(&dispatch, || {
+        tracing::info!("matches the default directive");
+
+        let filter = dispatch
+            .downcast_ref::<EnvFilter>()
+            .expect("dispatch should downcast to the filter");
+        let matched = filter.matched_directives();
+        assert_eq!(matched.len(), 2);
+        for (directive, count) in matched {
+            match directive.target() {
+                Some("this_target_is_a_typo") => assert_eq!(count, 0),
+                _ => assert!(count > 0, "the default directive should have matched"),
+            }
+        }
+
+        // The first call emits the warning; the second must not.
+        filter.check_unmatched();
+        filter.check_unmatched();
+    });
+
+    finished.assert_finished();
+}
+
+#[test]
+fn no_warning_when_every_directive_matches() {
+    let filter: EnvFilter = "info,matched_target=debug"
+        .parse()
+        .expect("filter should parse");
+    let (subscriber, finished) = collector::mock()
+        .event(event::mock().at_level(Level::DEBUG))
+        .done()
+        .run_with_handle();
+    let dispatch = tracing::dispatch::Dispatch::new(subscriber.with(filter));
+
+    tracing::dispatch::with_default(&dispatch, || {
+        tracing::debug!(target: "matched_target", "matches the target directive");
+
+        dispatch
+            .downcast_ref::<EnvFilter>()
+            .expect("dispatch should downcast to the filter")
+            .check_unmatched();
+    });
+
+    finished.assert_finished();
+}
@@ -0,0 +1,66 @@
+#![cfg(all(feature = "fmt", feature = "env-filter", feature = "tracing-log"))]
+//! Tests that `fmt()`-based global initialization bridges both ecosystems:
+//! `log` records and `tracing` events must reach the configured writer.
+//!
+//! This lives in its own integration test file, as installing the global
+//! dispatcher and logger can only happen once per process.
+use std::io;
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::filter::EnvFilter;
+use tracing_subscriber::fmt::writer::MakeWriter;
+
+#[derive(Clone, Default)]
+struct CaptureWriter(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for CaptureWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for CaptureWriter {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+#[test]
+fn log_and_tracing_events_reach_the_writer() {
+    let writer = CaptureWriter::default();
+    tracing_subscriber::fmt()
+        // The variable is unset, so the default directives apply.
+        .with_env_filter(EnvFilter::from_env_or("FMT_LOG_BRIDGE_LOG", "info"))
+        .with_writer(writer.clone())
+        .try_init()
+        .expect("first initialization should succeed");
+
+    tracing::info!("hello from tracing");
+    log::info!("hello from log");
+    log::debug!("this should be filtered out");
+
+    let output = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+    println!("{}", output);
+    assert!(
+        output.contains("hello from tracing"),
+        "tracing event should reach the writer"
+    );
+    assert!(
+        output.contains("hello from log"),
+        "log record should reach the writer"
+    );
+    assert!(
+        !output.contains("this should be filtered out"),
+        "default `info` directives should filter out debug records"
+    );
+
+    // Initializing again must report an error rather than panicking, as both
+    // the global dispatcher and the `log` logger are already installed.
+    assert!(tracing_subscriber::fmt().try_init().is_err());
+}
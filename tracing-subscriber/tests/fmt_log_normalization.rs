@@ -0,0 +1,75 @@
+#![cfg(all(feature = "fmt", feature = "tracing-log"))]
+//! Verifies that records bridged from the `log` crate are rendered with the
+//! metadata of the *original* callsite, not the bridge's static shim.
+//!
+//! Bridged events carry the record's target, module path, file, and line in
+//! `log.*` fields; the fmt formatters reconstruct accurate metadata from them
+//! via [`NormalizeEvent::normalized_metadata`], so the output should name the
+//! emitting crate and source location rather than `tracing_log`.
+//!
+//! This lives in its own integration test file, as installing the global
+//! dispatcher and logger can only happen once per process.
+//!
+//! [`NormalizeEvent::normalized_metadata`]: tracing_log::NormalizeEvent::normalized_metadata
+use std::io;
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::fmt::writer::MakeWriter;
+
+#[derive(Clone, Default)]
+struct CaptureWriter(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for CaptureWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for CaptureWriter {
+    type Writer = Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+#[test]
+fn bridged_records_render_with_original_metadata() {
+    let writer = CaptureWriter::default();
+    tracing_subscriber::fmt()
+        .with_writer(writer.clone())
+        .with_file(true)
+        .with_line_number(true)
+        .try_init()
+        .expect("initialization should succeed");
+
+    let line = line!() + 1;
+    log::warn!(target: "foo", "bridged warning");
+
+    let output = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+    println!("{}", output);
+    assert!(
+        output.contains("foo: bridged warning"),
+        "the record's own target should be displayed, got: {:?}",
+        output
+    );
+    assert!(
+        output.contains(&format!("at {}:{}", file!(), line)),
+        "the record's file and line should be displayed, got: {:?}",
+        output
+    );
+    assert!(
+        !output.contains("tracing_log"),
+        "the bridge's shim callsite should not leak into the output, got: {:?}",
+        output
+    );
+    assert!(
+        !output.contains("log.target"),
+        "the `log.*` carrier fields should not be rendered, got: {:?}",
+        output
+    );
+}
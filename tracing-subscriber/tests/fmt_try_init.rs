@@ -0,0 +1,22 @@
+#![cfg(feature = "fmt")]
+
+// The global dispatcher can only be set once per process, so this lives in
+// its own integration test binary rather than alongside the other `fmt`
+// tests.
+#[test]
+fn second_try_init_reports_dispatcher_already_set() {
+    tracing_subscriber::fmt()
+        .with_writer(std::io::sink)
+        .try_init()
+        .expect("first initialization should succeed");
+
+    // Events must now flow without panicking.
+    tracing::info!("initialized");
+
+    let err = tracing_subscriber::fmt()
+        .with_writer(std::io::sink)
+        .try_init()
+        .expect_err("second initialization should fail");
+    assert!(err.is_global_dispatcher_set());
+    assert!(!err.is_logger_set());
+}
@@ -0,0 +1,29 @@
+#![cfg(all(feature = "fmt", feature = "tracing-log"))]
+
+struct NopLogger;
+
+impl log::Log for NopLogger {
+    fn enabled(&self, _: &log::Metadata<'_>) -> bool {
+        false
+    }
+    fn log(&self, _: &log::Record<'_>) {}
+    fn flush(&self) {}
+}
+
+// The global `log` logger can only be set once per process, so this lives in
+// its own integration test binary.
+#[test]
+fn try_init_reports_logger_already_set() {
+    log::set_boxed_logger(Box::new(NopLogger)).expect("no logger should be set yet");
+
+    let err = tracing_subscriber::fmt()
+        .with_writer(std::io::sink)
+        .try_init()
+        .expect_err("initialization should fail when a `log` logger is set");
+    assert!(err.is_logger_set());
+    assert!(!err.is_global_dispatcher_set());
+
+    // The trace dispatcher itself was installed before the bridge failed, so
+    // `tracing` events still work.
+    tracing::info!("still works");
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "registry")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tracing::collect::with_default;
+use tracing_subscriber::{filter::Targets, hooks::CallbackSubscriber, prelude::*};
+
+#[derive(Clone, Default)]
+struct Counter(Arc<AtomicUsize>);
+
+impl Counter {
+    fn bump(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[test]
+fn each_hook_is_invoked() {
+    let new_spans = Counter::default();
+    let enters = Counter::default();
+    let exits = Counter::default();
+    let closes = Counter::default();
+    let events = Counter::default();
+
+    let subscriber = CallbackSubscriber::new()
+        .on_new_span({
+            let new_spans = new_spans.clone();
+            move |_attrs, _id| new_spans.bump()
+        })
+        .on_enter({
+            let enters = enters.clone();
+            move |_id, meta| {
+                assert_eq!(meta.name(), "traced");
+                enters.bump()
+            }
+        })
+        .on_exit({
+            let exits = exits.clone();
+            move |_id, meta| {
+                assert_eq!(meta.name(), "traced");
+                exits.bump()
+            }
+        })
+        .on_close({
+            let closes = closes.clone();
+            move |_id, meta| {
+                assert_eq!(meta.name(), "traced");
+                closes.bump()
+            }
+        })
+        .on_event({
+            let events = events.clone();
+            move |event| {
+                assert_eq!(event.metadata().target(), module_path!());
+                events.bump()
+            }
+        });
+    let collector = tracing_subscriber::registry().with(subscriber);
+
+    with_default(collector, || {
+        let span = tracing::info_span!("traced");
+        {
+            let _entered = span.enter();
+            tracing::info!("inside");
+        }
+        {
+            let _entered = span.enter();
+        }
+        drop(span);
+    });
+
+    assert_eq!(new_spans.get(), 1);
+    assert_eq!(enters.get(), 2);
+    assert_eq!(exits.get(), 2);
+    assert_eq!(closes.get(), 1);
+    assert_eq!(events.get(), 1);
+}
+
+#[test]
+fn hooks_are_filtered_like_any_other_subscriber() {
+    let events = Counter::default();
+    let subscriber = CallbackSubscriber::new().on_event({
+        let events = events.clone();
+        move |_event| events.bump()
+    });
+    let filter: Targets = "enabled_target=info".parse().expect("filter should parse");
+    let collector = tracing_subscriber::registry().with(subscriber).with(filter);
+
+    with_default(collector, || {
+        tracing::info!(target: "enabled_target", "seen");
+        tracing::info!(target: "other_target", "not seen");
+    });
+
+    assert_eq!(events.get(), 1);
+}
@@ -0,0 +1,94 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tracing::{collect::Interest, Collect, Event, Level, Metadata};
+use tracing_subscriber::{
+    prelude::*,
+    registry::Registry,
+    subscribe::{Context, Subscribe},
+};
+
+/// A subscriber that explicitly opts in to every callsite and counts the
+/// callsites and events it observes.
+#[derive(Clone, Default)]
+struct RecordingSubscriber {
+    callsites: Arc<AtomicUsize>,
+    events: Arc<AtomicUsize>,
+}
+
+impl<C: Collect> Subscribe<C> for RecordingSubscriber {
+    fn register_callsite(&self, _: &'static Metadata<'static>) -> Interest {
+        self.callsites.fetch_add(1, Ordering::Relaxed);
+        Interest::always()
+    }
+
+    fn enabled(&self, _: &Metadata<'_>, _: Context<'_, C>) -> bool {
+        true
+    }
+
+    fn on_event(&self, _: &Event<'_>, _: Context<'_, C>) {
+        self.events.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// A filter that cannot decide at registration time and enables everything
+/// except `TRACE`-level callsites per-call.
+struct SometimesFilter;
+
+impl<C: Collect> Subscribe<C> for SometimesFilter {
+    fn register_callsite(&self, _: &'static Metadata<'static>) -> Interest {
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, _: Context<'_, C>) -> bool {
+        metadata.level() != &Level::TRACE
+    }
+}
+
+/// A filter that disables every callsite outright.
+struct NeverFilter;
+
+impl<C: Collect> Subscribe<C> for NeverFilter {
+    fn register_callsite(&self, _: &'static Metadata<'static>) -> Interest {
+        Interest::never()
+    }
+
+    fn enabled(&self, _: &Metadata<'_>, _: Context<'_, C>) -> bool {
+        false
+    }
+}
+
+/// A subscriber that opts in to every callsite must still see the events
+/// that a "sometimes" filter higher in the stack lets through — and only
+/// those, since filters compose with AND.
+#[test]
+fn always_subscriber_sees_events_allowed_by_sometimes_filter() {
+    let recording = RecordingSubscriber::default();
+    let events = recording.events.clone();
+    let collector = Registry::default().with(recording).with(SometimesFilter);
+
+    tracing::collect::with_default(collector, || {
+        tracing::info!(target: "layered_always", "allowed");
+        tracing::trace!(target: "layered_always", "filtered out");
+    });
+
+    assert_eq!(events.load(Ordering::Relaxed), 1);
+}
+
+/// A definitive `Interest::never()` from the outermost filter suppresses
+/// both registration and events for the rest of the stack.
+#[test]
+fn never_filter_suppresses_inner_callbacks() {
+    let recording = RecordingSubscriber::default();
+    let callsites = recording.callsites.clone();
+    let events = recording.events.clone();
+    let collector = Registry::default().with(recording).with(NeverFilter);
+
+    tracing::collect::with_default(collector, || {
+        tracing::info!(target: "layered_never", "dropped");
+    });
+
+    assert_eq!(callsites.load(Ordering::Relaxed), 0);
+    assert_eq!(events.load(Ordering::Relaxed), 0);
+}
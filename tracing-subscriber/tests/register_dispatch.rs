@@ -0,0 +1,77 @@
+#![cfg(feature = "registry")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::dispatch::{self, Dispatch, WeakDispatch};
+use tracing_core::Collect;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::subscribe::Context;
+use tracing_subscriber::Subscribe;
+
+/// A subscriber that stashes a `WeakDispatch` to the dispatcher it was
+/// installed into.
+#[derive(Clone, Default)]
+struct CaptureDispatch {
+    dispatch: Arc<Mutex<Option<WeakDispatch>>>,
+    callbacks: Arc<AtomicUsize>,
+    events: Arc<AtomicUsize>,
+}
+
+impl<C: Collect> Subscribe<C> for CaptureDispatch {
+    fn on_register_dispatch(&self, collector: &Dispatch) {
+        self.callbacks.fetch_add(1, Ordering::Relaxed);
+        *self.dispatch.lock().unwrap() = Some(collector.downgrade());
+    }
+
+    fn on_event(&self, _: &tracing::Event<'_>, _: Context<'_, C>) {
+        self.events.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn layers_are_notified_exactly_once_per_dispatch() {
+    let capture = CaptureDispatch::default();
+    let dispatch = Dispatch::new(tracing_subscriber::registry().with(capture.clone()));
+    assert_eq!(capture.callbacks.load(Ordering::Relaxed), 1);
+
+    // Cloning the dispatch must not re-run the callback; only creating a new
+    // one does.
+    #[allow(clippy::redundant_clone)]
+    let _clone = dispatch.clone();
+    assert_eq!(capture.callbacks.load(Ordering::Relaxed), 1);
+
+    let _dispatch2 = Dispatch::new(tracing_subscriber::registry().with(capture.clone()));
+    assert_eq!(capture.callbacks.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn captured_dispatch_is_usable_from_another_thread() {
+    let capture = CaptureDispatch::default();
+    let dispatch = Dispatch::new(tracing_subscriber::registry().with(capture.clone()));
+
+    let weak = capture
+        .dispatch
+        .lock()
+        .unwrap()
+        .clone()
+        .expect("on_register_dispatch should have captured the dispatch");
+
+    let events = capture.events.clone();
+    std::thread::spawn(move || {
+        let dispatch = weak
+            .upgrade()
+            .expect("collector is still alive, upgrade should succeed");
+        dispatch::with_default(&dispatch, || {
+            tracing::info!("sent through the captured dispatch");
+        });
+    })
+    .join()
+    .expect("thread should not panic");
+
+    assert_eq!(events.load(Ordering::Relaxed), 1);
+
+    // Once all strong handles are gone, the weak handle no longer upgrades;
+    // holding it did not keep the collector alive.
+    let weak = capture.dispatch.lock().unwrap().clone().unwrap();
+    drop(dispatch);
+    assert!(weak.upgrade().is_none());
+}
@@ -10,11 +10,11 @@ pub struct NopCollector;
 
 impl Collect for NopCollector {
     fn register_callsite(&self, _: &'static Metadata<'static>) -> Interest {
-        Interest::never()
+        Interest::always()
     }
 
     fn enabled(&self, _: &Metadata<'_>) -> bool {
-        false
+        true
     }
 
     fn new_span(&self, _: &Attributes<'_>) -> Id {
@@ -31,6 +31,57 @@ impl Collect for NopCollector {
     }
 }
 
+#[test]
+fn reload_invalidates_callsite_interest_cache() {
+    static EVENTS: AtomicUsize = AtomicUsize::new(0);
+
+    enum Filter {
+        Off,
+        On,
+    }
+
+    impl<S: Collect> tracing_subscriber::Subscribe<S> for Filter {
+        fn register_callsite(&self, _: &Metadata<'_>) -> Interest {
+            match self {
+                Filter::Off => Interest::never(),
+                Filter::On => Interest::always(),
+            }
+        }
+
+        fn enabled(&self, _: &Metadata<'_>, _: subscribe::Context<'_, S>) -> bool {
+            match self {
+                Filter::Off => false,
+                Filter::On => true,
+            }
+        }
+
+        fn on_event(&self, _: &Event<'_>, _: subscribe::Context<'_, S>) {
+            EVENTS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+    fn event() {
+        tracing::trace!("my event");
+    }
+
+    let (subscriber, handle) = Subscriber::new(Filter::Off);
+
+    let dispatcher = tracing_core::dispatch::Dispatch::new(subscriber.with_collector(NopCollector));
+
+    tracing_core::dispatch::with_default(&dispatcher, || {
+        // While the `Off` filter is active, the callsite's cached interest
+        // disables the event entirely.
+        event();
+        assert_eq!(EVENTS.load(Ordering::SeqCst), 0);
+
+        handle.reload(Filter::On).expect("should reload");
+
+        // Reloading must rebuild the interest cache, so the previously
+        // disabled callsite starts emitting events again.
+        event();
+        assert_eq!(EVENTS.load(Ordering::SeqCst), 1);
+    })
+}
+
 #[test]
 fn reload_handle() {
     static FILTER1_CALLS: AtomicUsize = AtomicUsize::new(0);
@@ -81,3 +132,97 @@ fn reload_handle() {
         assert_eq!(FILTER2_CALLS.load(Ordering::SeqCst), 1);
     })
 }
+
+// `parking_lot` locks are never poisoned, so this test only makes sense with
+// the standard library's `RwLock`.
+#[cfg(not(feature = "parking_lot"))]
+#[test]
+fn poisoned_lock_passes_through() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    static EVENTS: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counting;
+
+    impl<S: Collect> tracing_subscriber::Subscribe<S> for Counting {
+        fn on_event(&self, _: &Event<'_>, _: subscribe::Context<'_, S>) {
+            EVENTS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+    fn event() {
+        tracing::trace!("my event");
+    }
+
+    let (subscriber, handle) = Subscriber::new(Counting);
+
+    let dispatcher = tracing_core::dispatch::Dispatch::new(subscriber.with_collector(NopCollector));
+
+    tracing_core::dispatch::with_default(&dispatcher, || {
+        event();
+        assert_eq!(EVENTS.load(Ordering::SeqCst), 1);
+        assert!(!handle.is_poisoned());
+
+        // Poison the lock by panicking while holding the write guard.
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            handle
+                .modify(|_| panic!("oh no, the reload closure panicked"))
+                .unwrap();
+        }));
+        assert!(result.is_err());
+        assert!(handle.is_poisoned());
+
+        // Instrumentation must neither panic nor reach the wrapped
+        // subscriber while the lock is poisoned.
+        event();
+        assert_eq!(EVENTS.load(Ordering::SeqCst), 1);
+
+        // Further reload attempts report the poisoned state as an error.
+        let err = handle.modify(|_| {}).unwrap_err();
+        assert!(err.is_poisoned());
+    })
+}
+
+#[test]
+fn reentrant_reload_errors_instead_of_deadlocking() {
+    use std::sync::{Arc, Mutex};
+
+    struct Reloader {
+        handle: Arc<Mutex<Option<Handle<Reloader>>>>,
+        result: Arc<Mutex<Option<Result<(), Error>>>>,
+    }
+
+    impl<S: Collect> tracing_subscriber::Subscribe<S> for Reloader {
+        fn on_event(&self, _: &Event<'_>, _: subscribe::Context<'_, S>) {
+            // Attempting to reload from inside a callback would deadlock on
+            // the write lock; it must return an error instead.
+            if let Some(handle) = self.handle.lock().unwrap().as_ref() {
+                *self.result.lock().unwrap() = Some(handle.modify(|_| {}));
+            }
+        }
+    }
+    fn event() {
+        tracing::trace!("my event");
+    }
+
+    let handle_slot = Arc::new(Mutex::new(None));
+    let result = Arc::new(Mutex::new(None));
+    let (subscriber, handle) = Subscriber::new(Reloader {
+        handle: handle_slot.clone(),
+        result: result.clone(),
+    });
+    *handle_slot.lock().unwrap() = Some(handle);
+
+    let dispatcher = tracing_core::dispatch::Dispatch::new(subscriber.with_collector(NopCollector));
+
+    tracing_core::dispatch::with_default(&dispatcher, || {
+        event();
+    });
+
+    let err = result
+        .lock()
+        .unwrap()
+        .take()
+        .expect("the event should have been observed")
+        .expect_err("a reentrant reload should fail");
+    assert!(err.is_reentrant());
+}
@@ -1,8 +1,2 @@
 #[cfg(test)]
-pub use self::support::*;
-// This has to have the same name as the module in `tracing`.
-#[path = "../../tracing/tests/support/mod.rs"]
-#[cfg(test)]
-// path attribute requires referenced module to have same name so allow module inception here
-#[allow(clippy::module_inception)]
-mod support;
+pub use tracing_mock::*;
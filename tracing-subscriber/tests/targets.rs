@@ -0,0 +1,77 @@
+mod support;
+use self::support::*;
+use tracing::{self, collect::with_default, Level};
+use tracing_subscriber::{
+    filter::{LevelFilter, Targets},
+    prelude::*,
+};
+
+#[test]
+fn targets_filter_by_prefix() {
+    let filter = Targets::new()
+        .with_target("hyper", LevelFilter::OFF)
+        .with_target("my_app", LevelFilter::DEBUG)
+        .with_default(LevelFilter::WARN);
+    let (subscriber, finished) = collector::mock()
+        .event(
+            event::mock()
+                .at_level(Level::DEBUG)
+                .with_target("my_app::module"),
+        )
+        .event(event::mock().at_level(Level::WARN))
+        .done()
+        .run_with_handle();
+    let subscriber = subscriber.with(filter);
+
+    with_default(subscriber, || {
+        // `hyper` matches its submodules' targets by prefix...
+        tracing::error!(target: "hyper::proto", "this should be disabled");
+        // ...and so does `my_app`.
+        tracing::debug!(target: "my_app::module", "this should be enabled");
+        tracing::trace!(target: "my_app", "this should be disabled");
+        // Targets matched by no directive fall back to the default level.
+        tracing::info!("this should be disabled");
+        tracing::warn!("this should be enabled");
+    });
+
+    finished.assert_finished();
+}
+
+#[test]
+fn targets_parsed_from_a_string() {
+    let filter: Targets = "hyper=off,my_app=debug,warn"
+        .parse()
+        .expect("filter should parse");
+    let (subscriber, finished) = collector::mock()
+        .event(event::mock().at_level(Level::DEBUG).with_target("my_app"))
+        .event(event::mock().at_level(Level::ERROR))
+        .done()
+        .run_with_handle();
+    let subscriber = subscriber.with(filter);
+
+    with_default(subscriber, || {
+        tracing::warn!(target: "hyper", "this should be disabled");
+        tracing::debug!(target: "my_app", "this should be enabled");
+        tracing::error!("this should be enabled too");
+    });
+
+    finished.assert_finished();
+}
+
+#[test]
+fn targets_without_a_default_disable_unmatched_targets() {
+    let filter = Targets::new().with_target("my_app", LevelFilter::INFO);
+    let (subscriber, finished) = collector::mock()
+        .event(event::mock().at_level(Level::INFO).with_target("my_app"))
+        .done()
+        .run_with_handle();
+    let subscriber = subscriber.with(filter);
+
+    with_default(subscriber, || {
+        tracing::error!(target: "other_crate", "this should be disabled");
+        tracing::info!(target: "my_app", "this should be enabled");
+        tracing::error!("this should be disabled too");
+    });
+
+    finished.assert_finished();
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "registry")]
+//! Tests that `Context::span_or_report` degrades gracefully — emitting a
+//! warning exactly once, rather than panicking — when a subscriber is passed
+//! a span ID that the registry has no data for.
+//!
+//! This lives in its own test file because the diagnostic is dispatched to the
+//! *global* default collector; the scoped dispatch context is already entered
+//! while a subscriber callback runs.
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tracing::Collect;
+use tracing_core::{span, Event};
+use tracing_subscriber::{prelude::*, registry::LookupSpan, subscribe::Context, Subscribe};
+
+/// A buggy subscriber that looks up a span ID from some other collector.
+struct ForwardsBogusId;
+
+impl<C> Subscribe<C> for ForwardsBogusId
+where
+    C: Collect + for<'lookup> LookupSpan<'lookup>,
+{
+    fn on_enter(&self, _: &span::Id, ctx: Context<'_, C>) {
+        let bogus = span::Id::from_u64(0xDEAD);
+        assert!(ctx.span_or_report(&bogus).is_none());
+    }
+}
+
+/// Counts the `unknown_span_id` warnings emitted by `span_or_report`.
+#[derive(Clone, Default)]
+struct CountWarnings(Arc<AtomicUsize>);
+
+impl<C> Subscribe<C> for CountWarnings
+where
+    C: Collect + for<'lookup> LookupSpan<'lookup>,
+{
+    fn on_event(&self, event: &Event<'_>, _: Context<'_, C>) {
+        let metadata = event.metadata();
+        if metadata.name() == "unknown_span_id"
+            && metadata.target() == "tracing_subscriber::subscribe"
+        {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[test]
+fn bogus_span_id_warns_once_instead_of_panicking() {
+    let warnings = CountWarnings::default();
+    let collector = tracing_subscriber::registry()
+        .with(ForwardsBogusId)
+        .with(warnings.clone());
+    collector.init();
+
+    // Each of these triggers a bogus lookup in `ForwardsBogusId::on_enter`;
+    // none of them should panic, and only the first should warn.
+    tracing::info_span!("first").in_scope(|| {});
+    tracing::info_span!("second").in_scope(|| {});
+
+    assert_eq!(warnings.0.load(Ordering::Relaxed), 1);
+}
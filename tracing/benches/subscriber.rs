@@ -170,6 +170,72 @@ fn criterion_benchmark(c: &mut Criterion) {
     c.finish();
 }
 
+fn bench_max_level_hint(c: &mut Criterion) {
+    /// A collector that enables spans and events up to the `INFO` level, and
+    /// says so via `max_level_hint`, so that more verbose callsites can be
+    /// disabled by the global max level without consulting the dispatcher.
+    struct InfoCollector;
+
+    impl tracing::Collect for InfoCollector {
+        fn new_span(&self, span: &span::Attributes<'_>) -> Id {
+            let _ = span;
+            Id::from_u64(0xDEAD_FACE)
+        }
+
+        fn event(&self, event: &Event<'_>) {
+            let _ = event;
+        }
+
+        fn record(&self, span: &Id, values: &span::Record<'_>) {
+            let _ = (span, values);
+        }
+
+        fn record_follows_from(&self, span: &Id, follows: &Id) {
+            let _ = (span, follows);
+        }
+
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.level() <= &Level::INFO
+        }
+
+        fn max_level_hint(&self) -> Option<tracing::level_filters::LevelFilter> {
+            Some(tracing::level_filters::LevelFilter::INFO)
+        }
+
+        fn enter(&self, span: &Id) {
+            let _ = span;
+        }
+
+        fn exit(&self, span: &Id) {
+            let _ = span;
+        }
+
+        fn current_span(&self) -> Current {
+            Current::unknown()
+        }
+    }
+
+    let mut group = c.benchmark_group("scoped/max_level_hint");
+    // An event below the collector's maximum level should be disabled by the
+    // global max level check alone; compare this against the atomic-load
+    // baselines in the `no_subscriber` benchmarks.
+    group.bench_function("disabled_event", |b| {
+        tracing::collect::with_default(InfoCollector, || {
+            b.iter(|| {
+                tracing::event!(Level::TRACE, "hello");
+            });
+        });
+    });
+    group.bench_function("enabled_event", |b| {
+        tracing::collect::with_default(InfoCollector, || {
+            b.iter(|| {
+                tracing::event!(Level::INFO, "hello");
+            });
+        });
+    });
+    group.finish();
+}
+
 fn bench_dispatch(c: &mut Criterion) {
     let mut group = c.benchmark_group("no/dispatch");
     group.bench_function("get_ref", |b| {
@@ -208,5 +274,10 @@ fn bench_dispatch(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, criterion_benchmark, bench_dispatch);
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    bench_max_level_hint,
+    bench_dispatch
+);
 criterion_main!(benches);
@@ -1,6 +1,10 @@
 //! Structured data associated with `Span`s and `Event`s.
 pub use tracing_core::field::*;
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod dynamic;
+
 use crate::Metadata;
 
 /// Trait implemented to allow a type to be used as a field key.
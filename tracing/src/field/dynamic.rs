@@ -0,0 +1,330 @@
+//! Support for emitting events whose field names are only known at runtime.
+//!
+//! The [event macros] require field names to be known at compile time, so
+//! that each callsite's [`Metadata`] can be constructed statically. Code that
+//! forwards diagnostics from another system — a scripting or plugin layer,
+//! for example — receives its key/value pairs at runtime, and would otherwise
+//! have to serialize them into a single `message` field, losing their
+//! structure.
+//!
+//! The [`EventBuilder`] type provides a non-macro path for such code: field
+//! names and values are pushed at runtime, and the event is dispatched
+//! through the normal [`Dispatch::event`] path, so collectors cannot tell it
+//! apart from a macro-generated event.
+//!
+//! # Costs
+//!
+//! Because [`Metadata`] must be `'static`, a callsite is allocated, leaked,
+//! and registered for every distinct event *shape* (the combination of
+//! target, level, and field names). Shapes are interned: emitting repeatedly
+//! with the same shape reuses the same leaked metadata, but each *new* shape
+//! permanently allocates. The intern table holds at most
+//! [`MAX_SHAPES`] shapes; [`EventBuilder::emit`] returns an error once the
+//! table is full, rather than leaking without bound. Emitting also pays for a
+//! hash-map lookup under a lock, so macro-generated events should be
+//! preferred wherever the field names are known at compile time.
+//!
+//! [event macros]: crate::event!
+//! [`Metadata`]: crate::Metadata
+//! [`Dispatch::event`]: crate::Dispatch::event
+use crate::{
+    callsite::{self, Callsite},
+    collect::Interest,
+    field::{Field, Value},
+    metadata::{Kind, Level, Metadata},
+    Event,
+};
+use core::fmt;
+use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Mutex;
+use tracing_core::callsite::Registration;
+use tracing_core::field::FieldSet;
+
+/// The maximum number of distinct event shapes that will be interned.
+///
+/// Each distinct shape (target, level, and field names) leaks one callsite
+/// and its metadata; this bound keeps a misbehaving caller — one that
+/// generates field names from unbounded input — from leaking memory without
+/// limit. Once the table is full, [`EventBuilder::emit`] returns an error
+/// for any *new* shape, while already-interned shapes keep working.
+pub const MAX_SHAPES: usize = 256;
+
+/// The maximum number of fields on a dynamic event.
+///
+/// This matches the upper bound on the number of values that can be passed
+/// to a collector in a single value set.
+pub const MAX_FIELDS: usize = 32;
+
+/// A builder for an [`Event`] whose fields are determined at runtime.
+///
+/// See the [module documentation][self] for details and costs.
+///
+/// # Examples
+///
+/// ```
+/// use tracing::field::dynamic::EventBuilder;
+/// use tracing::Level;
+///
+/// # fn fields_from_script() -> Vec<(String, String)> { vec![] }
+/// let fields: Vec<(String, String)> = fields_from_script();
+/// let fields: Vec<(&str, &str)> = fields
+///     .iter()
+///     .map(|(name, value)| (name.as_str(), value.as_str()))
+///     .collect();
+///
+/// let mut builder = EventBuilder::new(Level::INFO, "my_plugin");
+/// for (name, value) in &fields {
+///     builder = builder.field(name, value);
+/// }
+/// builder.emit().expect("failed to emit dynamic event");
+/// ```
+///
+/// [`Event`]: crate::Event
+pub struct EventBuilder<'a> {
+    level: Level,
+    target: &'a str,
+    fields: Vec<(&'a str, &'a (dyn Value + 'a))>,
+}
+
+impl fmt::Debug for EventBuilder<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventBuilder")
+            .field("level", &self.level)
+            .field("target", &self.target)
+            .field(
+                "fields",
+                &self
+                    .fields
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// An error returned by [`EventBuilder::emit`].
+#[derive(Debug)]
+pub struct EmitError {
+    kind: EmitErrorKind,
+}
+
+#[derive(Debug)]
+enum EmitErrorKind {
+    /// The intern table already holds [`MAX_SHAPES`] distinct shapes.
+    TooManyShapes,
+    /// The builder holds more than [`MAX_FIELDS`] fields.
+    TooManyFields(usize),
+}
+
+impl<'a> EventBuilder<'a> {
+    /// Returns a new `EventBuilder` for an event with the given level and
+    /// target.
+    pub fn new(level: Level, target: &'a str) -> Self {
+        Self {
+            level,
+            target,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Adds a field with the given runtime-determined name and value.
+    ///
+    /// An event may have at most [`MAX_FIELDS`] fields; [`emit`] returns an
+    /// error if more were added.
+    ///
+    /// [`emit`]: EventBuilder::emit
+    pub fn field(mut self, name: &'a str, value: &'a (dyn Value + 'a)) -> Self {
+        self.fields.push((name, value));
+        self
+    }
+
+    /// Dispatches the event to the current collector.
+    ///
+    /// The callsite for the event's shape is interned on first use; see the
+    /// [module documentation][self] for the costs involved. Returns an error
+    /// if the event has more than [`MAX_FIELDS`] fields, or if the shape is
+    /// new and the intern table already holds [`MAX_SHAPES`] shapes.
+    pub fn emit(self) -> Result<(), EmitError> {
+        if self.fields.len() > MAX_FIELDS {
+            return Err(EmitError {
+                kind: EmitErrorKind::TooManyFields(self.fields.len()),
+            });
+        }
+
+        let callsite = intern(self.level, self.target, &self.fields)?;
+        let meta = callsite.metadata();
+
+        let interest = callsite.interest();
+        if interest.is_never() {
+            return Ok(());
+        }
+        if !interest.is_always() && !crate::dispatch::get_default(|default| default.enabled(meta)) {
+            return Ok(());
+        }
+
+        let fields: Vec<Field> = meta.fields().iter().collect();
+        let values: Vec<(&Field, Option<&(dyn Value + '_)>)> = fields
+            .iter()
+            .zip(self.fields.iter())
+            .map(|(field, (_, value))| (field, Some(*value)))
+            .collect();
+
+        // `FieldSet::value_set` requires a fixed-length array, so dispatch on
+        // the (bounded) number of fields.
+        macro_rules! dispatch_lens {
+            ($($len:tt),*) => {
+                match values.len() {
+                    $(
+                        $len => {
+                            let values: [_; $len] = core::convert::TryInto::try_into(&values[..])
+                                .expect("length was just matched");
+                            Event::dispatch(meta, &meta.fields().value_set(&values));
+                        }
+                    )*
+                    _ => unreachable!("field count is limited to MAX_FIELDS"),
+                }
+            };
+        }
+        dispatch_lens! {
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18,
+            19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for EmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            EmitErrorKind::TooManyShapes => write!(
+                f,
+                "the dynamic callsite table already holds {} distinct event shapes",
+                MAX_SHAPES
+            ),
+            EmitErrorKind::TooManyFields(len) => write!(
+                f,
+                "dynamic events may have at most {} fields, but {} were added",
+                MAX_FIELDS, len
+            ),
+        }
+    }
+}
+
+impl Error for EmitError {}
+
+/// A callsite whose metadata is allocated at runtime.
+///
+/// The metadata is stored behind an `AtomicPtr` rather than inline, because
+/// the metadata must refer back to the callsite that owns it: the callsite
+/// is leaked first, the metadata (referring to it) is leaked second, and the
+/// pointer is published before the callsite is registered. The pointer is
+/// written exactly once, before the callsite can be observed by anything
+/// else, so `metadata()` never sees it unset.
+struct DynamicCallsite {
+    interest: AtomicUsize,
+    metadata: AtomicPtr<Metadata<'static>>,
+}
+
+impl DynamicCallsite {
+    fn interest(&self) -> Interest {
+        match self.interest.load(Ordering::Relaxed) {
+            0 => Interest::never(),
+            2 => Interest::always(),
+            _ => Interest::sometimes(),
+        }
+    }
+}
+
+impl Callsite for DynamicCallsite {
+    fn set_interest(&self, interest: Interest) {
+        let interest = match () {
+            _ if interest.is_never() => 0,
+            _ if interest.is_always() => 2,
+            _ => 1,
+        };
+        self.interest.store(interest, Ordering::SeqCst);
+    }
+
+    fn metadata(&self) -> &Metadata<'_> {
+        let meta = self.metadata.load(Ordering::Acquire);
+        debug_assert!(!meta.is_null(), "metadata is published before the callsite");
+        unsafe { &*meta }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Shape {
+    // `Level` does not implement `Hash`, so store its string form.
+    level: &'static str,
+    target: String,
+    names: Vec<String>,
+}
+
+static SHAPES: Mutex<Option<HashMap<Shape, &'static DynamicCallsite>>> = Mutex::new(None);
+
+/// Returns the interned callsite for the given shape, creating (and leaking)
+/// it on first use.
+fn intern(
+    level: Level,
+    target: &str,
+    fields: &[(&str, &(dyn Value + '_))],
+) -> Result<&'static DynamicCallsite, EmitError> {
+    let shape = Shape {
+        level: level.as_str(),
+        target: target.to_owned(),
+        names: fields.iter().map(|(name, _)| (*name).to_owned()).collect(),
+    };
+
+    let mut shapes = SHAPES.lock().unwrap_or_else(|e| e.into_inner());
+    let shapes = shapes.get_or_insert_with(HashMap::new);
+    if let Some(callsite) = shapes.get(&shape) {
+        return Ok(callsite);
+    }
+    if shapes.len() >= MAX_SHAPES {
+        return Err(EmitError {
+            kind: EmitErrorKind::TooManyShapes,
+        });
+    }
+
+    // Leak the callsite first, so that the metadata can refer to it...
+    let callsite: &'static DynamicCallsite = Box::leak(Box::new(DynamicCallsite {
+        interest: AtomicUsize::new(1),
+        metadata: AtomicPtr::new(core::ptr::null_mut()),
+    }));
+
+    // ...then leak the names, target, and metadata...
+    let names: Vec<&'static str> = shape
+        .names
+        .iter()
+        .map(|name| &*Box::leak(name.clone().into_boxed_str()))
+        .collect();
+    let names: &'static [&'static str] = Box::leak(names.into_boxed_slice());
+    let target: &'static str = Box::leak(shape.target.clone().into_boxed_str());
+    let metadata: &'static Metadata<'static> = Box::leak(Box::new(Metadata::new(
+        "dynamic event",
+        target,
+        level,
+        None,
+        None,
+        None,
+        FieldSet::new(names, callsite::Identifier(callsite)),
+        Kind::EVENT,
+    )));
+
+    // ...and publish the metadata before the callsite becomes reachable
+    // through the registry.
+    callsite
+        .metadata
+        .store(metadata as *const _ as *mut _, Ordering::Release);
+    let registration: &'static Registration = Box::leak(Box::new(Registration::new(
+        callsite as &'static dyn Callsite,
+    )));
+    callsite::register(registration);
+
+    shapes.insert(shape, callsite);
+    Ok(callsite)
+}
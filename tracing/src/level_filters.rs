@@ -35,6 +35,13 @@
 //! ```
 //! ## Notes
 //!
+//! Static filtering sets an *upper bound* on verbosity: instrumentation at
+//! disabled levels is compiled out entirely, so no collector can re-enable it
+//! at runtime. Within that bound, collectors still filter dynamically via
+//! [`Collect::enabled`] and may advertise their own ceiling with
+//! [`Collect::max_level_hint`]; a level enabled at compile time may therefore
+//! still be disabled by the current collector.
+//!
 //! Please note that `tracing`'s static max level features do *not* control the
 //! [`log`] records that may be emitted when [`tracing`'s "log" feature flag][f] is
 //! enabled. This is to allow `tracing` to be disabled entirely at compile time
@@ -50,6 +57,8 @@
 //! [`log`]: https://docs.rs/log/
 //! [`log` crate]: https://docs.rs/log/latest/log/#compile-time-filters
 //! [f]: https://docs.rs/tracing/latest/tracing/#emitting-log-records
+//! [`Collect::enabled`]: crate::Collect::enabled
+//! [`Collect::max_level_hint`]: crate::Collect::max_level_hint
 pub use tracing_core::{metadata::ParseLevelFilterError, LevelFilter};
 
 /// The statically configured maximum trace level.
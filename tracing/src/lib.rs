@@ -239,7 +239,20 @@
 //! ```
 //!
 //! The span macros also take a string literal after the level, to set the name
-//! of the span.
+//! of the span. The name need not be a literal: any constant expression
+//! evaluating to a `&'static str`, such as a `const`, [`concat!`], or
+//! [`env!`], is accepted, since span names are stored in the callsite's
+//! static [metadata]. Names computed at runtime are not supported; record
+//! a dynamic value as a field instead.
+//!
+//! ```
+//! # use tracing::{span, Level};
+//! # fn main() {
+//! const CONFIG_SPAN: &str = concat!("load_", "config");
+//! let span = span!(Level::TRACE, CONFIG_SPAN);
+//! let version_span = span!(Level::TRACE, env!("CARGO_PKG_NAME"));
+//! # }
+//! ```
 //!
 //! ### Recording Fields
 //!
@@ -970,6 +983,9 @@ pub mod field;
 pub mod instrument;
 pub mod level_filters;
 pub mod span;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod time;
 
 #[doc(hidden)]
 pub mod __macro_support {
@@ -16,6 +16,21 @@
 /// // do work inside the span...
 /// # }
 /// ```
+///
+/// The name (and target) may be any constant expression evaluating to a
+/// `&'static str`, not just a string literal — for example, a `const`, or the
+/// output of `concat!` or `env!`. Span names computed at runtime are not
+/// supported, as the name is stored in the callsite's static metadata; record
+/// a dynamic value as a field instead.
+///
+/// ```
+/// # use tracing::{span, Level};
+/// # fn main() {
+/// const HANDLER_SPAN: &str = concat!("handle_", "request");
+/// let span = span!(Level::TRACE, HANDLER_SPAN);
+/// let span = span!(target: env!("CARGO_PKG_NAME"), Level::TRACE, HANDLER_SPAN);
+/// # }
+/// ```
 #[macro_export]
 macro_rules! span {
     (target: $target:expr, parent: $parent:expr, $lvl:expr, $name:expr) => {
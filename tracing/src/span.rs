@@ -901,6 +901,83 @@ impl Span {
         }
     }
 
+    /// Returns this span, if it was [enabled] by the current collector, or
+    /// the [current span] (whose lexical distance may be further than
+    /// expected), if this span [is disabled].
+    ///
+    /// This method can be useful when propagating spans to spawned threads or
+    /// [async tasks]. Consider the following:
+    ///
+    /// ```
+    /// let _parent_span = tracing::info_span!("parent").entered();
+    ///
+    /// // ...
+    ///
+    /// let child_span = tracing::debug_span!("child");
+    ///
+    /// std::thread::spawn(move || {
+    ///     let _entered = child_span.entered();
+    ///
+    ///     tracing::info!("spawned a thread!");
+    ///
+    ///     // ...
+    /// });
+    /// ```
+    ///
+    /// If the current collector enables the [`DEBUG`] level, then both the
+    /// "parent" and "child" spans will be enabled. Thus, when the "spawned a
+    /// thread!" event occurs, it will be inside of the "child" span. Because
+    /// "parent" is the parent of "child", the event will _also_ be inside of
+    /// "parent".
+    ///
+    /// However, if the collector only enables the [`INFO`] level, the "child"
+    /// span will be disabled. When the thread is spawned, the
+    /// `child_span.entered()` call will do nothing, since "child" is not
+    /// enabled. In that case, the "spawned a thread!" event occurs outside of
+    /// *any* span, since the "child" span was responsible for propagating its
+    /// parent to the spawned thread.
+    ///
+    /// If this is not the desired behavior, `Span::or_current` can be used to
+    /// ensure that the "parent" span is propagated in both cases, either as a
+    /// parent of "child" _or_ directly. For example:
+    ///
+    /// ```
+    /// let _parent_span = tracing::info_span!("parent").entered();
+    ///
+    /// // ...
+    ///
+    /// // If DEBUG is enabled, then "child" will be propagated to the spawned
+    /// // thread. Otherwise, the thread will receive the current span.
+    /// let child_span = tracing::debug_span!("child").or_current();
+    ///
+    /// std::thread::spawn(move || {
+    ///     let _entered = child_span.entered();
+    ///
+    ///     tracing::info!("spawned a thread!");
+    ///
+    ///     // ...
+    /// });
+    /// ```
+    ///
+    /// This method is designed to be used in cases where propagating the
+    /// current span is important, but the cost of creating and entering a
+    /// span that may be disabled should be avoided where possible.
+    ///
+    /// [enabled]: crate::Collect::enabled
+    /// [current span]: Span::current
+    /// [is disabled]: Span::is_disabled
+    /// [async tasks]: std::task
+    /// [`DEBUG`]: crate::Level::DEBUG
+    /// [`INFO`]: crate::Level::INFO
+    #[inline]
+    #[must_use]
+    pub fn or_current(self) -> Self {
+        if self.is_disabled() {
+            return Self::current();
+        }
+        self
+    }
+
     #[inline]
     fn do_enter(&self) {
         if let Some(inner) = self.inner.as_ref() {
@@ -975,6 +1052,26 @@ impl Span {
 
     /// Returns a [`Field`](super::field::Field) for the field with the
     /// given `name`, if one exists,
+    ///
+    /// Looking up a field by name requires iterating over the span's field
+    /// names. When the same field is recorded repeatedly, such as in a hot
+    /// loop, the returned `Field` can be cached and passed to [`record`]
+    /// directly, skipping the lookup on subsequent records:
+    ///
+    /// ```
+    /// use tracing::{trace_span, field};
+    ///
+    /// let span = trace_span!("my_span", parting = field::Empty);
+    ///
+    /// // Look the field up once, outside of the loop.
+    /// if let Some(parting) = span.field("parting") {
+    ///     for _ in 0..10 {
+    ///         span.record(&parting, &"goodbye world!");
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`record`]: Span::record()
     pub fn field<Q: ?Sized>(&self, field: &Q) -> Option<field::Field>
     where
         Q: field::AsField,
@@ -0,0 +1,54 @@
+//! Support for attaching explicit timestamps to events.
+//!
+//! By default, subscribers that care about *when* an event occurred (such as
+//! `tracing-subscriber`'s [`fmt`] module, or `tracing-opentelemetry`) stamp
+//! each event with the current system time as it is recorded. When replaying
+//! historical data through `tracing` — for example, parsing an existing log
+//! file and re-emitting its entries as events so that subscribers can process
+//! them — this is not the desired behavior: the events should carry the time
+//! at which they originally occurred.
+//!
+//! This module defines an opt-in convention for this: an event may carry a
+//! field named [`TIMESTAMP_FIELD_NAME`] (`"event.timestamp"`) whose value is
+//! the number of nanoseconds since the [Unix epoch], as produced by the
+//! [`timestamp`] function. Subscribers that understand the convention consume
+//! the field, using the provided time in place of the current one rather than
+//! recording it as an ordinary field; events without the field are unchanged.
+//!
+//! # Examples
+//!
+//! Replaying an event that occurred at a known time:
+//!
+//! ```rust
+//! use std::time::{Duration, SystemTime};
+//!
+//! // The time at which the original event occurred.
+//! let occurred = SystemTime::UNIX_EPOCH + Duration::from_secs(1614223200);
+//!
+//! tracing::info!(
+//!     event.timestamp = tracing::time::timestamp(occurred),
+//!     "replayed from an existing log",
+//! );
+//! ```
+//!
+//! [Unix epoch]: std::time::UNIX_EPOCH
+//! [`fmt`]: https://docs.rs/tracing-subscriber/latest/tracing_subscriber/fmt/index.html
+use std::time::SystemTime;
+
+/// The name of the field used to attach an explicit timestamp to an event.
+///
+/// See the [module-level documentation](self) for details.
+pub const TIMESTAMP_FIELD_NAME: &str = "event.timestamp";
+
+/// Returns the value to record in an event's [`TIMESTAMP_FIELD_NAME`] field
+/// for the provided `SystemTime`.
+///
+/// The timestamp is represented as the number of nanoseconds since the [Unix
+/// epoch]. Times before the epoch saturate to 0.
+///
+/// [Unix epoch]: std::time::UNIX_EPOCH
+pub fn timestamp(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or(0)
+}
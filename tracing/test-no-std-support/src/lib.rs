@@ -0,0 +1,197 @@
+//! Support for testing `tracing` and `tracing-core` without the standard
+//! library.
+//!
+//! This crate provides a [`RingBufferCollector`]: a [`Collect`] implementation
+//! that formats events into a fixed-size byte ring buffer, requiring neither
+//! heap allocation nor OS threads. It models the setup used on bare-metal
+//! targets, where the collector lives in a `static` and is installed once with
+//! [`Dispatch::from_static`] and [`dispatch::set_global_default`]; building
+//! without the `std` feature disables the thread-local scoped dispatcher
+//! entirely, so every event is routed through the global default.
+//!
+//! The crate itself is `#![no_std]`, and both of its dependencies are built
+//! with `--no-default-features`; the accompanying integration test exercises
+//! the whole path from the `tracing` macros to the ring buffer on a hosted
+//! target.
+//!
+//! [`Dispatch::from_static`]: tracing_core::dispatch::Dispatch::from_static
+//! [`dispatch::set_global_default`]: tracing_core::dispatch::set_global_default
+#![no_std]
+
+use core::cell::UnsafeCell;
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use tracing_core::{
+    collect::Collect,
+    field::{Field, Visit},
+    span, Event, Metadata,
+};
+
+/// The number of bytes stored by a [`RingBufferCollector`].
+pub const CAPACITY: usize = 512;
+
+/// A [`Collect`] implementation that formats events into a fixed-size byte
+/// ring buffer.
+///
+/// When the buffer is full, new bytes overwrite the oldest ones, so the
+/// buffer always holds the most recent output — the behavior typically wanted
+/// for a crash log on an embedded target.
+pub struct RingBufferCollector {
+    /// A spin lock guarding `buf`.
+    ///
+    /// Events never recursively emit events, so the lock is only contended
+    /// between genuinely concurrent writers. A real firmware collector would
+    /// use a `critical-section` mutex instead, as spinning cannot exclude an
+    /// interrupt handler on the same core.
+    locked: AtomicBool,
+    buf: UnsafeCell<Buffer>,
+    next_id: AtomicU64,
+}
+
+// Safety: `buf` is only accessed while `locked` is held (see `with_buf`).
+unsafe impl Sync for RingBufferCollector {}
+
+struct Buffer {
+    bytes: [u8; CAPACITY],
+    /// The index of the oldest valid byte.
+    head: usize,
+    /// The number of valid bytes.
+    len: usize,
+}
+
+impl Buffer {
+    fn push(&mut self, byte: u8) {
+        let tail = (self.head + self.len) % CAPACITY;
+        self.bytes[tail] = byte;
+        if self.len == CAPACITY {
+            // The buffer is full; the oldest byte was just overwritten.
+            self.head = (self.head + 1) % CAPACITY;
+        } else {
+            self.len += 1;
+        }
+    }
+}
+
+impl Write for Buffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            self.push(byte);
+        }
+        Ok(())
+    }
+}
+
+impl RingBufferCollector {
+    /// Returns a new, empty `RingBufferCollector`.
+    ///
+    /// This is a `const fn`, so the collector can be stored in a `static` and
+    /// installed with [`Dispatch::from_static`].
+    ///
+    /// [`Dispatch::from_static`]: tracing_core::dispatch::Dispatch::from_static
+    pub const fn new() -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            buf: UnsafeCell::new(Buffer {
+                bytes: [0; CAPACITY],
+                head: 0,
+                len: 0,
+            }),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Copies the buffered bytes, oldest first, into `out`, returning the
+    /// number of bytes copied.
+    pub fn read(&self, out: &mut [u8]) -> usize {
+        self.with_buf(|buf| {
+            let len = core::cmp::min(buf.len, out.len());
+            for (i, slot) in out[..len].iter_mut().enumerate() {
+                *slot = buf.bytes[(buf.head + i) % CAPACITY];
+            }
+            len
+        })
+    }
+
+    /// Discards all buffered bytes.
+    pub fn clear(&self) {
+        self.with_buf(|buf| {
+            buf.head = 0;
+            buf.len = 0;
+        });
+    }
+
+    fn with_buf<T>(&self, f: impl FnOnce(&mut Buffer) -> T) -> T {
+        while self
+            .locked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        // Safety: the spin lock above guarantees exclusive access.
+        let result = f(unsafe { &mut *self.buf.get() });
+        self.locked.store(false, Ordering::Release);
+        result
+    }
+}
+
+impl Default for RingBufferCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collect for RingBufferCollector {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _attrs: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        self.with_buf(|buf| {
+            let meta = event.metadata();
+            let _ = write!(buf, "{} {}:", meta.level(), meta.target());
+            event.record(&mut FieldWriter { buf });
+            let _ = buf.write_str("\n");
+        });
+    }
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+
+    fn current_span(&self) -> span::Current {
+        span::Current::unknown()
+    }
+}
+
+/// Writes an event's fields into the ring buffer as `name=value` pairs, with
+/// the message field written bare.
+struct FieldWriter<'a> {
+    buf: &'a mut Buffer,
+}
+
+impl Visit for FieldWriter<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let _ = if field.name() == "message" {
+            write!(self.buf, " {:?}", value)
+        } else {
+            write!(self.buf, " {}={:?}", field.name(), value)
+        };
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        let _ = if field.name() == "message" {
+            write!(self.buf, " {}", value)
+        } else {
+            write!(self.buf, " {}={}", field.name(), value)
+        };
+    }
+}
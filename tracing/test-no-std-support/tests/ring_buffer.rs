@@ -0,0 +1,63 @@
+//! Verifies that events emitted through the `tracing` macros — with both
+//! `tracing` and `tracing-core` built with `--no-default-features` — reach a
+//! statically allocated collector through the global dispatcher.
+//!
+//! Without the `std` feature there is no thread-local scoped dispatcher, so
+//! `set_global_default` is the only way to install a collector; this test
+//! uses the same `static` + `Dispatch::from_static` setup a bare-metal target
+//! would.
+use test_no_std_support::{RingBufferCollector, CAPACITY};
+use tracing_core::dispatch::{self, Dispatch};
+
+static COLLECTOR: RingBufferCollector = RingBufferCollector::new();
+
+fn contents(buf: &mut [u8; CAPACITY]) -> &str {
+    let len = COLLECTOR.read(buf);
+    core::str::from_utf8(&buf[..len]).expect("output should be ASCII")
+}
+
+#[test]
+fn events_reach_the_ring_buffer() {
+    dispatch::set_global_default(Dispatch::from_static(&COLLECTOR))
+        .expect("global default should only be set once");
+
+    let mut buf = [0u8; CAPACITY];
+
+    tracing::info!(answer = 42, "hello from no-std");
+    let output = contents(&mut buf);
+    assert!(
+        output.contains("hello from no-std"),
+        "message should be recorded; got: {:?}",
+        output
+    );
+    assert!(
+        output.contains("answer=42"),
+        "fields should be recorded; got: {:?}",
+        output
+    );
+
+    // Spans must be assignable without panicking, even though the collector
+    // does not track them.
+    let span = tracing::info_span!("a span");
+    span.in_scope(|| tracing::info!("inside a span"));
+    assert!(contents(&mut buf).contains("inside a span"));
+
+    // Once more output has been written than the buffer can hold, the oldest
+    // bytes are overwritten, and the most recent events are retained.
+    COLLECTOR.clear();
+    for i in 0..64 {
+        tracing::info!(i, "filler");
+    }
+    let output = contents(&mut buf);
+    assert_eq!(output.len(), CAPACITY, "the buffer should be full");
+    assert!(
+        output.contains("i=63"),
+        "the most recent event should be retained; got: {:?}",
+        output
+    );
+    assert!(
+        !output.contains("i=0\n"),
+        "the oldest events should be overwritten; got: {:?}",
+        output
+    );
+}
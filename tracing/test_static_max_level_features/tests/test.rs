@@ -1,11 +1,18 @@
 #[macro_use]
 extern crate tracing;
 
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use tracing::span::{Attributes, Record};
 use tracing::{span, Collect, Event, Id, Level, Metadata};
 use tracing_core::span::Current;
 
+/// With `max_level_debug`, the `DEBUG` level is statically enabled in
+/// unoptimized builds; with `release_max_level_info`, it is statically
+/// disabled when optimizations are on. Running this suite under both profiles
+/// therefore exercises both feature configurations.
+const DEBUG_ENABLED: bool = cfg!(debug_assertions);
+
 struct State {
     last_level: Mutex<Option<Level>>,
 }
@@ -53,7 +60,14 @@ fn test_static_max_level_features() {
         info!("");
         last(&a, Some(Level::INFO));
         debug!("");
-        last(&a, Some(Level::DEBUG));
+        last(
+            &a,
+            if DEBUG_ENABLED {
+                Some(Level::DEBUG)
+            } else {
+                None
+            },
+        );
         trace!("");
         last(&a, None);
 
@@ -70,6 +84,39 @@ fn test_static_max_level_features() {
     });
 }
 
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn disabled_levels_do_not_evaluate_their_fields() {
+    let me = Arc::new(State {
+        last_level: Mutex::new(None),
+    });
+    tracing::collect::with_default(TestCollector(me), || {
+        let evaluated = AtomicUsize::new(0);
+        let count = || evaluated.fetch_add(1, Ordering::Relaxed);
+
+        trace!(value = count());
+        span!(Level::TRACE, "disabled", value = count());
+        assert_eq!(
+            evaluated.load(Ordering::Relaxed),
+            0,
+            "fields of statically disabled events and spans must not be evaluated"
+        );
+
+        debug!(value = count());
+        span!(Level::DEBUG, "maybe_disabled", value = count());
+        let expected = if DEBUG_ENABLED { 2 } else { 0 };
+        assert_eq!(evaluated.load(Ordering::Relaxed), expected);
+
+        info!(value = count());
+        span!(Level::INFO, "enabled", value = count());
+        assert_eq!(
+            evaluated.load(Ordering::Relaxed),
+            expected + 2,
+            "fields of enabled events and spans must be evaluated"
+        );
+    });
+}
+
 fn last(state: &State, expected: Option<Level>) {
     let mut lvl = state.last_level.lock().unwrap();
     assert_eq!(*lvl, expected);
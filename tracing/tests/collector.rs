@@ -15,9 +15,7 @@ use tracing::{
     Event, Level, Metadata,
 };
 
-mod support;
-
-use self::support::*;
+use tracing_mock::*;
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
 #[test]
@@ -0,0 +1,124 @@
+// These tests require the thread-local scoped dispatcher, which only works when
+// we have a standard library.
+#![cfg(feature = "std")]
+
+use tracing_mock::*;
+
+use std::sync::{Arc, Mutex};
+use tracing::{
+    collect::{with_default, Collect, Interest},
+    field::dynamic::EventBuilder,
+    metadata::Metadata,
+    span, Event, Level,
+};
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn dynamic_event_has_runtime_fields() {
+    let (collector, handle) = collector::mock()
+        .event(
+            event::mock().with_fields(
+                field::mock("answer")
+                    .with_value(&42)
+                    .and(field::mock("question").with_value(&"life, the universe"))
+                    .only(),
+            ),
+        )
+        .done()
+        .run_with_handle();
+
+    with_default(collector, || {
+        // Build the field names at runtime, as a caller forwarding another
+        // system's key/value pairs would.
+        let names = vec![String::from("answer"), String::from("question")];
+        EventBuilder::new(Level::INFO, "dynamic_fields")
+            .field(&names[0], &42)
+            .field(&names[1], &"life, the universe")
+            .emit()
+            .expect("emit should succeed");
+    });
+
+    handle.assert_finished();
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn repeated_shapes_reuse_interned_metadata() {
+    // A collector that records the metadata pointer of each event, so that
+    // the test can assert that two emissions with the same shape share one
+    // interned callsite rather than leaking a new one each time.
+    struct MetaCollector(Arc<Mutex<Vec<*const Metadata<'static>>>>);
+    unsafe impl Send for MetaCollector {}
+    unsafe impl Sync for MetaCollector {}
+
+    impl Collect for MetaCollector {
+        fn register_callsite(&self, _: &'static Metadata<'static>) -> Interest {
+            Interest::always()
+        }
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+        fn event(&self, event: &Event<'_>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(event.metadata() as *const Metadata<'static>);
+        }
+        fn enter(&self, _: &span::Id) {}
+        fn exit(&self, _: &span::Id) {}
+        fn current_span(&self) -> tracing_core::span::Current {
+            tracing_core::span::Current::unknown()
+        }
+    }
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    with_default(MetaCollector(seen.clone()), || {
+        for i in 0..3 {
+            EventBuilder::new(Level::INFO, "dynamic_fields::reuse")
+                .field("iteration", &i)
+                .emit()
+                .expect("emit should succeed");
+        }
+        EventBuilder::new(Level::INFO, "dynamic_fields::reuse")
+            .field("other", &0)
+            .emit()
+            .expect("emit should succeed");
+    });
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 4);
+    assert_eq!(
+        seen[0], seen[1],
+        "same shape should reuse the same metadata"
+    );
+    assert_eq!(
+        seen[1], seen[2],
+        "same shape should reuse the same metadata"
+    );
+    assert_ne!(
+        seen[2], seen[3],
+        "a different shape should get its own metadata"
+    );
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn too_many_fields_is_an_error() {
+    let (collector, handle) = collector::mock().done().run_with_handle();
+
+    with_default(collector, || {
+        let names: Vec<String> = (0..33).map(|i| format!("field_{}", i)).collect();
+        let mut builder = EventBuilder::new(Level::INFO, "dynamic_fields::overflow");
+        for name in &names {
+            builder = builder.field(name, &1);
+        }
+        assert!(builder.emit().is_err());
+    });
+
+    handle.assert_finished();
+}
@@ -8,9 +8,7 @@
 
 #[macro_use]
 extern crate tracing;
-mod support;
-
-use self::support::*;
+use tracing_mock::*;
 
 use tracing::{
     collect::with_default,
@@ -53,8 +51,10 @@ macro_rules! event_without_message {
 event_without_message! {event_without_message: 42}
 event_without_message! {wrapping_event_without_message: std::num::Wrapping(42)}
 event_without_message! {nonzeroi32_event_without_message: std::num::NonZeroI32::new(42).unwrap()}
-// needs API breakage
-//event_without_message!{nonzerou128_event_without_message: std::num::NonZeroU128::new(42).unwrap()}
+event_without_message! {u128_event_without_message: 42u128}
+event_without_message! {i128_event_without_message: 42i128}
+event_without_message! {nonzerou128_event_without_message: std::num::NonZeroU128::new(42).unwrap()}
+event_without_message! {nonzeroi128_event_without_message: std::num::NonZeroI128::new(42).unwrap()}
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
 #[test]
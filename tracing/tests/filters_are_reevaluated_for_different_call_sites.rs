@@ -13,9 +13,7 @@ extern crate std;
 
 #[macro_use]
 extern crate tracing;
-mod support;
-
-use self::support::*;
+use tracing_mock::*;
 use tracing::Level;
 
 use std::sync::{
@@ -27,6 +27,25 @@ fn span() {
     span!(Level::DEBUG, "bar",);
 }
 
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn span_with_const_names() {
+    // Span names (and targets) must be `&'static str`s, but need not be
+    // string literals: any constant expression is accepted.
+    const CONST_NAME: &str = "foo";
+    const CONST_TARGET: &str = concat!("foo_", "events");
+    span!(Level::DEBUG, CONST_NAME);
+    span!(Level::DEBUG, concat!("foo", "bar"), bar.baz = 2);
+    span!(Level::DEBUG, env!("CARGO_PKG_NAME"));
+    span!(target: CONST_TARGET, Level::DEBUG, CONST_NAME, quux = 3);
+    span!(target: concat!("foo", "_events"), Level::DEBUG, concat!("b", "ar"),);
+    trace_span!(CONST_NAME);
+    debug_span!(concat!("foo", "bar"), bar.baz = 2);
+    info_span!(target: CONST_TARGET, env!("CARGO_PKG_NAME"));
+    warn_span!(CONST_NAME,);
+    error_span!(target: CONST_TARGET, CONST_NAME, quux = 3);
+}
+
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
 #[test]
 fn trace_span() {
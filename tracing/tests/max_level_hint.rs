@@ -2,9 +2,7 @@
 // statically
 #![cfg(feature = "alloc")]
 
-mod support;
-
-use self::support::*;
+use tracing_mock::*;
 use tracing::Level;
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
@@ -5,15 +5,13 @@
 
 #[macro_use]
 extern crate tracing;
-mod support;
-
-use self::support::*;
 use std::thread;
 use tracing::{
     collect::with_default,
     field::{debug, display},
     Level, Span,
 };
+use tracing_mock::*;
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
 #[test]
@@ -210,6 +208,84 @@ fn cloning_a_span_calls_clone_span() {
     handle.assert_finished();
 }
 
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn closing_a_span_calls_try_close() {
+    let (collector, handle) = collector::mock()
+        .enter(span::mock().named("foo"))
+        .exit(span::mock().named("foo"))
+        .clone_span(span::mock().named("foo"))
+        .try_close(span::mock().named("foo"))
+        .try_close(span::mock().named("foo"))
+        .done()
+        .run_with_handle();
+    with_default(collector, || {
+        let span = span!(Level::TRACE, "foo");
+        span.in_scope(|| {});
+        let clone = span.clone();
+        // Each dropped handle decrements the span's ref count; the span is
+        // only closed once both handles have been dropped.
+        drop(clone);
+        drop(span);
+    });
+
+    handle.assert_finished();
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn unordered_expectations_match_in_any_order() {
+    let (collector, handle) = collector::mock()
+        .unordered()
+        .event(event::mock().with_target("app_a"))
+        .event(event::mock().with_target("app_b"))
+        .in_order()
+        .event(event::mock().with_target("app_c"))
+        .done()
+        .run_with_handle();
+    with_default(collector, || {
+        // These two events may occur in either order...
+        event!(target: "app_b", Level::INFO, "b");
+        event!(target: "app_a", Level::INFO, "a");
+        // ...but this one must come after both of them.
+        event!(target: "app_c", Level::INFO, "c");
+    });
+
+    handle.assert_finished();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn per_thread_expectations_are_matched_independently() {
+    let (collector, handle) = collector::mock()
+        .per_thread()
+        .enter(span::mock().named("worker"))
+        .event(event::mock())
+        .exit(span::mock().named("worker"))
+        .done()
+        .run_with_handle();
+    let dispatch = tracing::dispatch::Dispatch::new(collector);
+    // Each thread's notifications are matched against its own copy of the
+    // expected sequence, regardless of how the threads interleave.
+    let threads: Vec<_> = (0..2)
+        .map(|_| {
+            let dispatch = dispatch.clone();
+            thread::spawn(move || {
+                tracing::dispatch::with_default(&dispatch, || {
+                    span!(Level::TRACE, "worker").in_scope(|| {
+                        event!(Level::DEBUG, "working");
+                    });
+                })
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    handle.assert_finished();
+}
+
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
 #[test]
 fn drop_span_when_exiting_dispatchers_context() {
@@ -335,6 +411,67 @@ fn entered_api() {
     handle.assert_finished();
 }
 
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn or_current_returns_enabled_span() {
+    let (collector, handle) = collector::mock()
+        .enter(span::mock().named("foo"))
+        .event(event::mock())
+        .exit(span::mock().named("foo"))
+        .done()
+        .run_with_handle();
+    with_default(collector, || {
+        let _span = span!(Level::TRACE, "foo").or_current().entered();
+        debug!("event inside foo");
+    });
+
+    handle.assert_finished();
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn or_current_falls_back_to_current_span_when_disabled() {
+    // The collector only enables the "outer" span; "disabled" is filtered
+    // out, so `or_current` should return the current ("outer") span instead,
+    // and entering it should enter "outer" a second time.
+    let (collector, handle) = collector::mock()
+        .with_filter(|meta| meta.name() != "disabled")
+        .enter(span::mock().named("outer"))
+        .enter(span::mock().named("outer"))
+        .event(event::mock())
+        .exit(span::mock().named("outer"))
+        .exit(span::mock().named("outer"))
+        .done()
+        .run_with_handle();
+    with_default(collector, || {
+        let outer = span!(Level::TRACE, "outer");
+        let _outer = outer.enter();
+        let span = span!(Level::TRACE, "disabled").or_current();
+        let _entered = span.entered();
+        debug!("event inside outer");
+    });
+
+    handle.assert_finished();
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn or_current_with_nothing_current_is_none() {
+    let (collector, handle) = collector::mock()
+        .with_filter(|meta| meta.name() != "disabled")
+        .event(event::mock())
+        .done()
+        .run_with_handle();
+    with_default(collector, || {
+        let span = span!(Level::TRACE, "disabled").or_current();
+        assert!(span.is_none());
+        let _entered = span.entered();
+        debug!("no current span");
+    });
+
+    handle.assert_finished();
+}
+
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
 #[test]
 fn moved_field() {
@@ -587,6 +724,57 @@ fn record_new_values_for_fields() {
     handle.assert_finished();
 }
 
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn record_empty_field_declared_by_each_macro() {
+    let mut collector = collector::mock();
+    for name in &["s", "t", "d", "i", "w", "e"] {
+        collector = collector.new_span(span::mock().named(*name)).record(
+            span::mock().named(*name),
+            field::mock("answer").with_value(&42).only(),
+        );
+    }
+    let (collector, handle) = collector.done().run_with_handle();
+
+    with_default(collector, || {
+        span!(Level::TRACE, "s", answer = tracing::field::Empty).record("answer", &42);
+        trace_span!("t", answer = tracing::field::Empty).record("answer", &42);
+        debug_span!("d", answer = tracing::field::Empty).record("answer", &42);
+        info_span!("i", answer = tracing::field::Empty).record("answer", &42);
+        warn_span!("w", answer = tracing::field::Empty).record("answer", &42);
+        error_span!("e", answer = tracing::field::Empty).record("answer", &42);
+    });
+
+    handle.assert_finished();
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[test]
+fn record_with_cached_field_handle() {
+    let (collector, handle) = collector::mock()
+        .new_span(span::mock().named("foo"))
+        .record(
+            span::mock().named("foo"),
+            field::mock("bar").with_value(&1).only(),
+        )
+        .record(
+            span::mock().named("foo"),
+            field::mock("bar").with_value(&2).only(),
+        )
+        .done()
+        .run_with_handle();
+
+    with_default(collector, || {
+        let span = span!(Level::TRACE, "foo", bar = tracing::field::Empty);
+        let bar = span.field("bar").expect("span should have a `bar` field");
+        assert!(span.has_field(&bar));
+        span.record(&bar, &1);
+        span.record(&bar, &2);
+    });
+
+    handle.assert_finished();
+}
+
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
 #[test]
 fn new_span_with_target_and_log_level() {